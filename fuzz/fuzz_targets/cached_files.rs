@@ -0,0 +1,16 @@
+//! The files in the cache and data dirs, which anything may have written.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wordle_rs::{parse_word_cache, progress::SavedGame};
+
+fuzz_target!(|contents: &str| {
+    if let Ok(puzzle) = parse_word_cache(contents) {
+        assert!(wordle_rs::source::is_solution(puzzle.solution.reveal()));
+    }
+    if let Ok(game) = serde_json::from_str::<SavedGame>(contents) {
+        game.won();
+        game.think_times();
+    }
+});
@@ -0,0 +1,18 @@
+//! What's typed at the guess prompt, and rows of feedback for `solve`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wordle_rs::{grade, input, strategy};
+
+fuzz_target!(|line: &str| {
+    if input::command(line).is_none() {
+        let guess = input::normalize(line);
+        if guess.len() == 5 && guess.bytes().all(|b| b.is_ascii_lowercase()) {
+            grade::grade(&guess, "crane");
+        }
+    }
+    if let Ok(strategy::Feedback::Known(row)) = strategy::parse_feedback(line) {
+        strategy::kinds_code(row.iter().map(|cg| cg.kind));
+    }
+});
@@ -0,0 +1,14 @@
+//! What NYT, or something pretending to be it, answers with.
+
+#![no_main]
+
+use chrono::NaiveDate;
+use libfuzzer_sys::fuzz_target;
+use wordle_rs::source;
+
+fuzz_target!(|body: &str| {
+    let day = NaiveDate::from_ymd_opt(2025, 1, 1).expect("a real day");
+    if let Ok(puzzle) = source::parse_response(body, day) {
+        assert!(source::is_solution(puzzle.solution.reveal()));
+    }
+});
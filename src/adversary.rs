@@ -0,0 +1,51 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::strategy::pattern_code;
+
+/// An opponent that never settles on an answer, as in Absurdle. After each
+/// guess it gives whichever feedback leaves the most words possible, so it
+/// only concedes once a single word is left.
+pub struct Adversary {
+    candidates: Vec<String>,
+}
+
+impl Adversary {
+    pub fn new(words: Vec<String>) -> Self {
+        Self { candidates: words }
+    }
+
+    /// Answers `guess` with the feedback that keeps the most candidates and
+    /// returns one of them, which grades the guess with that feedback. Ties
+    /// go to the feedback with the fewest greens and yellows.
+    pub fn respond(&mut self, guess: &str) -> &str {
+        let mut buckets: HashMap<u32, Vec<String>> = HashMap::new();
+        for word in std::mem::take(&mut self.candidates) {
+            buckets.entry(pattern_code(guess.as_bytes(), word.as_bytes())).or_default().push(word);
+        }
+        if let Some((_, words)) = buckets.into_iter().max_by_key(|(code, words)| (words.len(), Reverse(*code))) {
+            self.candidates = words;
+        }
+        &self.candidates[0]
+    }
+
+    /// How many words could still be the answer.
+    pub fn left(&self) -> usize {
+        self.candidates.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_largest_group_until_one_word_is_left() {
+        let words = ["crane", "crone", "slate", "pride"].map(str::to_string).to_vec();
+        let mut adversary = Adversary::new(words);
+        // Crane and crone grade pride alike, so both are kept. Then guessing
+        // one of them leaves the other.
+        assert_ne!(adversary.respond("pride"), "pride");
+        assert_ne!(adversary.respond("crane"), "crane");
+        assert_eq!(adversary.left(), 1);
+    }
+}
@@ -0,0 +1,55 @@
+//! A word a day whose letters are given scrambled before the first guess, so
+//! the game is finding the one word they spell. It's graded like any other
+//! game, but saved apart so it has stats of its own.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+use crate::{app::App, daily_random, error::Result};
+
+/// What sets the words apart from the daily random ones of the same day.
+static SALT: &[u8] = b"anagram";
+
+pub fn saves_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("anagram")
+}
+
+/// The word to unscramble on `day`: one everyone with the same answer list
+/// gets, picked with a salt of its own so it isn't that day's daily random
+/// word too.
+pub fn answer(app: &App, day: NaiveDate) -> Result<String> {
+    daily_random::salted_answer(app, day, SALT)
+}
+
+/// The letters of `answer` shuffled, never in the order of the answer itself
+/// unless every order is.
+pub fn scramble(answer: &str, day: NaiveDate) -> String {
+    let mut letters: Vec<char> = answer.chars().collect();
+    letters.shuffle(&mut StdRng::seed_from_u64(day.num_days_from_ce() as u64));
+    if letters.iter().collect::<String>() == answer {
+        letters.rotate_left(1);
+    }
+    letters.iter().map(|ch| ch.to_ascii_uppercase().to_string()).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrambles_the_same_letters_out_of_order() {
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        for answer in ["crane", "abbey", "eerie"] {
+            let scrambled = scramble(answer, day);
+            assert_eq!(scramble(answer, day), scrambled);
+            let mut letters: Vec<char> = scrambled.split(' ').map(|l| l.to_ascii_lowercase().chars().next().unwrap()).collect();
+            assert_ne!(letters.iter().collect::<String>(), answer);
+            letters.sort();
+            let mut expected: Vec<char> = answer.chars().collect();
+            expected.sort();
+            assert_eq!(letters, expected);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Letter statistics over the answer list, for working out a strategy.
+
+
+use colored::Colorize;
+
+use crate::{cli::AnalyzeArgs, index::WordIndex};
+
+static LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// How often each letter is in each position, and in a word at all.
+pub struct Frequencies {
+    pub answers: usize,
+    pub length: usize,
+    /// `at[position][letter]`, counting answers.
+    pub at: Vec<[usize; 26]>,
+    /// Answers that have the letter anywhere.
+    pub anywhere: [usize; 26],
+}
+
+fn index(ch: char) -> Option<usize> {
+    ch.is_ascii_lowercase().then(|| (ch as u8 - b'a') as usize)
+}
+
+impl Frequencies {
+    pub fn of<'a>(answers: impl IntoIterator<Item = &'a String>, length: usize) -> Self {
+        let mut frequencies = Self { answers: 0, length, at: vec![[0; 26]; length], anywhere: [0; 26] };
+        for answer in answers.into_iter().filter(|w| w.len() == length) {
+            frequencies.answers += 1;
+            let mut seen = [false; 26];
+            for (i, ch) in answer.chars().enumerate() {
+                let Some(l) = index(ch) else { continue };
+                frequencies.at[i][l] += 1;
+                if !seen[l] {
+                    seen[l] = true;
+                    frequencies.anywhere[l] += 1;
+                }
+            }
+        }
+        frequencies
+    }
+
+    /// About how many greens and yellows `guess` gets on an average answer.
+    /// A letter guessed twice only counts the first time it could be yellow,
+    /// which is near enough for ranking openers.
+    pub fn expected(&self, guess: &str) -> (f64, f64) {
+        let n = self.answers.max(1) as f64;
+        let (mut greens, mut yellows) = (0.0, 0.0);
+        let mut seen = [false; 26];
+        for (i, ch) in guess.chars().enumerate() {
+            let Some(l) = index(ch) else { continue };
+            let green = self.at[i][l] as f64 / n;
+            greens += green;
+            if !seen[l] {
+                seen[l] = true;
+                yellows += (self.anywhere[l] as f64 / n - green).max(0.0);
+            }
+        }
+        (greens, yellows)
+    }
+}
+
+/// The words of the frequencies' length, with the greens and yellows each is
+/// expected to get, the ones that reveal the most first.
+pub fn openers<'a>(frequencies: &Frequencies, words: impl IntoIterator<Item = &'a String>) -> Vec<(&'a String, f64, f64)> {
+    let mut openers: Vec<(&String, f64, f64)> = words.into_iter()
+        .filter(|w| w.len() == frequencies.length)
+        .map(|w| {
+            let (greens, yellows) = frequencies.expected(w);
+            (w, greens, yellows)
+        })
+        .collect();
+    openers.sort_by(|a, b| (b.1 + b.2).total_cmp(&(a.1 + a.2)).then(a.0.cmp(b.0)));
+    openers
+}
+
+/// A cell of the heatmap, redder the more common the letter is there.
+fn heat(count: usize, most: usize) -> String {
+    let cell = format!("{:>5}", count);
+    let level = (count * 255 / most.max(1)) as u8;
+    cell.on_truecolor(level, 40, 255 - level).to_string()
+}
+
+/// Prints the heatmap of letters by position and the openers that reveal
+/// the most on average.
+pub fn run(args: AnalyzeArgs, answers: &WordIndex, dictionary: &WordIndex) {
+    let frequencies = Frequencies::of(answers, args.length);
+    println!("Letters by position over {} answers", frequencies.answers);
+    println!();
+    print!("    ");
+    for i in 1..=args.length {
+        print!("{:>5}", i);
+    }
+    println!("  anywhere");
+    let most = frequencies.at.iter().flatten().copied().max().unwrap_or(0);
+    for (l, letter) in LETTERS.chars().enumerate() {
+        print!("  {} ", letter);
+        for position in &frequencies.at {
+            print!("{}", heat(position[l], most));
+        }
+        println!("  {:>8}", frequencies.anywhere[l]);
+    }
+
+    let openers = openers(&frequencies, dictionary);
+    println!();
+    println!("Best openers by expected greens and yellows");
+    for (i, (word, greens, yellows)) in openers.iter().take(args.top).enumerate() {
+        println!("{:>3}. {}  {:.2} green  {:.2} yellow", i + 1, word, greens, yellows);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expects_greens_where_letters_usually_are() {
+        let answers = ["crane", "crate", "slate"].map(str::to_string);
+        let frequencies = Frequencies::of(&answers, 5);
+        assert_eq!(frequencies.at[0][index('c').unwrap()], 2);
+        assert_eq!(frequencies.anywhere[index('e').unwrap()], 3);
+        let (greens, yellows) = frequencies.expected("crate");
+        assert!((greens - 4.0).abs() < 1e-9);
+        assert!(yellows.abs() < 1e-9);
+    }
+}
@@ -0,0 +1,100 @@
+//! A little show when a guess is graded: the tiles of the row turn over one
+//! by one, and the winning row jumps. It repaints lines in place, so it's
+//! only done on a terminal.
+
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::{CharGuess, i18n, theme::Theme};
+
+/// How long each tile takes to turn over.
+static FLIP: Duration = Duration::from_millis(150);
+
+/// How long each tile of the winning row is up in the air.
+static JUMP: Duration = Duration::from_millis(90);
+
+/// What the official site says to a win, by how many guesses it took.
+static RANKS: [&str; 6] = ["rank-1", "rank-2", "rank-3", "rank-4", "rank-5", "rank-6"];
+
+/// Moves the cursor to the start of the line `n` lines up.
+pub fn up(n: usize) -> String {
+    format!("\x1b[{}A\r", n)
+}
+
+/// Clears the rest of the line from the cursor.
+pub static CLEAR_LINE: &str = "\x1b[K";
+
+fn painted(cg: &CharGuess, theme: &Theme) -> String {
+    theme.paint(cg.kind, &cg.ch.to_string()).to_string()
+}
+
+/// A tile that wasn't turned over yet, as wide as it will be.
+fn unturned(cg: &CharGuess, theme: &Theme) -> String {
+    match theme.tile(cg.kind).marks {
+        Some(_) => format!(" {} ", cg.ch),
+        None => cg.ch.to_string(),
+    }
+}
+
+fn width(cg: &CharGuess, theme: &Theme) -> usize {
+    theme.label(cg.kind, &cg.ch.to_string()).chars().count()
+}
+
+/// Repaints the guess that was just typed on the line above, after
+/// `prefix`, turning its tiles over from left to right.
+pub fn reveal(prefix: &str, cgs: &[CharGuess], theme: &Theme) {
+    let mut stdout = io::stdout();
+    for turned in 0..=cgs.len() {
+        let row: String = cgs.iter().enumerate()
+            .map(|(i, cg)| if i < turned { painted(cg, theme) } else { unturned(cg, theme) })
+            .collect();
+        let _ = writeln!(stdout, "{}{}{}{}", up(1), prefix, row, CLEAR_LINE);
+        let _ = stdout.flush();
+        thread::sleep(FLIP);
+    }
+}
+
+/// Makes each tile of the row printed on the line above jump in turn, into
+/// the blank line above it.
+pub fn jump(cgs: &[CharGuess], theme: &Theme) {
+    let mut stdout = io::stdout();
+    for up_tile in (0..cgs.len()).map(Some).chain([None]) {
+        let (mut top, mut bottom) = (String::new(), String::new());
+        for (i, cg) in cgs.iter().enumerate() {
+            let (tile, gap) = (painted(cg, theme), " ".repeat(width(cg, theme)));
+            if Some(i) == up_tile {
+                top.push_str(&tile);
+                bottom.push_str(&gap);
+            } else {
+                top.push_str(&gap);
+                bottom.push_str(&tile);
+            }
+        }
+        let _ = writeln!(stdout, "{}{}{}", up(2), top.trim_end(), CLEAR_LINE);
+        let _ = writeln!(stdout, "{}{}", bottom, CLEAR_LINE);
+        let _ = stdout.flush();
+        thread::sleep(JUMP);
+    }
+}
+
+/// The praise for a win in `guesses`, the last one going to every win past
+/// six guesses too.
+pub fn rank(guesses: usize) -> String {
+    i18n::message(RANKS[guesses.clamp(1, RANKS.len()) - 1], &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_wins_like_the_official_site() {
+        assert_eq!(rank(1), "Genius");
+        assert_eq!(rank(4), "Splendid");
+        assert_eq!(rank(6), "Phew");
+        assert_eq!(rank(8), "Phew");
+    }
+}
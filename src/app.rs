@@ -0,0 +1,364 @@
+use std::{
+    cell::{OnceCell, RefCell},
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::header;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::{
+    cache,
+    dictionary,
+    cli::WordListArgs,
+    dict_index::{self, DictIndex},
+    config::Config,
+    error::{Context, Result, WordleError},
+    index::WordIndex,
+    output::OutputFormat,
+    source::{self, Nyt},
+};
+
+/// How long a request may take when neither the flag nor the config file say.
+pub static DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How many times a request that failed on the network is tried again.
+pub static RETRIES: u32 = 3;
+pub static FIRST_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The longest a `Retry-After` is waited for before giving up instead.
+pub static MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Who is asking, so NYT can tell the program apart from a browser.
+pub static USER_AGENT: &str = concat!("wordle-rs/", env!("CARGO_PKG_VERSION"), " (+https://github.com/emanueljg/wordle-rs)");
+
+/// How long a 429 answer asks to wait before trying again. Only the seconds
+/// form of `Retry-After` is understood, the date form waits like a hiccup.
+pub fn retry_after(headers: &reqwest::header::HeaderMap) -> Duration {
+    headers.get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok()?.trim().parse().ok())
+        .map_or(FIRST_BACKOFF, Duration::from_secs)
+}
+
+/// The file a `file://` URL points at, for word lists and puzzles mirrored on
+/// disk or a network share.
+pub fn local_path(url: &str) -> Option<PathBuf> {
+    reqwest::Url::parse(url).ok().filter(|url| url.scheme() == "file")?.to_file_path().ok()
+}
+
+/// What a server said about a download last time, so it can be asked whether
+/// it changed without downloading it again.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub checked: Option<DateTime<Utc>>,
+}
+
+impl Validators {
+    /// Reads the validators at `path`, which are simply missing for what was
+    /// downloaded before they were kept.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).expect("validators serialize");
+        cache::write_atomic(path, json.as_bytes()).context("writing what the server said about a download")
+    }
+
+    pub fn from_response(response: &reqwest::blocking::Response) -> Self {
+        let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        Self {
+            etag: header(header::ETAG),
+            last_modified: header(header::LAST_MODIFIED),
+            checked: Some(Utc::now()),
+        }
+    }
+
+    /// `request` made conditional, so the server can answer 304 Not Modified
+    /// instead of sending it all again.
+    pub fn ask(&self, mut request: reqwest::blocking::RequestBuilder) -> reqwest::blocking::RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
+/// An HTTP client that is only built once something needs the network, so
+/// playing from a warm cache starts without touching it. Like every reqwest
+/// client it goes through `HTTPS_PROXY` and friends when they are set.
+pub struct LazyClient {
+    client: OnceCell<reqwest::blocking::Client>,
+    pub timeout: Duration,
+
+    /// Whether everything has to come from the cache.
+    pub no_network: bool,
+}
+
+impl LazyClient {
+    pub fn new(timeout: Duration, no_network: bool) -> Self {
+        Self { client: OnceCell::new(), timeout, no_network }
+    }
+
+    /// Fails with what was going to be done if the network is off.
+    pub fn online(&self, context: &'static str) -> Result<()> {
+        match self.no_network {
+            true => Err(WordleError::NoNetwork(context)),
+            false => Ok(()),
+        }
+    }
+
+    pub fn get(&self) -> &reqwest::blocking::Client {
+        self.client.get_or_init(|| {
+            reqwest::blocking::Client::builder()
+                .timeout(self.timeout)
+                .user_agent(USER_AGENT)
+                .build()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Gets `url`, trying again with a growing pause when the network or the
+    /// server had a hiccup, and after the pause the server asks for when it
+    /// says there were too many requests. Other error statuses are left to
+    /// the caller.
+    pub fn send(&self, url: &str, context: &'static str) -> Result<reqwest::blocking::Response> {
+        self.send_with(|client| client.get(url), context)
+    }
+
+    /// Like [`LazyClient::send`], for requests that need more than a URL.
+    pub fn send_with(
+        &self,
+        request: impl Fn(&reqwest::blocking::Client) -> reqwest::blocking::RequestBuilder,
+        context: &'static str,
+    ) -> Result<reqwest::blocking::Response> {
+        self.online(context)?;
+        let mut backoff = FIRST_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            let (client, request) = request(self.get()).build_split();
+            let request = request.context(context)?;
+            let (method, url) = (request.method().clone(), request.url().clone());
+            debug!(%method, %url, attempt, "sending a request");
+            let started = Instant::now();
+            let result = client.execute(request);
+            match &result {
+                Ok(response) => debug!(%url, status = %response.status(), ms = started.elapsed().as_millis(), "got a response"),
+                Err(e) => info!(%url, error = %e, "the request failed"),
+            }
+            if let Ok(response) = &result
+                && response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+            {
+                let wait = retry_after(response.headers());
+                warn!(context, wait_s = wait.as_secs(), "rate limited");
+                if attempt == RETRIES || wait > MAX_RETRY_AFTER {
+                    return Err(WordleError::RateLimited { context, wait });
+                }
+                thread::sleep(wait);
+                attempt += 1;
+                continue;
+            }
+            let transient = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+            match result {
+                _ if attempt < RETRIES && transient => {
+                    info!(context, backoff_ms = backoff.as_millis(), "trying again");
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                },
+                result => return result.context(context),
+            }
+        }
+    }
+}
+
+/// What the subcommands share during one run, so that chained subcommands
+/// don't each load the dictionary again.
+pub struct App {
+    pub cache_dir: PathBuf,
+    /// Where saved games go, which unlike the cache shouldn't be cleaned up.
+    pub data_dir: PathBuf,
+    pub client: LazyClient,
+    pub config: Config,
+    pub config_path: PathBuf,
+    pub output: OutputFormat,
+    /// `--quiet`, for what has more to say than logs.
+    pub quiet: bool,
+    word_lists: WordListArgs,
+    dictionary: OnceCell<WordIndex>,
+    answers: OnceCell<Option<WordIndex>>,
+
+    /// Optional features that were unavailable, reported once at exit.
+    degradations: RefCell<Vec<String>>,
+}
+
+impl App {
+    pub fn new(
+        cache_dir: PathBuf,
+        data_dir: PathBuf,
+        config: Config,
+        config_path: PathBuf,
+        output: OutputFormat,
+        client: LazyClient,
+        word_lists: WordListArgs,
+    ) -> Result<Self> {
+        cache::prepare(&cache_dir)?;
+        Ok(Self {
+            cache_dir,
+            data_dir,
+            client,
+            config,
+            config_path,
+            output,
+            word_lists,
+            dictionary: OnceCell::new(),
+            answers: OnceCell::new(),
+            quiet: false,
+            degradations: RefCell::default(),
+        })
+    }
+
+    /// Where days that aren't cached are fetched from.
+    pub fn nyt(&self) -> Nyt<'_> {
+        Nyt { cache_dir: Some(&self.cache_dir), ..Nyt::new(&self.client, self.nyt_url()) }
+    }
+
+    /// The NYT API, or the mirror of it the flags or config file name.
+    pub fn nyt_url(&self) -> &str {
+        self.config.nyt_url.as_deref().unwrap_or(source::NYT_URL)
+    }
+
+    /// Where the allowed guesses are downloaded from.
+    pub fn dictionary_url(&self) -> &str {
+        self.config.dictionary_url.as_deref().unwrap_or(dictionary::URL)
+    }
+
+    /// Where the answer list is downloaded from.
+    pub fn answers_url(&self) -> &str {
+        self.config.answers_url.as_deref().unwrap_or(dictionary::ANSWERS_URL)
+    }
+
+    /// Whether results are written as JSON lines rather than for people.
+    pub fn json(&self) -> bool {
+        self.output == OutputFormat::Json
+    }
+
+    /// How long the dictionary goes before it's checked for changes.
+    pub fn dictionary_ttl(&self) -> Duration {
+        let days = self.config.dictionary_ttl_days.unwrap_or(dictionary::DEFAULT_TTL_DAYS);
+        Duration::from_secs(days * 24 * 60 * 60)
+    }
+
+    /// The allowed guesses, loaded the first time they're needed.
+    pub fn dictionary(&self, offline: bool) -> Result<&WordIndex> {
+        if let Some(dictionary) = self.dictionary.get() {
+            return Ok(dictionary);
+        }
+        if self.word_lists.dictionary.is_none() && !offline && !self.client.no_network {
+            match dictionary::refresh(&self.cache_dir, self.dictionary_url(), self.dictionary_ttl(), &self.client) {
+                Ok((0, 0)) => (),
+                Ok((added, removed)) => eprintln!("The dictionary was updated: {} words added, {} removed.", added, removed),
+                Err(e) => self.degrade(format!("the dictionary wasn't checked for changes ({})", e)),
+            }
+            if self.word_lists.answers.is_none()
+                && !cache::answers_path(&self.cache_dir).exists()
+                && let Err(e) = dictionary::write_answers(&self.cache_dir, self.answers_url(), &self.client)
+            {
+                self.degrade(format!("the answer list wasn't downloaded ({})", e));
+            }
+        }
+        let started = Instant::now();
+        let dictionary = match self.disk_dictionary(offline)? {
+            Some(dictionary) => dictionary,
+            None => WordIndex::new(self.word_lists.dictionary(&self.cache_dir, self.dictionary_url(), offline, &self.client)?),
+        };
+        debug!(words = dictionary.len(), ms = started.elapsed().as_millis(), "loaded the dictionary");
+        Ok(self.dictionary.get_or_init(|| dictionary))
+    }
+
+    /// With `--dict-index fst`, the cached dictionary looked up in its index on
+    /// disk. An index that isn't there yet, or is out of date, is built for
+    /// next time.
+    fn disk_dictionary(&self, offline: bool) -> Result<Option<WordIndex>> {
+        if self.config.dict_index != DictIndex::Fst || self.word_lists.dictionary.is_some() {
+            return Ok(None);
+        }
+        let extra = self.word_lists.answers()?.unwrap_or_default();
+        if let Ok(Some(words)) = dict_index::load(&self.cache_dir) {
+            return Ok(Some(WordIndex::on_disk(words, extra)));
+        }
+        let mut words = dictionary::read(&self.cache_dir, self.dictionary_url(), offline, &self.client)?;
+        if let Err(e) = dict_index::save(&self.cache_dir, words.iter().map(String::as_str)) {
+            self.degrade(format!("the dictionary index couldn't be built, so the dictionary is in memory ({})", e));
+        }
+        words.extend(extra);
+        Ok(Some(WordIndex::new(words)))
+    }
+
+    /// Notes that an optional feature couldn't be used and what happened
+    /// instead, without interrupting the game.
+    pub fn degrade(&self, what: impl Into<String>) {
+        let what = what.into();
+        info!(what, "degraded");
+        let mut degradations = self.degradations.borrow_mut();
+        if !degradations.contains(&what) {
+            degradations.push(what);
+        }
+    }
+
+    /// Prints everything that was degraded this run as one notice.
+    pub fn report_degradations(&self) {
+        let degradations = self.degradations.borrow();
+        if degradations.is_empty() {
+            return;
+        }
+        eprintln!();
+        eprintln!("Some features were unavailable:");
+        for what in degradations.iter() {
+            eprintln!("  - {}", what);
+        }
+    }
+
+    /// Whether [`App::answers`] has a list to give, found without
+    /// downloading anything or noting that it's missing.
+    pub fn has_answers(&self) -> bool {
+        self.word_lists.answers.is_some()
+            || self.word_lists.dictionary.is_none() && cache::answers_path(&self.cache_dir).exists()
+    }
+
+    /// The words answers are picked from: the custom answers if given, else
+    /// NYT's list unless the dictionary is a custom one that it wouldn't
+    /// match. `None` means any allowed guess can be the answer.
+    pub fn answers(&self) -> Result<Option<&WordIndex>> {
+        if let Some(answers) = self.answers.get() {
+            return Ok(answers.as_ref());
+        }
+        let answers = match self.word_lists.answers()? {
+            Some(answers) => Some(answers),
+            None if self.word_lists.dictionary.is_some() => None,
+            None => {
+                let answers = dictionary::read_answers(&self.cache_dir)?;
+                if answers.is_none() {
+                    self.degrade("the answer list isn't downloaded, so any allowed guess can be the answer. Run `dict update` to get it");
+                }
+                answers
+            },
+        };
+        Ok(self.answers.get_or_init(|| answers.map(WordIndex::new)).as_ref())
+    }
+}
@@ -0,0 +1,58 @@
+use std::{collections::HashMap, io};
+
+use chrono::NaiveDate;
+
+use crate::{
+    DATE_FORMAT,
+    app::App,
+    cache,
+    cli::PlayArgs,
+    error::{Result, WordleError},
+    progress::SavedGame,
+    storage,
+};
+
+fn status(game: Option<&SavedGame>) -> String {
+    match game {
+        None => "unplayed".to_string(),
+        Some(game) if !game.finished => "in progress".to_string(),
+        Some(game) if game.won() => format!("won in {}", game.char_guesses.len()),
+        Some(_) => "lost".to_string(),
+    }
+}
+
+/// Lists the cached days with how they went, then plays the one picked.
+pub fn run(app: &App, mut args: PlayArgs) -> Result<()> {
+    let days = cache::cached_days(&app.cache_dir)?;
+    if days.is_empty() {
+        eprintln!("No cached wordles yet. Run `wordle-rs fetch` to download some.");
+        return Ok(());
+    }
+    let (first, last) = (days[0], days[days.len() - 1]);
+    let games: HashMap<NaiveDate, SavedGame> = storage::open(&app.data_dir)?.games_between(first, last)?.into_iter().collect();
+
+    println!("  #  Day         Status");
+    for (i, day) in days.iter().enumerate() {
+        println!("{:>3}  {}  {}", i + 1, day.format(DATE_FORMAT), status(games.get(day)));
+    }
+    println!();
+    println!("Pick a day to play by its number or date, or press enter to quit:");
+
+    let Some(line) = io::stdin().lines().next() else { return Ok(()) };
+    let line = line.map_err(|source| WordleError::Terminal { context: "reading your pick", source })?;
+    let pick = line.trim();
+    if pick.is_empty() {
+        return Ok(());
+    }
+    let day = match pick.parse::<usize>() {
+        Ok(n) => days.get(n.wrapping_sub(1)).copied(),
+        Err(_) => NaiveDate::parse_from_str(pick, DATE_FORMAT).ok().filter(|d| days.contains(d)),
+    };
+    let Some(day) = day else {
+        eprintln!("'{}' isn't one of the listed days.", pick);
+        return Ok(());
+    };
+
+    args.day = day;
+    crate::play(app, args).map(|_| ())
+}
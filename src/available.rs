@@ -0,0 +1,55 @@
+//! `available`: whether a day's wordle is out yet, for scripts that wait to
+//! fetch or play it. A cached day is out; any other is asked of NYT, or the
+//! mirror `--nyt-url` names. It exits with 5 when the day isn't out.
+
+use chrono::NaiveDate;
+
+use crate::{
+    Puzzle,
+    app::{self, App},
+    cache,
+    cli::AvailableArgs,
+    date_arg,
+    error::{Result, WordleError},
+    output::{self, Event, OutputFormat},
+    read_word_cache,
+    source::WordSource,
+};
+
+fn event(day: NaiveDate, puzzle: Option<&Puzzle>) -> Event<'_> {
+    Event::Available {
+        day,
+        published: puzzle.is_some(),
+        number: puzzle.and_then(|p| p.days_since_launch.or_else(|| date_arg::puzzle_number(day))),
+        editor: puzzle.and_then(|p| p.editor.as_deref()),
+    }
+}
+
+pub fn run(app: &App, args: AvailableArgs) -> Result<()> {
+    let day = args.day;
+    let cached = cache::word_path(&app.cache_dir, day);
+    let puzzle = match cached.exists() {
+        true => read_word_cache(&cached),
+        false => {
+            if app::local_path(app.nyt_url()).is_none() {
+                app.client.online("asking NYT about the wordle")?;
+            }
+            app.nyt().answer_for(day)
+        },
+    };
+    let puzzle = match puzzle {
+        Ok(puzzle) => puzzle,
+        Err(e @ (WordleError::NotPublished(_) | WordleError::Unavailable(..))) => {
+            if app.output == OutputFormat::Json {
+                output::emit(&event(day, None));
+            }
+            return Err(e);
+        },
+        Err(e) => return Err(e),
+    };
+    match app.output {
+        OutputFormat::Json => output::emit(&event(day, Some(&puzzle))),
+        OutputFormat::Plain => println!("{} is out.", puzzle.header(day)),
+    }
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! As many practice words as can be solved before the clock runs out, each
+//! one solved or lost making way for the next.
+
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CurrentWord, GuessOutcome,
+    app::App,
+    cache,
+    cli::BlitzArgs,
+    date_arg,
+    error::{Context, Result, WordleError},
+    format_duration, input, pick_theme, practice_word, prompt,
+};
+
+/// How many of the best runs are listed.
+static HIGH_SCORES: usize = 5;
+
+/// How one blitz went, as kept in [`cache::blitz_path`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub day: NaiveDate,
+    /// The time limit, since only runs against the same clock compare.
+    pub seconds: u64,
+    pub solved: usize,
+    /// Guesses over the solved words.
+    pub guesses: usize,
+}
+
+/// The best runs with a limit of `seconds`: the most words solved, then the
+/// fewest guesses for them, then the earliest.
+fn high_scores(runs: &[Run], seconds: u64) -> Vec<&Run> {
+    let mut best: Vec<&Run> = runs.iter().filter(|run| run.seconds == seconds && run.solved > 0).collect();
+    best.sort_by_key(|run| (std::cmp::Reverse(run.solved), run.guesses, run.day));
+    best.truncate(HIGH_SCORES);
+    best
+}
+
+fn load(data_dir: &Path) -> Result<Vec<Run>> {
+    let path = cache::blitz_path(data_dir);
+    if !path.try_exists().context("checking for blitz scores")? {
+        return Ok(vec![]);
+    }
+    let json = fs::read_to_string(&path).context("reading blitz scores")?;
+    serde_json::from_str(&json).map_err(|e| WordleError::CorruptSave(path, e))
+}
+
+/// Adds a run to the record, returning every run so far.
+fn record(data_dir: &Path, run: Run) -> Result<Vec<Run>> {
+    cache::with_lock(data_dir, || {
+        let mut runs = load(data_dir)?;
+        runs.push(run);
+        fs::create_dir_all(data_dir).context("creating data dir")?;
+        let json = serde_json::to_string(&runs).expect("runs serialize");
+        cache::write_atomic(&cache::blitz_path(data_dir), json.as_bytes()).context("writing blitz scores")?;
+        Ok(runs)
+    })
+}
+
+fn show_high_scores(runs: &[Run], seconds: u64) {
+    let best = high_scores(runs, seconds);
+    if best.is_empty() {
+        println!("No blitz solves against {} yet.", format_duration(Duration::from_secs(seconds)));
+        return;
+    }
+    println!("Blitz high scores, {}", format_duration(Duration::from_secs(seconds)));
+    for (i, run) in best.iter().enumerate() {
+        println!("{}. {}  {:>3} solved  in {} guesses", i + 1, run.day, run.solved, run.guesses);
+    }
+}
+
+/// Plays words until `--seconds` are up. A guess made after the clock ran
+/// out doesn't count, and a run quit early isn't recorded.
+pub fn play(app: &App, args: BlitzArgs) -> Result<()> {
+    if args.scores {
+        show_high_scores(&load(&app.data_dir)?, args.seconds);
+        return Ok(());
+    }
+    let dictionary = app.dictionary(true)?;
+    let theme = pick_theme(app, None)?;
+    let tries = app.config.max_guesses.unwrap_or(6);
+    let mut words = 0;
+    let mut next_word = || {
+        words += 1;
+        practice_word(app, dictionary, args.seed.map(|seed| seed.wrapping_add(words))).map(|answer| CurrentWord::new(answer, tries))
+    };
+
+    let interactive = io::stdin().is_terminal();
+    let mut lines = match interactive {
+        true => prompt::lines(dictionary, app.config.streamer),
+        false => Box::new(io::stdin().lines()),
+    };
+    let limit = Duration::from_secs(args.seconds);
+    println!("Solve as many words as you can in {}.", format_duration(limit));
+    let deadline = Instant::now() + limit;
+    let mut current_word = next_word()?;
+    let (mut solved, mut guesses) = (vec![], 0);
+    let timed_out = loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            break true;
+        }
+        if interactive {
+            println!();
+            current_word.display_word(false, &theme);
+            println!("{} left, {} solved", format_duration(left), solved.len());
+        }
+
+        let Some(line) = lines.next() else { break false };
+        let line = line.map_err(|source| WordleError::Terminal { context: "reading your guess", source })?;
+        if Instant::now() >= deadline {
+            break true;
+        }
+        match current_word.guess(input::normalize(&line), dictionary) {
+            GuessOutcome::InvalidGuess(kind) => println!("{}", kind.message()),
+            GuessOutcome::Continue | GuessOutcome::GameOver => (),
+            GuessOutcome::Win => {
+                println!("Solved {} in {}!", current_word.correct_answer.reveal(), current_word.char_guesses.len());
+                guesses += current_word.char_guesses.len();
+                solved.push(std::mem::replace(&mut current_word, next_word()?).correct_answer.into_revealed());
+            },
+            GuessOutcome::NoTriesLeft => {
+                println!("The word was {}.", current_word.correct_answer.reveal());
+                current_word = next_word()?;
+            },
+        }
+    };
+
+    println!();
+    match timed_out {
+        true => println!("Time's up! The word was {}.", current_word.correct_answer.reveal()),
+        false => println!("Stopped early, the word was {}.", current_word.correct_answer.reveal()),
+    }
+    match solved.as_slice() {
+        [] => println!("No words solved."),
+        words => println!("{} solved: {}.", words.len(), words.join(", ")),
+    }
+    if !timed_out {
+        return Ok(());
+    }
+    let run = Run { day: date_arg::today(), seconds: args.seconds, solved: solved.len(), guesses };
+    let runs = record(&app.data_dir, run)?;
+    println!();
+    show_high_scores(&runs, args.seconds);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(day: u32, seconds: u64, solved: usize, guesses: usize) -> Run {
+        Run { day: NaiveDate::from_ymd_opt(2025, 1, day).unwrap(), seconds, solved, guesses }
+    }
+
+    #[test]
+    fn ranks_by_words_solved_then_guesses() {
+        let runs = [run(1, 180, 3, 12), run(2, 180, 4, 20), run(3, 60, 9, 30), run(4, 180, 3, 10), run(5, 180, 0, 0)];
+        let best: Vec<u32> = high_scores(&runs, 180).iter().map(|run| chrono::Datelike::day(&run.day)).collect();
+        assert_eq!(best, [2, 4, 1]);
+    }
+}
@@ -0,0 +1,111 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache,
+    cli::BotArgs,
+    error::{Context, Result},
+    index::WordIndex,
+    rules::Rules,
+    simulate::{self, Outcome},
+};
+
+/// How the bot fared over all the days it played.
+#[derive(Default)]
+struct Report {
+    played: usize,
+    won: usize,
+    /// How many games were won in 1, 2, 3... guesses.
+    distribution: Vec<usize>,
+}
+
+impl Report {
+    fn add(&mut self, outcome: &Outcome) {
+        self.played += 1;
+        if let Some(guesses) = outcome.guess_count() {
+            self.won += 1;
+            if self.distribution.len() < guesses {
+                self.distribution.resize(guesses, 0);
+            }
+            self.distribution[guesses - 1] += 1;
+        }
+    }
+
+    fn average_guesses(&self) -> f64 {
+        let total: usize = self.distribution.iter().enumerate().map(|(i, &n)| (i + 1) * n).sum();
+        total as f64 / self.won.max(1) as f64
+    }
+
+    fn display(&self) {
+        println!("Played  Win %  Average guesses");
+        println!(
+            "{:>6}  {:>5}  {:>15.3}",
+            self.played,
+            (self.won * 100).checked_div(self.played).unwrap_or(0),
+            self.average_guesses(),
+        );
+
+        if self.distribution.is_empty() {
+            return;
+        }
+        println!();
+        println!("Guess distribution");
+        let most = self.distribution.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in self.distribution.iter().enumerate() {
+            println!("{} | {} {}", i + 1, "#".repeat(count * 30 / most), count);
+        }
+    }
+}
+
+/// How many guesses the bot took on each answer in its last run, `None` for
+/// the ones it lost, kept for the difficulty shown after a game.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Simulated {
+    pub strategy: String,
+    pub guesses: BTreeMap<String, Option<usize>>,
+}
+
+impl Simulated {
+    /// The last run's, if there was one.
+    pub fn load(cache_dir: &Path) -> Option<Self> {
+        serde_json::from_str(&fs::read_to_string(cache::bot_path(cache_dir)).ok()?).ok()
+    }
+
+    /// The average guesses of the answers it won, with how many those were.
+    pub fn average(&self) -> Option<(f64, usize)> {
+        let won: Vec<usize> = self.guesses.values().flatten().copied().collect();
+        (!won.is_empty()).then(|| (won.iter().sum::<usize>() as f64 / won.len() as f64, won.len()))
+    }
+}
+
+/// Plays the cached wordles with the given strategy, many at once.
+pub fn run(cache_dir: &Path, args: BotArgs, answers: &WordIndex, dictionary: &WordIndex) -> Result<()> {
+    let mut days = cache::cached_days(cache_dir)?;
+    if let Some(n) = args.days {
+        days.drain(..days.len().saturating_sub(n));
+    }
+    if days.is_empty() {
+        eprintln!("No cached wordles to play. Run `wordle-rs fetch` first.");
+        return Ok(());
+    }
+
+    let solutions = days.iter()
+        .map(|&day| crate::read_word_cache(&cache::word_path(cache_dir, day)).map(|puzzle| puzzle.solution.into_revealed()))
+        .collect::<Result<Vec<String>>>()?;
+    let ranked = simulate::Ranked::new(answers, dictionary, args.strategy);
+    let mut report = Report::default();
+    let mut simulated = Simulated { strategy: format!("{:?}", args.strategy).to_lowercase(), ..Simulated::default() };
+    // A game stops early when no dictionary word fits, which happens when
+    // the answer isn't in it.
+    for (day, outcome) in days.iter().zip(simulate::run(&ranked, &solutions, Rules::default().tries)) {
+        eprintln!("{}: {} {}", day, if outcome.won { "won" } else { "lost" }, outcome.guesses.join(" "));
+        report.add(&outcome);
+        simulated.guesses.insert(outcome.answer.clone(), outcome.guess_count());
+    }
+    let json = serde_json::to_string(&simulated).expect("results serialize");
+    cache::write_atomic(&cache::bot_path(cache_dir), json.as_bytes()).context("writing the bot's results")?;
+
+    report.display();
+    Ok(())
+}
@@ -0,0 +1,331 @@
+//! Where things live in the cache dir:
+//!
+//! ```text
+//! index.json                 which days have their word cached
+//! lock                       held while writing, see [`with_lock`]
+//! words/YYYY-MM-DD           the word of each day
+//! words/.key                 the key of the words scrambled with --encrypt-cache
+//! headers/YYYY-MM-DD.json    what NYT said about each day, to ask again politely
+//! dictionary/words.txt       the allowed guesses
+//! dictionary/answers.txt     the words NYT picks answers from
+//! dictionary/validators.json what the server said about them, to check for changes
+//! dictionary/frequencies.txt how often words are used
+//! definitions/<word>.json    what answers mean, looked up with --define
+//! bot.json                   how many guesses the last `bot` run took on each answer
+//! ```
+//!
+//! and in the data dir, which cache cleaners leave alone:
+//!
+//! ```text
+//! lock                       held while writing saved games
+//! results/YYYY-MM-DD.json    the game played on each day
+//! results.sqlite3            the same, with `storage = "sqlite"`
+//! duels.json                 the outcome of every duel
+//! sessions/<name>/           the same for each --session
+//! ```
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    DATE_FORMAT,
+    error::{Context, Result},
+};
+
+/// Bumped whenever the layout changes, so old caches can be migrated.
+static VERSION: u32 = 1;
+
+pub fn words_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("words")
+}
+
+pub fn word_path(cache_dir: &Path, day: NaiveDate) -> PathBuf {
+    words_dir(cache_dir).join(day.format(DATE_FORMAT).to_string())
+}
+
+/// What NYT said about a day, see [`crate::source::Nyt`].
+pub fn headers_path(cache_dir: &Path, day: NaiveDate) -> PathBuf {
+    cache_dir.join("headers").join(format!("{}.json", day.format(DATE_FORMAT)))
+}
+
+pub fn results_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("results")
+}
+
+pub fn database_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("results.sqlite3")
+}
+
+pub fn duels_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("duels.json")
+}
+
+pub fn blitz_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("blitz.json")
+}
+
+pub fn cross_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("cross.json")
+}
+
+pub fn limits_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("played-today.json")
+}
+
+pub fn dictionary_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("dictionary").join("words.txt")
+}
+
+/// The dictionary compiled by [`crate::compiled`] for faster loading.
+pub fn compiled_dictionary_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("dictionary").join("words.bin")
+}
+
+/// The dictionary built by [`crate::dict_index`] for `--dict-index fst`.
+pub fn dictionary_index_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("dictionary").join("words.fst")
+}
+
+pub fn definition_path(cache_dir: &Path, word: &str) -> PathBuf {
+    cache_dir.join("definitions").join(format!("{}.json", word))
+}
+
+pub fn answers_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("dictionary").join("answers.txt")
+}
+
+pub fn dictionary_validators_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("dictionary").join("validators.json")
+}
+
+pub fn frequencies_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("dictionary").join("frequencies.txt")
+}
+
+/// What the last `bot` run took, see [`crate::bot::Simulated`].
+pub fn bot_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("bot.json")
+}
+
+/// What `fetch --if-stale` did, see [`crate::prefetch::if_stale`].
+pub fn fetch_log_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("fetch.log")
+}
+
+fn lock_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("lock")
+}
+
+fn index_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("index.json")
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Index {
+    version: u32,
+    days: BTreeSet<NaiveDate>,
+}
+
+impl Index {
+    /// Reads the index, or rebuilds it from the words dir if it's missing or
+    /// unreadable.
+    fn load(cache_dir: &Path) -> Result<Self> {
+        match fs::read_to_string(index_path(cache_dir)) {
+            Ok(json) => if let Ok(index) = serde_json::from_str(&json) {
+                return Ok(index);
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+            Err(e) => return Err(e).context("reading cache index"),
+        }
+        Self::rebuild(cache_dir)
+    }
+
+    fn rebuild(cache_dir: &Path) -> Result<Self> {
+        let mut index = Self { version: VERSION, days: BTreeSet::new() };
+        for entry in fs::read_dir(words_dir(cache_dir)).context("listing the word cache")? {
+            let entry = entry.context("listing the word cache")?;
+            if let Some(name) = entry.file_name().to_str()
+                && let Ok(day) = NaiveDate::parse_from_str(name, DATE_FORMAT)
+            {
+                index.days.insert(day);
+            }
+        }
+        index.save(cache_dir)?;
+        Ok(index)
+    }
+
+    fn save(&self, cache_dir: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).expect("the index serializes");
+        write_atomic(&index_path(cache_dir), json.as_bytes()).context("writing cache index")
+    }
+}
+
+/// Where words and the dictionary are cached when no dir was picked.
+pub fn default_dir() -> PathBuf {
+    dirs::cache_dir().unwrap_or_else(std::env::temp_dir).join("wordle-rs")
+}
+
+/// Runs `f` holding the cache dir's lock, so that another wordle-rs running
+/// at the same time, e.g. a prefetch, can't interleave its writes with these.
+/// The lock isn't reentrant, so `f` mustn't take it again.
+pub fn with_lock<T>(cache_dir: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    fs::create_dir_all(cache_dir).context("creating the cache lock's dir")?;
+    let file = fs::File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path(cache_dir))
+        .context("opening the cache lock")?;
+    match file.try_lock() {
+        Ok(()) => (),
+        Err(fs::TryLockError::WouldBlock) => {
+            eprintln!("Another wordle-rs is writing to {}, waiting for it to finish.", cache_dir.display());
+            file.lock().context("locking the cache")?;
+        },
+        Err(fs::TryLockError::Error(e)) => return Err(e).context("locking the cache"),
+    }
+    // The lock is released when the file is closed.
+    f()
+}
+
+/// Writes a file through a temporary one that is renamed into place, so an
+/// interrupted run leaves either the old file or the new one, never half of it.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("file");
+    let temp = path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()));
+    let result = fs::File::create(&temp)
+        .and_then(|mut f| f.write_all(contents).and_then(|_| f.sync_all()))
+        .and_then(|_| fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Creates the layout, moving the files of caches from before it existed,
+/// which were all in the top of the cache dir, into place.
+pub fn prepare(cache_dir: &Path) -> Result<()> {
+    fs::create_dir_all(cache_dir).context("creating cache dir")?;
+    if index_path(cache_dir).try_exists().context("checking for cache index")? {
+        return Ok(());
+    }
+    with_lock(cache_dir, || migrate(cache_dir))
+}
+
+fn migrate(cache_dir: &Path) -> Result<()> {
+    // Another run may have migrated while this one waited for the lock.
+    if index_path(cache_dir).try_exists().context("checking for cache index")? {
+        return Ok(());
+    }
+
+    // The old dictionary file is in the way of the new dictionary dir.
+    let old_dictionary = cache_dir.join("dictionary");
+    let moved_dictionary = cache_dir.join("dictionary.old");
+    if old_dictionary.is_file() {
+        fs::rename(&old_dictionary, &moved_dictionary).context("migrating the dictionary")?;
+    }
+    for dir in [words_dir(cache_dir), old_dictionary] {
+        fs::create_dir_all(dir).context("creating cache dir")?;
+    }
+    if moved_dictionary.is_file() {
+        fs::rename(&moved_dictionary, dictionary_path(cache_dir)).context("migrating the dictionary")?;
+    }
+    let old_frequencies = cache_dir.join("frequencies");
+    if old_frequencies.is_file() {
+        fs::rename(old_frequencies, frequencies_path(cache_dir)).context("migrating the word frequencies")?;
+    }
+
+    let old_games = cache_dir.join("games");
+    if old_games.is_dir() {
+        fs::create_dir_all(results_dir(cache_dir)).context("migrating saved games")?;
+        for entry in fs::read_dir(&old_games).context("migrating saved games")? {
+            let entry = entry.context("migrating saved games")?;
+            fs::rename(entry.path(), results_dir(cache_dir).join(entry.file_name())).context("migrating saved games")?;
+        }
+        fs::remove_dir(old_games).context("migrating saved games")?;
+    }
+
+    for entry in fs::read_dir(cache_dir).context("migrating the word cache")? {
+        let entry = entry.context("migrating the word cache")?;
+        if let Some(name) = entry.file_name().to_str()
+            && NaiveDate::parse_from_str(name, DATE_FORMAT).is_ok()
+            && entry.path().is_file()
+        {
+            fs::rename(entry.path(), words_dir(cache_dir).join(name)).context("migrating the word cache")?;
+        }
+    }
+
+    Index::rebuild(cache_dir).map(|_| ())
+}
+
+/// Moves saved games out of the cache dir, where they were kept before they
+/// got a data dir of their own, unless the two are the same dir.
+pub fn migrate_results(cache_dir: &Path, data_dir: &Path) -> Result<()> {
+    let (old, new) = (results_dir(cache_dir), results_dir(data_dir));
+    if old == new || !old.is_dir() {
+        return Ok(());
+    }
+    fs::create_dir_all(&new).context("creating data dir")?;
+    with_lock(data_dir, || {
+        let mut moved = 0;
+        for entry in fs::read_dir(&old).context("migrating saved games")? {
+            let entry = entry.context("migrating saved games")?;
+            let target = new.join(entry.file_name());
+            if target.try_exists().context("migrating saved games")? {
+                continue;
+            }
+            // The data dir may be on another file system than the cache.
+            fs::rename(entry.path(), &target)
+                .or_else(|_| fs::copy(entry.path(), &target).and_then(|_| fs::remove_file(entry.path())))
+                .context("migrating saved games")?;
+            moved += 1;
+        }
+        // Games that were in both places stay behind rather than overwrite.
+        let _ = fs::remove_dir(&old);
+        if moved > 0 {
+            eprintln!("Moved {} saved games from {} to {}.", moved, old.display(), new.display());
+        }
+        Ok(())
+    })
+}
+
+/// The days whose words are in the cache, oldest first.
+pub fn cached_days(cache_dir: &Path) -> Result<Vec<NaiveDate>> {
+    Ok(Index::load(cache_dir)?.days.into_iter().collect())
+}
+
+/// Records that a day's word was cached.
+pub fn add_day(cache_dir: &Path, day: NaiveDate) -> Result<()> {
+    with_lock(cache_dir, || {
+        let mut index = Index::load(cache_dir)?;
+        if index.days.insert(day) {
+            index.save(cache_dir)?;
+        }
+        Ok(())
+    })
+}
+
+/// Removes the cached words of `days`, which are fetched again if needed.
+pub fn remove_days(cache_dir: &Path, days: &[NaiveDate]) -> Result<()> {
+    with_lock(cache_dir, || {
+        let mut index = Index::load(cache_dir)?;
+        for &day in days {
+            match fs::remove_file(word_path(cache_dir, day)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e).context("removing a cached word"),
+                _ => index.days.remove(&day),
+            };
+            match fs::remove_file(headers_path(cache_dir, day)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e).context("removing a cached word"),
+                _ => (),
+            }
+        }
+        index.save(cache_dir)
+    })
+}
@@ -0,0 +1,83 @@
+use crate::{
+    app::App,
+    cli::PlayArgs,
+    error::Result,
+    progress,
+};
+
+/// How the days played so far went.
+#[derive(Default)]
+struct Score {
+    won: usize,
+    lost: usize,
+    /// Guesses over the won games.
+    guesses: usize,
+}
+
+impl Score {
+    fn add(&mut self, won: bool, guesses: usize) {
+        match won {
+            true => {
+                self.won += 1;
+                self.guesses += guesses;
+            },
+            false => self.lost += 1,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = format!("{} won, {} lost", self.won, self.lost);
+        if self.won > 0 {
+            summary.push_str(&format!(", {:.1} guesses on average", self.guesses as f64 / self.won as f64));
+        }
+        summary
+    }
+}
+
+/// Plays the cached days that aren't finished one after the other, oldest
+/// first, with the score so far after each. Quitting a game ends the session,
+/// and it's resumed from there the next time.
+pub fn run(app: &App, args: PlayArgs) -> Result<()> {
+    let days = progress::unfinished_cached_days(&app.cache_dir, &app.data_dir)?;
+    if days.is_empty() {
+        eprintln!("You're all caught up, every cached wordle is finished.");
+        return Ok(());
+    }
+    eprintln!("{} days to catch up on, from {} to {}.", days.len(), days[0], days[days.len() - 1]);
+
+    let mut score = Score::default();
+    for (i, &day) in days.iter().enumerate() {
+        if !app.json() {
+            println!();
+            println!("Day {} of {}: {}", i + 1, days.len(), day);
+        }
+        let current_word = crate::play(app, PlayArgs { day, ..args.clone() })?;
+        if !current_word.is_over() {
+            eprintln!("Stopped at {}. Run catchup again to carry on from there.", day);
+            break;
+        }
+        score.add(current_word.is_solved(), current_word.char_guesses.len());
+        if !app.json() {
+            println!("So far: {}.", score.summary());
+        }
+    }
+    if !app.json() && score.won + score.lost == days.len() {
+        println!();
+        println!("All caught up! {}.", score.summary());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_the_guesses_of_won_games() {
+        let mut score = Score::default();
+        score.add(true, 3);
+        score.add(false, 6);
+        score.add(true, 4);
+        assert_eq!(score.summary(), "2 won, 1 lost, 3.5 guesses on average");
+    }
+}
@@ -0,0 +1,117 @@
+//! Practice words in a chain: each answer starts with the last letter of the
+//! one before, and the points of every round add up.
+
+use std::io::{self, IsTerminal};
+
+use rand::{SeedableRng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::{
+    CurrentWord, GuessOutcome,
+    app::App,
+    cli::ChainArgs,
+    error::{Result, WordleError},
+    index::WordIndex,
+    input, pick_theme, prompt,
+};
+
+/// The answer of the next round, not one played already, starting with the
+/// last letter of `previous` unless it's the first. `None` when the chain
+/// can't go on.
+fn next_answer(words: &[&String], previous: Option<&str>, played: &[String], seed: u64) -> Option<String> {
+    let last = previous.and_then(|word| word.chars().last());
+    let mut candidates: Vec<&&String> = words
+        .iter()
+        .filter(|word| last.is_none_or(|ch| word.starts_with(ch)) && !played.contains(word))
+        .collect();
+    candidates.sort();
+    candidates.choose(&mut StdRng::seed_from_u64(seed)).map(|word| word.to_string())
+}
+
+/// A round's points: one for the win, and one more for each try left.
+fn points(current_word: &CurrentWord) -> u32 {
+    match current_word.won() {
+        true => current_word.tries + 1,
+        false => 0,
+    }
+}
+
+/// Plays `--rounds` words, each one started once the last is solved or lost.
+pub fn play(app: &App, args: ChainArgs) -> Result<()> {
+    let dictionary = app.dictionary(true)?;
+    let answers: &WordIndex = app.answers()?.unwrap_or(dictionary);
+    let words: Vec<&String> = answers.iter().filter(|w| w.len() == 5).collect();
+    if words.is_empty() {
+        return Err(WordleError::EmptyDictionary);
+    }
+    let theme = pick_theme(app, None)?;
+    let tries = app.config.max_guesses.unwrap_or(6);
+
+    let interactive = io::stdin().is_terminal();
+    let mut lines = match interactive {
+        true => prompt::lines(dictionary, app.config.streamer),
+        false => Box::new(io::stdin().lines()),
+    };
+    let mut played: Vec<String> = vec![];
+    let mut score = 0;
+    for round in 1..=args.rounds {
+        let seed = args.seed.map_or_else(rand::random, |seed| seed.wrapping_add(round.into()));
+        let Some(answer) = next_answer(&words, played.last().map(String::as_str), &played, seed) else {
+            println!("\nNo word starts with the last letter of {}, that's the end of the chain.", played.last().expect("the first round has words"));
+            break;
+        };
+        let mut current_word = CurrentWord::new(answer, tries);
+        println!();
+        match played.last().and_then(|word| word.chars().last()) {
+            Some(letter) => println!("Round {} of {}, the word starts with {}.", round, args.rounds, letter.to_ascii_uppercase()),
+            None => println!("Round {} of {}.", round, args.rounds),
+        }
+        loop {
+            if interactive {
+                current_word.display_word(false, &theme);
+            }
+            let Some(line) = lines.next() else {
+                println!("Stopped early, the word was {}. {} points.", current_word.correct_answer.reveal(), score);
+                return Ok(());
+            };
+            let line = line.map_err(|source| WordleError::Terminal { context: "reading your guess", source })?;
+            match current_word.guess(input::normalize(&line), dictionary) {
+                GuessOutcome::InvalidGuess(kind) => println!("{}", kind.message()),
+                GuessOutcome::Continue | GuessOutcome::GameOver => (),
+                GuessOutcome::Win | GuessOutcome::NoTriesLeft => break,
+            }
+        }
+        score += points(&current_word);
+        match current_word.won() {
+            true => println!("Solved {} in {}! {} points so far.", current_word.correct_answer.reveal(), current_word.char_guesses.len(), score),
+            false => println!("The word was {}. {} points so far.", current_word.correct_answer.reveal(), score),
+        }
+        played.push(current_word.correct_answer.into_revealed());
+    }
+
+    println!("\n{} points over {}.", score, played.join(" → "));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_each_answer_to_the_last() {
+        let words: Vec<String> = ["crane", "eagle", "earth", "hotel"].iter().map(|w| w.to_string()).collect();
+        let words: Vec<&String> = words.iter().collect();
+        let played = vec!["crane".to_string()];
+        for seed in 0..10 {
+            assert!(next_answer(&words, Some("crane"), &played, seed).unwrap().starts_with('e'));
+        }
+        let played = vec!["crane".to_string(), "eagle".to_string()];
+        assert_eq!(next_answer(&words, Some("eagle"), &played, 0).as_deref(), Some("earth"));
+        assert_eq!(next_answer(&words, Some("hotel"), &played, 0), None);
+
+        let dictionary: WordIndex = ["crane", "slate"].iter().map(|w| w.to_string()).collect();
+        let mut current_word = CurrentWord::new("crane".to_string(), 6);
+        current_word.guess("slate".to_string(), &dictionary);
+        current_word.guess("crane".to_string(), &dictionary);
+        assert_eq!(points(&current_word), 5);
+    }
+}
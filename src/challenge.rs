@@ -0,0 +1,79 @@
+//! Words picked by a friend, passed around as a code that doesn't give the
+//! word away at a glance. The code is the word mixed with a fixed key and
+//! written as base64, which stops peeking but not anyone set on decoding it.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+
+use crate::{
+    app::App,
+    cli::{ChallengePlayArgs, PlayArgs},
+    error::{Result, WordleError},
+};
+
+const VERSION: u8 = 1;
+
+static KEY: &[u8] = b"wordle-rs challenge";
+
+/// The key byte the letter at `i` is mixed with.
+fn key(i: usize) -> u8 {
+    KEY[i % KEY.len()].wrapping_add(i as u8 * 31)
+}
+
+/// A byte telling a mistyped code apart from a real one.
+fn check(word: &[u8]) -> u8 {
+    word.iter().fold(VERSION, |sum, &b| sum.rotate_left(3) ^ b)
+}
+
+/// The code of `word`, which has to be 4 to 8 lowercase letters.
+pub fn encode(word: &str) -> String {
+    let mut bytes = vec![VERSION];
+    bytes.extend(word.bytes().enumerate().map(|(i, b)| b ^ key(i)));
+    bytes.push(check(word.as_bytes()));
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Reads a challenge code back as its word, for `challenge play`.
+pub fn parse_code(code: &str) -> std::result::Result<String, String> {
+    let invalid = || format!("'{}' isn't a challenge code", code);
+    let bytes = URL_SAFE_NO_PAD.decode(code.trim()).map_err(|_| invalid())?;
+    let [VERSION, mixed @ .., sum] = bytes.as_slice() else { return Err(invalid()) };
+    let word: Vec<u8> = mixed.iter().enumerate().map(|(i, b)| b ^ key(i)).collect();
+    if check(&word) != *sum || !(4..=8).contains(&word.len()) || !word.iter().all(u8::is_ascii_lowercase) {
+        return Err(invalid());
+    }
+    Ok(String::from_utf8(word).expect("the letters are ascii"))
+}
+
+/// Prints the code of a word, if it's one that can be guessed.
+pub fn create(app: &App, word: &str) -> Result<()> {
+    if !app.dictionary(false)?.contains(word) {
+        return Err(WordleError::UnguessableChallenge(word.to_string()));
+    }
+    let code = encode(word);
+    println!("{}", code);
+    eprintln!("Send it to a friend, who plays it with `wordle-rs challenge play {}`.", code);
+    Ok(())
+}
+
+/// Plays the word of a challenge code like any fixed word.
+pub fn play(app: &App, args: ChallengePlayArgs) -> Result<()> {
+    crate::play(app, PlayArgs { word: Some(args.code), ..args.play }).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_hide_the_word_and_give_it_back() {
+        for word in ["crane", "abbey", "quizzing"] {
+            let code = encode(word);
+            assert!(!code.contains(word));
+            assert_eq!(parse_code(&code).as_deref(), Ok(word));
+        }
+        assert!(parse_code("crane").is_err());
+        let mut mistyped = encode("crane").into_bytes();
+        mistyped[3] = if mistyped[3] == b'A' { b'B' } else { b'A' };
+        assert!(parse_code(std::str::from_utf8(&mistyped).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,1079 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDate, NaiveTime};
+use clap::Parser;
+use clap_complete::ArgValueCandidates;
+
+use crate::{
+    anagram, challenge, completions, config, continuation, daily_random, date_arg, difficulty, i18n, obscurity, repeats, replay, scheduler, watch,
+    dict_index::DictIndex,
+    output::OutputFormat,
+    share,
+    source::{self, SourceChoice},
+    strategy,
+};
+
+static EXIT_CODES: &str = "Exit codes:
+  0  success
+  2  network error
+  3  cache IO error
+  4  unparseable NYT response or board
+  5  the day's wordle is not published yet
+  6  no usable dictionary
+  7  terminal error
+  8  the day's wordle is already finished
+  9  invalid config file or theme
+  10 today's wordle isn't finished (status)
+  11 a check failed (doctor, --replay-transcript)
+  12 a play limit of the config file was reached";
+
+/// Wordle in Rust.
+#[derive(clap::Parser, Debug)]
+#[command(version, about, long_about = None, after_help = EXIT_CODES)]
+pub struct Cli {
+    /// The directory to cache words and the dictionary in [default: your cache dir]
+    #[arg(short, long, global = true)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// The directory to keep your saved games in [default: the cache dir if one
+    /// is given, else your data dir]
+    #[arg(long, global = true)]
+    pub data_dir: Option<PathBuf>,
+
+    /// When to use colors: auto leaves them off for NO_COLOR, TERM=dumb and
+    /// output that isn't a terminal, and shows [c] (r) marks instead
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// The config file to read defaults from [default: wordle-rs/config.toml in
+    /// your config dir, e.g. ~/.config or %APPDATA%]
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// How to write results: plain text, or one JSON object per line for scripts
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Plain)]
+    pub output: OutputFormat,
+
+    /// How many seconds a download may take before it's given up on [default: 10]
+    #[arg(long, global = true, value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// The time zone whose date decides today's wordle. Each day's comes out
+    /// at midnight there [default: your local time zone]
+    #[arg(long, global = true, value_parser = date_arg::parse_timezone)]
+    pub timezone: Option<chrono_tz::Tz>,
+
+    /// The language of the messages while playing [default: the one of your
+    /// locale, if there's a translation]
+    #[arg(long, global = true, value_enum)]
+    pub lang: Option<i18n::Lang>,
+
+    /// Where to get the wordles from instead of NYT's API: a mirror of it,
+    /// or a file:// directory of <day>.json files
+    #[arg(long, global = true, value_name = "URL")]
+    pub nyt_url: Option<String>,
+
+    /// Where to download the dictionary of allowed guesses from, http(s) or
+    /// file:// [default: a gist]
+    #[arg(long, global = true, value_name = "URL")]
+    pub dictionary_url: Option<String>,
+
+    /// Where to download the answer list from, http(s) or file:// [default: a
+    /// gist]
+    #[arg(long, global = true, value_name = "URL")]
+    pub answers_url: Option<String>,
+
+    /// Play as someone else on this computer, e.g. work or kids: each session
+    /// has its own games, stats and settings in sessions/NAME.toml next to the
+    /// config file, over the ones there
+    #[arg(long, global = true, value_name = "NAME", value_parser = config::parse_session)]
+    pub session: Option<String>,
+
+    /// Whether to only use what is already cached, without any downloads
+    #[arg(long, global = true, default_value_t = false)]
+    pub no_network: bool,
+
+    /// Whether to play for a stream: the answer is covered until a key is
+    /// pressed, guesses are masked while typed and the cache's path is left
+    /// out of errors
+    #[arg(long, global = true, default_value_t = false)]
+    pub streamer: bool,
+
+    /// Whether to keep the cached words scrambled with a key of this
+    /// computer's, so looking in the cache dir doesn't spoil the days fetched
+    /// ahead of time
+    #[arg(long, global = true, default_value_t = false)]
+    pub encrypt_cache: bool,
+
+    /// How the dictionary is kept while playing: memory, or fst to look
+    /// guesses up in an index on disk [default: memory]
+    #[arg(long, global = true, value_enum)]
+    pub dict_index: Option<DictIndex>,
+
+    /// Log more of what happens behind the board to stderr: -v for what's
+    /// fetched, -vv for cache hits, load times and grading, -vvv for everything
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only log errors, and leave out the notice of unavailable features
+    #[arg(short, long, global = true, default_value_t = false)]
+    pub quiet: bool,
+
+    /// The file to append the logs to instead of stderr
+    #[arg(long, global = true)]
+    pub log_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub word_lists: WordListArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Playing is the default when no subcommand is given.
+    #[command(flatten)]
+    pub play: PlayArgs,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn should_colorize(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && std::env::var_os("TERM").is_none_or(|term| term != "dumb")
+                    && std::io::IsTerminal::is_terminal(&std::io::stdout())
+            },
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct WordListArgs {
+    /// A file of allowed guesses, one per line, instead of the downloaded dictionary
+    #[arg(long, global = true)]
+    pub dictionary: Option<PathBuf>,
+
+    /// A file of words to pick practice answers from, one per line [default: NYT's answer list]
+    #[arg(long, global = true)]
+    pub answers: Option<PathBuf>,
+}
+
+impl Cli {
+    /// Parses the command line. Days like today and yesterday depend on the
+    /// time zone, which can be given on the same line or in the config file,
+    /// so when one is the line is parsed again once it's known.
+    pub fn parse_with_timezone() -> Self {
+        Self::parse_in_timezone().settle_days()
+    }
+
+    fn parse_in_timezone() -> Self {
+        let cli = Self::parse();
+        let config_path = cli.config.clone().unwrap_or_else(config::default_path);
+        // A broken config file is reported once the command runs.
+        let configured = config::Config::load_session(&config_path, cli.session.as_deref()).ok().and_then(|c| c.timezone);
+        match cli.timezone.or(configured) {
+            Some(zone) => {
+                date_arg::set_timezone(zone);
+                Self::parse()
+            },
+            None => cli,
+        }
+    }
+
+    /// Turns `--puzzle-number` into the day it names, so the rest of the
+    /// program only has `--day` to look at.
+    fn settle_days(mut self) -> Self {
+        self.play.settle_day();
+        if let Some(Command::Play(args) | Command::Daily(args) | Command::Anagram(args) | Command::Archive(args) | Command::Catchup(args)) = &mut self.command {
+            args.settle_day();
+        }
+        if let Some(Command::Leaderboard(LeaderboardArgs { command: LeaderboardCommand::Add { play, .. }, .. })) = &mut self.command {
+            play.settle_day();
+        }
+        self
+    }
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Play a wordle (the default)
+    Play(PlayArgs),
+
+    /// Play the day's wordle, then see how your guesses did and your stats
+    Daily(PlayArgs),
+
+    /// List the cached days and how they went, and pick one to play
+    Archive(PlayArgs),
+
+    /// Play the day's anagram: find the word its scrambled letters spell
+    Anagram(PlayArgs),
+
+    /// Play every cached day you haven't finished, oldest first, keeping score as you go
+    Catchup(PlayArgs),
+
+    /// Show a finished game a row at a time, e.g. to friends
+    Replay(ReplayArgs),
+
+    /// Pick a word for a friend to play, passed on as a code that doesn't give it away
+    Challenge {
+        #[command(subcommand)]
+        command: ChallengeCommand,
+    },
+
+    /// Two players take turns guessing the same practice word, first to solve it wins
+    Duel(DuelArgs),
+
+    /// Solve as many practice words as you can before the time runs out
+    Blitz(BlitzArgs),
+
+    /// Practice words in a row, each starting with the last letter of the one before
+    Chain(ChainArgs),
+
+    /// A mini crossword of answers a day, each guess played on one of its words
+    Cross(CrossArgs),
+
+    /// Wait for someone to join and race them on the same practice word
+    Host(HostArgs),
+
+    /// Race someone who is hosting a game
+    Join(JoinArgs),
+
+    /// Answer a JSON API over HTTP for playing from other programs
+    Serve(ServeArgs),
+
+    /// Download wordles ahead of time, so they can be played offline
+    Fetch(FetchArgs),
+
+    /// Wait for the next wordle to come out and send a desktop notification when it does
+    Watch(WatchArgs),
+
+    /// Have systemd (Linux) or launchd (macOS) fetch tomorrow's wordle every
+    /// day, so it can always be played offline
+    InstallScheduler(InstallSchedulerArgs),
+
+    /// Manage the dictionary of allowed guesses
+    Dict {
+        #[command(subcommand)]
+        command: DictCommand,
+    },
+
+    /// See what's in the cache dir and clear out what isn't needed
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+
+    /// Show statistics of your finished daily games
+    Stats {
+        #[command(subcommand)]
+        command: Option<StatsCommand>,
+
+        /// Whether to show the games played with --daily-random instead
+        #[arg(long, global = true, default_value_t = false)]
+        daily_random: bool,
+
+        /// Whether to show the anagram games instead
+        #[arg(long, global = true, conflicts_with = "daily_random", default_value_t = false)]
+        anagram: bool,
+
+        /// Whether to draw charts of the stats in the terminal, with a calendar
+        /// of the last months
+        #[arg(long, default_value_t = false)]
+        dashboard: bool,
+    },
+
+    /// Tell whether today's wordle is finished, for shell prompts and reminders
+    Status,
+
+    /// Tell whether a day's wordle is out yet, exiting with 5 when it isn't
+    Available(AvailableArgs),
+
+    /// Check that the cache, the word lists, the network, colors and the
+    /// config file are in order, with hints for what isn't
+    Doctor,
+
+    /// List the keys of the terminal UI, which the config file can change
+    Keys,
+
+    /// Rewrite your saved games in the smaller format
+    Compact,
+
+    /// Write your saved games as CSV or JSON, for spreadsheets and other trackers
+    Export(ExportArgs),
+
+    /// Add games played on the official site to your stats, from their share text
+    Import(ImportArgs),
+
+    /// Write the cached wordles and word lists to a .tar.zst archive, for
+    /// computers that can't download them
+    ExportArchive(ExportArchiveArgs),
+
+    /// Cache the wordles and word lists of an archive export-archive wrote
+    ImportArchive(ImportArchiveArgs),
+
+    /// Sync your saved games with your other computers through a git
+    /// repository or a WebDAV folder
+    Sync(SyncArgs),
+
+    /// Add your daily games to a leaderboard file shared with others, and rank everyone on it
+    Leaderboard(LeaderboardArgs),
+
+    /// Manage the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// Print the script setting up completion for your shell, e.g.
+    /// `source <(wordle-rs completions bash)` in ~/.bashrc
+    Completions {
+        shell: clap_complete::Shell,
+    },
+
+    /// Print the man page, for packaging
+    #[command(hide = true)]
+    Man {
+        /// Write the page and one for every subcommand to this dir instead
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Suggest the next guesses from the feedback you got, e.g. on the official site
+    Solve(SolveArgs),
+
+    /// Finish a board started somewhere else, e.g. in the official app, from
+    /// its guesses and colors
+    Continue(ContinueArgs),
+
+    /// Let a solver play the cached wordles and report how well it did
+    Bot(BotArgs),
+
+    /// Show how often letters are in each position of the answers, and the openers that reveal most
+    Analyze(AnalyzeArgs),
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum DictCommand {
+    /// Download the dictionary, the answer list and the word frequencies again
+    Update,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Remove leftover temporary files, a stale compiled dictionary and,
+    /// with --keep-days, the words of old days
+    Gc(GcArgs),
+
+    /// Show how many files of each kind the cache has and their size
+    Info,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct GcArgs {
+    /// Also remove the words of days more than this many days ago. They're
+    /// downloaded again when played
+    #[arg(long)]
+    pub keep_days: Option<u64>,
+
+    /// Only list what would be removed
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum StatsCommand {
+    /// Check the saved games the stats are tallied from, and fix files that
+    /// aren't games, several games for one day and games that can't have
+    /// been played
+    Rebuild {
+        /// Only list what's wrong
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum ChallengeCommand {
+    /// Print the code of a word
+    Create {
+        #[arg(value_parser = source::parse_word)]
+        word: String,
+    },
+
+    /// Play the word of a code a friend sent
+    Play(Box<ChallengePlayArgs>),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ChallengePlayArgs {
+    /// The code, as `challenge create` printed it
+    #[arg(value_parser = challenge::parse_code)]
+    pub code: String,
+
+    #[command(flatten)]
+    pub play: PlayArgs,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Write a commented config file to start from
+    Init,
+
+    /// Answer a few questions to write the config file, as on the first run
+    Setup,
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct PlayArgs {
+    /// The day of the wordle to play, e.g. 2025-01-31, yesterday, -3 or monday
+    #[arg(
+        short,
+        long,
+        value_parser = date_arg::parse_day,
+        default_value_t = date_arg::today(),
+        add = ArgValueCandidates::new(completions::cached_days)
+    )]
+    pub day: NaiveDate,
+
+    /// The number of the wordle to play instead of its day, e.g. 1123
+    #[arg(long, value_parser = date_arg::parse_puzzle_number, conflicts_with = "day")]
+    pub puzzle_number: Option<NaiveDate>,
+
+    /// Whether the game is an anagram, set by the `anagram` command.
+    #[arg(skip)]
+    pub anagram: bool,
+
+    /// Guesses played somewhere else to start from, set by the `continue`
+    /// command.
+    #[arg(skip)]
+    pub board: Vec<String>,
+
+    /// Whether to play the day again even though it was finished
+    #[arg(long, default_value_t = false)]
+    pub replay: bool,
+
+    /// Whether to play in the interactive terminal UI
+    #[arg(long, default_value_t = false)]
+    pub tui: bool,
+
+    /// A Unix socket to mirror the terminal UI's game on as lines of JSON,
+    /// taking guesses sent as {"guess": "crane"}, for overlays and LED boards
+    #[arg(long, value_name = "PATH", requires = "tui")]
+    pub control_socket: Option<PathBuf>,
+
+    /// Guesses to play without prompting, e.g. crane,slate,pride. Guesses are
+    /// also read without prompting when stdin isn't a terminal
+    #[arg(long, value_delimiter = ',', conflicts_with = "tui")]
+    pub guesses: Vec<String>,
+
+    /// Whether to play a random practice word without contacting NYT
+    #[arg(long, visible_alias = "random", default_value_t = false)]
+    pub offline: bool,
+
+    /// Whether to play the day's word picked from the date and the answer
+    /// list instead of NYT's, the same for everyone with the same list
+    #[arg(long, conflicts_with = "offline", default_value_t = false)]
+    pub daily_random: bool,
+
+    /// Where the day's word comes from: nyt, file:PATH with a line like
+    /// "2025-01-01 crane" for each day, seed:N for a word a day picked from
+    /// the answers by N, or word:WORD. Only NYT games are saved [default: nyt]
+    #[arg(long, value_parser = source::parse_source, conflicts_with_all = ["offline", "daily_random"])]
+    pub source: Option<SourceChoice>,
+
+    /// The word to play, for testing and demos. Short for --source word:WORD
+    #[arg(long, value_parser = source::parse_word, conflicts_with_all = ["offline", "daily_random", "source"])]
+    pub word: Option<String>,
+
+    /// The seed picking the practice word, for reproducible puzzles
+    #[arg(long, requires = "offline")]
+    pub seed: Option<u64>,
+
+    /// Only pick practice words this common: a percentile of the dictionary by
+    /// word frequency (1-100), or easy, medium, hard or any. The frequencies
+    /// come from `dict update`
+    #[arg(long, requires = "offline", value_parser = obscurity::parse_obscurity)]
+    pub obscurity: Option<u8>,
+
+    /// Only pick practice words this hard to find, judged by how rare and
+    /// repeated their letters are
+    #[arg(long, requires = "offline", value_enum)]
+    pub difficulty: Option<difficulty::Difficulty>,
+
+    /// How many letters the practice word has, given a dictionary with such words
+    #[arg(long, requires = "offline", default_value_t = 5, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(4..=8))]
+    pub length: usize,
+
+    /// How many practice words to play at once, every guess going to all of
+    /// them: 2 for Dordle, 4 for Quordle
+    #[arg(long, requires = "offline", conflicts_with = "tui", default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=8))]
+    pub boards: usize,
+
+    /// Whether the practice word dodges your guesses, keeping as many words
+    /// possible as it can until only one is left
+    #[arg(long, requires = "offline", conflicts_with = "boards")]
+    pub adversarial: bool,
+
+    /// How much of the result to share once the game is over [default: full]
+    #[arg(long, value_enum)]
+    pub share: Option<share::ShareLevel>,
+
+    /// A Discord or Slack webhook to post the share text to once the game is over
+    #[arg(long, value_name = "URL")]
+    pub post_webhook: Option<String>,
+
+    /// Whether to copy the share text to the clipboard once the game is over,
+    /// through the terminal, so over ssh too
+    #[arg(long, default_value_t = false)]
+    pub copy: bool,
+
+    /// How many guesses you get [default: 6]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_guesses: Option<u32>,
+
+    /// Whether revealed letters have to be used in later guesses
+    #[arg(long, default_value_t = false)]
+    pub hard: bool,
+
+    /// Whether to refuse guesses the answer can't be, going by the colors
+    /// so far, telling which earlier clue they go against
+    #[arg(long, default_value_t = false)]
+    pub strict_logic: bool,
+
+    /// Point out guesses that were the answer of a day you played, or with
+    /// reject don't take them
+    #[arg(long, value_enum, value_name = "HOW", num_args = 0..=1, default_missing_value = "warn")]
+    pub no_repeats: Option<repeats::Repeats>,
+
+    /// Whether to tag guesses that are rare words with (obscure), going by
+    /// the word frequencies, which are downloaded the first time
+    #[arg(long, default_value_t = false)]
+    pub obscure_tags: bool,
+
+    /// Only take guesses this common, e.g. for kids: a percentile of the
+    /// dictionary by word frequency (1-100), or easy, medium, hard or any
+    #[arg(long, value_name = "OBSCURITY", value_parser = obscurity::parse_obscurity)]
+    pub common_guesses: Option<u8>,
+
+    /// Whether to suggest next guesses after each guess
+    #[arg(long, default_value_t = false)]
+    pub assist: bool,
+
+    /// How tiles look: classic, high-contrast, mono or a theme from the config file [default: classic]
+    #[arg(long)]
+    pub theme: Option<String>,
+
+    /// Whether to describe each guess in words instead of colors, for screen
+    /// readers and braille displays
+    #[arg(long, default_value_t = false)]
+    pub accessible: bool,
+
+    /// Whether to play without sound effects, in builds that have them
+    #[arg(long, default_value_t = false)]
+    pub mute: bool,
+
+    /// Whether to show graded rows at once, without turning their tiles over
+    #[arg(long, default_value_t = false)]
+    pub no_animations: bool,
+
+    /// Whether letters go into the board's row as they're typed, like on the
+    /// website, instead of on a line below the board
+    #[arg(long, default_value_t = false)]
+    pub incremental: bool,
+
+    /// Whether to show the whole board after every guess, instead of the
+    /// board once and then just the row each guess adds
+    #[arg(long, default_value_t = false)]
+    pub full_board: bool,
+
+    /// Whether to show a running timer while playing
+    #[arg(long, default_value_t = false)]
+    pub timed: bool,
+
+    /// Whether to also say after each guess how many of its letters are in the
+    /// answer wherever they are, an easier game for newcomers
+    #[arg(long, default_value_t = false)]
+    pub casual: bool,
+
+    /// Whether to say after each guess how many likely answers are left, and
+    /// how they went down over the game at the end
+    #[arg(long, default_value_t = false)]
+    pub show_remaining: bool,
+
+    /// Whether to show notes under the board of what the guesses tell about
+    /// each spot and letter
+    #[arg(long, default_value_t = false)]
+    pub notes: bool,
+
+    /// Whether to have the first row played for you with a strong opener,
+    /// with notes of what its colors tell, for learning how to use them.
+    /// Stats count such games as training
+    #[arg(long, default_value_t = false)]
+    pub training: bool,
+
+    /// Whether to rate the skill and luck of each guess against the solver
+    /// once the game is over
+    #[arg(long, default_value_t = false)]
+    pub wordlebot: bool,
+
+    /// Whether the answer of a lost game stays hidden until a key is pressed
+    #[arg(long, default_value_t = false)]
+    pub hold_reveal: bool,
+
+    /// How many seconds to wait before showing the answer of a lost game
+    #[arg(long, value_name = "SECONDS")]
+    pub reveal_delay: Option<u64>,
+
+    /// Whether to use the preset for playing with young kids
+    #[arg(long, default_value_t = false)]
+    pub simple: bool,
+
+    /// Whether to play a common word kids will know, with 8 tries and any
+    /// five letters allowed as a guess
+    #[arg(long, requires = "offline", default_value_t = false)]
+    pub kids: bool,
+
+    /// Whether the first hint is a dictionary definition of the answer, when
+    /// there is one, instead of a letter
+    #[arg(long, requires = "offline", default_value_t = false)]
+    pub definition_hints: bool,
+
+    /// Whether to look up what the answer means and where it comes from once
+    /// the game is over
+    #[arg(long, default_value_t = false)]
+    pub define: bool,
+
+    /// Whether to rate how hard the answer was once the game is over, from
+    /// its letters and the answers a letter away, next to how the bot did
+    #[arg(long, default_value_t = false)]
+    pub answer_difficulty: bool,
+
+    /// Whether to score points for each green and yellow tile, and for the
+    /// tries left when it's solved
+    #[arg(long, default_value_t = false)]
+    pub score: bool,
+
+    /// A file to write a transcript of the game to, with the answer, the
+    /// rules and every move, for bug reports
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Play the moves of a transcript written with --record again, telling
+    /// which of them went differently
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["record", "tui", "guesses"])]
+    pub replay_transcript: Option<PathBuf>,
+}
+
+impl PlayArgs {
+    /// Where the games played with these args are saved.
+    pub fn saves_dir(&self, data_dir: &Path) -> PathBuf {
+        match self.anagram {
+            true => anagram::saves_dir(data_dir),
+            false => daily_random::saves_dir(data_dir, self.daily_random),
+        }
+    }
+
+    /// The command that picks the saved game of these args up again.
+    pub fn resume_command(&self) -> String {
+        let mut command = "wordle-rs".to_string();
+        if self.anagram {
+            command.push_str(" anagram");
+        }
+        if self.daily_random {
+            command.push_str(" --daily-random");
+        }
+        if self.day != date_arg::today() {
+            command.push_str(&format!(" --day {}", self.day));
+        }
+        command
+    }
+
+    fn settle_day(&mut self) {
+        if let Some(day) = self.puzzle_number.take() {
+            self.day = day;
+        }
+    }
+
+    /// Where the day's word comes from, with `--word` as a fixed word source.
+    pub fn source(&self) -> SourceChoice {
+        match (&self.source, &self.word) {
+            (Some(source), _) => source.clone(),
+            (None, Some(word)) => SourceChoice::Word(word.clone()),
+            (None, None) => SourceChoice::Nyt,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ReplayArgs {
+    /// The day whose game to show, e.g. 2025-01-31, yesterday or -3
+    #[arg(
+        value_parser = date_arg::parse_day,
+        default_value_t = date_arg::today(),
+        add = ArgValueCandidates::new(completions::cached_days)
+    )]
+    pub day: NaiveDate,
+
+    /// How many seconds to wait before turning over each row
+    #[arg(long, default_value_t = 1.5, conflicts_with = "step", value_parser = replay::parse_delay)]
+    pub delay: f64,
+
+    /// Whether to wait for enter before each row instead
+    #[arg(long, default_value_t = false)]
+    pub step: bool,
+
+    /// Whether to show the game played with --daily-random that day
+    #[arg(long, default_value_t = false)]
+    pub daily_random: bool,
+
+    /// How tiles look: classic, high-contrast, mono or a theme from the config file [default: classic]
+    #[arg(long)]
+    pub theme: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DuelArgs {
+    /// The two players' names
+    #[arg(num_args = 2, value_names = ["PLAYER", "OTHER_PLAYER"], default_values_t = ["player 1".to_string(), "player 2".to_string()])]
+    pub players: Vec<String>,
+
+    /// The seed picking the word, for reproducible duels
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct BlitzArgs {
+    /// How long the blitz lasts
+    #[arg(long, default_value_t = 180, value_parser = clap::value_parser!(u64).range(10..))]
+    pub seconds: u64,
+
+    /// The seed picking the words, for reproducible blitzes
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Only show the high scores of blitzes this long
+    #[arg(long)]
+    pub scores: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ChainArgs {
+    /// How many words the chain is
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    pub rounds: u32,
+
+    /// The seed picking the words, for reproducible chains
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CrossArgs {
+    /// The day whose grid to play, e.g. 2025-01-31, yesterday or -3
+    #[arg(long, value_parser = date_arg::parse_day, default_value_t = date_arg::today())]
+    pub day: NaiveDate,
+
+    /// How many words the grid has: 3, or 4 for the whole frame
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(3..=4))]
+    pub words: u8,
+
+    /// How many guesses the whole grid gets [default: 4 a word]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub tries: Option<u32>,
+
+    /// The seed picking a practice grid instead of the day's, which isn't recorded
+    #[arg(long, conflicts_with = "day")]
+    pub seed: Option<u64>,
+
+    /// Only show how the grids of this many words went
+    #[arg(long)]
+    pub stats: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ServeArgs {
+    /// The address and port to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub bind: String,
+
+    #[command(flatten)]
+    pub limits: LimitArgs,
+}
+
+/// How much a client of `serve` or `host` is let do.
+#[derive(clap::Args, Clone, Debug)]
+pub struct LimitArgs {
+    /// How many guesses a client may make a minute
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_guess_rate: u32,
+
+    /// How many invalid guesses or messages a client may send before it's
+    /// turned away for --ban-secs
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_invalid: u32,
+
+    /// How many seconds a client is turned away for
+    #[arg(long, default_value_t = 300)]
+    pub ban_secs: u64,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct HostArgs {
+    /// The port to wait on
+    #[arg(long, default_value_t = 7878)]
+    pub port: u16,
+
+    /// Your name, as the other player sees it [default: host]
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// The seed picking the word, for reproducible races
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub limits: LimitArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct JoinArgs {
+    /// Where the game is hosted, e.g. 192.168.1.20:7878
+    pub address: String,
+
+    /// Your name, as the other player sees it [default: guest]
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FetchArgs {
+    /// The first day to fetch
+    #[arg(
+        long,
+        value_parser = date_arg::parse,
+        default_value_t = date_arg::today(),
+        add = ArgValueCandidates::new(completions::cached_days)
+    )]
+    pub from: NaiveDate,
+
+    /// The last day to fetch, which may be before --from to fetch backwards
+    /// [default: the last published day]
+    #[arg(long, value_parser = date_arg::parse)]
+    pub to: Option<NaiveDate>,
+
+    /// How many downloads to start a second at most [default: 4]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub rate: Option<u32>,
+
+    /// Only tell how many days would be downloaded
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Only fetch tomorrow's word if it isn't cached and check the dictionary
+    /// for changes if it's due, for cron or a systemd timer. What's done is
+    /// written to fetch.log in the cache dir, and with --quiet nothing but
+    /// errors is printed
+    #[arg(long, conflicts_with_all = ["from", "to", "rate", "dry_run"])]
+    pub if_stale: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct InstallSchedulerArgs {
+    /// The time of day to fetch at, e.g. 04:30
+    #[arg(long, value_parser = scheduler::parse_time, default_value = "04:00")]
+    pub at: NaiveTime,
+
+    /// Only print the files that would be written
+    #[arg(long, conflicts_with = "remove")]
+    pub print: bool,
+
+    /// Remove the schedule again
+    #[arg(long)]
+    pub remove: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AvailableArgs {
+    /// The day to ask about, e.g. 2025-01-31, today or tomorrow
+    #[arg(value_parser = date_arg::parse, default_value_t = date_arg::today())]
+    pub day: NaiveDate,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct WatchArgs {
+    /// The day to wait for [default: the first day from today that isn't cached]
+    #[arg(long, value_parser = date_arg::parse)]
+    pub day: Option<NaiveDate>,
+
+    /// How many minutes to wait between checks once the day has started
+    #[arg(long, default_value_t = watch::DEFAULT_INTERVAL, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    /// Whether to also download the wordle when it comes out, so it can be
+    /// played offline
+    #[arg(long, default_value_t = false)]
+    pub prefetch: bool,
+
+    /// Whether to carry on waiting for the day after, every day, instead of
+    /// stopping after one
+    #[arg(long, default_value_t = false)]
+    pub forever: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ContinueArgs {
+    /// The board so far, a guess and its colors per row as g/y/b letters or
+    /// the share text's squares, e.g. crane=bgybb or "crane ⬛🟩🟨⬛⬛".
+    /// Pasted after starting when left out
+    #[arg(value_parser = continuation::parse_row)]
+    pub rows: Vec<strategy::Feedback>,
+
+    /// Whether the board is of the wordle of --day, to finish it against
+    /// that answer instead of a word picked from the ones that fit
+    #[arg(long, default_value_t = false)]
+    pub daily: bool,
+
+    /// Whether to have the solver suggest the next guesses instead of playing on
+    #[arg(long, default_value_t = false)]
+    pub advice: bool,
+
+    #[command(flatten)]
+    pub play: PlayArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SolveArgs {
+    /// The feedback so far, one <guess>=<pattern> per guess, where the pattern
+    /// has g for green, y for yellow and b for gray, e.g. crane=bgybb. Use ? as
+    /// the guess when only the colors are known, e.g. ?=bgybb
+    #[arg(value_parser = strategy::parse_feedback)]
+    pub feedback: Vec<strategy::Feedback>,
+
+    /// How to rank the suggestions
+    #[arg(long, value_enum, default_value_t = strategy::Strategy::Entropy)]
+    pub strategy: strategy::Strategy,
+
+    /// How many suggestions to show
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+
+    /// Whether to only suggest guesses that hard mode allows
+    #[arg(long, default_value_t = false)]
+    pub hard: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct BotArgs {
+    /// How the bot picks its guesses
+    #[arg(long, value_enum, default_value_t = strategy::Strategy::Entropy)]
+    pub strategy: strategy::Strategy,
+
+    /// Only play the most recent N cached days [default: all of them]
+    #[arg(long)]
+    pub days: Option<usize>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ExportArgs {
+    /// What to write the games as
+    #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+    pub format: ExportFormat,
+
+    /// Only export games from this day on
+    #[arg(long, value_parser = date_arg::parse)]
+    pub since: Option<NaiveDate>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ImportArgs {
+    /// A file of pasted share text [default: read from stdin]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ExportArchiveArgs {
+    /// The archive to write, e.g. wordles.tar.zst
+    pub file: PathBuf,
+
+    /// The first day to export
+    #[arg(long, value_parser = date_arg::parse)]
+    pub from: Option<NaiveDate>,
+
+    /// The last day to export
+    #[arg(long, value_parser = date_arg::parse)]
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ImportArchiveArgs {
+    /// The archive to import
+    pub file: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SyncArgs {
+    /// A git repository, or the http(s) URL of a WebDAV folder [default:
+    /// sync_remote from the config file]
+    #[arg(long)]
+    pub remote: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct LeaderboardArgs {
+    /// The leaderboard file everyone adds to [default: leaderboard from the config file]
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: LeaderboardCommand,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum LeaderboardCommand {
+    /// Add your finished game of the day under your name, with as much of it
+    /// as --share or share in the config file lets out
+    Add {
+        name: String,
+
+        /// The day of the game to add and the rules it was played with
+        #[command(flatten)]
+        play: Box<PlayArgs>,
+    },
+
+    /// Rank the players by their average guesses, then their streak
+    Show {
+        /// Whether to leave out games played with hints or other help
+        #[arg(long, default_value_t = false)]
+        clean: bool,
+
+        /// Whether to rank by the average score instead, highest first
+        #[arg(long, default_value_t = false)]
+        by_score: bool,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AnalyzeArgs {
+    /// How many openers to list
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+
+    /// How many letters the words have
+    #[arg(long, default_value_t = 5, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(4..=8))]
+    pub length: usize,
+}
@@ -0,0 +1,112 @@
+//! The dictionary compiled once into a binary file next to the text one, so
+//! later runs map it into memory instead of splitting lines.
+//!
+//! The file is a header followed by every word padded with zeros to the same
+//! width. The header records the size and modification time of the text it
+//! was compiled from, and a file that doesn't match them is compiled again.
+
+use std::{
+    collections::HashSet,
+    fs::{self, File, Metadata},
+    io,
+    path::Path,
+    time::UNIX_EPOCH,
+};
+
+use memmap2::Mmap;
+
+use crate::cache;
+
+static MAGIC: &[u8; 4] = b"WRDB";
+static VERSION: u8 = 1;
+/// Magic, version, width, source size, source modification time and count.
+static HEADER_LEN: usize = 4 + 1 + 1 + 8 + 8 + 4;
+
+/// What the compiled file remembers about the text, to tell when it changed.
+pub fn stamp(source: &Metadata) -> (u64, u64) {
+    let modified = source.modified().ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos() as u64);
+    (source.len(), modified)
+}
+
+fn encode(words: &HashSet<String>, source: &Metadata) -> Vec<u8> {
+    let mut sorted: Vec<&String> = words.iter().collect();
+    sorted.sort();
+    let width = sorted.iter().map(|w| w.len()).max().unwrap_or(0);
+    let (len, modified) = stamp(source);
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN + width * sorted.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    bytes.push(width as u8);
+    bytes.extend_from_slice(&len.to_le_bytes());
+    bytes.extend_from_slice(&modified.to_le_bytes());
+    bytes.extend_from_slice(&(sorted.len() as u32).to_le_bytes());
+    for word in sorted {
+        bytes.extend_from_slice(word.as_bytes());
+        bytes.resize(bytes.len() + width - word.len(), 0);
+    }
+    bytes
+}
+
+/// The words in a compiled file, or `None` if it isn't one, was compiled
+/// from a different text or has anything but lowercase words in it.
+fn decode(bytes: &[u8], source: &Metadata) -> Option<HashSet<String>> {
+    let (header, body) = bytes.split_at_checked(HEADER_LEN)?;
+    let u64_at = |i: usize| u64::from_le_bytes(header[i..i + 8].try_into().unwrap());
+    if &header[..4] != MAGIC || header[4] != VERSION || (u64_at(6), u64_at(14)) != stamp(source) {
+        return None;
+    }
+    let width = header[5] as usize;
+    let count = u32::from_le_bytes(header[22..26].try_into().unwrap()) as usize;
+    if width == 0 || body.len() != width * count {
+        return (count == 0 && body.is_empty()).then(HashSet::new);
+    }
+    body.chunks_exact(width)
+        .map(|word| {
+            let end = word.iter().position(|&b| b == 0).unwrap_or(width);
+            let word = &word[..end];
+            word.iter().all(u8::is_ascii_lowercase).then(|| String::from_utf8_lossy(word).into_owned())
+        })
+        .collect()
+}
+
+/// Reads the compiled dictionary if it's up to date with the text.
+pub fn load(cache_dir: &Path) -> io::Result<Option<HashSet<String>>> {
+    let source = fs::metadata(cache::dictionary_path(cache_dir))?;
+    let file = match File::open(cache::compiled_dictionary_path(cache_dir)) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    // Safety: the file is only ever replaced by a rename, never written in
+    // place, so the mapped bytes can't change underneath.
+    let map = unsafe { Mmap::map(&file)? };
+    Ok(decode(&map, &source))
+}
+
+/// Compiles the words read from the text dictionary for next time.
+pub fn save(cache_dir: &Path, words: &HashSet<String>) -> io::Result<()> {
+    let source = fs::metadata(cache::dictionary_path(cache_dir))?;
+    cache::write_atomic(&cache::compiled_dictionary_path(cache_dir), &encode(words, &source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_back_what_it_wrote_until_the_text_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        cache::prepare(dir.path()).unwrap();
+        fs::write(cache::dictionary_path(dir.path()), "crane\nslate\n").unwrap();
+        let words: HashSet<String> = ["crane", "slate", "abc"].map(str::to_string).into();
+
+        save(dir.path(), &words).unwrap();
+        assert_eq!(load(dir.path()).unwrap(), Some(words));
+
+        fs::write(cache::dictionary_path(dir.path()), "crane\nslate\npride\n").unwrap();
+        assert_eq!(load(dir.path()).unwrap(), None);
+    }
+}
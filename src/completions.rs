@@ -0,0 +1,35 @@
+//! Shell completion. The scripts call back into wordle-rs as it completes, so
+//! values that depend on the cache, like the cached days, are completed too.
+
+use std::io;
+
+use clap_complete::{CompletionCandidate, Shell, env::Shells};
+
+use crate::{cache, config};
+
+/// The variable the completion scripts set when calling back, see
+/// [`clap_complete::CompleteEnv`].
+pub static VAR: &str = "COMPLETE";
+
+/// The days in the cache, newest first, for completing `--day`. The cache
+/// dir can only come from the config file here, since the flags of the line
+/// being completed aren't parsed yet.
+pub fn cached_days() -> Vec<CompletionCandidate> {
+    let config = config::Config::load(&config::default_path()).unwrap_or_default();
+    let cache_dir = config.cache_dir.unwrap_or_else(cache::default_dir);
+    let mut days = cache::cached_days(&cache_dir).unwrap_or_default();
+    days.reverse();
+    std::iter::once(CompletionCandidate::new("today"))
+        .chain(days.into_iter().map(|day| CompletionCandidate::new(day.to_string())))
+        .collect()
+}
+
+/// Prints the script that sets up completion in `shell`.
+pub fn print(shell: Shell) -> io::Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell.to_string())
+        .ok_or_else(|| io::Error::other(format!("no completion for {}", shell)))?;
+    let exe = std::env::current_exe()?;
+    completer.write_registration(VAR, "wordle-rs", "wordle-rs", &exe.to_string_lossy(), &mut io::stdout())
+}
@@ -0,0 +1,369 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    dict_index::DictIndex,
+    endscreen::EndScreen,
+    error::{Context, Result, WordleError},
+    i18n,
+    keys::Keys,
+    limits::Limits,
+    mastodon::Mastodon,
+    repeats::Repeats,
+    share::ShareLevel,
+    storage::Backend,
+    theme::Theme,
+};
+
+static TEMPLATE: &str = r##"# wordle-rs configuration. Flags given on the command line win over these.
+
+# The directory to cache words and the dictionary in.
+# cache_dir = "/home/you/.cache/wordle-rs"
+
+# The directory to keep your saved games in. Defaults to cache_dir when that
+# is set, else to your data dir.
+# data_dir = "/home/you/.local/share/wordle-rs"
+
+# Whether to keep the cached words scrambled, as with --encrypt-cache, so a
+# look in the cache dir doesn't spoil the days fetched ahead of time.
+# encrypt_cache = false
+
+# How saved games are kept: json, a file a day, or sqlite, a database in the
+# data dir that's quicker over years of games. sqlite needs a build with the
+# sqlite feature, and copies the JSON files in the first time. Games played
+# with sqlite stay in the database if you switch back.
+# storage = "json"
+
+# How the dictionary is kept while playing: memory, or fst to look guesses up
+# in an index on disk, for computers short on memory. The index is built when
+# the dictionary is downloaded.
+# dict_index = "memory"
+
+# How many seconds a download may take before it's given up on. Set
+# HTTPS_PROXY to download through a proxy.
+# timeout = 10
+
+# Where the wordles and word lists are downloaded from, for when the usual
+# places are gone or to use a mirror. file:// URLs are read from disk, e.g. a
+# directory of <day>.json files for nyt_url.
+# nyt_url = "https://www.nytimes.com/svc/wordle/v2"
+# dictionary_url = "https://mirror.example.com/valid-wordle-words.txt"
+# answers_url = "file:///srv/wordle/answers.txt"
+
+# How many days `fetch` starts downloading a second at most.
+# fetch_rate = 4
+
+# The time zone whose date decides today's wordle, e.g. "America/New_York".
+# Each day's wordle comes out at midnight there. Defaults to your own.
+# timezone = "UTC"
+
+# How many days the dictionary is used before asking whether it changed.
+# dictionary_ttl_days = 30
+
+# Whether revealed letters have to be used in later guesses.
+# hard = false
+
+# Whether guesses the answer can't be, going by the colors so far, are
+# refused with the clue they go against.
+# strict_logic = false
+
+# Whether guessing the answer of a day you played is pointed out (warn) or
+# not taken at all (reject).
+# no_repeats = "warn"
+
+# Whether rare words guessed are tagged (obscure), as with --obscure-tags.
+# obscure_tags = false
+
+# Only take guesses among the most common this many percent of the
+# dictionary, as with --common-guesses.
+# common_guesses = 50
+
+# Whether to suggest next guesses after each guess.
+# assist = false
+
+# How many guesses you get.
+# max_guesses = 6
+
+# How many tries each :hint costs. Games with free hints count as assisted in
+# the stats.
+# hint_penalty = 0
+
+# How many words the third hint narrows the answer down to.
+# hint_shortlist = 3
+
+# How much of the result to share once the game is over, and to add to the
+# leaderboard: full, no-count, outcome or none.
+# share = "full"
+
+# Whether to copy the share text to the clipboard once the game is over.
+# copy = false
+
+# A Discord or Slack webhook to post the share text to once the game is over.
+# Only what share allows is posted, never the answer or the letters.
+# webhook = "https://discord.com/api/webhooks/..."
+
+# A leaderboard file shared with others, for `leaderboard add` and `show`.
+# leaderboard = "/srv/shared/wordle-leaderboard.json"
+
+# Where `sync` keeps your saved games for your other computers: a git
+# repository, or the http(s) URL of a WebDAV folder.
+# sync_remote = "git@example.com:me/wordle-games.git"
+
+# Whether to play without sound effects, and how loud they are from 0 to 1,
+# in builds with the audio feature.
+# mute = false
+# volume = 0.3
+
+# Whether to describe each guess in words instead of colors, for screen
+# readers and braille displays.
+# accessible = false
+
+# Whether to show the whole board after every guess, as with --full-board,
+# instead of just the new row.
+# full_board = false
+
+# Whether to look up what the answer means once the game is over, as with
+# --define.
+# define = false
+
+# Whether to rate how hard the answer was once the game is over, as with
+# --answer-difficulty.
+# answer_difficulty = false
+
+# Whether to show notes under the board of what the guesses tell about each
+# spot and letter, as with --notes.
+# notes = false
+
+# Whether to go over each guess with the solver once the game is over, as
+# with --wordlebot.
+# wordlebot = false
+
+# Whether the answer of a lost game is hidden until a key is pressed, and how
+# many seconds to wait before showing it, as with --hold-reveal and
+# --reveal-delay.
+# hold_reveal = false
+# reveal_delay = 3
+
+# Whether to score points for tiles and tries left, as with --score. Stats
+# show the average score too.
+# score = false
+
+# Whether to always play as with --streamer, keeping the answer off screen
+# until a key is pressed.
+# streamer = false
+
+# The language of the messages while playing: en or sv. Left out, it's
+# the one of your locale.
+# lang = "sv"
+
+# How tiles look: classic, high-contrast, mono or one of the themes below.
+# theme = "classic"
+
+# The keys of the terminal UI, one or a list for each. Keys are names like
+# enter, tab, esc, backspace, space or f2, a character like ?, or one of them
+# with ctrl-, alt- or shift-. `wordle-rs keys` lists them; Ctrl-C always quits.
+# [keys]
+# submit = "enter"
+# backspace = "backspace"
+# hint = "?"
+# quit = "esc"
+# keyboard = "tab"
+# undo = "ctrl-z"
+
+# A Mastodon account to post the share text to once the game is over, with
+# an access token that may write statuses. Only what share allows is posted.
+# [mastodon]
+# instance = "https://mastodon.social"
+# token = "..."
+
+# Limits for a shared family computer: how many practice games can be
+# started a day, and hours when no game can be started at all.
+# [limits]
+# offline_games_per_day = 3
+# no_play = "21:00-07:00"
+
+# What a finished game says instead of congratz! or womp womp. {guesses},
+# {word}, {time} and {streak} are filled in, won_in has a message for a win
+# in so many guesses, and banner draws the first line in big letters.
+# [end_screen]
+# won = "{word} in {guesses}! That's a streak of {streak}."
+# lost = "It was {word}. See you tomorrow."
+# banner = false
+# [end_screen.won_in]
+# 1 = "Hole in one!"
+# 6 = "Phew, just made it."
+
+# Your own themes. Colors are names like "green" or "bright black", or
+# "#rrggbb". Marks go around the letter, e.g. ["[", "]"].
+# [themes.mine]
+# correct = { background = "#f5793a", text = "black" }
+# wrong_place = { background = "#85c0f9", text = "black" }
+# not_in_word = { background = "bright black", text = "black" }
+"##;
+
+/// Defaults read from the config file, for options that would otherwise
+/// have to be passed every day.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub cache_dir: Option<PathBuf>,
+    pub data_dir: Option<PathBuf>,
+    pub storage: Backend,
+    pub dict_index: DictIndex,
+    pub encrypt_cache: bool,
+    pub timeout: Option<u64>,
+    pub nyt_url: Option<String>,
+    pub dictionary_url: Option<String>,
+    pub answers_url: Option<String>,
+    pub fetch_rate: Option<u32>,
+    pub dictionary_ttl_days: Option<u64>,
+    pub hard: bool,
+    pub strict_logic: bool,
+    pub no_repeats: Option<Repeats>,
+    pub obscure_tags: bool,
+    pub common_guesses: Option<u8>,
+    pub assist: bool,
+    pub max_guesses: Option<u32>,
+    pub hint_penalty: Option<u32>,
+    pub hint_shortlist: Option<usize>,
+    pub share: Option<ShareLevel>,
+    pub copy: bool,
+    pub webhook: Option<String>,
+    pub mastodon: Option<Mastodon>,
+    pub leaderboard: Option<PathBuf>,
+    pub sync_remote: Option<String>,
+    pub timezone: Option<chrono_tz::Tz>,
+    pub mute: bool,
+    pub accessible: bool,
+    pub full_board: bool,
+    pub define: bool,
+    pub answer_difficulty: bool,
+    pub notes: bool,
+    pub wordlebot: bool,
+    pub hold_reveal: bool,
+    pub reveal_delay: Option<u64>,
+    pub score: bool,
+    pub streamer: bool,
+    pub volume: Option<f32>,
+    pub lang: Option<i18n::Lang>,
+    pub theme: Option<String>,
+    pub themes: HashMap<String, Theme>,
+    pub keys: Keys,
+    pub limits: Limits,
+    pub end_screen: EndScreen,
+}
+
+pub fn default_path() -> PathBuf {
+    dirs::config_dir().unwrap_or_else(std::env::temp_dir).join("wordle-rs").join("config.toml")
+}
+
+/// Reads a `--session` name, which names files and dirs so can only have
+/// letters, digits, - and _.
+pub fn parse_session(name: &str) -> std::result::Result<String, String> {
+    match !name.is_empty() && name.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_') {
+        true => Ok(name.to_string()),
+        false => Err(format!("'{}' can only have letters, digits, - and _", name)),
+    }
+}
+
+/// The settings of `--session name`, next to the config file at `path`.
+pub fn session_path(path: &Path, name: &str) -> PathBuf {
+    path.with_file_name("sessions").join(format!("{}.toml", name))
+}
+
+/// The settings of a config file as they were written, which don't have to
+/// be there.
+fn table(path: &Path) -> Result<toml::Table> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(toml::Table::new()),
+        Err(e) => return Err(e).context("reading config file"),
+    };
+    toml::from_str(&text).map_err(|e| WordleError::Config(path.to_path_buf(), e))
+}
+
+impl Config {
+    /// Reads the config file, which doesn't have to exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        table(path)?.try_into().map_err(|e| WordleError::Config(path.to_path_buf(), e))
+    }
+
+    /// Reads the config file with the settings of `session` over it, so a
+    /// session only has to say what it does differently.
+    pub fn load_session(path: &Path, session: Option<&str>) -> Result<Self> {
+        let Some(name) = session else { return Self::load(path) };
+        let session_path = session_path(path, name);
+        // The config file's own mistakes are reported as its.
+        Self::load(path)?;
+        let mut settings = table(path)?;
+        settings.extend(table(&session_path)?);
+        settings.try_into().map_err(|e| WordleError::Config(session_path, e))
+    }
+}
+
+/// The template with each of `settings` set where it's commented out.
+fn filled(settings: &[(&str, toml::Value)]) -> String {
+    let mut lines: Vec<String> = TEMPLATE.lines().map(str::to_string).collect();
+    for (key, value) in settings {
+        let setting = format!("{} = {}", key, value);
+        match lines.iter().position(|line| line.starts_with(&format!("# {} = ", key))) {
+            Some(i) => lines[i] = setting,
+            // Below the heading, where it isn't in a table.
+            None => lines.insert(1, setting),
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Writes a commented template to start the config file from.
+pub fn init(path: &Path) -> Result<()> {
+    write(path, &[])?;
+    eprintln!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Writes the template with `settings` in it, unless there's a config file
+/// already.
+pub fn write(path: &Path, settings: &[(&str, toml::Value)]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("creating config dir")?;
+    }
+    fs::File::create_new(path)
+        .and_then(|mut f| io::Write::write_all(&mut f, filled(settings).as_bytes()))
+        .context("writing config file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_change_only_what_they_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "hard = true\nmax_guesses = 5\n").unwrap();
+        fs::create_dir(dir.path().join("sessions")).unwrap();
+        fs::write(session_path(&path, "kids"), "max_guesses = 8\n").unwrap();
+
+        let config = Config::load_session(&path, Some("kids")).unwrap();
+        assert!(config.hard);
+        assert_eq!(config.max_guesses, Some(8));
+        assert_eq!(Config::load_session(&path, Some("work")).unwrap().max_guesses, Some(5));
+        assert!(parse_session("../games").is_err());
+    }
+
+    #[test]
+    fn fills_in_the_template() {
+        let settings = [("hard", toml::Value::Boolean(true)), ("theme", toml::Value::String("mono".to_string()))];
+        let config: Config = toml::from_str(&filled(&settings)).unwrap();
+        assert!(config.hard);
+        assert_eq!(config.theme.as_deref(), Some("mono"));
+        assert!(!toml::from_str::<Config>(&filled(&[])).unwrap().hard);
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use crate::{CharGuess, CharGuessKind};
+
+/// Why a guess isn't allowed in hard mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardModeViolation {
+    /// A revealed green letter was not kept in its place.
+    MovedCorrectLetter { position: usize, ch: char },
+    /// A revealed letter was left out (or used fewer times than revealed).
+    MissingRevealedLetter(char),
+}
+
+impl HardModeViolation {
+    pub fn message(&self) -> String {
+        match self {
+            HardModeViolation::MovedCorrectLetter { position, ch } =>
+                format!("Letter {} must be {}!", position + 1, ch.to_ascii_uppercase()),
+            HardModeViolation::MissingRevealedLetter(ch) =>
+                format!("Guess must contain {}!", ch.to_ascii_uppercase()),
+        }
+    }
+}
+
+/// The hard-mode rules that the guesses so far impose on the next one:
+/// greens stay where they are, and every revealed letter is used again.
+pub struct Constraints {
+    greens: Vec<Option<char>>,
+    min_counts: HashMap<char, usize>,
+}
+
+impl Constraints {
+    pub fn from_guesses(char_guesses: &[Vec<CharGuess>]) -> Self {
+        let mut greens = vec![];
+        let mut min_counts: HashMap<char, usize> = HashMap::new();
+
+        for cgs in char_guesses {
+            let mut row_counts: HashMap<char, usize> = HashMap::new();
+            for (i, cg) in cgs.iter().enumerate() {
+                if greens.len() <= i {
+                    greens.push(None);
+                }
+                if cg.kind == CharGuessKind::Correct {
+                    greens[i] = Some(cg.ch);
+                }
+                if cg.kind != CharGuessKind::NotInWord {
+                    *row_counts.entry(cg.ch).or_default() += 1;
+                }
+            }
+            for (ch, count) in row_counts {
+                let min = min_counts.entry(ch).or_default();
+                *min = (*min).max(count);
+            }
+        }
+
+        Self { greens, min_counts }
+    }
+
+    /// The first hard-mode rule the guess breaks, if any.
+    pub fn violation(&self, guess: &str) -> Option<HardModeViolation> {
+        let chars: Vec<char> = guess.chars().collect();
+        for (position, green) in self.greens.iter().enumerate() {
+            if let Some(ch) = *green && chars.get(position) != Some(&ch) {
+                return Some(HardModeViolation::MovedCorrectLetter { position, ch });
+            }
+        }
+
+        let mut letters: Vec<(&char, &usize)> = self.min_counts.iter().collect();
+        letters.sort();
+        letters.into_iter()
+            .find(|&(ch, &min)| chars.iter().filter(|c| *c == ch).count() < min)
+            .map(|(&ch, _)| HardModeViolation::MissingRevealedLetter(ch))
+    }
+
+    pub fn allows(&self, guess: &str) -> bool {
+        self.violation(guess).is_none()
+    }
+}
+
+/// Why a guess can't be the answer, given what an earlier guess showed.
+/// `guess` is that earlier guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogicViolation {
+    /// The letter was green there.
+    Moved { position: usize, ch: char, guess: String },
+    /// The word has at least `count` of the letter.
+    Missing { ch: char, count: usize, guess: String },
+    /// The letter is gray everywhere.
+    Absent { ch: char, guess: String },
+    /// The word has only `count` of the letter.
+    TooMany { ch: char, count: usize, guess: String },
+    /// The letter was yellow or gray there.
+    NotThere { position: usize, ch: char, guess: String },
+}
+
+/// Everything the feedback so far tells about the answer, each fact with the
+/// guess that showed it first, for `--strict-logic`. Unlike [`Constraints`]
+/// grays count too: a guess has to be one the answer could still be.
+pub struct Knowledge {
+    greens: Vec<Option<(char, usize)>>,
+    not_there: Vec<HashMap<char, usize>>,
+    /// The fewest and the most of a letter the answer can have.
+    min_counts: HashMap<char, (usize, usize)>,
+    max_counts: HashMap<char, (usize, usize)>,
+    guesses: Vec<String>,
+}
+
+impl Knowledge {
+    pub fn from_guesses(char_guesses: &[Vec<CharGuess>]) -> Self {
+        let mut knowledge = Self { greens: vec![], not_there: vec![], min_counts: HashMap::new(), max_counts: HashMap::new(), guesses: vec![] };
+        for (row, cgs) in char_guesses.iter().enumerate() {
+            knowledge.guesses.push(cgs.iter().map(|cg| cg.ch).collect());
+            let mut row_counts: HashMap<char, usize> = HashMap::new();
+            let mut grayed = vec![];
+            for (i, cg) in cgs.iter().enumerate() {
+                if knowledge.greens.len() <= i {
+                    knowledge.greens.push(None);
+                    knowledge.not_there.push(HashMap::new());
+                }
+                match cg.kind {
+                    CharGuessKind::Correct => {
+                        knowledge.greens[i].get_or_insert((cg.ch, row));
+                    },
+                    _ => {
+                        knowledge.not_there[i].entry(cg.ch).or_insert(row);
+                    },
+                }
+                match cg.kind {
+                    CharGuessKind::NotInWord => grayed.push(cg.ch),
+                    _ => *row_counts.entry(cg.ch).or_default() += 1,
+                }
+            }
+            for (&ch, &count) in &row_counts {
+                let min = knowledge.min_counts.entry(ch).or_insert((0, row));
+                if count > min.0 {
+                    *min = (count, row);
+                }
+            }
+            // A gray next to colored copies of the letter tells how many there are.
+            for ch in grayed {
+                let count = row_counts.get(&ch).copied().unwrap_or(0);
+                let max = knowledge.max_counts.entry(ch).or_insert((count, row));
+                if count < max.0 {
+                    *max = (count, row);
+                }
+            }
+        }
+        knowledge
+    }
+
+    /// The first thing known that the guess goes against, if any.
+    pub fn violation(&self, guess: &str) -> Option<LogicViolation> {
+        let chars: Vec<char> = guess.chars().collect();
+        let shown = |row: usize| self.guesses[row].clone();
+        for (position, green) in self.greens.iter().enumerate() {
+            if let Some((ch, row)) = *green && chars.get(position) != Some(&ch) {
+                return Some(LogicViolation::Moved { position, ch, guess: shown(row) });
+            }
+        }
+
+        let count = |ch: char| chars.iter().filter(|&&c| c == ch).count();
+        let mut min_counts: Vec<(&char, &(usize, usize))> = self.min_counts.iter().collect();
+        min_counts.sort();
+        if let Some((&ch, &(min, row))) = min_counts.into_iter().find(|&(&ch, &(min, _))| count(ch) < min) {
+            return Some(LogicViolation::Missing { ch, count: min, guess: shown(row) });
+        }
+        // Letters in the order they're guessed, so the first wrong one is told.
+        for &ch in &chars {
+            match self.max_counts.get(&ch) {
+                Some(&(0, row)) => return Some(LogicViolation::Absent { ch, guess: shown(row) }),
+                Some(&(max, row)) if count(ch) > max => return Some(LogicViolation::TooMany { ch, count: max, guess: shown(row) }),
+                _ => (),
+            }
+        }
+        chars.iter().enumerate()
+            .find_map(|(position, ch)| self.not_there.get(position)?.get(ch).map(|&row| LogicViolation::NotThere { position, ch: *ch, guess: shown(row) }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grade;
+
+    #[test]
+    fn tells_which_guess_a_guess_goes_against() {
+        let rows: Vec<Vec<CharGuess>> = ["arise", "eerie"].iter()
+            .map(|guess| guess.chars().zip(grade::grade(guess, "theme")).map(|(ch, kind)| CharGuess::new(ch, kind)).collect())
+            .collect();
+        let knowledge = Knowledge::from_guesses(&rows);
+        let violation = |guess| knowledge.violation(guess);
+        assert_eq!(violation("tempo"), Some(LogicViolation::Moved { position: 4, ch: 'e', guess: "arise".to_string() }));
+        assert_eq!(violation("slate"), Some(LogicViolation::Missing { ch: 'e', count: 2, guess: "eerie".to_string() }));
+        assert_eq!(violation("theme"), None);
+        assert_eq!(violation("there"), Some(LogicViolation::Absent { ch: 'r', guess: "arise".to_string() }));
+        assert_eq!(violation("eeeee"), Some(LogicViolation::TooMany { ch: 'e', count: 2, guess: "eerie".to_string() }));
+        assert_eq!(violation("ebbhe"), Some(LogicViolation::NotThere { position: 0, ch: 'e', guess: "eerie".to_string() }));
+    }
+}
@@ -0,0 +1,126 @@
+//! `continue`: carrying on a board started somewhere else, e.g. the official
+//! app, from its guesses and colors. It's finished against the day's answer
+//! with `--daily`, else against a word that fits it, and either way only
+//! guesses that keep to what the colors tell are taken. With `--advice` the
+//! solver suggests the next guesses instead.
+
+use std::io::{self, BufRead, IsTerminal};
+
+use crate::{
+    CharGuess, CharGuessKind,
+    app::App,
+    cli::{ContinueArgs, PlayArgs, SolveArgs},
+    error::{Result, WordleError},
+    get_and_write_word, grade, import, random_answer, rules, solve,
+    strategy::{self, Feedback},
+};
+
+/// Parses a row of the board: a guess and its colors as g/y/b letters or
+/// the share text's squares, e.g. `crane=bgybb`, `crane bgybb` or
+/// `CRANE ⬛🟩🟨⬛⬛`.
+pub fn parse_row(row: &str) -> std::result::Result<Feedback, String> {
+    let row = row.trim();
+    let Some((guess, colors)) = row.split_once(|ch: char| ch == '=' || ch == ':' || ch.is_whitespace()) else {
+        return Err(format!("expected a guess and its colors, e.g. crane=bgybb, got '{}'", row));
+    };
+    let pattern: String = colors.chars().filter(|ch| !ch.is_whitespace()).map(|ch| match import::tile(ch) {
+        Some(CharGuessKind::Correct) => 'g',
+        Some(CharGuessKind::WrongPlace) => 'y',
+        Some(CharGuessKind::NotInWord) => 'b',
+        None => ch,
+    }).collect();
+    match strategy::parse_feedback(&format!("{}={}", guess, pattern))? {
+        Feedback::Unknown(_) => Err(format!("'{}' has colors but no guess, put the guess before them", row)),
+        known => Ok(known),
+    }
+}
+
+/// Reads the board pasted at the terminal, or piped in, up to an empty line.
+fn paste() -> Result<Vec<Feedback>> {
+    if io::stdin().is_terminal() {
+        println!("Paste the board, a guess and its colors a line, e.g. crane ⬛🟩🟨⬛⬛, then an empty line:");
+    }
+    let mut rows = vec![];
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(|source| WordleError::Terminal { context: "reading the board", source })?;
+        // The share text's header line can be pasted along with it.
+        if line.starts_with("Wordle ") {
+            continue;
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+        rows.push(parse_row(&line).map_err(WordleError::BadBoard)?);
+    }
+    Ok(rows)
+}
+
+pub fn run(app: &App, args: ContinueArgs) -> Result<()> {
+    let feedback = match args.rows.is_empty() {
+        true => paste()?,
+        false => args.rows,
+    };
+    let rows: Vec<Vec<CharGuess>> = feedback.iter().filter_map(|row| match row {
+        Feedback::Known(cgs) => Some(cgs.clone()),
+        Feedback::Unknown(_) => None,
+    }).collect();
+    if rows.is_empty() {
+        return Err(WordleError::BadBoard("there are no rows to continue from".to_string()));
+    }
+    let guess_of = |cgs: &[CharGuess]| cgs.iter().map(|cg| cg.ch).collect::<String>();
+    // Only the day's answer needs NYT.
+    let dictionary = app.dictionary(!args.daily)?;
+    let answers = app.answers()?.unwrap_or(dictionary);
+    let tries = rules::Rules::from_args(&app.config, &args.play).tries as usize;
+    if rows.iter().any(|cgs| cgs.iter().all(|cg| cg.kind == CharGuessKind::Correct)) || rows.len() >= tries {
+        return Err(WordleError::BadBoard("it's already finished".to_string()));
+    }
+    if let Some(cgs) = rows.iter().find(|cgs| !dictionary.contains(&guess_of(cgs))) {
+        return Err(WordleError::BadBoard(format!("'{}' isn't in the dictionary", guess_of(cgs))));
+    }
+
+    if args.advice {
+        let solve = SolveArgs { feedback, strategy: strategy::Strategy::Entropy, top: 10, hard: args.play.hard };
+        solve::solve(solve, answers, dictionary);
+        return Ok(());
+    }
+
+    let answer = match args.daily {
+        true => {
+            let answer = get_and_write_word(&app.cache_dir, args.play.day, &app.nyt())?.solution.into_revealed();
+            if let Some(cgs) = rows.iter().find(|cgs| grade::grade(&guess_of(cgs), &answer) != cgs.iter().map(|cg| cg.kind).collect::<Vec<_>>()) {
+                return Err(WordleError::BadBoard(format!("{} didn't get those colors in the wordle of {}", guess_of(cgs).to_uppercase(), args.play.day)));
+            }
+            answer
+        },
+        false => {
+            let fitting = strategy::likely_candidates(&rows, answers, dictionary);
+            if fitting.is_empty() {
+                return Err(WordleError::BadBoard("no word in the dictionary gets all those colors".to_string()));
+            }
+            random_answer(fitting, rand::random())?
+        },
+    };
+    eprintln!("Carrying on from {} {}.", rows.len(), if rows.len() == 1 { "guess" } else { "guesses" });
+    let board = rows.iter().map(|cgs| guess_of(cgs)).collect();
+    crate::play(app, PlayArgs { word: Some(answer), board, strict_logic: true, ..args.play }).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rows_in_letters_or_squares() {
+        let pattern = |row| match parse_row(row).unwrap() {
+            Feedback::Known(cgs) => crate::output::pattern(&cgs),
+            Feedback::Unknown(_) => unreachable!(),
+        };
+        assert_eq!(pattern("crane=bgybb"), "bgybb");
+        assert_eq!(pattern("CRANE ⬛🟩🟨⬛⬛"), "bgybb");
+        assert_eq!(pattern("slate: 🟧🟦⬜ ⬜⬜"), "gybbb");
+        assert!(parse_row("⬛🟩🟨⬛⬛").is_err());
+        assert!(parse_row("?=bgybb").is_err());
+        assert!(parse_row("crane 🟩🟩").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! `--control-socket`: the game in the terminal UI, mirrored over a Unix
+//! socket for stream overlays and LED boards, which can play guesses too.
+//!
+//! The protocol is one JSON object per line. A client sends
+//! `{"guess": "crane"}` to play a guess, and gets the same events as
+//! `--output json` for everything that happens in the game, whoever played
+//! it. A client that connects late is sent what it missed first.
+
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, mpsc},
+};
+
+use serde::Deserialize;
+
+use crate::output::Event;
+
+#[derive(Deserialize)]
+struct Request {
+    guess: String,
+}
+
+/// The lines sent so far, and the clients to send the next ones to.
+#[derive(Default)]
+struct Clients {
+    sent: Vec<String>,
+    streams: Vec<Box<dyn Write + Send>>,
+}
+
+impl Clients {
+    /// Sends `line` to every client, forgetting the ones that went away.
+    fn send(&mut self, line: String) {
+        self.streams.retain_mut(|stream| stream.write_all(line.as_bytes()).and_then(|_| stream.flush()).is_ok());
+        self.sent.push(line);
+    }
+
+    /// Adds a client, catching it up on what was sent before.
+    fn add(&mut self, mut stream: Box<dyn Write + Send>) {
+        if self.sent.iter().all(|line| stream.write_all(line.as_bytes()).is_ok()) && stream.flush().is_ok() {
+            self.streams.push(stream);
+        }
+    }
+}
+
+fn line(event: &Event) -> String {
+    format!("{}\n", serde_json::to_string(event).expect("events always serialize"))
+}
+
+pub struct Control {
+    path: PathBuf,
+    guesses: mpsc::Receiver<String>,
+    clients: Arc<Mutex<Clients>>,
+}
+
+impl Control {
+    /// Listens on a socket at `path`, taking the place of one left behind by
+    /// a game that didn't get to remove it.
+    #[cfg(unix)]
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        use std::{
+            io::{BufRead, BufReader},
+            os::unix::net::{UnixListener, UnixStream},
+            thread,
+        };
+
+        if path.exists() && UnixStream::connect(path).is_err() {
+            std::fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        let clients = Arc::new(Mutex::new(Clients::default()));
+        let (sender, guesses) = mpsc::channel();
+        let accepted = Arc::clone(&clients);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let Ok(writer) = stream.try_clone() else { continue };
+                accepted.lock().expect("no thread panics holding the clients").add(Box::new(writer));
+                let Ok(mut reply) = stream.try_clone() else { continue };
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for request in BufReader::new(stream).lines() {
+                        let Ok(request) = request else { break };
+                        // Only the client that sent it hears that it wasn't a guess.
+                        let sent = match serde_json::from_str::<Request>(&request) {
+                            Ok(Request { guess }) => sender.send(guess).is_ok(),
+                            Err(e) => reply.write_all(line(&Event::Invalid { guess: request.trim(), reason: e.to_string() }).as_bytes()).is_ok(),
+                        };
+                        if !sent {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        Ok(Control { path: path.to_path_buf(), guesses, clients })
+    }
+
+    #[cfg(not(unix))]
+    pub fn bind(_path: &Path) -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "Unix sockets are only on Linux and macOS"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The next guess a client sent, if there is one.
+    pub fn guess(&self) -> Option<String> {
+        self.guesses.try_recv().ok()
+    }
+
+    pub fn send(&self, event: &Event) {
+        self.clients.lock().expect("no thread panics holding the clients").send(line(event));
+    }
+}
+
+impl Drop for Control {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        os::unix::net::UnixStream,
+        thread,
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[test]
+    fn mirrors_the_game_and_takes_guesses() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wordle.sock");
+        let control = Control::bind(&path).unwrap();
+        control.send(&Event::Start { title: "practice #1", header: "Wordle practice #1", word_length: 5, tries: 6 });
+
+        let mut client = UnixStream::connect(&path).unwrap();
+        let mut lines = BufReader::new(client.try_clone().unwrap()).lines();
+        assert!(lines.next().unwrap().unwrap().contains(r#""event":"start""#));
+        client.write_all(b"{\"guess\": \"crane\"}\nnot json\n").unwrap();
+        assert!(lines.next().unwrap().unwrap().contains(r#""event":"invalid""#));
+        let guess = (0..100).find_map(|_| control.guess().or_else(|| { thread::sleep(Duration::from_millis(10)); None }));
+        assert_eq!(guess.as_deref(), Some("crane"));
+
+        drop(control);
+        assert!(!path.exists());
+    }
+}
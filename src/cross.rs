@@ -0,0 +1,397 @@
+//! `cross`: a mini crossword of three or four answers a day, meeting at the
+//! corners of a 5×5 grid. Each guess is played on one of the words, and a
+//! letter found in one word holds for the word crossing it there too.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use chrono::NaiveDate;
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CharGuessKind, CurrentWord, GuessOutcome,
+    app::App,
+    cache,
+    cli::CrossArgs,
+    daily_random,
+    error::{Context, Result, WordleError},
+    i18n::tr,
+    index::WordIndex,
+    input, pick_theme, prompt, streamer, terminal,
+    theme::Theme,
+};
+
+static SIZE: usize = 5;
+
+/// Columns between the grid and the words next to it.
+static GRID_GAP: usize = 4;
+
+/// Where a word goes in the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Slot {
+    Top,
+    Left,
+    Right,
+    Bottom,
+}
+
+/// The slots of a grid of four words, the first three making one of three.
+static SLOTS: [Slot; 4] = [Slot::Top, Slot::Left, Slot::Right, Slot::Bottom];
+
+impl Slot {
+    /// The square of letter `i`, as row and column.
+    fn square(self, i: usize) -> (usize, usize) {
+        match self {
+            Slot::Top => (0, i),
+            Slot::Bottom => (SIZE - 1, i),
+            Slot::Left => (i, 0),
+            Slot::Right => (i, SIZE - 1),
+        }
+    }
+
+    /// Which letter of the word is on `square`, if it goes through it.
+    fn letter_at(self, square: (usize, usize)) -> Option<usize> {
+        (0..SIZE).find(|&i| self.square(i) == square)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Slot::Top => "top",
+            Slot::Left => "left",
+            Slot::Right => "right",
+            Slot::Bottom => "bottom",
+        }
+    }
+}
+
+/// The answers of a grid of `count` words that meet where their slots do,
+/// the first found after shuffling `words` with `seed`.
+fn layout(words: &[&String], count: usize, seed: u64) -> Option<Vec<String>> {
+    let mut words: Vec<&str> = words.iter().filter(|w| w.chars().count() == SIZE).map(|w| w.as_str()).collect();
+    words.sort();
+    words.dedup();
+    words.shuffle(&mut StdRng::seed_from_u64(seed));
+    let first = |w: &str| w.chars().next().expect("words have letters");
+    let last = |w: &str| w.chars().last().expect("words have letters");
+    let mut starting: HashMap<char, Vec<&str>> = HashMap::new();
+    let mut ends: HashMap<(char, char), Vec<&str>> = HashMap::new();
+    for &word in &words {
+        starting.entry(first(word)).or_default().push(word);
+        ends.entry((first(word), last(word))).or_default().push(word);
+    }
+    for &top in &words {
+        for &left in starting.get(&first(top)).into_iter().flatten().filter(|&&w| w != top) {
+            for &right in starting.get(&last(top)).into_iter().flatten().filter(|&&w| w != top && w != left) {
+                if count == 3 {
+                    return Some(vec![top.to_string(), left.to_string(), right.to_string()]);
+                }
+                let bottom = ends.get(&(last(left), last(right))).into_iter().flatten().find(|&&w| ![top, left, right].contains(&w));
+                if let Some(bottom) = bottom {
+                    return Some([top, left, right, bottom].map(str::to_string).to_vec());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The words of a grid, and the tries they share.
+pub struct CrossGame {
+    boards: Vec<CurrentWord>,
+    pub tries: u32,
+}
+
+impl CrossGame {
+    pub fn new(answers: Vec<String>, tries: u32) -> Self {
+        Self { boards: answers.into_iter().map(|answer| CurrentWord::new(answer, tries)).collect(), tries }
+    }
+
+    fn slots(&self) -> &[Slot] {
+        &SLOTS[..self.boards.len()]
+    }
+
+    /// The letter known to be on `square` from a word crossing it, other than
+    /// the one in slot `except`.
+    fn known(&self, square: (usize, usize), except: Option<usize>) -> Option<(char, Slot)> {
+        self.slots().iter().zip(&self.boards).enumerate()
+            .filter(|&(i, _)| Some(i) != except)
+            .find_map(|(_, (&slot, board))| {
+                let i = slot.letter_at(square)?;
+                let row = board.char_guesses.iter().find(|row| row[i].kind == CharGuessKind::Correct)?;
+                Some((row[i].ch, slot))
+            })
+    }
+
+    /// Plays `guess` on the word in `slot`. It's turned away, with why, when
+    /// that word is solved or the guess goes against a letter found by a
+    /// word crossing it.
+    fn guess(&mut self, slot: usize, guess: String, dictionary: &WordIndex) -> std::result::Result<GuessOutcome, String> {
+        if self.tries == 0 || self.boards.iter().all(CurrentWord::is_solved) {
+            return Ok(GuessOutcome::GameOver);
+        }
+        let name = self.slots()[slot].name();
+        if self.boards[slot].is_solved() {
+            return Err(format!("The {} word is solved already.", name));
+        }
+        for (i, ch) in guess.chars().enumerate().take(SIZE) {
+            if let Some((known, other)) = self.known(self.slots()[slot].square(i), Some(slot)) && known != ch {
+                return Err(format!("Letter {} of the {} word is {}, from the {} word.", i + 1, name, known.to_ascii_uppercase(), other.name()));
+            }
+        }
+        match self.boards[slot].guess(guess, dictionary) {
+            invalid @ GuessOutcome::InvalidGuess(_) => return Ok(invalid),
+            _ => self.tries -= 1,
+        }
+        Ok(if self.boards.iter().all(CurrentWord::is_solved) {
+            GuessOutcome::Win
+        } else if self.tries == 0 {
+            GuessOutcome::NoTriesLeft
+        } else {
+            GuessOutcome::Continue
+        })
+    }
+
+    /// Which word a line is for and the guess on it, as "2 crane" or
+    /// "left crane", or just the guess when only one word is left.
+    fn parse(&self, line: &str) -> std::result::Result<(usize, String), String> {
+        let unsolved: Vec<usize> = (0..self.boards.len()).filter(|&i| !self.boards[i].is_solved()).collect();
+        let slot = |word: &str| {
+            self.slots().iter().position(|slot| slot.name() == word)
+                .or_else(|| word.parse::<usize>().ok().filter(|n| (1..=self.boards.len()).contains(n)).map(|n| n - 1))
+        };
+        match line.split_once(char::is_whitespace) {
+            Some((word, guess)) if let Some(slot) = slot(&input::normalize(word)) => Ok((slot, input::normalize(guess))),
+            None if let [only] = unsolved.as_slice() => Ok((*only, input::normalize(line))),
+            _ => Err(format!("Say which word the guess is for, like \"1 {}\" or \"top {}\".", line.trim(), line.trim())),
+        }
+    }
+
+    /// The grid, with the letters found so far.
+    fn grid(&self, theme: &Theme) -> Vec<String> {
+        (0..SIZE)
+            .map(|row| {
+                (0..SIZE)
+                    .map(|column| {
+                        let square = (row, column);
+                        match self.known(square, None) {
+                            Some((ch, _)) => theme.paint(CharGuessKind::Correct, &ch.to_ascii_uppercase().to_string()).to_string(),
+                            None if self.slots().iter().any(|slot| slot.letter_at(square).is_some()) => theme.label(CharGuessKind::NotInWord, "_"),
+                            None => " ".to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    /// A line a word, with the guesses played on it.
+    fn words(&self, theme: &Theme) -> Vec<String> {
+        self.slots().iter().zip(&self.boards).enumerate()
+            .map(|(i, (slot, board))| {
+                let guesses = board.rows(theme)[..board.char_guesses.len()].join(" ");
+                format!("{} {:<6} {}", i + 1, slot.name(), guesses).trim_end().to_string()
+            })
+            .collect()
+    }
+
+    fn display(&self, theme: &Theme) {
+        for line in terminal::side_by_side(&self.grid(theme), &self.words(theme), GRID_GAP) {
+            println!("{}", line);
+        }
+        println!("{} tries left", self.tries);
+    }
+
+    /// How many guesses each word took, X for the unsolved ones.
+    fn scores(&self) -> String {
+        self.boards.iter()
+            .map(|b| if b.is_solved() { b.char_guesses.len().to_string() } else { "X".to_string() })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn guesses(&self) -> usize {
+        self.boards.iter().map(|b| b.char_guesses.len()).sum()
+    }
+}
+
+/// How a day's grid went, as kept in [`cache::cross_path`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Played {
+    pub day: NaiveDate,
+    pub words: u8,
+    pub solved: bool,
+    pub guesses: usize,
+}
+
+fn load(data_dir: &Path) -> Result<Vec<Played>> {
+    let path = cache::cross_path(data_dir);
+    if !path.try_exists().context("checking for cross results")? {
+        return Ok(vec![]);
+    }
+    let json = fs::read_to_string(&path).context("reading cross results")?;
+    serde_json::from_str(&json).map_err(|e| WordleError::CorruptSave(path, e))
+}
+
+fn record(data_dir: &Path, played: Played) -> Result<Vec<Played>> {
+    cache::with_lock(data_dir, || {
+        let mut games = load(data_dir)?;
+        games.push(played);
+        fs::create_dir_all(data_dir).context("creating data dir")?;
+        let json = serde_json::to_string(&games).expect("results serialize");
+        cache::write_atomic(&cache::cross_path(data_dir), json.as_bytes()).context("writing cross results")?;
+        Ok(games)
+    })
+}
+
+/// The days in a row up to the last one played whose grid of `words` was
+/// solved.
+fn streak(games: &[Played], words: u8) -> usize {
+    let mut solved: Vec<NaiveDate> = games.iter().filter(|g| g.words == words && g.solved).map(|g| g.day).collect();
+    solved.sort();
+    solved.dedup();
+    solved.iter().rev().zip(solved.iter().rev().skip(1)).take_while(|(day, before)| day.pred_opt() == Some(**before)).count()
+        + usize::from(!solved.is_empty())
+}
+
+fn show_stats(games: &[Played], words: u8) {
+    let grids: Vec<&Played> = games.iter().filter(|g| g.words == words).collect();
+    if grids.is_empty() {
+        println!("No grids of {} words played yet.", words);
+        return;
+    }
+    let solved: Vec<&&Played> = grids.iter().filter(|g| g.solved).collect();
+    println!("Grids of {} words: {} played, {} solved, a streak of {}", words, grids.len(), solved.len(), streak(games, words));
+    if !solved.is_empty() {
+        let guesses = solved.iter().map(|g| g.guesses).sum::<usize>() as f64 / solved.len() as f64;
+        println!("{:.1} guesses a solved grid", guesses);
+    }
+}
+
+/// Plays the day's grid, or a practice one with `--seed`. The day's is
+/// recorded once; playing it again shows how it went.
+pub fn play(app: &App, args: CrossArgs) -> Result<()> {
+    if args.stats {
+        show_stats(&load(&app.data_dir)?, args.words);
+        return Ok(());
+    }
+    let daily = args.seed.is_none();
+    if daily && let Some(played) = load(&app.data_dir)?.iter().find(|g| g.day == args.day && g.words == args.words) {
+        match played.solved {
+            true => println!("The grid of {} was solved already, in {} guesses.", args.day, played.guesses),
+            false => println!("The grid of {} was played already.", args.day),
+        }
+        return Ok(());
+    }
+
+    let theme = pick_theme(app, None)?;
+    let dictionary = app.dictionary(true)?;
+    let words: Vec<&String> = app.answers()?.unwrap_or(dictionary).iter().collect();
+    let salt = format!("cross{}", args.words);
+    let seed = args.seed.unwrap_or_else(|| daily_random::seed(args.day, salt.as_bytes()));
+    let answers = layout(&words, args.words.into(), seed).ok_or(WordleError::EmptyDictionary)?;
+    let mut game = CrossGame::new(answers, args.tries.unwrap_or(4 * u32::from(args.words)));
+    let title = match daily {
+        true => format!("cross {}", args.day),
+        false => format!("cross practice #{}", seed),
+    };
+    println!("Wordle {}", title);
+    println!("Guess a word with its number or place, like \"1 crane\".");
+
+    let interactive = io::stdin().is_terminal();
+    let mut lines = match interactive {
+        true => prompt::lines(dictionary, app.config.streamer),
+        false => Box::new(io::stdin().lines()),
+    };
+    let outcome = loop {
+        if interactive {
+            println!();
+            game.display(&theme);
+        }
+        let Some(line) = lines.next() else {
+            if !interactive {
+                game.display(&theme);
+            }
+            return Ok(());
+        };
+        let line = line.map_err(|source| WordleError::Terminal { context: "reading your guess", source })?;
+        let guessed = game.parse(&line).and_then(|(slot, guess)| game.guess(slot, guess, dictionary));
+        match guessed {
+            Err(reason) => println!("{}", reason),
+            Ok(GuessOutcome::InvalidGuess(kind)) => println!("{}", kind.message()),
+            Ok(GuessOutcome::Continue) => (),
+            Ok(outcome) => break outcome,
+        }
+    };
+
+    println!();
+    game.display(&theme);
+    match outcome {
+        GuessOutcome::Win => println!("{}", tr!("won")),
+        _ => {
+            println!("{}", tr!("lost"));
+            let answers: Vec<&str> = game.boards.iter().map(|b| b.correct_answer.reveal()).collect();
+            streamer::spoiler(app, &format!("The words were {}.", answers.join(", ")));
+        },
+    }
+    println!("\nWordle {} {}", title, game.scores());
+    if daily {
+        let played = Played { day: args.day, words: args.words, solved: matches!(outcome, GuessOutcome::Win), guesses: game.guesses() };
+        let games = record(&app.data_dir, played)?;
+        println!();
+        show_stats(&games, args.words);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lays_out_words_that_cross() {
+        let words: Vec<String> = ["crane", "cheat", "eagle", "tribe", "rumba"].iter().map(|w| w.to_string()).collect();
+        let words: Vec<&String> = words.iter().collect();
+        for seed in 0..5 {
+            let [top, left, right, bottom] = <[String; 4]>::try_from(layout(&words, 4, seed).unwrap()).unwrap();
+            assert_eq!(top.chars().next(), left.chars().next());
+            assert_eq!(top.chars().last(), right.chars().next());
+            assert_eq!((left.chars().last(), right.chars().last()), (bottom.chars().next(), bottom.chars().last()));
+        }
+        assert_eq!(layout(&words[..2], 3, 0), None);
+    }
+
+    #[test]
+    fn found_letters_hold_for_crossing_words() {
+        let dictionary: WordIndex = ["crane", "cheat", "eagle", "tribe", "rumba", "slate", "candy"].iter().map(|w| w.to_string()).collect();
+        let answers = ["crane", "cheat", "eagle", "tribe"].map(str::to_string).to_vec();
+        let mut game = CrossGame::new(answers, 8);
+        assert!(matches!(game.guess(0, "candy".to_string(), &dictionary), Ok(GuessOutcome::Continue)));
+        assert!(game.guess(1, "slate".to_string(), &dictionary).is_err());
+        assert_eq!(game.parse("left cheat"), Ok((1, "cheat".to_string())));
+        assert!(matches!(game.guess(1, "cheat".to_string(), &dictionary), Ok(GuessOutcome::Continue)));
+        assert_eq!(game.tries, 6);
+        assert!(game.guess(1, "cheat".to_string(), &dictionary).is_err());
+        assert!(game.parse("crane").is_err());
+        for (slot, word) in [(0, "crane"), (2, "eagle")] {
+            game.guess(slot, word.to_string(), &dictionary).unwrap();
+        }
+        assert_eq!(game.parse("tribe"), Ok((3, "tribe".to_string())));
+        assert!(matches!(game.guess(3, "tribe".to_string(), &dictionary), Ok(GuessOutcome::Win)));
+        assert_eq!(game.scores(), "2 1 1 1");
+    }
+
+    #[test]
+    fn counts_the_days_solved_in_a_row() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let played = |d, solved| Played { day: day(d), words: 4, solved, guesses: 10 };
+        assert_eq!(streak(&[played(1, true), played(2, true), played(3, true)], 4), 3);
+        assert_eq!(streak(&[played(1, true), played(3, true)], 4), 1);
+        assert_eq!(streak(&[played(1, true)], 3), 0);
+    }
+}
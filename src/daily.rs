@@ -0,0 +1,59 @@
+use crate::{
+    CurrentWord,
+    app::App,
+    cli::PlayArgs,
+    daily_random,
+    error::{Result, WordleError},
+    output::{self, Event},
+    progress::SaveFile,
+    stats, strategy,
+};
+
+/// Plays the day's wordle, then goes over the game and the stats, all on
+/// one loaded dictionary.
+pub fn run(app: &App, args: PlayArgs) -> Result<()> {
+    let day = args.day;
+    let saves = args.saves_dir(&app.data_dir);
+    let random = args.daily_random;
+    let current_word = match crate::play(app, args) {
+        Ok(current_word) => current_word,
+        Err(WordleError::AlreadyFinished(_)) => {
+            eprintln!("You already finished the wordle for {}, here is how it went.", day);
+            let answer = match random {
+                true => daily_random::answer(app, day)?,
+                false => crate::get_and_write_word(&app.cache_dir, day, &app.nyt())?.solution.into_revealed(),
+            };
+            let mut current_word = CurrentWord::new(answer, 0);
+            if let Some(saved) = SaveFile::for_day(&saves, day).load()? {
+                current_word.char_guesses = saved.char_guesses;
+            }
+            current_word
+        },
+        Err(e) => return Err(e),
+    };
+
+    if !app.json() {
+        println!();
+    }
+    analyze(app, &current_word)?;
+    if !app.json() {
+        println!();
+    }
+    stats::show(app, &saves)
+}
+
+/// Shows how much each guess narrowed down the possible answers.
+fn analyze(app: &App, current_word: &CurrentWord) -> Result<()> {
+    let dictionary = app.dictionary(false)?;
+    let answers = app.answers()?.unwrap_or(dictionary);
+    let remaining = strategy::remaining(&current_word.char_guesses, current_word.word_length, answers, dictionary);
+    for (i, (cgs, counts)) in current_word.char_guesses.iter().zip(remaining.windows(2)).enumerate() {
+        let guess: String = cgs.iter().map(|cg| cg.ch).collect();
+        let (possible, left) = (counts[0], counts[1]);
+        match app.json() {
+            true => output::emit(&Event::Narrowed { guess, possible, left }),
+            false => println!("{}. {}: {} possible answers, {} left after it", i + 1, guess, possible, left),
+        }
+    }
+    Ok(())
+}
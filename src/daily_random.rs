@@ -0,0 +1,70 @@
+//! A puzzle a day picked from the date and the answer list alone, so a
+//! group that doesn't want to contact NYT still shares one word a day.
+
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+
+use crate::{
+    DATE_FORMAT,
+    app::App,
+    error::{Result, WordleError},
+};
+
+/// Where games are saved. Daily random games are kept apart from the
+/// official ones so they have stats of their own.
+pub fn saves_dir(data_dir: &Path, daily_random: bool) -> PathBuf {
+    match daily_random {
+        true => data_dir.join("daily-random"),
+        false => data_dir.to_path_buf(),
+    }
+}
+
+/// FNV-1a, which unlike the standard library's hasher is the same in every
+/// build, so everyone gets the same word.
+fn hash(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// A seed for `day`, the same in every build, for games that pick more than
+/// a word a day.
+pub fn seed(day: NaiveDate, salt: &[u8]) -> u64 {
+    hash(salt.iter().copied().chain(day.format(DATE_FORMAT).to_string().bytes()))
+}
+
+/// The word for `day` out of `words`, the same for anyone with the same list.
+/// Other games picking a word a day this way give a `salt` of their own.
+fn pick(day: NaiveDate, salt: &[u8], mut words: Vec<&String>) -> Option<String> {
+    words.sort();
+    let date = day.format(DATE_FORMAT).to_string();
+    let seed = hash(salt.iter().copied().chain(date.bytes()).chain(words.iter().flat_map(|w| w.bytes().chain([b'\n']))));
+    words.get((seed % words.len().max(1) as u64) as usize).map(|w| w.to_string())
+}
+
+/// The day's word, out of the five letter answers.
+pub fn answer(app: &App, day: NaiveDate) -> Result<String> {
+    salted_answer(app, day, b"")
+}
+
+/// Like [`answer`], for other games with a word a day. The `salt` goes into
+/// the pick, so each game's word differs from the daily random one and from
+/// each other's on the same day.
+pub fn salted_answer(app: &App, day: NaiveDate, salt: &[u8]) -> Result<String> {
+    let words = app.answers()?.unwrap_or(app.dictionary(true)?).iter().filter(|w| w.len() == 5).collect();
+    pick(day, salt, words).ok_or(WordleError::EmptyDictionary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_same_word_for_a_day_whatever_the_order() {
+        let words = ["crane", "slate", "pride", "abbey"].map(str::to_string);
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let forwards = pick(day, b"", words.iter().collect());
+        assert_eq!(forwards, pick(day, b"", words.iter().rev().collect()));
+        let week: Vec<_> = day.iter_days().take(7).map(|d| pick(d, b"", words.iter().collect())).collect();
+        assert!(week.iter().any(|w| *w != forwards));
+    }
+}
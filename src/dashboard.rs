@@ -0,0 +1,159 @@
+//! Stats drawn on one screen in the terminal: the guess distribution, a
+//! calendar of the last months, how solve times went and the openers played
+//! most. Everything comes from the saved games.
+
+use std::{collections::HashMap, io, path::Path, time::Duration};
+
+use chrono::{Datelike, Months, NaiveDate};
+use ratatui::{
+    Frame,
+    crossterm::event::{self, Event, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{BarChart, Block, Paragraph, Sparkline},
+};
+
+use crate::{
+    date_arg, format_duration,
+    error::{Result, WordleError},
+    progress::{self, SavedGame},
+    stats::Stats,
+};
+
+/// How many months the calendar shows, this one included.
+static CALENDAR_MONTHS: u32 = 3;
+
+/// How many months back the solve times go.
+static TREND_MONTHS: usize = 12;
+
+static OPENERS: usize = 5;
+
+/// The average time of the timed wins of each month that had any, oldest
+/// first, as the first day of the month and the average.
+fn monthly_solve_times(games: &[(NaiveDate, SavedGame)]) -> Vec<(NaiveDate, Duration)> {
+    let mut months: Vec<(NaiveDate, Duration, u32)> = vec![];
+    for (day, game) in games.iter().filter(|(_, g)| g.finished && g.won() && g.elapsed_ms > 0) {
+        let month = day.with_day(1).expect("every month has a first day");
+        match months.last_mut() {
+            Some((last, total, count)) if *last == month => {
+                *total += game.elapsed();
+                *count += 1;
+            },
+            _ => months.push((month, game.elapsed(), 1)),
+        }
+    }
+    let skip = months.len().saturating_sub(TREND_MONTHS);
+    months.into_iter().skip(skip).map(|(month, total, count)| (month, total / count)).collect()
+}
+
+fn day_style(game: Option<&SavedGame>) -> Style {
+    match game {
+        Some(game) if game.finished && game.won() && game.char_guesses.len() <= 3 => Style::new().fg(Color::Black).bg(Color::LightGreen),
+        Some(game) if game.finished && game.won() => Style::new().fg(Color::Black).bg(Color::Green),
+        Some(game) if game.finished => Style::new().fg(Color::Black).bg(Color::Red),
+        Some(_) => Style::new().fg(Color::Black).bg(Color::Yellow),
+        None => Style::new().fg(Color::DarkGray),
+    }
+}
+
+/// A month as a calendar, Monday first, each day colored by how it went.
+fn month(first: NaiveDate, games: &HashMap<NaiveDate, &SavedGame>) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(first.format("%B %Y").to_string()),
+        Line::from("Mo Tu We Th Fr Sa Su"),
+    ];
+    let mut week: Vec<Span> = vec![Span::raw("   "); first.weekday().num_days_from_monday() as usize];
+    for day in first.iter_days().take_while(|d| d.month() == first.month()) {
+        week.push(Span::styled(format!("{:>2}", day.day()), day_style(games.get(&day).copied())));
+        week.push(Span::raw(" "));
+        if day.weekday().num_days_from_monday() == 6 {
+            lines.push(Line::from(std::mem::take(&mut week)));
+        }
+    }
+    if !week.is_empty() {
+        lines.push(Line::from(week));
+    }
+    lines
+}
+
+fn draw(frame: &mut Frame, games: &[(NaiveDate, SavedGame)], today: NaiveDate) {
+    let stats = Stats::from_games(games, today);
+    let [top, calendar_area, trend_area, help_area] = Layout::vertical([
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Length(1),
+    ]).areas(frame.area());
+    let [distribution_area, openers_area] = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(top);
+
+    let labels: Vec<String> = (1..=stats.distribution.len()).map(|n| n.to_string()).collect();
+    let bars: Vec<(&str, u64)> = labels.iter().map(String::as_str).zip(stats.distribution.iter().map(|&n| n as u64)).collect();
+    let title = format!("Guess distribution · {} played, {}% won, streak {}", stats.played, stats.win_percentage(), stats.current_streak);
+    frame.render_widget(BarChart::default().block(Block::bordered().title(title)).data(bars.as_slice()).bar_width(3).bar_gap(1), distribution_area);
+
+    let openers: Vec<Line> = stats.openers.iter().take(OPENERS).map(|o| Line::from(format!("{}  {}", o.word, o.played))).collect();
+    frame.render_widget(Paragraph::new(openers).block(Block::bordered().title("Favorite openers")), openers_area);
+
+    let by_day: HashMap<NaiveDate, &SavedGame> = games.iter().map(|(day, game)| (*day, game)).collect();
+    let this_month = today.with_day(1).expect("every month has a first day");
+    let months: Vec<NaiveDate> = (0..CALENDAR_MONTHS).rev().filter_map(|back| this_month.checked_sub_months(Months::new(back))).collect();
+    let calendar_block = Block::bordered().title("Calendar");
+    let inner = calendar_block.inner(calendar_area);
+    frame.render_widget(calendar_block, calendar_area);
+    let columns = Layout::horizontal(months.iter().map(|_| Constraint::Length(23))).split(inner);
+    for (first, area) in months.iter().zip(columns.iter()) {
+        frame.render_widget(Paragraph::new(month(*first, &by_day)), *area);
+    }
+
+    let times = monthly_solve_times(games);
+    let title = match (times.first(), times.last()) {
+        (Some((from, first)), Some((to, last))) => format!(
+            "Average solve time · {} {} → {} {}",
+            from.format("%b %Y"), format_duration(*first), to.format("%b %Y"), format_duration(*last),
+        ),
+        _ => "Average solve time · no timed wins yet".to_string(),
+    };
+    let seconds: Vec<u64> = times.iter().map(|(_, took)| took.as_secs()).collect();
+    frame.render_widget(Sparkline::default().block(Block::bordered().title(title)).data(&seconds), trend_area);
+
+    frame.render_widget(Paragraph::new("Press any key to leave."), help_area);
+}
+
+/// Shows the dashboard of the games in `data_dir` until a key is pressed.
+pub fn run(data_dir: &Path) -> Result<()> {
+    let games = progress::saved_games(data_dir)?;
+    let today = date_arg::today();
+    let mut terminal = ratatui::init();
+    let shown = (|| -> io::Result<()> {
+        loop {
+            terminal.draw(|f| draw(f, &games, today))?;
+            if let Event::Key(key) = event::read()? && key.kind == KeyEventKind::Press {
+                return Ok(());
+            }
+        }
+    })();
+    ratatui::restore();
+    shown.map_err(|source| WordleError::Terminal { context: "showing the dashboard", source })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Days;
+
+    use super::*;
+    use crate::CharGuess;
+
+    fn game(day: u32, opener: &str, elapsed_ms: u64) -> (NaiveDate, SavedGame) {
+        let row = |word: &str| word.chars().map(|ch| CharGuess::new(ch, crate::CharGuessKind::Correct)).collect();
+        let game = SavedGame { char_guesses: vec![row(opener)], finished: true, elapsed_ms, ..SavedGame::default() };
+        (NaiveDate::from_ymd_opt(2025, 1, 1).unwrap() + Days::new(day.into()), game)
+    }
+
+    #[test]
+    fn averages_solve_times_by_month() {
+        let games = [game(0, "crane", 60_000), game(1, "slate", 120_000), game(2, "crane", 0), game(40, "?????", 30_000)];
+        let times: Vec<(u32, u64)> = monthly_solve_times(&games).into_iter().map(|(m, t)| (m.month(), t.as_secs())).collect();
+        assert_eq!(times, [(1, 90), (2, 30)]);
+    }
+}
@@ -0,0 +1,155 @@
+//! Days as they're given on the command line: a date, or one relative to
+//! today like yesterday, -3 or monday.
+//!
+//! Like on the official site, a new wordle comes out at midnight where you
+//! are, so today is the local date unless another time zone was picked.
+
+use std::{sync::OnceLock, time::Duration};
+
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::{DATE_FORMAT, prefetch::FIRST_DAY};
+
+/// The time zone picked with `--timezone` or in the config file.
+static TIMEZONE: OnceLock<Tz> = OnceLock::new();
+
+/// Makes [`today`] the date in `zone` rather than the local one. Only the
+/// first call counts.
+pub fn set_timezone(zone: Tz) {
+    let _ = TIMEZONE.set(zone);
+}
+
+/// Today's date, which decides the day's wordle.
+pub fn today() -> NaiveDate {
+    match TIMEZONE.get() {
+        Some(zone) => Utc::now().with_timezone(zone).date_naive(),
+        None => Local::now().date_naive(),
+    }
+}
+
+/// The date and time of day where you are.
+pub fn now() -> NaiveDateTime {
+    match TIMEZONE.get() {
+        Some(zone) => Utc::now().with_timezone(zone).naive_local(),
+        None => Local::now().naive_local(),
+    }
+}
+
+/// How long until `day` starts where you are, which is when its wordle
+/// comes out. Zero once it has.
+pub fn until(day: NaiveDate) -> Duration {
+    let midnight = day.and_time(NaiveTime::MIN);
+    let start = match TIMEZONE.get() {
+        Some(zone) => zone.from_local_datetime(&midnight).earliest().map(|t| t.with_timezone(&Utc)),
+        None => Local.from_local_datetime(&midnight).earliest().map(|t| t.with_timezone(&Utc)),
+    };
+    start.and_then(|start| (start - Utc::now()).to_std().ok()).unwrap_or(Duration::ZERO)
+}
+
+/// Parses a time zone name like Europe/Stockholm or UTC.
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse().map_err(|_| format!("unknown time zone '{}', expected a name like Europe/Stockholm or UTC", name))
+}
+
+/// Reads `date` as a date, today, yesterday, tomorrow, a number of days
+/// back like -3, or a weekday, which is the last one on or before `today`.
+pub fn relative_to(date: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let date = date.trim().to_lowercase();
+    let days_back = match date.as_str() {
+        "today" => Some(0),
+        "yesterday" => Some(1),
+        "tomorrow" => return today.checked_add_days(Days::new(1)).ok_or_else(|| "that's too far ahead".to_string()),
+        _ => date.strip_prefix('-').and_then(|n| n.parse::<u64>().ok()),
+    };
+    if let Some(days_back) = days_back {
+        return today.checked_sub_days(Days::new(days_back)).ok_or_else(|| "that's too far back".to_string());
+    }
+    if let Ok(weekday) = date.parse::<Weekday>() {
+        let days_back = (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+        return Ok(today - Days::new(days_back.into()));
+    }
+    NaiveDate::parse_from_str(&date, DATE_FORMAT)
+        .map_err(|_| "expected a date like 2025-01-31, today, yesterday, tomorrow, -3 or a weekday".to_string())
+}
+
+/// Parses any day, for ranges like the ones `fetch` takes.
+pub fn parse(date: &str) -> Result<NaiveDate, String> {
+    relative_to(date, today())
+}
+
+/// Parses the day of a wordle, which has to be one there is: from the first
+/// one on, and at most a day ahead, since the next day's is out before
+/// midnight in some time zones.
+pub fn parse_day(date: &str) -> Result<NaiveDate, String> {
+    check_day(relative_to(date, today())?)
+}
+
+/// Parses the number of a wordle, e.g. 1123 or #1,123, as its day.
+pub fn parse_puzzle_number(number: &str) -> Result<NaiveDate, String> {
+    let digits: String = number.trim().trim_start_matches('#').chars().filter(|&ch| ch != ',').collect();
+    let number: u64 = digits.parse().map_err(|_| format!("expected a puzzle number like 1123, not '{}'", number))?;
+    check_day(FIRST_DAY.checked_add_days(Days::new(number)).ok_or_else(|| "that's too far ahead".to_string())?)
+}
+
+/// The number of the day's wordle, counting the first one as 0.
+pub fn puzzle_number(day: NaiveDate) -> Option<u32> {
+    u32::try_from((day - FIRST_DAY).num_days()).ok()
+}
+
+fn check_day(day: NaiveDate) -> Result<NaiveDate, String> {
+    let today = today();
+    if day < FIRST_DAY {
+        return Err(format!("the first wordle was on {}", FIRST_DAY));
+    }
+    if today.checked_add_days(Days::new(1)).is_some_and(|tomorrow| day > tomorrow) {
+        return Err(format!("the wordle for {} isn't out yet", day));
+    }
+    Ok(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_relative_days() {
+        // A wednesday.
+        let today = NaiveDate::from_ymd_opt(2025, 1, 8).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        assert_eq!(relative_to("today", today), Ok(today));
+        assert_eq!(relative_to("Yesterday", today), Ok(day(7)));
+        assert_eq!(relative_to("-3", today), Ok(day(5)));
+        assert_eq!(relative_to("monday", today), Ok(day(6)));
+        assert_eq!(relative_to("wed", today), Ok(today));
+        assert_eq!(relative_to("thursday", today), Ok(day(2)));
+        assert_eq!(relative_to("2025-01-01", today), Ok(day(1)));
+        assert!(relative_to("someday", today).is_err());
+    }
+
+    #[test]
+    fn rejects_days_without_a_wordle() {
+        assert!(parse_day("2021-06-18").is_err());
+        assert!(parse_day("2021-06-19").is_ok());
+        assert!(parse_day("tomorrow").is_ok());
+        let later = today().checked_add_days(Days::new(2)).unwrap().to_string();
+        assert!(parse_day(&later).is_err());
+    }
+
+    #[test]
+    fn reads_puzzle_numbers_as_days() {
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert_eq!(parse_puzzle_number("1292"), Ok(day));
+        assert_eq!(parse_puzzle_number("#1,292"), Ok(day));
+        assert_eq!(puzzle_number(day), Some(1292));
+        assert!(parse_puzzle_number("99999").is_err());
+        assert!(parse_puzzle_number("crane").is_err());
+    }
+
+    #[test]
+    fn waits_until_the_day_starts() {
+        assert_eq!(until(today()), Duration::ZERO);
+        let in_two_days = until(today() + Days::new(2));
+        assert!(in_two_days > Duration::from_secs(24 * 60 * 60) && in_two_days <= Duration::from_secs(49 * 60 * 60));
+    }
+}
@@ -0,0 +1,154 @@
+//! Short definitions of answers, for hints that don't give letters away.
+//! They come from Webster's 1913 dictionary, which is only built in with
+//! the `definitions` feature.
+//!
+//! Once the game is over `--define` looks the answer up online instead, in a
+//! free dictionary API that also knows where many words come from.
+
+use std::fs;
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::App,
+    cache,
+    error::{Context, Result, WordleError},
+};
+
+/// Where `--define` looks words up, as `<url>/<word>`.
+pub static API_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries/en";
+
+/// What a word means, as looked up once the game is over.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Meaning {
+    pub part_of_speech: Option<String>,
+    pub definition: String,
+    /// Where the word comes from, which the API only knows for some.
+    pub origin: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    origin: Option<String>,
+    #[serde(default)]
+    meanings: Vec<EntryMeaning>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntryMeaning {
+    part_of_speech: Option<String>,
+    #[serde(default)]
+    definitions: Vec<Sense>,
+}
+
+#[derive(Deserialize)]
+struct Sense {
+    definition: String,
+}
+
+/// The first sense of the entries the API answered with.
+fn first_meaning(entries: Vec<Entry>) -> Option<Meaning> {
+    let origin = entries.iter().filter_map(|e| e.origin.clone()).find(|o| !o.is_empty());
+    let (part_of_speech, sense) = entries.into_iter()
+        .flat_map(|e| e.meanings)
+        .find_map(|m| Some((m.part_of_speech, m.definitions.into_iter().next()?)))?;
+    Some(Meaning { part_of_speech, definition: sense.definition, origin })
+}
+
+/// `word` looked up in the dictionary API, or in the cache after the first
+/// time. Words the API doesn't know are remembered as `None` too, so they
+/// aren't asked for again.
+pub fn look_up(app: &App, word: &str) -> Result<Option<Meaning>> {
+    let path = cache::definition_path(&app.cache_dir, word);
+    if let Ok(json) = fs::read_to_string(&path) {
+        return serde_json::from_str(&json).map_err(|e| WordleError::CorruptSave(path, e));
+    }
+    let context = "looking up the answer";
+    let response = app.client.send(&format!("{}/{}", API_URL, word), context)?;
+    let meaning = match response.status() {
+        StatusCode::NOT_FOUND => None,
+        _ => first_meaning(response.error_for_status().and_then(|r| r.json()).context(context)?),
+    };
+    let json = serde_json::to_string(&meaning).expect("a meaning serializes");
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("creating the definitions dir")?;
+    }
+    cache::write_atomic(&path, json.as_bytes()).context("caching the definition")?;
+    Ok(meaning)
+}
+
+/// What the built in dictionary says `word` means, for when it can't be
+/// looked up online.
+#[cfg(feature = "definitions")]
+pub fn built_in(word: &str) -> Option<Meaning> {
+    let text = webster::dictionary(word)?;
+    let definition = text.split(';').next().unwrap_or(text).trim().to_string();
+    Some(Meaning { part_of_speech: None, definition, origin: None })
+}
+
+#[cfg(not(feature = "definitions"))]
+pub fn built_in(_word: &str) -> Option<Meaning> {
+    None
+}
+
+/// Whether this build has definitions to give.
+pub static AVAILABLE: bool = cfg!(feature = "definitions");
+
+/// The first sense of `word` from the dictionary, with the word itself
+/// blanked out wherever the definition uses it. Entries that only point to
+/// another word don't make a clue.
+#[cfg(feature = "definitions")]
+pub fn of(word: &str) -> Option<String> {
+    webster::dictionary(word).map(|text| clue(word, text)).filter(|clue| !clue.starts_with("See "))
+}
+
+#[cfg(not(feature = "definitions"))]
+pub fn of(_word: &str) -> Option<String> {
+    None
+}
+
+#[cfg_attr(not(feature = "definitions"), allow(dead_code))]
+fn clue(word: &str, text: &str) -> String {
+    let sense = text.split(';').next().unwrap_or(text).trim().trim_end_matches('.');
+    let blank = "_".repeat(word.len());
+    sense.split(' ')
+        .map(|w| match w.to_lowercase().starts_with(word) {
+            true => format!("{}{}", blank, w.get(word.len()..).unwrap_or("")),
+            false => w.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_first_sense_without_the_word() {
+        assert_eq!(
+            clue("slate", "An argillaceous rock which readily splits into thin plates;argillite."),
+            "An argillaceous rock which readily splits into thin plates",
+        );
+        assert_eq!(clue("crane", "Cranes are birds. A crane lifts."), "_____s are birds. A _____ lifts");
+    }
+
+    #[test]
+    fn takes_the_first_sense_and_any_origin() {
+        let json = r#"[
+            {"word": "parer", "meanings": []},
+            {"word": "parer", "origin": "From pare.", "meanings": [
+                {"partOfSpeech": "noun", "definitions": [{"definition": "A tool for paring."}, {"definition": "One who pares."}]}
+            ]}
+        ]"#;
+        let meaning = first_meaning(serde_json::from_str(json).unwrap()).unwrap();
+        assert_eq!(meaning, Meaning {
+            part_of_speech: Some("noun".to_string()),
+            definition: "A tool for paring.".to_string(),
+            origin: Some("From pare.".to_string()),
+        });
+        assert_eq!(first_meaning(vec![]), None);
+    }
+}
@@ -0,0 +1,104 @@
+//! `--dict-index fst`: the dictionary built into an FST next to the text
+//! one, so guesses are looked up in the mapped file instead of a set of every
+//! word on the heap. Only what needs every word, like hints and picking a
+//! practice word, reads them all.
+//!
+//! The file starts with a header like [`crate::compiled`]'s, telling what
+//! text it was built from, and one that doesn't match is built again.
+
+use std::{
+    fs::{self, File, Metadata},
+    io,
+    path::Path,
+};
+
+use memmap2::Mmap;
+use serde::Deserialize;
+
+use crate::{cache, compiled};
+
+/// How the dictionary is kept while playing.
+#[derive(clap::ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DictIndex {
+    /// Every word in memory
+    #[default]
+    Memory,
+    /// Looked up in an FST on disk, built when the dictionary is downloaded
+    Fst,
+}
+
+static MAGIC: &[u8; 4] = b"WRDF";
+static VERSION: u8 = 1;
+/// Magic, version, source size and source modification time.
+static HEADER_LEN: usize = 4 + 1 + 8 + 8;
+
+/// The mapped file, past its header.
+pub struct Mapped(Mmap);
+
+impl AsRef<[u8]> for Mapped {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[HEADER_LEN..]
+    }
+}
+
+fn header(source: &Metadata) -> Vec<u8> {
+    let (len, modified) = compiled::stamp(source);
+    let mut bytes = Vec::with_capacity(HEADER_LEN);
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    bytes.extend_from_slice(&len.to_le_bytes());
+    bytes.extend_from_slice(&modified.to_le_bytes());
+    bytes
+}
+
+/// Builds the index of `words`, the ones of the text dictionary as it is now.
+pub fn save<'a>(cache_dir: &Path, words: impl IntoIterator<Item = &'a str>) -> io::Result<()> {
+    let source = fs::metadata(cache::dictionary_path(cache_dir))?;
+    let mut sorted: Vec<&str> = words.into_iter().collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let mut builder = fst::SetBuilder::new(header(&source)).map_err(io::Error::other)?;
+    builder.extend_iter(sorted).map_err(io::Error::other)?;
+    let bytes = builder.into_inner().map_err(io::Error::other)?;
+    cache::write_atomic(&cache::dictionary_index_path(cache_dir), &bytes)
+}
+
+/// The index, if it was built from the text dictionary as it is.
+pub fn load(cache_dir: &Path) -> io::Result<Option<fst::Set<Mapped>>> {
+    let source = fs::metadata(cache::dictionary_path(cache_dir))?;
+    let file = match File::open(cache::dictionary_index_path(cache_dir)) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    // Safety: like the compiled dictionary, the file is only ever replaced by
+    // a rename, never written in place.
+    let map = unsafe { Mmap::map(&file)? };
+    if map.get(..HEADER_LEN) != Some(header(&source).as_slice()) {
+        return Ok(None);
+    }
+    Ok(fst::Set::new(Mapped(map)).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_words_up_until_the_text_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        cache::prepare(dir.path()).unwrap();
+        fs::write(cache::dictionary_path(dir.path()), "slate\ncrane\n").unwrap();
+        save(dir.path(), ["slate", "crane"]).unwrap();
+        let words = load(dir.path()).unwrap().unwrap();
+        assert!(words.contains("crane") && !words.contains("pride"));
+        assert_eq!(words.len(), 2);
+        let index = crate::index::WordIndex::on_disk(words, ["pride".to_string()].into());
+        assert!(index.contains("crane") && index.contains("pride"));
+        assert_eq!((index.len(), index.iter().count()), (3, 3));
+
+        fs::write(cache::dictionary_path(dir.path()), "slate\ncrane\npride\n").unwrap();
+        assert!(load(dir.path()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io::{self, Read},
+    path::Path,
+    time::Duration,
+};
+
+use chrono::Utc;
+use rayon::{prelude::*, str::ParallelString};
+use reqwest::{StatusCode, blocking::Response};
+
+use crate::{
+    ALPHABET, WORD_LENGTHS,
+    app::{self, LazyClient, Validators},
+    cache, compiled, dict_index,
+    error::{Context, Result, WordleError},
+};
+
+/// Where the allowed guesses are downloaded from unless `--dictionary-url` or
+/// the config file point at a mirror.
+pub static URL: &str = "https://gist.githubusercontent.com/dracos/dd0668f281e685bad51479e5acaadb93/raw/6bfa15d263d6d5b63840a8e5b64e04b382fdb079/valid-wordle-words.txt";
+
+/// The words NYT picks answers from, a small part of the allowed guesses.
+pub static ANSWERS_URL: &str = "https://gist.githubusercontent.com/cfreshman/a03ef2cba789d8cf00c08f767e0fad7b/raw/wordle-answers-alphabetical.txt";
+
+/// How many days the dictionary is used before asking whether it changed.
+pub static DEFAULT_TTL_DAYS: u64 = 30;
+
+/// What was wrong with the lines of a word list, which were either fixed or
+/// left out.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct Anomalies {
+    /// Lines with capitals or spaces around the word, which are kept fixed.
+    pub fixed: usize,
+    pub wrong_length: usize,
+    pub non_letters: usize,
+    pub duplicates: usize,
+}
+
+impl Anomalies {
+    /// What was wrong in words, e.g. "2 duplicates, 1 with other than
+    /// letters", or nothing when all was well.
+    pub fn describe(&self) -> Option<String> {
+        let counts = [
+            (self.fixed, "with capitals or spaces, which were fixed"),
+            (self.wrong_length, "that aren't 4 to 8 letters long"),
+            (self.non_letters, "with other than letters"),
+            (self.duplicates, "that were duplicates"),
+        ];
+        let found: Vec<String> = counts.iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, what)| format!("{} {}", count, what))
+            .collect();
+        (!found.is_empty()).then(|| found.join(", "))
+    }
+}
+
+enum Entry {
+    Word { word: String, fixed: bool },
+    Blank,
+    WrongLength,
+    NonLetters,
+}
+
+fn entry(line: &str) -> Entry {
+    let word = line.trim().to_ascii_lowercase();
+    if word.is_empty() {
+        Entry::Blank
+    } else if !word.chars().all(|ch| ALPHABET.contains(&ch)) {
+        Entry::NonLetters
+    } else if !WORD_LENGTHS.contains(&word.len()) {
+        Entry::WrongLength
+    } else {
+        Entry::Word { fixed: word != line, word }
+    }
+}
+
+/// The words of a word list, lowercased, without duplicates and sorted, and
+/// what was wrong with the rest of its lines. Dictionaries are big enough for
+/// the lines to be gone through in parallel.
+pub fn normalize(text: &str) -> (Vec<String>, Anomalies) {
+    let entries: Vec<Entry> = text.par_lines().map(entry).collect();
+    let mut anomalies = Anomalies::default();
+    let mut words = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match entry {
+            Entry::Word { word, fixed } => {
+                anomalies.fixed += fixed as usize;
+                words.push(word);
+            },
+            Entry::Blank => (),
+            Entry::WrongLength => anomalies.wrong_length += 1,
+            Entry::NonLetters => anomalies.non_letters += 1,
+        }
+    }
+    words.par_sort_unstable();
+    let listed = words.len();
+    words.dedup();
+    anomalies.duplicates = listed - words.len();
+    (words, anomalies)
+}
+
+/// Normalizes the text of a word list, saying what was wrong with it.
+fn checked(text: &str, what: &str) -> Vec<String> {
+    let (words, anomalies) = normalize(text);
+    if let Some(found) = anomalies.describe() {
+        eprintln!("Lines of the {} were left out or fixed: {}.", what, found);
+    }
+    words
+}
+
+/// The text of a response and what the server said about it.
+fn received(response: Response, context: &'static str) -> Result<(String, Validators)> {
+    let validators = Validators::from_response(&response);
+    let text = response.error_for_status()
+        .and_then(|r| r.text())
+        .context(context)?;
+    Ok((text, validators))
+}
+
+/// The text of the word list at `url`. A `file://` one is read from disk,
+/// which works with --no-network too.
+fn download(url: &str, client: &LazyClient, context: &'static str) -> Result<(String, Validators)> {
+    match app::local_path(url) {
+        Some(path) => {
+            let text = fs::read_to_string(path).context(context)?;
+            Ok((text, Validators { checked: Some(Utc::now()), ..Validators::default() }))
+        },
+        None => received(client.send(url, context)?, context),
+    }
+}
+
+/// Downloads the dictionary from `url`, replacing the cached one.
+pub fn write(cache_dir: &Path, url: &str, client: &LazyClient) -> Result<File> {
+    let (text, validators) = download(url, client, "downloading dictionary")?;
+    store(cache_dir, &text, validators)?;
+    File::open(cache::dictionary_path(cache_dir)).context("opening dictionary file")
+}
+
+fn store(cache_dir: &Path, text: &str, validators: Validators) -> Result<String> {
+    let mut text = checked(text, "downloaded dictionary").join("\n");
+    text.push('\n');
+    cache::write_atomic(&cache::dictionary_path(cache_dir), text.as_bytes()).context("writing dict file")?;
+    validators.save(&cache::dictionary_validators_path(cache_dir))?;
+    // Like the compiled copy, the index is built again when it's next needed.
+    let _ = dict_index::save(cache_dir, text.lines());
+    Ok(text)
+}
+
+/// Reads the cached dictionary, downloading it from `url` the first time.
+pub fn read(cache_dir: &Path, url: &str, offline: bool, client: &LazyClient) -> Result<HashSet<String>> {
+    let mut f = match File::open(cache::dictionary_path(cache_dir)) {
+        Ok(f) => f,
+        Err(e) if e.kind() == io::ErrorKind::NotFound && offline => return embedded().ok_or(WordleError::NoDictionary),
+        // Until one is downloaded the built in copy will do.
+        Err(e) if e.kind() == io::ErrorKind::NotFound => match write(cache_dir, url, client) {
+            Ok(f) => f,
+            Err(e) => return embedded().ok_or(e),
+        },
+        Err(e) => return Err(e).context("opening dictionary file"),
+    };
+    if let Ok(Some(words)) = compiled::load(cache_dir) {
+        return Ok(words);
+    }
+    let mut text = String::new();
+    f.read_to_string(&mut text).context("reading dictionary")?;
+    let words: HashSet<String> = checked(&text, "cached dictionary").into_iter().collect();
+    // The compiled copy only makes the next start faster, so it not being
+    // written is no reason to stop.
+    let _ = compiled::save(cache_dir, &words);
+    Ok(words)
+}
+
+/// The allowed guesses as they were when this was built.
+#[cfg(feature = "embedded-dictionary")]
+fn embedded() -> Option<HashSet<String>> {
+    use std::io::Read;
+
+    static WORDS: &[u8] = include_bytes!("../assets/valid-wordle-words.txt.gz");
+    let mut text = String::new();
+    flate2::read::GzDecoder::new(WORDS).read_to_string(&mut text).expect("the built in dictionary is valid");
+    Some(text.lines().map(str::to_string).collect())
+}
+
+#[cfg(not(feature = "embedded-dictionary"))]
+fn embedded() -> Option<HashSet<String>> {
+    None
+}
+
+/// Downloads the list of likely answers from `url`, replacing the cached one.
+pub fn write_answers(cache_dir: &Path, url: &str, client: &LazyClient) -> Result<()> {
+    let (text, _) = download(url, client, "downloading the answer list")?;
+    let mut text = checked(&text, "downloaded answer list").join("\n");
+    text.push('\n');
+    cache::write_atomic(&cache::answers_path(cache_dir), text.as_bytes()).context("writing the answer list")
+}
+
+/// Caches `text` as the dictionary, e.g. from an archive. It's checked for
+/// changes the next time there's a network.
+pub fn import(cache_dir: &Path, text: &str) -> Result<()> {
+    store(cache_dir, text, Validators::default()).map(|_| ())
+}
+
+/// Caches `text` as the list of likely answers.
+pub fn import_answers(cache_dir: &Path, text: &str) -> Result<()> {
+    let mut text = checked(text, "imported answer list").join("\n");
+    text.push('\n');
+    cache::write_atomic(&cache::answers_path(cache_dir), text.as_bytes()).context("writing the answer list")
+}
+
+/// Reads the cached list of likely answers, which is only there once it was
+/// downloaded along with the dictionary.
+pub fn read_answers(cache_dir: &Path) -> Result<Option<HashSet<String>>> {
+    let path = cache::answers_path(cache_dir);
+    if !path.try_exists().context("checking for the answer list")? {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&path).context("reading the answer list")?;
+    Ok(Some(checked(&text, "cached answer list").into_iter().collect()))
+}
+
+/// Asks the server at `url` whether the dictionary changed once it was last
+/// checked more than `ttl` ago, and downloads it again only if it did. A
+/// mirror on disk is simply read again. Returns how many words were added
+/// and removed.
+pub fn refresh(cache_dir: &Path, url: &str, ttl: Duration, client: &LazyClient) -> Result<(usize, usize)> {
+    let path = cache::dictionary_path(cache_dir);
+    if !path.try_exists().context("checking for dictionary")? {
+        return Ok((0, 0));
+    }
+    let mut validators = Validators::load(&cache::dictionary_validators_path(cache_dir));
+    if validators.checked.is_some_and(|checked| (Utc::now() - checked).to_std().is_ok_and(|age| age < ttl)) {
+        return Ok((0, 0));
+    }
+
+    let (text, validators) = match app::local_path(url) {
+        Some(_) => download(url, client, "checking the dictionary for changes")?,
+        None => {
+            let response = client.send_with(|c| validators.ask(c.get(url)), "checking the dictionary for changes")?;
+            if response.status() == StatusCode::NOT_MODIFIED {
+                validators.checked = Some(Utc::now());
+                return validators.save(&cache::dictionary_validators_path(cache_dir)).map(|_| (0, 0));
+            }
+            received(response, "downloading dictionary")?
+        },
+    };
+
+    let old = read(cache_dir, url, true, client)?;
+    let text = store(cache_dir, &text, validators)?;
+    let new: HashSet<&str> = text.lines().collect();
+    let added = new.iter().filter(|w| !old.contains(**w)).count();
+    let removed = old.iter().filter(|w| !new.contains(w.as_str())).count();
+    Ok((added, removed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_word_lists_and_tells_what_was_wrong() {
+        let (words, anomalies) = normalize("slate\nCrane \n\ncrane\nab\ncr4ne\nslate\n");
+        assert_eq!(words, ["crane", "slate"]);
+        assert_eq!(anomalies, Anomalies { fixed: 1, wrong_length: 1, non_letters: 1, duplicates: 2 });
+        assert_eq!(normalize("crane\nslate\n").1.describe(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-dictionary")]
+    fn falls_back_to_the_built_in_dictionary_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        cache::prepare(dir.path()).unwrap();
+        let client = LazyClient::new(Duration::from_secs(1), true);
+        let words = read(dir.path(), URL, true, &client).unwrap();
+        assert!(words.len() > 10_000);
+        assert!(words.contains("crane"));
+    }
+}
@@ -0,0 +1,149 @@
+//! How hard a practice word is to find, judged from its letters: common
+//! letters that each appear once are easy, rare and repeated letters hard.
+//! The rating shown after a game with `--answer-difficulty` also counts the
+//! answers a letter away, the trap of words like -ight.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::analysis::Frequencies;
+
+/// Which third of the answers, ranked by [`score`], practice words come from.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+/// What a repeated letter adds to the score. Repeats are what most often
+/// leave a player stuck with a few tries and one letter to go.
+static REPEAT_PENALTY: f64 = 0.5;
+
+/// The average rarity of the word's letters among the answers, from 0 for
+/// letters in every answer to 1 for letters in none, plus a penalty for each
+/// repeat.
+pub fn score(word: &str, frequencies: &Frequencies) -> f64 {
+    let letters: HashSet<char> = word.chars().collect();
+    let rarity: f64 = word.chars()
+        .map(|ch| match ch.is_ascii_lowercase() {
+            true => 1.0 - frequencies.anywhere[(ch as u8 - b'a') as usize] as f64 / frequencies.answers.max(1) as f64,
+            false => 1.0,
+        })
+        .sum();
+    let repeats = word.chars().count() - letters.len();
+    rarity / word.chars().count().max(1) as f64 + repeats as f64 * REPEAT_PENALTY
+}
+
+/// Letters in fewer answers than this share are rare.
+static RARE: f64 = 0.1;
+
+/// What the words a letter away add to the rating's score, for the first few
+/// more than the rest.
+static NEIGHBOR_WEIGHT: f64 = 0.25;
+
+/// `word` with a `_` in place of the letter at `i`, which the words a letter
+/// away from it share.
+fn gap(word: &str, i: usize) -> String {
+    word.chars().enumerate().map(|(j, ch)| if i == j { '_' } else { ch }).collect()
+}
+
+/// How many of `words` have each [`gap`] in them.
+fn gaps<'a>(words: impl IntoIterator<Item = &'a String>) -> HashMap<String, usize> {
+    let mut gaps = HashMap::new();
+    for word in words {
+        for i in 0..word.chars().count() {
+            *gaps.entry(gap(word, i)).or_insert(0) += 1;
+        }
+    }
+    gaps
+}
+
+/// How hard an answer was, next to the others.
+#[derive(Debug, PartialEq)]
+pub struct Rating {
+    /// From 1 for the easiest tenth of the answers to 10 for the hardest.
+    pub out_of_ten: u8,
+    /// The share of the answers that are easier, in percent.
+    pub harder_than: u8,
+    pub rare_letters: Vec<char>,
+    pub repeated: Vec<char>,
+    /// The answers that differ from it by one letter.
+    pub neighbors: Vec<String>,
+}
+
+/// Rates `word` against `answers`, the ones of its length scored the same way.
+pub fn rate(word: &str, answers: &[&String]) -> Rating {
+    let length = word.chars().count();
+    let answers: Vec<&String> = answers.iter().copied().filter(|w| w.chars().count() == length).collect();
+    let frequencies = Frequencies::of(answers.iter().copied(), length);
+    let gaps = gaps(answers.iter().copied());
+    let listed = answers.iter().any(|w| *w == word);
+    // A word's own gaps count it too, when it's one of the answers.
+    let neighbor_count = |w: &str, itself: bool| -> usize {
+        (0..length).map(|i| gaps.get(&gap(w, i)).copied().unwrap_or(0).saturating_sub(usize::from(itself))).sum()
+    };
+    let total = |w: &str, itself: bool| score(w, &frequencies) + (1.0 + neighbor_count(w, itself) as f64).ln() * NEIGHBOR_WEIGHT;
+
+    let mine = total(word, listed);
+    let easier = answers.iter().filter(|w| total(w, true) < mine).count();
+    let harder_than = (easier * 100 / answers.len().max(1)) as u8;
+
+    let mut letters: Vec<char> = word.chars().collect::<HashSet<_>>().into_iter().collect();
+    letters.sort_unstable();
+    let rare_letters = letters.iter().copied()
+        .filter(|&ch| !ch.is_ascii_lowercase() || (frequencies.anywhere[(ch as u8 - b'a') as usize] as f64) < RARE * frequencies.answers as f64)
+        .collect();
+    let repeated = letters.iter().copied().filter(|&ch| word.chars().filter(|&c| c == ch).count() > 1).collect();
+    let mut neighbors: Vec<String> = answers.iter()
+        .filter(|w| w.chars().zip(word.chars()).filter(|(a, b)| a != b).count() == 1)
+        .map(|w| w.to_string())
+        .collect();
+    neighbors.sort_unstable();
+    Rating { out_of_ten: (harder_than / 10 + 1).min(10), harder_than, rare_letters, repeated, neighbors }
+}
+
+/// The third of `words` at the given difficulty, with every word scored over
+/// the same list first.
+pub fn filter(words: Vec<&String>, difficulty: Difficulty, length: usize) -> Vec<&String> {
+    let frequencies = Frequencies::of(words.iter().copied(), length);
+    let mut scored: Vec<(f64, &String)> = words.into_iter().map(|w| (score(w, &frequencies), w)).collect();
+    scored.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(b.1)));
+    let third = scored.len().div_ceil(3);
+    let start = match difficulty {
+        Difficulty::Easy => 0,
+        Difficulty::Medium => third,
+        Difficulty::Hard => 2 * third,
+    };
+    scored.into_iter().skip(start).take(third).map(|(_, w)| w).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_and_rare_letters_are_hard() {
+        let words: Vec<String> = ["arose", "stare", "crane", "slate", "jazzy", "fuzzy"].map(String::from).to_vec();
+        let frequencies = Frequencies::of(&words, 5);
+        assert!(score("stare", &frequencies) < score("jazzy", &frequencies));
+        assert!(score("slate", &frequencies) < score("sleet", &frequencies));
+
+        let hard = filter(words.iter().collect(), Difficulty::Hard, 5);
+        assert_eq!(hard.len(), 2);
+        assert!(hard.iter().all(|w| *w == "jazzy" || *w == "fuzzy"));
+        assert!(!filter(words.iter().collect(), Difficulty::Easy, 5).iter().any(|w| *w == "jazzy"));
+    }
+
+    #[test]
+    fn rates_answers_against_the_others() {
+        let words: Vec<String> = ["light", "might", "night", "right", "sight", "crane", "stare", "jazzy"].map(String::from).to_vec();
+        let words: Vec<&String> = words.iter().collect();
+        let jazzy = rate("jazzy", &words);
+        assert_eq!((jazzy.repeated.as_slice(), jazzy.neighbors.len()), (&['z'][..], 0));
+        assert_eq!(rate("fuzzy", &words).rare_letters, ['f', 'u']);
+        assert!(jazzy.out_of_ten > rate("crane", &words).out_of_ten);
+        let night = rate("night", &words);
+        assert_eq!(night.neighbors, ["light", "might", "right", "sight"]);
+        assert!(night.harder_than > rate("stare", &words).harder_than);
+    }
+}
@@ -0,0 +1,246 @@
+//! `doctor`: checks that what the program needs is in order, for when
+//! something doesn't work and it isn't clear why. Each check passes, fails
+//! with a hint on what to do about it, or is skipped.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    env, fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process,
+    time::Instant,
+};
+
+use chrono::Days;
+use colored::Colorize;
+use reqwest::StatusCode;
+
+use crate::{
+    app::{self, App, Validators},
+    cache,
+    config::Config,
+    date_arg, dictionary,
+    error::{Context, Result, WordleError},
+    output::{self, Event},
+    source::{self, puzzle_url},
+    terminal, theme::Theme,
+    thousands,
+};
+
+/// Fewer 5 letter words than this and the dictionary was cut short. NYT
+/// allows over 14,000.
+static MIN_GUESSES: usize = 10_000;
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Pass(String),
+    Fail { problem: String, hint: String },
+    Skip(String),
+}
+
+fn fail(problem: impl Into<String>, hint: impl Into<String>) -> Outcome {
+    Outcome::Fail { problem: problem.into(), hint: hint.into() }
+}
+
+/// Whether files can be written to `dir`, by writing one and removing it.
+fn writable(dir: &Path, flag: &str) -> Outcome {
+    let probe = dir.join(format!(".doctor-{}", process::id()));
+    let written = fs::create_dir_all(dir).and_then(|_| fs::write(&probe, b"")).and_then(|_| fs::remove_file(&probe));
+    match written {
+        Ok(()) => Outcome::Pass(format!("{} is writable", dir.display())),
+        Err(e) => fail(
+            format!("{} can't be written to ({})", dir.display(), e),
+            format!("Check who owns it and its permissions, or pick another dir with {}.", flag),
+        ),
+    }
+}
+
+/// The words of each length, e.g. "14,855 of 5 letters".
+fn lengths(words: &[String]) -> String {
+    let mut counts: BTreeMap<usize, u32> = BTreeMap::new();
+    for word in words {
+        *counts.entry(word.len()).or_default() += 1;
+    }
+    let counts: Vec<String> = counts.iter().map(|(len, n)| format!("{} of {} letters", thousands(*n), len)).collect();
+    counts.join(", ")
+}
+
+/// Whether the cached dictionary is all there and only has words in it.
+fn dictionary(cache_dir: &Path) -> Outcome {
+    let redownload = "Run `wordle-rs dict update` to download it again.";
+    let text = match fs::read_to_string(cache::dictionary_path(cache_dir)) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return fail("the dictionary isn't downloaded, so the built in copy is used if there is one", "Run `wordle-rs dict update` to download it.");
+        },
+        Err(e) => return fail(format!("the dictionary can't be read ({})", e), redownload),
+    };
+    let (words, anomalies) = dictionary::normalize(&text);
+    if let Some(found) = anomalies.describe() {
+        return fail(format!("lines of the dictionary had to be left out or fixed: {}", found), redownload);
+    }
+    let guesses = words.iter().filter(|w| w.len() == 5).count();
+    if guesses < MIN_GUESSES {
+        return fail(format!("the dictionary only has {} 5 letter words, so it looks cut short", guesses), redownload);
+    }
+    Outcome::Pass(format!("{} words: {}", thousands(words.len() as u32), lengths(&words)))
+}
+
+/// Whether the answer list is there and every answer can be guessed.
+fn answers(cache_dir: &Path) -> Outcome {
+    let redownload = "Run `wordle-rs dict update` to download it again.";
+    let Ok(text) = fs::read_to_string(cache::answers_path(cache_dir)) else {
+        return fail("the answer list isn't downloaded, so any allowed guess can be the answer", "Run `wordle-rs dict update` to download it.");
+    };
+    let (answers, _) = dictionary::normalize(&text);
+    let guesses: HashSet<String> = fs::read_to_string(cache::dictionary_path(cache_dir))
+        .map(|text| dictionary::normalize(&text).0.into_iter().collect())
+        .unwrap_or_default();
+    let unguessable = answers.iter().filter(|a| !guesses.is_empty() && !guesses.contains(*a)).count();
+    match unguessable {
+        0 => Outcome::Pass(format!("{} answers, all of them allowed guesses", thousands(answers.len() as u32))),
+        n => fail(format!("{} of the answers aren't in the dictionary, so they can't be guessed", n), redownload),
+    }
+}
+
+/// Whether `url` answers, tried once, or the file of a `file://` one is there.
+/// It's asked with what it said last time, so it needn't send it all again.
+fn reachable(app: &App, url: &str, validators: Validators, context: &'static str) -> Outcome {
+    if let Some(path) = app::local_path(url) {
+        return match path.exists() {
+            true => Outcome::Pass(format!("{} is there", path.display())),
+            false => fail(format!("there's no {}", path.display()), "Copy it to the mirror, or point the URL somewhere else."),
+        };
+    }
+    if app.client.no_network {
+        return Outcome::Skip("downloads are off because of --no-network".to_string());
+    }
+    let hint = "Check your connection, and HTTPS_PROXY if you're behind a proxy. Cached days can still be played with --no-network.";
+    let started = Instant::now();
+    match validators.ask(app.client.get().get(url)).send().context(context) {
+        Ok(response) if response.status() == StatusCode::NOT_MODIFIED => {
+            Outcome::Pass(format!("answered in {} ms, with nothing changed since last time", started.elapsed().as_millis()))
+        },
+        Ok(response) if response.status().is_success() => Outcome::Pass(format!("answered in {} ms", started.elapsed().as_millis())),
+        Ok(response) => fail(format!("answered {}", response.status()), hint),
+        Err(e) => fail(e.to_string(), hint),
+    }
+}
+
+/// Whether colors and the animations can be shown.
+fn colors() -> Outcome {
+    if !terminal::escapes() {
+        return fail(
+            "the console doesn't understand escape codes, so colors and animations are left out",
+            "Use Windows Terminal or a console from Windows 10 or later. --color always forces colors anyway.",
+        );
+    }
+    if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return Outcome::Pass("off, as NO_COLOR asks".to_string());
+    }
+    if env::var_os("TERM").is_some_and(|term| term == "dumb") {
+        return Outcome::Pass("off, as TERM=dumb asks".to_string());
+    }
+    match io::stdout().is_terminal() {
+        true if colored::control::SHOULD_COLORIZE.should_colorize() => Outcome::Pass("colors and animations work".to_string()),
+        true => Outcome::Pass("off, as --color asks".to_string()),
+        false => Outcome::Skip("the output isn't a terminal".to_string()),
+    }
+}
+
+/// Whether the config file can be read and what it names exists.
+fn config(path: &Path) -> Outcome {
+    let hint = "Fix the line it points at. `wordle-rs config init --config <file>` writes a commented one to compare with.";
+    if !path.exists() {
+        return Outcome::Pass(format!("there's no {}, so the defaults are used", path.display()));
+    }
+    let config = match Config::load(path) {
+        Ok(config) => config,
+        Err(e) => return fail(e.to_string(), hint),
+    };
+    if let Some(name) = &config.theme && let Err(e) = Theme::named(name, &config.themes) {
+        return fail(e.to_string(), "Set theme to classic, high-contrast, mono or one of [themes].");
+    }
+    if let Some(volume) = config.volume && !(0.0..=1.0).contains(&volume) {
+        return fail(format!("the volume is {}, but goes from 0 to 1", volume), "Set volume to a number from 0 to 1.");
+    }
+    Outcome::Pass(format!("{} is valid", path.display()))
+}
+
+fn show(app: &App, name: &str, outcome: &Outcome) {
+    if app.json() {
+        let (status, detail, hint) = match outcome {
+            Outcome::Pass(detail) => ("pass", detail, None),
+            Outcome::Fail { problem, hint } => ("fail", problem, Some(hint.as_str())),
+            Outcome::Skip(reason) => ("skip", reason, None),
+        };
+        output::emit(&Event::Check { name, status, detail, hint });
+        return;
+    }
+    match outcome {
+        Outcome::Pass(detail) => println!("{} {:<12} {}", "✓".green(), name, detail),
+        Outcome::Fail { problem, hint } => {
+            println!("{} {:<12} {}", "✗".red(), name, problem);
+            println!("  {:<12} {}", "", hint.dimmed());
+        },
+        Outcome::Skip(reason) => println!("{} {:<12} skipped: {}", "-".dimmed(), name, reason),
+    }
+}
+
+/// Runs every check, failing if any of them did.
+pub fn run(app: &App) -> Result<()> {
+    // What was the day before anywhere, so NYT has published it.
+    let day = date_arg::today() - Days::new(1);
+    let checks = [
+        ("Cache dir", writable(&app.cache_dir, "--cache-dir")),
+        ("Data dir", writable(&app.data_dir, "--data-dir")),
+        ("Dictionary", dictionary(&app.cache_dir)),
+        ("Answers", answers(&app.cache_dir)),
+        ("NYT", reachable(app, &puzzle_url(app.nyt_url(), day), source::validators(&app.cache_dir, day), "reaching NYT")),
+        (
+            "Word lists",
+            reachable(
+                app,
+                app.dictionary_url(),
+                Validators::load(&cache::dictionary_validators_path(&app.cache_dir)),
+                "reaching where the dictionary is downloaded from",
+            ),
+        ),
+        ("Colors", colors()),
+        ("Config", config(&app.config_path)),
+    ];
+    for (name, outcome) in &checks {
+        show(app, name, outcome);
+    }
+    match checks.iter().filter(|(_, outcome)| matches!(outcome, Outcome::Fail { .. })).count() {
+        0 => Ok(()),
+        failed => Err(WordleError::ChecksFailed(failed)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fails_dictionaries_that_were_cut_short_or_garbled() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(dictionary(dir.path()), Outcome::Fail { .. }));
+        fs::create_dir_all(dir.path().join("dictionary")).unwrap();
+        fs::write(cache::dictionary_path(dir.path()), "crane\nslate\n").unwrap();
+        assert!(matches!(dictionary(dir.path()), Outcome::Fail { problem, .. } if problem.contains("only has 2")));
+        fs::write(cache::dictionary_path(dir.path()), "crane\ncr4ne\n").unwrap();
+        assert!(matches!(dictionary(dir.path()), Outcome::Fail { problem, .. } if problem.contains("other than letters")));
+    }
+
+    #[test]
+    fn checks_what_the_config_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        assert!(matches!(config(&path), Outcome::Pass(_)));
+        fs::write(&path, "theme = \"sparkly\"\n").unwrap();
+        assert!(matches!(config(&path), Outcome::Fail { problem, .. } if problem.contains("sparkly")));
+        fs::write(&path, "hard = maybe\n").unwrap();
+        assert!(matches!(config(&path), Outcome::Fail { .. }));
+    }
+}
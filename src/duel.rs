@@ -0,0 +1,170 @@
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CurrentWord, GuessOutcome,
+    app::App,
+    cache,
+    cli::DuelArgs,
+    date_arg,
+    error::{Context, Result, WordleError},
+    input, pick_theme, practice_word, prompt, terminal,
+};
+
+/// How one duel ended, as kept in [`cache::duels_path`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Duel {
+    pub day: NaiveDate,
+    pub players: [String; 2],
+    /// `None` for a draw, when both ran out of tries.
+    pub winner: Option<String>,
+}
+
+/// Wins of `of` against `against`, wins of `against` and draws, in every
+/// duel the two played no matter who went first.
+pub fn head_to_head(duels: &[Duel], of: &str, against: &str) -> (usize, usize, usize) {
+    let mut tally = (0, 0, 0);
+    let between = duels.iter().filter(|d| {
+        d.players.iter().any(|p| p == of) && d.players.iter().any(|p| p == against)
+    });
+    for duel in between {
+        match duel.winner.as_deref() {
+            Some(winner) if winner == of => tally.0 += 1,
+            Some(_) => tally.1 += 1,
+            None => tally.2 += 1,
+        }
+    }
+    tally
+}
+
+fn load(data_dir: &Path) -> Result<Vec<Duel>> {
+    let path = cache::duels_path(data_dir);
+    if !path.try_exists().context("checking for duels")? {
+        return Ok(vec![]);
+    }
+    let json = fs::read_to_string(&path).context("reading duels")?;
+    serde_json::from_str(&json).map_err(|e| WordleError::CorruptSave(path, e))
+}
+
+/// Adds a duel to the record, returning every duel played so far.
+fn record(data_dir: &Path, duel: Duel) -> Result<Vec<Duel>> {
+    cache::with_lock(data_dir, || {
+        let mut duels = load(data_dir)?;
+        duels.push(duel);
+        fs::create_dir_all(data_dir).context("creating data dir")?;
+        let json = serde_json::to_string(&duels).expect("duels serialize");
+        cache::write_atomic(&cache::duels_path(data_dir), json.as_bytes()).context("writing duels")?;
+        Ok(duels)
+    })
+}
+
+/// Lets two players take turns on the same practice word, each on their own
+/// board. On a terminal the screen is cleared between turns so neither sees
+/// the other's guesses. A duel left unfinished isn't recorded.
+pub fn play(app: &App, args: DuelArgs) -> Result<()> {
+    let dictionary = app.dictionary(true)?;
+    let theme = pick_theme(app, None)?;
+    let answer = practice_word(app, dictionary, args.seed)?;
+    let tries = app.config.max_guesses.unwrap_or(6);
+    let players = [args.players[0].clone(), args.players[1].clone()];
+    let mut boards = [CurrentWord::new(answer.clone(), tries), CurrentWord::new(answer, tries)];
+
+    let interactive = io::stdin().is_terminal();
+    let mut lines = match interactive {
+        true => prompt::lines(dictionary, app.config.streamer),
+        false => Box::new(io::stdin().lines()),
+    };
+    let mut turn = 0;
+    let winner = loop {
+        if boards.iter().all(|b| b.tries == 0) {
+            break None;
+        }
+        if boards[turn].tries == 0 {
+            turn = 1 - turn;
+            continue;
+        }
+
+        if interactive {
+            terminal::clear();
+            println!("{}'s turn, {} tries left.", players[turn], boards[turn].tries);
+            println!();
+            boards[turn].display_word(false, &theme);
+            println!();
+        } else {
+            println!("{}'s turn.", players[turn]);
+        }
+
+        let Some(line) = lines.next() else { return Ok(()) };
+        let line = line.map_err(|source| WordleError::Terminal { context: "reading your guess", source })?;
+        match boards[turn].guess(input::normalize(&line), dictionary) {
+            GuessOutcome::InvalidGuess(kind) => println!("{}", kind.message()),
+            GuessOutcome::Win => break Some(turn),
+            GuessOutcome::Continue | GuessOutcome::NoTriesLeft | GuessOutcome::GameOver => {
+                if interactive {
+                    println!();
+                    boards[turn].display_word(false, &theme);
+                    println!("Pass it to {} and press enter.", players[1 - turn]);
+                    if lines.next().is_none() {
+                        return Ok(());
+                    }
+                }
+                turn = 1 - turn;
+            },
+        }
+    };
+
+    if interactive {
+        terminal::clear();
+    }
+    for (player, board) in players.iter().zip(&boards) {
+        println!("{}", player);
+        board.display_word(false, &theme);
+        println!();
+    }
+    match winner {
+        Some(i) => println!("{} wins! The word was {}.", players[i], boards[i].correct_answer.reveal()),
+        None => println!("Nobody got it, the word was {}.", boards[0].correct_answer.reveal()),
+    }
+
+    let duel = Duel {
+        day: date_arg::today(),
+        players: players.clone(),
+        winner: winner.map(|i| players[i].clone()),
+    };
+    let duels = record(&app.data_dir, duel)?;
+    let (first, second, draws) = head_to_head(&duels, &players[0], &players[1]);
+    println!("{} {} - {} {}, {} draws", players[0], first, second, players[1], draws);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn duel(players: [&str; 2], winner: Option<&str>) -> Duel {
+        Duel {
+            day: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            players: players.map(str::to_string),
+            winner: winner.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn counts_duels_either_way_round() {
+        let duels = [
+            duel(["ann", "bob"], Some("ann")),
+            duel(["bob", "ann"], Some("ann")),
+            duel(["bob", "ann"], Some("bob")),
+            duel(["ann", "bob"], None),
+            duel(["ann", "cat"], Some("cat")),
+        ];
+        assert_eq!(head_to_head(&duels, "ann", "bob"), (2, 1, 1));
+        assert_eq!(head_to_head(&duels, "bob", "ann"), (1, 2, 1));
+    }
+}
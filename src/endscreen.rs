@@ -0,0 +1,98 @@
+//! What a finished game says, in place of "congratz!" and "womp womp" when
+//! the config file's `[end_screen]` has something to say instead: a message
+//! for a win in each number of guesses, with the game's numbers filled in,
+//! and its first line drawn big if asked.
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Deserialize;
+
+use crate::{CurrentWord, date_arg, error::Result, format_duration, progress, stats::Stats, terminal};
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct EndScreen {
+    pub won: Option<String>,
+    pub lost: Option<String>,
+
+    /// What a win in so many guesses says instead of `won`, by the number.
+    pub won_in: BTreeMap<String, String>,
+
+    /// Whether the first line is drawn in big letters.
+    pub banner: bool,
+}
+
+impl EndScreen {
+    fn template(&self, current_word: &CurrentWord) -> Option<&str> {
+        match current_word.won() {
+            true => self.won_in.get(&current_word.char_guesses.len().to_string()).or(self.won.as_ref()),
+            false => self.lost.as_ref(),
+        }
+        .map(String::as_str)
+    }
+
+    /// The message for how `current_word` ended, with `{guesses}`, `{word}`,
+    /// `{time}` and `{streak}` filled in. The streak is of the games saved in
+    /// `saves_dir`. `None` when there's no message for it.
+    pub fn text(&self, current_word: &CurrentWord, saves_dir: &Path) -> Result<Option<String>> {
+        let Some(template) = self.template(current_word) else { return Ok(None) };
+        let mut text = template
+            .replace("{guesses}", &current_word.char_guesses.len().to_string())
+            .replace("{word}", &current_word.correct_answer.reveal().to_uppercase())
+            .replace("{time}", &format_duration(current_word.elapsed()));
+        if text.contains("{streak}") {
+            let streak = Stats::from_games(&progress::saved_games(saves_dir)?, date_arg::today()).current_streak;
+            text = text.replace("{streak}", &streak.to_string());
+        }
+        Ok(Some(text))
+    }
+
+    /// [`Self::text`] as it's printed, the first line as a banner when
+    /// asked for and it fits.
+    pub fn screen(&self, current_word: &CurrentWord, saves_dir: &Path) -> Result<Option<String>> {
+        let Some(text) = self.text(current_word, saves_dir)? else { return Ok(None) };
+        if !self.banner {
+            return Ok(Some(text));
+        }
+        let (first, rest) = text.split_once('\n').unwrap_or((&text, ""));
+        Ok(Some(match banner(first, terminal::width()) {
+            Some(banner) if rest.is_empty() => banner,
+            Some(banner) => format!("{}\n{}", banner, rest),
+            None => text,
+        }))
+    }
+}
+
+/// `line` in big letters, when the font has all of them and it fits in
+/// `width` columns.
+fn banner(line: &str, width: Option<usize>) -> Option<String> {
+    let font = figlet_rs::FIGlet::standard().ok()?;
+    let drawn = font.convert(line)?.to_string();
+    let lines: Vec<&str> = drawn.lines().map(str::trim_end).collect();
+    let lines = &lines[..lines.iter().rposition(|l| !l.is_empty())? + 1];
+    let widest = lines.iter().map(|l| l.chars().count()).max()?;
+    width.is_none_or(|columns| widest < columns).then(|| lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::WordIndex;
+
+    #[test]
+    fn fills_in_the_message_for_the_guesses() {
+        let dictionary: WordIndex = ["crane", "slate"].iter().map(|w| w.to_string()).collect();
+        let mut current_word = CurrentWord::new("crane".to_string(), 6);
+        current_word.guess("slate".to_string(), &dictionary);
+        current_word.guess("crane".to_string(), &dictionary);
+        let dir = tempfile::tempdir().unwrap();
+        let mut end = EndScreen { won: Some("{word} in {guesses}, streak {streak}".to_string()), ..EndScreen::default() };
+        assert_eq!(end.text(&current_word, dir.path()).unwrap().as_deref(), Some("CRANE in 2, streak 0"));
+        end.won_in.insert("2".to_string(), "Two!".to_string());
+        assert_eq!(end.text(&current_word, dir.path()).unwrap().as_deref(), Some("Two!"));
+        assert_eq!(EndScreen::default().text(&current_word, dir.path()).unwrap(), None);
+
+        assert_eq!(banner("Hi", None).unwrap().lines().count(), 5);
+        assert_eq!(banner("Hi", Some(4)), None);
+    }
+}
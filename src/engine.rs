@@ -0,0 +1,256 @@
+//! The game as a state machine that the plain prompt, the terminal UI and
+//! the HTTP API all drive. Each turns what the player did into a [`Move`]
+//! and shows the [`Step`] it led to, while what's allowed when, and saving
+//! after every change, is decided here once.
+//!
+//! A game goes Fetching → Ready → InProgress → Finished, and back to Ready
+//! when the only guess is undone.
+
+use crate::{
+    CharGuess, CurrentWord, GuessOutcome,
+    error::Result,
+    hint::Hint,
+    i18n::tr,
+    index::WordIndex,
+    progress::SaveFile,
+    transcript::{Entry, Outcome, Recorded, Recorder},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ending {
+    Won,
+    /// Out of tries, or given up.
+    Lost,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// The answer isn't known yet.
+    Fetching,
+    /// Nothing is guessed yet.
+    Ready,
+    InProgress,
+    Finished(Ending),
+}
+
+/// What happened to a game, moving it from one phase to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Fetched,
+    /// A saved game with guesses in it was picked up.
+    Resumed,
+    Guessed,
+    Solved,
+    OutOfTries,
+    GaveUp,
+    Hinted,
+    /// A guess was taken back, leaving `guesses` of them.
+    Undone { guesses: usize },
+}
+
+impl Phase {
+    /// The phase `event` leads to from this one, or `None` if it can't
+    /// happen now.
+    pub fn on(self, event: Event) -> Option<Phase> {
+        use Phase::*;
+        match (self, event) {
+            (Fetching, Event::Fetched) => Some(Ready),
+            (Ready, Event::Resumed) => Some(InProgress),
+            (Ready | InProgress, Event::Guessed) => Some(InProgress),
+            (Ready | InProgress, Event::Hinted) => Some(self),
+            (Ready | InProgress, Event::Solved) => Some(Finished(Ending::Won)),
+            (Ready | InProgress, Event::OutOfTries | Event::GaveUp) => Some(Finished(Ending::Lost)),
+            (InProgress, Event::Undone { guesses: 0 }) => Some(Ready),
+            (InProgress, Event::Undone { .. }) => Some(InProgress),
+            _ => None,
+        }
+    }
+
+    /// The phase by name, for the API.
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Fetching => "fetching",
+            Phase::Ready => "ready",
+            Phase::InProgress => "in_progress",
+            Phase::Finished(Ending::Won) => "won",
+            Phase::Finished(Ending::Lost) => "lost",
+        }
+    }
+}
+
+/// Something the player does.
+pub enum Move {
+    Guess(String),
+    Hint,
+    Undo,
+    GiveUp,
+}
+
+/// What a move did.
+pub enum Step {
+    /// The guess was graded, or turned away without using a try.
+    Guessed(GuessOutcome),
+    Hinted(Hint),
+    /// The row that was taken back.
+    Undone(Vec<CharGuess>),
+    GaveUp,
+    /// The move isn't allowed now, with why.
+    Refused(String),
+    /// The game is finished, so nothing more can be played.
+    Over,
+}
+
+/// The allowed guesses, and the answers hints are about.
+#[derive(Clone, Copy)]
+pub struct Words<'a> {
+    pub dictionary: &'a WordIndex,
+    pub answers: &'a WordIndex,
+}
+
+/// The rules of the moves besides guessing.
+#[derive(Clone, Copy, Default)]
+pub struct Options {
+    /// Whether guesses can be taken back, which only practice games allow.
+    pub undo: bool,
+
+    /// How many words the shortlist hint names.
+    pub hint_shortlist: usize,
+
+    /// Tries each hint costs.
+    pub hint_penalty: u32,
+}
+
+pub struct Engine {
+    pub current_word: CurrentWord,
+    phase: Phase,
+    save_file: Option<SaveFile>,
+    options: Options,
+    recorder: Option<Recorder>,
+}
+
+impl Engine {
+    /// A game of the word just fetched, picked up where `current_word` was
+    /// left. It's saved to `save_file` after every move that changes it.
+    pub fn new(current_word: CurrentWord, save_file: Option<SaveFile>, options: Options) -> Self {
+        let mut engine = Engine { current_word, phase: Phase::Fetching, save_file, options, recorder: None };
+        engine.advance(Event::Fetched);
+        if !engine.current_word.char_guesses.is_empty() {
+            engine.advance(Event::Resumed);
+        }
+        if engine.current_word.is_over() {
+            engine.advance(match engine.current_word.won() {
+                true => Event::Solved,
+                false => Event::OutOfTries,
+            });
+        }
+        engine
+    }
+
+    /// Records every move from now on with `recorder`.
+    pub fn record(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::Finished(_))
+    }
+
+    /// Whether the game is saved after every move.
+    pub fn is_saved(&self) -> bool {
+        self.save_file.is_some()
+    }
+
+    /// Saves the game as it is, for the time played since the last move.
+    pub fn save(&self) -> Result<()> {
+        match &self.save_file {
+            Some(file) => file.save(&self.current_word),
+            None => Ok(()),
+        }
+    }
+
+    pub fn into_word(self) -> CurrentWord {
+        self.current_word
+    }
+
+    fn advance(&mut self, event: Event) {
+        self.phase = self.phase.on(event).unwrap_or_else(|| panic!("{:?} can't happen in {:?}", event, self.phase));
+    }
+
+    pub fn play(&mut self, mv: Move, words: Words) -> Result<Step> {
+        if self.is_finished() {
+            return Ok(Step::Over);
+        }
+        let recorded = self.recorder.is_some().then(|| Recorded::of(&mv));
+        let (step, event) = match mv {
+            Move::Guess(guess) => {
+                let outcome = self.current_word.guess(guess, words.dictionary);
+                let event = match outcome {
+                    GuessOutcome::InvalidGuess(_) | GuessOutcome::GameOver => None,
+                    GuessOutcome::Continue => Some(Event::Guessed),
+                    GuessOutcome::Win => Some(Event::Solved),
+                    GuessOutcome::NoTriesLeft => Some(Event::OutOfTries),
+                };
+                (Step::Guessed(outcome), event)
+            },
+            Move::Hint => match self.current_word.hint(words.answers, self.options.hint_shortlist, self.options.hint_penalty) {
+                Ok(hint) => (Step::Hinted(hint), Some(Event::Hinted)),
+                Err(reason) => (Step::Refused(reason), None),
+            },
+            // The daily stats should only count games played straight.
+            Move::Undo if !self.options.undo => (Step::Refused(tr!("undo-practice-only")), None),
+            Move::Undo => match self.current_word.undo() {
+                Some(row) => (Step::Undone(row), Some(Event::Undone { guesses: self.current_word.char_guesses.len() })),
+                None => (Step::Refused(tr!("nothing-to-undo")), None),
+            },
+            Move::GiveUp => {
+                self.current_word.give_up();
+                (Step::GaveUp, Some(Event::GaveUp))
+            },
+        };
+        if let Some(event) = event {
+            self.advance(event);
+            if let Some(file) = &self.save_file {
+                file.save(&self.current_word)?;
+            }
+        }
+        if let (Some(recorder), Some(mv)) = (&mut self.recorder, recorded) {
+            recorder.push(Entry { mv, outcome: Outcome::of(&step, &self.current_word) })?;
+        }
+        Ok(step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_through_the_phases() {
+        use Phase::*;
+        assert_eq!(Fetching.on(Event::Fetched), Some(Ready));
+        assert_eq!(Fetching.on(Event::Guessed), None);
+        assert_eq!(Ready.on(Event::Undone { guesses: 0 }), None);
+        assert_eq!(InProgress.on(Event::Undone { guesses: 0 }), Some(Ready));
+        assert_eq!(InProgress.on(Event::GaveUp), Some(Finished(Ending::Lost)));
+        assert_eq!(Finished(Ending::Won).on(Event::Guessed), None);
+
+        let words: WordIndex = ["crane", "slate"].iter().map(|w| w.to_string()).collect();
+        let words = Words { dictionary: &words, answers: &words };
+        let options = Options { undo: true, ..Options::default() };
+        let mut engine = Engine::new(CurrentWord::new("crane".to_string(), 6), None, options);
+        assert_eq!(engine.phase(), Ready);
+        assert!(matches!(engine.play(Move::Guess("zzzzz".to_string()), words).unwrap(), Step::Guessed(GuessOutcome::InvalidGuess(_))));
+        assert_eq!(engine.phase(), Ready);
+        engine.play(Move::Guess("slate".to_string()), words).unwrap();
+        assert_eq!(engine.phase(), InProgress);
+        assert!(matches!(engine.play(Move::Undo, words).unwrap(), Step::Undone(_)));
+        assert_eq!(engine.phase(), Ready);
+        engine.play(Move::Guess("crane".to_string()), words).unwrap();
+        assert_eq!(engine.phase(), Finished(Ending::Won));
+        assert!(matches!(engine.play(Move::GiveUp, words).unwrap(), Step::Over));
+    }
+}
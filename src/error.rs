@@ -0,0 +1,210 @@
+use std::{io, path::PathBuf};
+
+use chrono::NaiveDate;
+
+#[derive(thiserror::Error, Debug)]
+pub enum WordleError {
+    #[error("Error {context}: {}", describe_network(.source))]
+    Network {
+        context: &'static str,
+        source: reqwest::Error,
+    },
+
+    #[error("Error {context}: NYT asked to slow down and try again in {} seconds", .wait.as_secs())]
+    RateLimited {
+        context: &'static str,
+        wait: std::time::Duration,
+    },
+
+    #[error("Error {context}: {}", describe_io(.source))]
+    CacheIo {
+        context: &'static str,
+        source: io::Error,
+    },
+
+    #[error("Error {0}: downloads are off because of --no-network")]
+    NoNetwork(&'static str),
+
+    #[error("Error parsing the response from NYT: {0}")]
+    Parse(#[source] serde_json::Error),
+
+    #[error("NYT's response for {0} can't be played: {1}")]
+    Unplayable(NaiveDate, Malformed),
+
+    #[error("Recieved an error response from NYT for {0}. This probably means that the day's wordle is not published yet.")]
+    NotPublished(NaiveDate),
+
+    #[error("No cached dictionary to pick a practice word from. Run once without --offline to download it.")]
+    NoDictionary,
+
+    #[error("There is no word to practice with in the dictionary. Check --answers and --length.")]
+    EmptyDictionary,
+
+    #[error("Error in word list {path} line {line}: '{word}' is not a 4 to 8 letter word", path = path.display())]
+    InvalidWord {
+        path: PathBuf,
+        line: usize,
+        word: String,
+    },
+
+    #[error("Error in word file {path} line {line}: '{text}' isn't a day and a word, e.g. 2025-01-01 crane", path = path.display())]
+    InvalidWordFile {
+        path: PathBuf,
+        line: usize,
+        text: String,
+    },
+
+    #[error("There's no word for {0} in {1}.")]
+    Unavailable(NaiveDate, String),
+
+    #[error("Error reading {path}: {source}", path = .0.display(), source = .1)]
+    CorruptSave(PathBuf, #[source] serde_json::Error),
+
+    #[error("The cached word in {} is not a 5 letter word.", .0.display())]
+    CorruptWord(PathBuf),
+
+    #[error("You already finished the wordle for {0}. Pass --replay to play it again.")]
+    AlreadyFinished(NaiveDate),
+
+    /// Why a game can't be started now, from the limits in the config file.
+    #[error("{0}")]
+    PlayLimit(String),
+
+    #[error("Error in config file {path}: {source}", path = .0.display(), source = .1)]
+    Config(PathBuf, #[source] toml::de::Error),
+
+    #[error("'{0}' isn't in the dictionary, so it couldn't be guessed.")]
+    UnguessableChallenge(String),
+
+    #[error("Unknown theme '{0}'. Use classic, high-contrast, mono or one from your config file.")]
+    UnknownTheme(String),
+
+    #[error("Wordle {0}: not finished, {1} guesses so far")]
+    Unfinished(NaiveDate, usize),
+
+    #[error("Error {context}: {source}")]
+    Peer {
+        context: &'static str,
+        source: io::Error,
+    },
+
+    #[error("The other player sent something this version doesn't understand: {0}")]
+    Protocol(String),
+
+    #[error("No leaderboard file. Pass --file or set leaderboard in the config file.")]
+    NoLeaderboard,
+
+    #[error("The word frequencies aren't downloaded, and --offline doesn't download anything. Run `wordle-rs dict update` to get them.")]
+    NoFrequencies,
+
+    #[error("Sharing is off (share is none), so the game wasn't added to the leaderboard.")]
+    SharingOff,
+
+    #[error("Error serving on {address}: {reason}")]
+    Serve {
+        address: String,
+        reason: String,
+    },
+
+    #[error("The board can't be continued: {0}.")]
+    BadBoard(String),
+
+    #[error("{0} of the checks failed.")]
+    ChecksFailed(usize),
+
+    #[error("{0} of the transcript's moves went differently.")]
+    Diverged(usize),
+
+    #[error("This wordle-rs was built without SQLite, so saved games can't be kept in it. Build it with --features sqlite, or set storage to json in the config file.")]
+    NoSqlite,
+
+    #[error("Error {context}: {reason}")]
+    Database {
+        context: &'static str,
+        reason: String,
+    },
+
+    #[error("No remote to sync with. Pass --remote or set sync_remote in the config file.")]
+    NoSyncRemote,
+
+    #[error("The prefetch can't be scheduled on {0}. Run `wordle-rs fetch --if-stale --quiet` every day from your own scheduler instead.")]
+    NoScheduler(&'static str),
+
+    #[error("Error syncing with {remote}: {reason}")]
+    Sync {
+        remote: String,
+        reason: String,
+    },
+
+    #[error("Error {context}: {source}")]
+    Terminal {
+        context: &'static str,
+        source: io::Error,
+    },
+}
+
+/// What's wrong with a cached word or a response that can't be played.
+#[derive(thiserror::Error, Debug)]
+pub enum Malformed {
+    #[error("it isn't JSON: {0}")]
+    Json(#[source] serde_json::Error),
+    /// Left out of the message, where the answer would show.
+    #[error("the solution isn't a 5 letter word")]
+    NotAWord,
+}
+
+fn describe_io(e: &io::Error) -> String {
+    match e.kind() {
+        io::ErrorKind::PermissionDenied => "no permission".to_string(),
+        io::ErrorKind::AlreadyExists => "it already exists".to_string(),
+        _ => format!("unknown error ({})", e),
+    }
+}
+
+fn describe_network(e: &reqwest::Error) -> String {
+    if e.is_timeout() {
+        "the server took too long to answer. Are you offline?".to_string()
+    } else if e.is_connect() {
+        "couldn't reach the server. Are you offline?".to_string()
+    } else {
+        e.to_string()
+    }
+}
+
+impl WordleError {
+    /// The process exit code for this error, so scripts can tell failures apart.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            WordleError::Network { .. } | WordleError::RateLimited { .. } | WordleError::NoNetwork(_) | WordleError::Peer { .. } | WordleError::Serve { .. } | WordleError::Sync { .. } => 2,
+            WordleError::CacheIo { .. } | WordleError::CorruptSave(..) | WordleError::CorruptWord(_) | WordleError::Database { .. } => 3,
+            WordleError::Parse(_) | WordleError::Unplayable(..) | WordleError::Protocol(_) | WordleError::BadBoard(_) => 4,
+            WordleError::NotPublished(_) | WordleError::Unavailable(..) => 5,
+            WordleError::NoDictionary | WordleError::EmptyDictionary | WordleError::InvalidWord { .. } | WordleError::InvalidWordFile { .. } | WordleError::UnguessableChallenge(_) | WordleError::NoFrequencies => 6,
+            WordleError::Terminal { .. } => 7,
+            WordleError::AlreadyFinished(_) => 8,
+            WordleError::Config(..) | WordleError::UnknownTheme(_) | WordleError::NoLeaderboard | WordleError::SharingOff | WordleError::NoSqlite | WordleError::NoSyncRemote | WordleError::NoScheduler(_) => 9,
+            WordleError::Unfinished(..) => 10,
+            WordleError::ChecksFailed(_) | WordleError::Diverged(_) => 11,
+            WordleError::PlayLimit(_) => 12,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, WordleError>;
+
+/// Attaches what was being done to IO and network errors.
+pub trait Context<T> {
+    fn context(self, context: &'static str) -> Result<T>;
+}
+
+impl<T> Context<T> for io::Result<T> {
+    fn context(self, context: &'static str) -> Result<T> {
+        self.map_err(|source| WordleError::CacheIo { context, source })
+    }
+}
+
+impl<T> Context<T> for reqwest::Result<T> {
+    fn context(self, context: &'static str) -> Result<T> {
+        self.map_err(|source| WordleError::Network { context, source })
+    }
+}
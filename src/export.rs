@@ -0,0 +1,117 @@
+//! Saved daily games as CSV or JSON, for spreadsheets and other trackers.
+
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{
+    cache,
+    cli::{ExportArgs, ExportFormat},
+    error::Result,
+    output,
+    progress::{self, Aid},
+    read_word_cache,
+};
+
+/// One day's game as it is exported.
+#[derive(Serialize, Debug)]
+pub struct Row {
+    pub day: NaiveDate,
+    /// NYT's number for the puzzle, when the day's word is cached.
+    pub puzzle: Option<u32>,
+    pub answer: Option<String>,
+    pub finished: bool,
+    pub won: bool,
+    pub guesses: Vec<String>,
+    /// The colors of each guess, see [`output::pattern`].
+    pub patterns: Vec<String>,
+    pub elapsed_ms: u64,
+    pub hints: usize,
+    pub aids: Vec<Aid>,
+    /// Whether the game had no hints or other help.
+    pub clean: bool,
+}
+
+/// Quotes a CSV field when it has anything that would break the row.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl Row {
+    fn csv(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        [
+            self.day.to_string(),
+            optional(self.puzzle.map(|n| n.to_string())),
+            optional(self.answer.clone()),
+            self.finished.to_string(),
+            self.won.to_string(),
+            self.guesses.len().to_string(),
+            self.guesses.join(" "),
+            self.patterns.join(" "),
+            self.elapsed_ms.to_string(),
+            self.hints.to_string(),
+            self.aids.iter().map(Aid::name).collect::<Vec<_>>().join(" "),
+            self.clean.to_string(),
+        ].iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(",")
+    }
+}
+
+static CSV_HEADER: &str = "day,puzzle,answer,finished,won,guess_count,guesses,patterns,elapsed_ms,hints,aids,clean";
+
+/// Every saved game from `--since` on, oldest first.
+pub fn rows(cache_dir: &Path, data_dir: &Path, since: Option<NaiveDate>) -> Result<Vec<Row>> {
+    let mut rows = vec![];
+    for (day, game) in progress::saved_games(data_dir)? {
+        if since.is_some_and(|since| day < since) {
+            continue;
+        }
+        // Days whose word isn't cached anymore are still exported.
+        let puzzle = read_word_cache(&cache::word_path(cache_dir, day)).ok();
+        rows.push(Row {
+            day,
+            puzzle: puzzle.as_ref().and_then(|p| p.days_since_launch),
+            answer: puzzle.map(|p| p.solution.into_revealed()),
+            finished: game.finished,
+            won: game.finished && game.won(),
+            guesses: game.char_guesses.iter().map(|cgs| cgs.iter().map(|cg| cg.ch).collect()).collect(),
+            patterns: game.char_guesses.iter().map(|cgs| output::pattern(cgs)).collect(),
+            elapsed_ms: game.elapsed_ms,
+            hints: game.hints.len(),
+            clean: game.clean(),
+            aids: game.aids,
+        });
+    }
+    Ok(rows)
+}
+
+pub fn run(cache_dir: &Path, data_dir: &Path, args: ExportArgs) -> Result<()> {
+    let rows = rows(cache_dir, data_dir, args.since)?;
+    match args.format {
+        ExportFormat::Csv => {
+            println!("{}", CSV_HEADER);
+            for row in &rows {
+                println!("{}", row.csv());
+            }
+        },
+        ExportFormat::Json => println!("{}", serde_json::to_string_pretty(&rows).expect("rows serialize")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_csv_fields_only_when_needed() {
+        assert_eq!(csv_field("crane slate"), "crane slate");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,195 @@
+//! What takes up room in the cache dir, and clearing out what isn't needed.
+//! Saved games are counted when they share the dir but never removed.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    DATE_FORMAT, cache,
+    cli::GcArgs,
+    compiled, date_arg, dict_index,
+    error::{Context, Result},
+};
+
+/// How old a temporary file has to be before it's taken to be left over from
+/// an interrupted write, rather than one still going on.
+static TEMP_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Words,
+    Dictionary,
+    Temporary,
+    Saves,
+    Other,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Words => "words",
+            Kind::Dictionary => "dictionary",
+            Kind::Temporary => "temporary",
+            Kind::Saves => "saved games",
+            Kind::Other => "other",
+        }
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    kind: Kind,
+    len: u64,
+    modified: SystemTime,
+}
+
+fn kind(cache_dir: &Path, path: &Path) -> Kind {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    if name.starts_with('.') && name.ends_with(".tmp") {
+        return Kind::Temporary;
+    }
+    let top = path.strip_prefix(cache_dir).ok()
+        .and_then(|p| p.components().next())
+        .and_then(|c| c.as_os_str().to_str())
+        .unwrap_or_default();
+    match top {
+        "words" => Kind::Words,
+        "dictionary" | "dictionary.old" => Kind::Dictionary,
+        "results" | "daily-random" | "anagram" | "duels.json" | "blitz.json" | "cross.json" => Kind::Saves,
+        _ => Kind::Other,
+    }
+}
+
+/// Every file under `dir`.
+fn walk(cache_dir: &Path, dir: &Path, entries: &mut Vec<Entry>) -> Result<()> {
+    for entry in fs::read_dir(dir).context("listing the cache")? {
+        let entry = entry.context("listing the cache")?;
+        let metadata = entry.metadata().context("listing the cache")?;
+        let path = entry.path();
+        if metadata.is_dir() {
+            walk(cache_dir, &path, entries)?;
+        } else {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push(Entry { kind: kind(cache_dir, &path), path, len: metadata.len(), modified });
+        }
+    }
+    Ok(())
+}
+
+/// A size for people, e.g. 12.3 KB.
+fn size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+fn files(count: usize) -> String {
+    format!("{} {}", count, if count == 1 { "file" } else { "files" })
+}
+
+/// Shows how many files of each kind the cache has and how big they are.
+pub fn info(cache_dir: &Path) -> Result<()> {
+    let mut entries = vec![];
+    walk(cache_dir, cache_dir, &mut entries)?;
+    println!("Cache in {}", cache_dir.display());
+    for kind in [Kind::Words, Kind::Dictionary, Kind::Temporary, Kind::Saves, Kind::Other] {
+        let of_kind: Vec<&Entry> = entries.iter().filter(|e| e.kind == kind).collect();
+        if !of_kind.is_empty() {
+            println!("{:<12} {:>10} {:>10}", kind.name(), files(of_kind.len()), size(of_kind.iter().map(|e| e.len).sum()));
+        }
+    }
+    println!("{:<12} {:>10} {:>10}", "total", files(entries.len()), size(entries.iter().map(|e| e.len).sum()));
+    Ok(())
+}
+
+/// The files that can go: words of days before `keep_from`, temporary files
+/// nothing is writing any more and a compiled dictionary that's out of date.
+fn garbage(cache_dir: &Path, entries: Vec<Entry>, keep_from: Option<NaiveDate>, now: SystemTime) -> Vec<Entry> {
+    let compiled_path = cache::compiled_dictionary_path(cache_dir);
+    // A missing or unreadable text dictionary makes the compiled one useless too.
+    let compiled_stale = !matches!(compiled::load(cache_dir), Ok(Some(_)));
+    let index_path = cache::dictionary_index_path(cache_dir);
+    let index_stale = !matches!(dict_index::load(cache_dir), Ok(Some(_)));
+    entries.into_iter().filter(|entry| match entry.kind {
+        Kind::Temporary => now.duration_since(entry.modified).is_ok_and(|age| age > TEMP_AGE),
+        Kind::Words => {
+            let day = entry.path.file_name().and_then(|n| n.to_str())
+                .and_then(|name| NaiveDate::parse_from_str(name, DATE_FORMAT).ok());
+            keep_from.is_some_and(|keep_from| day.is_some_and(|day| day < keep_from))
+        },
+        Kind::Dictionary => {
+            entry.path == compiled_path && compiled_stale || entry.path == index_path && index_stale || entry.path == cache_dir.join("dictionary.old")
+        },
+        Kind::Saves | Kind::Other => false,
+    }).collect()
+}
+
+/// Removes what [`garbage`] finds and says how much room that made, or with
+/// `--dry-run` only lists it.
+pub fn collect(cache_dir: &Path, args: GcArgs) -> Result<()> {
+    let mut entries = vec![];
+    walk(cache_dir, cache_dir, &mut entries)?;
+    let keep_from = args.keep_days.map(|days| date_arg::today() - chrono::Days::new(days));
+    let garbage = garbage(cache_dir, entries, keep_from, SystemTime::now());
+    let reclaimed = size(garbage.iter().map(|e| e.len).sum());
+
+    if args.dry_run {
+        for entry in &garbage {
+            println!("{} ({})", entry.path.display(), size(entry.len));
+        }
+        println!("{} would be removed, freeing {}.", files(garbage.len()), reclaimed);
+        return Ok(());
+    }
+    let days: Vec<NaiveDate> = garbage.iter()
+        .filter(|e| e.kind == Kind::Words)
+        .filter_map(|e| NaiveDate::parse_from_str(e.path.file_name()?.to_str()?, DATE_FORMAT).ok())
+        .collect();
+    cache::remove_days(cache_dir, &days)?;
+    for entry in garbage.iter().filter(|e| e.kind != Kind::Words) {
+        fs::remove_file(&entry.path).context("removing from the cache")?;
+    }
+    println!("Removed {}, freeing {}.", files(garbage.len()), reclaimed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_old_words_leftovers_and_stale_dictionaries_but_not_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path();
+        cache::prepare(cache_dir).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        for d in [1, 20] {
+            fs::write(cache::word_path(cache_dir, day(d)), "crane").unwrap();
+        }
+        fs::write(cache::compiled_dictionary_path(cache_dir), "WRDB").unwrap();
+        let leftover = cache_dir.join("words").join(".2025-01-02.1234.tmp");
+        fs::write(&leftover, "cr").unwrap();
+        fs::create_dir_all(cache::results_dir(cache_dir)).unwrap();
+        fs::write(cache::results_dir(cache_dir).join("2025-01-01.json"), "{}").unwrap();
+
+        let mut entries = vec![];
+        walk(cache_dir, cache_dir, &mut entries).unwrap();
+        let now = SystemTime::now() + 2 * TEMP_AGE;
+        let mut removed: Vec<PathBuf> = garbage(cache_dir, entries, Some(day(10)), now)
+            .into_iter()
+            .map(|e| e.path)
+            .collect();
+        removed.sort();
+        assert_eq!(removed, [
+            cache::compiled_dictionary_path(cache_dir),
+            leftover,
+            cache::word_path(cache_dir, day(1)),
+        ]);
+    }
+}
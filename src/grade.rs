@@ -0,0 +1,209 @@
+//! Coloring a guess against the answer. This and the solver modules it feeds
+//! don't touch the filesystem or the network, so they build without the
+//! `cli` feature, e.g. for `wasm32-unknown-unknown`.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CharGuessKind {
+    NotInWord,
+    WrongPlace,
+    Correct,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CharGuess {
+    pub ch: char,
+    pub kind: CharGuessKind
+}
+
+impl CharGuess {
+    pub fn new(ch: char, kind: CharGuessKind) -> Self {
+        Self { ch, kind }
+    }
+}
+
+/// Grades a guess against the answer the way Wordle does: greens first, then
+/// yellows from left to right for as many copies of a letter as the answer has
+/// left over, so a repeated letter isn't marked yellow more often than it occurs.
+pub fn grade(guess: &str, answer: &str) -> Vec<CharGuessKind> {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+    let mut kinds = vec![CharGuessKind::NotInWord; guess.len()];
+    let mut unmatched = vec![];
+
+    for (i, &ch) in guess.iter().enumerate() {
+        match answer.get(i) {
+            Some(&a) if a == ch => kinds[i] = CharGuessKind::Correct,
+            Some(&a) => unmatched.push(a),
+            None => (),
+        }
+    }
+    unmatched.extend(answer.iter().skip(guess.len()));
+
+    for (i, ch) in guess.iter().enumerate() {
+        if kinds[i] != CharGuessKind::Correct
+            && let Some(pos) = unmatched.iter().position(|a| a == ch)
+        {
+            kinds[i] = CharGuessKind::WrongPlace;
+            unmatched.swap_remove(pos);
+        }
+    }
+
+    kinds
+}
+
+/// The color of one letter of a graded guess.
+pub type Tile = CharGuessKind;
+
+/// A word of `N` lowercase letters, five unless said otherwise, so a guess can
+/// only be graded against an answer of the same length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Word<const N: usize = 5>([u8; N]);
+
+/// Why a string isn't a [`Word`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WordError {
+    WrongLength { expected: usize, found: usize },
+    NotALetter(char),
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordError::WrongLength { expected, found } => write!(f, "expected {} letters, not {}", expected, found),
+            WordError::NotALetter(ch) => write!(f, "'{}' isn't a letter from a to z", ch),
+        }
+    }
+}
+
+impl std::error::Error for WordError {}
+
+impl<const N: usize> FromStr for Word<N> {
+    type Err = WordError;
+
+    /// Reads a word in either case, e.g. "crane" or "CRANE".
+    fn from_str(s: &str) -> Result<Self, WordError> {
+        if let Some(ch) = s.chars().find(|ch| !ch.is_ascii_alphabetic()) {
+            return Err(WordError::NotALetter(ch));
+        }
+        let letters: [u8; N] = s.as_bytes().try_into().map_err(|_| WordError::WrongLength { expected: N, found: s.len() })?;
+        Ok(Word(letters.map(|b| b.to_ascii_lowercase())))
+    }
+}
+
+impl<const N: usize> Word<N> {
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("words are ascii letters")
+    }
+
+    /// The tiles of this word guessed against `answer`, by the same rules as
+    /// [`grade`]: greens first, then yellows left to right for as many copies
+    /// of a letter as the answer has left.
+    pub fn grade(&self, answer: &Word<N>) -> [Tile; N] {
+        grade(self.as_str(), answer.as_str()).try_into().expect("both words have N letters")
+    }
+}
+
+impl<const N: usize> fmt::Display for Word<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How many letters of the guess are in the answer wherever they are, each
+/// copy counting as often as the answer has it.
+pub fn shared_letters(guess: &str, answer: &str) -> usize {
+    grade(guess, answer).into_iter().filter(|&kind| kind != CharGuessKind::NotInWord).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use CharGuessKind::{Correct, NotInWord, WrongPlace};
+
+    #[test]
+    fn grades_exact_and_misplaced_letters() {
+        assert_eq!(grade("crane", "crane"), vec![Correct; 5]);
+        assert_eq!(grade("slate", "crane"), vec![NotInWord, NotInWord, Correct, NotInWord, Correct]);
+        assert_eq!(grade("nacre", "crane"), vec![WrongPlace, WrongPlace, WrongPlace, WrongPlace, Correct]);
+    }
+
+    #[test]
+    fn grades_repeated_letters_only_as_often_as_the_answer_has_them() {
+        assert_eq!(grade("speed", "abide"), vec![NotInWord, NotInWord, WrongPlace, NotInWord, WrongPlace]);
+        assert_eq!(grade("geese", "those"), vec![NotInWord, NotInWord, NotInWord, Correct, Correct]);
+        assert_eq!(grade("eerie", "there"), vec![WrongPlace, NotInWord, WrongPlace, NotInWord, Correct]);
+    }
+
+    #[test]
+    fn grades_checked_words_of_the_same_length() {
+        let guess: Word = "EERIE".parse().unwrap();
+        let answer: Word = "there".parse().unwrap();
+        assert_eq!(guess.grade(&answer), [WrongPlace, NotInWord, WrongPlace, NotInWord, Correct]);
+        assert_eq!("cranes".parse::<Word>(), Err(WordError::WrongLength { expected: 5, found: 6 }));
+        assert_eq!("cr4ne".parse::<Word>(), Err(WordError::NotALetter('4')));
+        assert_eq!("quizzing".parse::<Word<8>>().unwrap().to_string(), "quizzing");
+    }
+
+    #[test]
+    fn counts_shared_letters_wherever_they_are() {
+        assert_eq!(shared_letters("nacre", "crane"), 5);
+        assert_eq!(shared_letters("speed", "abide"), 2);
+        assert_eq!(shared_letters("moist", "crane"), 0);
+    }
+
+    mod properties {
+        use proptest::prelude::*;
+
+        use super::*;
+
+        /// Pairs of words of the same length over a few letters, so repeats
+        /// are common.
+        fn pairs() -> impl Strategy<Value = (String, String)> {
+            (4usize..=8).prop_flat_map(|n| {
+                let word = || proptest::string::string_regex(&format!("[abcde]{{{}}}", n)).expect("the pattern is valid");
+                (word(), word())
+            })
+        }
+
+        fn count(word: &str, ch: char) -> usize {
+            word.chars().filter(|&c| c == ch).count()
+        }
+
+        proptest! {
+            #[test]
+            fn greens_are_exactly_the_matching_positions((guess, answer) in pairs()) {
+                let kinds = grade(&guess, &answer);
+                for ((g, a), kind) in guess.chars().zip(answer.chars()).zip(kinds) {
+                    prop_assert_eq!(kind == Correct, g == a);
+                }
+            }
+
+            #[test]
+            fn a_letter_is_marked_only_as_often_as_both_words_have_it((guess, answer) in pairs()) {
+                let kinds = grade(&guess, &answer);
+                for ch in guess.chars() {
+                    let marked = guess.chars().zip(&kinds).filter(|&(g, &kind)| g == ch && kind != NotInWord).count();
+                    prop_assert_eq!(marked, count(&guess, ch).min(count(&answer, ch)));
+                }
+            }
+
+            #[test]
+            fn a_yellow_is_a_letter_the_answer_has_where_it_isnt_green((guess, answer) in pairs()) {
+                let unmatched: Vec<char> = guess.chars().zip(answer.chars()).filter(|(g, a)| g != a).map(|(_, a)| a).collect();
+                for (g, kind) in guess.chars().zip(grade(&guess, &answer)) {
+                    prop_assert!(kind != WrongPlace || unmatched.contains(&g));
+                }
+            }
+
+            #[test]
+            fn every_word_solves_itself((guess, _) in pairs()) {
+                prop_assert!(grade(&guess, &guess).into_iter().all(|kind| kind == Correct));
+            }
+        }
+    }
+}
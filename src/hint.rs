@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{CharGuessKind, CurrentWord, definition, i18n::tr, index::WordIndex, strategy};
+
+/// How many words the candidates hint lists.
+pub static DEFAULT_SHORTLIST: usize = 3;
+
+/// Something a hint revealed, kept with the saved game so it isn't given
+/// again after resuming.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    /// A letter that is somewhere in the word.
+    Letter(char),
+    /// A letter and where it goes, counting from 0.
+    Position(usize, char),
+    /// A few words, one of which is the answer.
+    Candidates(Vec<String>),
+    /// What the answer means, see [`definition::of`].
+    Definition(String),
+}
+
+impl Hint {
+    pub fn message(&self) -> String {
+        match self {
+            Hint::Letter(ch) => tr!("hint-letter", letter = ch.to_ascii_uppercase()),
+            Hint::Position(i, ch) => tr!("hint-position", position = i + 1, letter = ch.to_ascii_uppercase()),
+            Hint::Candidates(words) => tr!("hint-candidates", words = words.join(", ")),
+            Hint::Definition(clue) => tr!("hint-definition", clue = clue),
+        }
+    }
+}
+
+/// Whether `word` could be the answer given what `hint` revealed.
+fn fits(hint: &Hint, word: &str) -> bool {
+    match hint {
+        Hint::Letter(ch) => word.contains(*ch),
+        Hint::Position(i, ch) => word.chars().nth(*i) == Some(*ch),
+        Hint::Candidates(words) => words.iter().any(|w| w == word),
+        Hint::Definition(_) => true,
+    }
+}
+
+/// The next hint, each more telling than the last: first a letter that's in
+/// the word, then where a letter goes, then a shortlist of words the answer
+/// is among. After that every hint places one more letter. With definition
+/// hints on, a definition of the answer comes before all of them.
+pub fn next(current_word: &CurrentWord, answers: &WordIndex, shortlist: usize) -> Option<Hint> {
+    let known_letter = |ch: char| {
+        current_word.char_guesses.iter().flatten().any(|cg| cg.ch == ch && cg.kind != CharGuessKind::NotInWord)
+            || current_word.hints.iter().any(|hint| matches!(hint, Hint::Letter(c) | Hint::Position(_, c) if *c == ch))
+    };
+    let known_position = |i: usize| {
+        current_word.char_guesses.iter().any(|cgs| cgs[i].kind == CharGuessKind::Correct)
+            || current_word.hints.iter().any(|hint| matches!(hint, Hint::Position(j, _) if *j == i))
+    };
+
+    let letter = || current_word.correct_answer.reveal().chars().find(|&ch| !known_letter(ch)).map(Hint::Letter);
+    let position = || current_word.correct_answer.reveal().chars()
+        .enumerate()
+        .find(|&(i, _)| !known_position(i))
+        .map(|(i, ch)| Hint::Position(i, ch));
+    let candidates = || {
+        let mut words: Vec<String> = strategy::candidates(&current_word.char_guesses, answers)
+            .into_iter()
+            .filter(|w| current_word.correct_answer != **w && current_word.hints.iter().all(|hint| fits(hint, w)))
+            .take(shortlist.saturating_sub(1))
+            .cloned()
+            .collect();
+        if words.is_empty() {
+            return None;
+        }
+        words.push(current_word.correct_answer.reveal().to_string());
+        words.sort();
+        Some(Hint::Candidates(words))
+    };
+
+    let defined = current_word.hints.iter().any(|hint| matches!(hint, Hint::Definition(_)));
+    if current_word.definition_hints && !defined
+        && let Some(clue) = definition::of(current_word.correct_answer.reveal())
+    {
+        return Some(Hint::Definition(clue));
+    }
+
+    match current_word.hints.len() - defined as usize {
+        0 => letter().or_else(position),
+        2 => candidates().or_else(position),
+        _ => position(),
+    }
+}
@@ -0,0 +1,144 @@
+//! The messages shown while playing, in the language picked with `--lang`,
+//! the config file or the locale. The catalogs are in `src/lang`, one TOML
+//! file of keys and messages per language, with English for whatever a
+//! translation is missing.
+
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    sync::{LazyLock, OnceLock},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::WordleError;
+
+#[derive(clap::ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    /// English
+    En,
+    /// Swedish
+    Sv,
+}
+
+static EN: &str = include_str!("lang/en.toml");
+static SV: &str = include_str!("lang/sv.toml");
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+static CATALOGS: LazyLock<HashMap<Lang, HashMap<String, String>>> = LazyLock::new(|| {
+    [(Lang::En, EN), (Lang::Sv, SV)].into_iter()
+        .map(|(lang, text)| (lang, toml::from_str(text).expect("the catalogs are valid TOML")))
+        .collect()
+});
+
+impl Lang {
+    /// The language of the locale in `LC_ALL`, `LC_MESSAGES` or `LANG`, the
+    /// first of them that's set, e.g. Swedish for `sv_SE.UTF-8`. English when
+    /// there's no translation for it.
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        Self::of_locale(&locale)
+    }
+
+    fn of_locale(locale: &str) -> Self {
+        match locale.split(['_', '.', '-', '@']).next().unwrap_or_default() {
+            "sv" => Lang::Sv,
+            _ => Lang::En,
+        }
+    }
+}
+
+/// Picks the language of the messages for the rest of the run. Until it's
+/// picked they're in English.
+pub fn set(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+/// The language the messages are in.
+pub fn current() -> Lang {
+    LANG.get().copied().unwrap_or(Lang::En)
+}
+
+/// The message `key` in the language picked, with each `{name}` in it
+/// filled in from `args`.
+pub fn message(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let lang = current();
+    let template = CATALOGS[&lang].get(key)
+        .or_else(|| CATALOGS[&Lang::En].get(key))
+        .map_or(key, String::as_str);
+    args.iter().fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), &value.to_string()))
+}
+
+/// [`message`] with the args given as `name = value`.
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::message($key, &[])
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::message($key, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
+pub(crate) use tr;
+
+/// `words` quoted and listed, e.g. "'a', 'b' or 'c'".
+pub fn or_list(words: &[String]) -> String {
+    let quoted: Vec<String> = words.iter().map(|w| format!("'{}'", w)).collect();
+    match quoted.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} {} {}", rest.join(", "), tr!("or"), last),
+        None => String::new(),
+    }
+}
+
+/// `e` as shown to people, translated when the catalog has it.
+pub fn error(e: &WordleError) -> String {
+    match e {
+        WordleError::NotPublished(day) => tr!("error-not-published", day = day),
+        WordleError::NoDictionary => tr!("error-no-dictionary"),
+        WordleError::EmptyDictionary => tr!("error-empty-dictionary"),
+        WordleError::AlreadyFinished(day) => tr!("error-already-finished", day = day),
+        WordleError::UnknownTheme(name) => tr!("error-unknown-theme", name = name),
+        WordleError::Unfinished(day, guesses) => tr!("error-unfinished", day = day, guesses = guesses),
+        e => e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `{name}`s a message fills in.
+    fn names(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template.split('{').skip(1).filter_map(|rest| rest.split_once('}')).map(|(name, _)| name).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn translations_have_every_message_with_the_same_names() {
+        let english = &CATALOGS[&Lang::En];
+        for (lang, catalog) in CATALOGS.iter() {
+            let mut missing: Vec<&String> = english.keys().filter(|key| !catalog.contains_key(*key)).collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{:?} is missing {:?}", lang, missing);
+            for (key, template) in catalog {
+                assert_eq!(names(template), names(&english[key]), "{:?} {}", lang, key);
+            }
+        }
+    }
+
+    #[test]
+    fn fills_in_messages_and_falls_back_to_the_key() {
+        assert_eq!(tr!("solved-in", time = "1:02"), "Solved in 1:02.");
+        assert_eq!(tr!("no-such-message"), "no-such-message");
+        assert_eq!(or_list(&["crane".to_string(), "crate".to_string(), "crank".to_string()]), "'crane', 'crate' or 'crank'");
+        assert_eq!(Lang::of_locale("sv_SE.UTF-8"), Lang::Sv);
+        assert_eq!(Lang::of_locale("C"), Lang::En);
+    }
+}
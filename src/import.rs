@@ -0,0 +1,139 @@
+//! Reading the share text of the official site, so games played there count
+//! towards the stats here.
+
+use std::{fs, io::{self, Read}, path::Path};
+
+use chrono::{Days, NaiveDate};
+
+use crate::{
+    CharGuess, CharGuessKind,
+    cli::ImportArgs,
+    error::{Context, Result},
+    prefetch::FIRST_DAY,
+    progress::{self, SavedGame},
+};
+
+/// One game read from share text like "Wordle 1,123 4/6" and its rows.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Shared {
+    pub number: u32,
+    pub won: bool,
+    pub rows: Vec<Vec<CharGuessKind>>,
+}
+
+impl Shared {
+    /// The day of the puzzle, counting from the first wordle as number 0.
+    pub fn day(&self) -> Option<NaiveDate> {
+        FIRST_DAY.checked_add_days(Days::new(self.number.into()))
+    }
+
+    /// The game as it is saved. The letters aren't in the share text, so
+    /// every guess is saved as question marks.
+    fn saved_game(&self) -> SavedGame {
+        SavedGame {
+            char_guesses: self.rows.iter()
+                .map(|kinds| kinds.iter().map(|&kind| CharGuess::new('?', kind)).collect())
+                .collect(),
+            finished: true,
+            ..SavedGame::default()
+        }
+    }
+}
+
+/// The color of a square of a share text, in either the classic or the
+/// high contrast colors.
+pub fn tile(ch: char) -> Option<CharGuessKind> {
+    match ch {
+        '🟩' | '🟧' => Some(CharGuessKind::Correct),
+        '🟨' | '🟦' => Some(CharGuessKind::WrongPlace),
+        '⬛' | '⬜' => Some(CharGuessKind::NotInWord),
+        _ => None,
+    }
+}
+
+/// The puzzle number and whether it was solved, from a line like
+/// "Wordle 1,123 4/6*".
+fn header(line: &str) -> Option<(u32, bool)> {
+    let mut words = line.split_whitespace();
+    if words.next()? != "Wordle" {
+        return None;
+    }
+    let number = words.next()?.replace([',', '.', '\u{a0}'], "").parse().ok()?;
+    let score = words.next()?.trim_end_matches('*');
+    let (guesses, _) = score.split_once('/')?;
+    Some((number, guesses != "X"))
+}
+
+/// Every game in `text`, which can hold several pasted one after another.
+/// A game whose rows don't add up to its score is left out.
+pub fn parse(text: &str) -> Vec<Shared> {
+    let mut games: Vec<Shared> = vec![];
+    let mut lines = text.lines().map(str::trim).peekable();
+    while let Some(line) = lines.next() {
+        let Some((number, won)) = header(line) else { continue };
+        let mut rows = vec![];
+        while let Some(next) = lines.peek() {
+            if next.is_empty() && rows.is_empty() {
+                lines.next();
+                continue;
+            }
+            let row: Option<Vec<CharGuessKind>> = next.chars().filter(|ch| !ch.is_whitespace()).map(tile).collect();
+            match row {
+                Some(row) if !row.is_empty() => rows.push(row),
+                _ => break,
+            }
+            lines.next();
+        }
+        let solved = rows.last().is_some_and(|row| row.iter().all(|&k| k == CharGuessKind::Correct));
+        if !rows.is_empty() && solved == won {
+            games.push(Shared { number, won, rows });
+        }
+    }
+    games
+}
+
+pub fn run(data_dir: &Path, args: ImportArgs) -> Result<()> {
+    let text = match &args.file {
+        Some(path) => fs::read_to_string(path).context("reading share text")?,
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).context("reading share text")?;
+            text
+        },
+    };
+    let games: Vec<(NaiveDate, SavedGame)> = parse(&text)
+        .iter()
+        .filter_map(|shared| Some((shared.day()?, shared.saved_game())))
+        .collect();
+    let found = games.len();
+    let imported = progress::import(data_dir, games)?;
+    eprintln!("Imported {} games, skipped {} already saved.", imported, found - imported);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use CharGuessKind::{Correct, NotInWord, WrongPlace};
+
+    #[test]
+    fn reads_pasted_share_blocks() {
+        let text = "Wordle 1,292 2/6*\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩🟩🟩\n\nWordle 1,293 X/6\n⬛🟨⬛⬛⬛\n";
+        let games = parse(text);
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0], Shared {
+            number: 1292,
+            won: true,
+            rows: vec![vec![NotInWord, NotInWord, Correct, NotInWord, Correct], vec![Correct; 5]],
+        });
+        assert_eq!(games[0].day(), NaiveDate::from_ymd_opt(2025, 1, 1));
+        assert_eq!(games[1].rows, vec![vec![NotInWord, WrongPlace, NotInWord, NotInWord, NotInWord]]);
+        assert!(!games[1].won);
+    }
+
+    #[test]
+    fn skips_games_whose_rows_contradict_the_score() {
+        assert!(parse("Wordle 1,292 2/6\n⬛⬛🟩⬛🟩\n").is_empty());
+    }
+}
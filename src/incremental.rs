@@ -0,0 +1,96 @@
+//! Typing a guess straight into its row of the board, a key at a time as on
+//! the website, instead of on a line under the board.
+
+use std::io::{self, Write};
+
+use ratatui::crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+
+use crate::{
+    CharGuessKind, CurrentWord,
+    animation::{CLEAR_LINE, up},
+    input,
+    theme::Theme,
+};
+
+/// Clears everything from the cursor to the end of the screen.
+static CLEAR_BELOW: &str = "\x1b[J";
+
+/// A letter typed into the row, as wide as the blank it fills.
+fn typed(ch: char, theme: &Theme) -> String {
+    match theme.tile(CharGuessKind::NotInWord).marks {
+        Some(_) => format!(" {} ", ch),
+        None => ch.to_string(),
+    }
+}
+
+/// The row being typed on: its number, what's typed so far and blanks for
+/// the rest. A `:` command is shown as it's typed.
+fn row(current_word: &CurrentWord, theme: &Theme, line: &str, masked: bool) -> String {
+    let number = current_word.row_number(current_word.char_guesses.len());
+    if line.starts_with(':') {
+        return format!("{} {}", number, line);
+    }
+    let letters = line.chars().map(|ch| typed(if masked { '•' } else { ch }, theme));
+    let blanks = (line.len()..current_word.word_length).map(|_| theme.label(CharGuessKind::NotInWord, "_"));
+    format!("{} {}", number, letters.chain(blanks).collect::<String>())
+}
+
+/// What's typed once enter is pressed, or `None` when the typing is stopped
+/// with Esc, Ctrl-C or Ctrl-D.
+fn keys(current_word: &CurrentWord, theme: &Theme, masked: bool) -> io::Result<Option<String>> {
+    let mut stdout = io::stdout();
+    let mut line = String::new();
+    loop {
+        write!(stdout, "\r{}{}", row(current_word, theme, &line, masked), CLEAR_LINE)?;
+        stdout.flush()?;
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c' | 'd') if control => return Ok(None),
+            KeyCode::Enter if !line.is_empty() => return Ok(Some(line)),
+            KeyCode::Backspace => _ = line.pop(),
+            KeyCode::Char(':') if line.is_empty() => line.push(':'),
+            KeyCode::Char(ch) if line.starts_with(':') => line.push(ch),
+            KeyCode::Char(ch) if let Some(letter) = input::letter(ch) && line.len() < current_word.word_length => {
+                line.push(letter);
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Reads a guess typed into the board just printed, whose blank rows are the
+/// last lines on the screen. Once it's entered the blank rows under it are
+/// cleared and the cursor is left on the line under the row, where a guess
+/// typed on a line of its own would have left it.
+pub fn read(current_word: &CurrentWord, theme: &Theme, masked: bool) -> Option<io::Result<String>> {
+    print!("{}", up(current_word.tries as usize));
+    let typed = enable_raw_mode().and_then(|()| keys(current_word, theme, masked));
+    let restored = disable_raw_mode();
+    print!("{}", CLEAR_BELOW);
+    println!();
+    match (typed, restored) {
+        (Ok(line), Ok(())) => line.map(Ok),
+        (Err(e), _) | (_, Err(e)) => Some(Err(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_what_is_typed_in_the_row_with_blanks_after() {
+        let current_word = CurrentWord::new("crane".to_string(), 6);
+        assert_eq!(row(&current_word, &Theme::classic(), "cr", false), "1/6 cr___");
+        assert_eq!(row(&current_word, &Theme::classic(), "cr", true), "1/6 ••___");
+        assert_eq!(row(&current_word, &Theme::classic(), ":hi", false), "1/6 :hi");
+    }
+}
@@ -0,0 +1,257 @@
+//! The word lists, with every word's letters indexed so the words that fit
+//! some feedback can be found without grading the whole list.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+    sync::OnceLock,
+};
+
+use crate::{CharGuess, CharGuessKind, strategy};
+
+/// A set of word ids, one bit per word.
+#[derive(Clone)]
+struct Bitset(Vec<u64>);
+
+impl Bitset {
+    fn new(len: usize) -> Self {
+        Bitset(vec![0; len.div_ceil(64)])
+    }
+
+    fn insert(&mut self, id: usize) {
+        self.0[id / 64] |= 1 << (id % 64);
+    }
+
+    fn intersect(&mut self, other: &Bitset) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a &= b;
+        }
+    }
+
+    fn ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(i, &block)| {
+            let mut block = block;
+            std::iter::from_fn(move || (block != 0).then(|| {
+                let bit = block.trailing_zeros() as usize;
+                block &= block - 1;
+                i * 64 + bit
+            }))
+        })
+    }
+}
+
+/// The place of a letter in the alphabet, if it's one of a-z.
+fn letter(ch: u8) -> Option<usize> {
+    let i = ch.wrapping_sub(b'a') as usize;
+    (i < 26).then_some(i)
+}
+
+/// The letters of a word, one bit per letter from a.
+fn letter_mask(word: &str) -> u32 {
+    word.bytes().filter_map(letter).fold(0, |mask, i| mask | 1 << i)
+}
+
+/// Every word of a list, indexed.
+struct Loaded {
+    set: HashSet<String>,
+    /// Every word, sorted, so a word's place in here is its id.
+    words: Vec<String>,
+    /// The [`letter_mask`] of each word.
+    letters: Vec<u32>,
+    lengths: HashMap<usize, Bitset>,
+    /// For each position and letter, the words with that letter there.
+    positions: Vec<[Bitset; 26]>,
+}
+
+impl Loaded {
+    fn new(set: HashSet<String>) -> Self {
+        let mut words: Vec<String> = set.iter().cloned().collect();
+        words.sort();
+        let n = words.len();
+        let longest = words.iter().map(String::len).max().unwrap_or(0);
+        let mut lengths: HashMap<usize, Bitset> = HashMap::new();
+        let mut positions: Vec<[Bitset; 26]> = (0..longest).map(|_| std::array::from_fn(|_| Bitset::new(n))).collect();
+        for (id, word) in words.iter().enumerate() {
+            lengths.entry(word.len()).or_insert_with(|| Bitset::new(n)).insert(id);
+            for (p, i) in word.bytes().enumerate().filter_map(|(p, ch)| Some((p, letter(ch)?))) {
+                positions[p][i].insert(id);
+            }
+        }
+        let letters = words.iter().map(|w| letter_mask(w)).collect();
+        Self { set, words, letters, lengths, positions }
+    }
+}
+
+/// A dictionary in an FST on disk, with the words of other lists added to it.
+#[cfg(feature = "cli")]
+struct OnDisk {
+    words: fst::Set<crate::dict_index::Mapped>,
+    extra: HashSet<String>,
+}
+
+/// A word list that can be looked up like the set it was made from, and
+/// also narrowed down to the words that fit some feedback quickly.
+pub struct WordIndex {
+    loaded: OnceLock<Loaded>,
+    /// Where the words are looked up until something needs all of them.
+    #[cfg(feature = "cli")]
+    on_disk: Option<OnDisk>,
+}
+
+impl WordIndex {
+    pub fn new(set: HashSet<String>) -> Self {
+        Self {
+            loaded: OnceLock::from(Loaded::new(set)),
+            #[cfg(feature = "cli")]
+            on_disk: None,
+        }
+    }
+
+    /// The words of an index on disk and `extra`, only read into memory
+    /// once they're iterated or narrowed down.
+    #[cfg(feature = "cli")]
+    pub fn on_disk(words: fst::Set<crate::dict_index::Mapped>, extra: HashSet<String>) -> Self {
+        Self { loaded: OnceLock::new(), on_disk: Some(OnDisk { words, extra }) }
+    }
+
+    fn loaded(&self) -> &Loaded {
+        self.loaded.get_or_init(|| Loaded::new(self.read_disk()))
+    }
+
+    #[cfg(feature = "cli")]
+    fn read_disk(&self) -> HashSet<String> {
+        let disk = self.on_disk.as_ref().expect("an index not on disk is loaded from the start");
+        let mut set = disk.extra.clone();
+        set.extend(disk.words.stream().into_strs().expect("the words on disk are text"));
+        set
+    }
+
+    #[cfg(not(feature = "cli"))]
+    fn read_disk(&self) -> HashSet<String> {
+        unreachable!("an index not on disk is loaded from the start")
+    }
+
+    /// Whether `word` is in the list, without reading an index on disk into
+    /// memory.
+    pub fn contains(&self, word: &str) -> bool {
+        #[cfg(feature = "cli")]
+        if let Some(disk) = &self.on_disk {
+            return disk.words.contains(word) || disk.extra.contains(word);
+        }
+        self.loaded().set.contains(word)
+    }
+
+    pub fn len(&self) -> usize {
+        #[cfg(feature = "cli")]
+        if let Some(disk) = &self.on_disk && self.loaded.get().is_none() {
+            return disk.words.len() + disk.extra.iter().filter(|w| !disk.words.contains(w)).count();
+        }
+        self.loaded().set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The words that would have produced exactly the feedback seen so far,
+    /// sorted. Greens pick the words out of the position index and the
+    /// letter masks rule out most of the rest before any word is graded.
+    pub fn fitting(&self, char_guesses: &[Vec<CharGuess>]) -> Vec<&String> {
+        let index = self.loaded();
+        let Some(len) = char_guesses.first().map(Vec::len) else {
+            return index.words.iter().collect();
+        };
+        let Some(mut fits) = index.lengths.get(&len).cloned() else {
+            return vec![];
+        };
+
+        let mut required = 0u32;
+        let mut gray = 0u32;
+        // Letters that aren't in each position: a gray or yellow letter would
+        // have been green there.
+        let mut not_at = vec![0u32; len];
+        for cgs in char_guesses {
+            for (p, cg) in cgs.iter().enumerate() {
+                let Some(i) = u8::try_from(cg.ch).ok().and_then(letter) else { continue };
+                match cg.kind {
+                    CharGuessKind::Correct => {
+                        required |= 1 << i;
+                        if let Some(at) = index.positions.get(p) {
+                            fits.intersect(&at[i]);
+                        }
+                    },
+                    CharGuessKind::WrongPlace => required |= 1 << i,
+                    CharGuessKind::NotInWord => gray |= 1 << i,
+                }
+                if cg.kind != CharGuessKind::Correct && let Some(mask) = not_at.get_mut(p) {
+                    *mask |= 1 << i;
+                }
+            }
+        }
+        // A gray letter that was colored elsewhere is only limited in count,
+        // which grading the word checks.
+        let absent = gray & !required;
+
+        fits.ids()
+            .filter(|&id| index.letters[id] & required == required && index.letters[id] & absent == 0)
+            .map(|id| &index.words[id])
+            .filter(|w| w.bytes().zip(&not_at).all(|(ch, mask)| letter(ch).is_none_or(|i| mask & 1 << i == 0)))
+            .filter(|w| strategy::is_consistent(w, char_guesses))
+            .collect()
+    }
+}
+
+impl Deref for WordIndex {
+    type Target = HashSet<String>;
+
+    fn deref(&self) -> &HashSet<String> {
+        &self.loaded().set
+    }
+}
+
+impl<'a> IntoIterator for &'a WordIndex {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.loaded().words.iter()
+    }
+}
+
+impl From<HashSet<String>> for WordIndex {
+    fn from(set: HashSet<String>) -> Self {
+        Self::new(set)
+    }
+}
+
+impl FromIterator<String> for WordIndex {
+    fn from_iter<I: IntoIterator<Item = String>>(words: I) -> Self {
+        Self::new(words.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(guess: &str, answer: &str) -> Vec<CharGuess> {
+        guess.chars().zip(crate::grade::grade(guess, answer)).map(|(ch, kind)| CharGuess::new(ch, kind)).collect()
+    }
+
+    #[test]
+    fn finds_the_same_words_as_grading_every_one() {
+        let index: WordIndex = ["crane", "crate", "trace", "slate", "eerie", "geese", "sheep", "abbey", "abide"]
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+        for answer in ["crate", "geese", "abbey"] {
+            for guess in ["slate", "eerie", "crane", "bobby"] {
+                let rows = [row(guess, answer)];
+                let mut scanned: Vec<&String> = index.iter().filter(|w| strategy::is_consistent(w, &rows)).collect();
+                scanned.sort();
+                assert_eq!(index.fitting(&rows), scanned, "{} against {}", guess, answer);
+            }
+        }
+        assert_eq!(index.fitting(&[]).len(), index.len());
+    }
+}
@@ -0,0 +1,107 @@
+/// Quotes that tend to come along when a word is pasted.
+static QUOTES: [char; 6] = ['"', '\'', '‘', '’', '“', '”'];
+
+/// The plain letter of an accented one, so "crème" can be played as "creme".
+pub fn fold_accent(ch: char) -> char {
+    match ch {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ñ' | 'ń' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+        'ß' => 's',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ž' | 'ź' | 'ż' => 'z',
+        _ => ch,
+    }
+}
+
+/// The letter a typed key stands for, if it stands for one.
+pub fn letter(ch: char) -> Option<char> {
+    let ch = fold_accent(ch.to_lowercase().next()?);
+    ch.is_ascii_lowercase().then_some(ch)
+}
+
+/// Turns a typed or pasted guess into the form it is graded in: trimmed,
+/// unquoted, lowercase and without accents. Anything else that isn't a
+/// letter is left for the guess to be rejected over.
+pub fn normalize(input: &str) -> String {
+    input.trim()
+        .trim_matches(|ch| QUOTES.contains(&ch))
+        .trim()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(fold_accent)
+        .collect()
+}
+
+/// What can be typed at the guess prompt instead of a guess, after a colon.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Stop playing, keeping the game to resume later.
+    Quit,
+    /// Reveal the answer and take the loss.
+    GiveUp,
+    /// List the commands.
+    Help,
+    /// Reveal a letter that isn't known yet.
+    Hint,
+    /// Take back the last guess, in practice games.
+    Undo,
+}
+
+/// Parses a `:command`, or returns `None` when the input is a guess. An
+/// unknown command is given back by name.
+pub fn command(input: &str) -> Option<Result<Command, String>> {
+    let name = input.trim().strip_prefix(':')?;
+    Some(match name.trim().to_lowercase().as_str() {
+        "quit" | "q" => Ok(Command::Quit),
+        "giveup" => Ok(Command::GiveUp),
+        "help" | "?" => Ok(Command::Help),
+        "hint" => Ok(Command::Hint),
+        "undo" => Ok(Command::Undo),
+        _ => Err(name.trim().to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufRead};
+
+    use super::*;
+
+    #[test]
+    fn folds_case_accents_and_quotes() {
+        assert_eq!(normalize("  Crane\n"), "crane");
+        assert_eq!(normalize("crème"), "creme");
+        assert_eq!(normalize("ÉCLAT"), "eclat");
+        assert_eq!(normalize("“slate”"), "slate");
+        assert_eq!(normalize("'pride'"), "pride");
+    }
+
+    #[test]
+    fn parses_commands_apart_from_guesses() {
+        assert_eq!(command(":undo"), Some(Ok(Command::Undo)));
+        assert_eq!(command(" :UNDO "), Some(Ok(Command::Undo)));
+        assert_eq!(command(":giveup"), Some(Ok(Command::GiveUp)));
+        assert_eq!(command(":q"), Some(Ok(Command::Quit)));
+        assert_eq!(command("crane"), None);
+        assert!(matches!(command(":fly"), Some(Err(_))));
+    }
+
+    #[test]
+    fn takes_windows_line_endings() {
+        assert_eq!(normalize("crane\r\n"), "crane");
+        assert_eq!(command(":hint\r\n"), Some(Ok(Command::Hint)));
+        let piped: Vec<String> = io::Cursor::new("crane\r\n:quit\r\n").lines().map(Result::unwrap).collect();
+        assert_eq!(piped, ["crane", ":quit"]);
+    }
+
+    #[test]
+    fn keeps_what_it_cannot_fold() {
+        assert_eq!(normalize("cr4ne"), "cr4ne");
+        assert_eq!(normalize("слово"), "слово");
+    }
+}
@@ -0,0 +1,62 @@
+//! What Ctrl-C does outside the prompts, which take it as a key and end the
+//! game like running out of input: the terminal is put back the way it was
+//! before anything is printed, and a saved game being played is left as it
+//! was saved after its last move, with how to pick it up again.
+
+use std::{
+    io::{self, Write},
+    sync::Mutex,
+};
+
+use ratatui::crossterm::{
+    cursor::Show,
+    execute,
+    style::ResetColor,
+    terminal::{LeaveAlternateScreen, disable_raw_mode, is_raw_mode_enabled},
+};
+
+/// What to say on Ctrl-C, while a saved game is played.
+static SAVED: Mutex<Option<String>> = Mutex::new(None);
+
+/// The exit code of a program stopped by SIGINT, by the shell's convention.
+const INTERRUPTED: i32 = 130;
+
+/// Says `message` on Ctrl-C until the guard is dropped.
+pub struct Saving(());
+
+impl Drop for Saving {
+    fn drop(&mut self) {
+        *SAVED.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+pub fn saving(message: String) -> Saving {
+    *SAVED.lock().unwrap_or_else(|e| e.into_inner()) = Some(message);
+    Saving(())
+}
+
+/// Puts the terminal back: the colors and cursor an animation may have left
+/// changed, and raw mode and the alternate screen of a full screen view,
+/// which is the only time raw mode is on for long.
+fn restore() {
+    let mut stdout = io::stdout();
+    if is_raw_mode_enabled().unwrap_or(false) {
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout, LeaveAlternateScreen);
+    }
+    let _ = execute!(stdout, ResetColor, Show);
+    let _ = stdout.flush();
+}
+
+/// Handles Ctrl-C for the rest of the run.
+pub fn install() {
+    // Without a handler Ctrl-C still stops the program, just less tidily.
+    let _ = ctrlc::set_handler(|| {
+        restore();
+        println!();
+        if let Some(message) = SAVED.lock().unwrap_or_else(|e| e.into_inner()).as_deref() {
+            eprintln!("{}", message);
+        }
+        std::process::exit(INTERRUPTED);
+    });
+}
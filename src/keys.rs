@@ -0,0 +1,265 @@
+//! The keys of the terminal UI, which the `[keys]` table of the config file
+//! can change, e.g. for a layout where Enter is out of reach or a switch that
+//! only sends a few keys. `wordle-rs keys` lists them.
+
+use std::fmt;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    app::App,
+    error::Result,
+    input,
+    output::{self, Event},
+};
+
+/// A key with the modifiers held down with it, e.g. `ctrl-h`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+/// The names of keys that aren't a character.
+static NAMED: [(&str, KeyCode); 13] = [
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("space", KeyCode::Char(' ')),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("insert", KeyCode::Insert),
+];
+
+impl Key {
+    const fn plain(code: KeyCode) -> Self {
+        Self { code, modifiers: KeyModifiers::NONE }
+    }
+
+    const fn ctrl(ch: char) -> Self {
+        Self { code: KeyCode::Char(ch), modifiers: KeyModifiers::CONTROL }
+    }
+
+    /// Whether `event` is this key. Shift is left out of it for characters,
+    /// since it's what makes `?` on most layouts.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let modifiers = match event.code {
+            KeyCode::Char(_) => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        let code = match event.code {
+            KeyCode::Char(ch) => KeyCode::Char(ch.to_ascii_lowercase()),
+            code => code,
+        };
+        self.code == code && self.modifiers == modifiers
+    }
+
+    /// Whether the key types a letter of the guess, so binding it takes the
+    /// letter away.
+    fn is_letter(&self) -> bool {
+        matches!(self.code, KeyCode::Char(ch) if input::letter(ch).is_some()) && self.modifiers.is_empty()
+    }
+}
+
+/// Parses a key like `enter`, `?`, `f2` or `ctrl-h`.
+pub fn parse_key(s: &str) -> std::result::Result<Key, String> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = s.trim().to_ascii_lowercase();
+    loop {
+        let (modifier, name) = match rest.split_once('-') {
+            Some((modifier, name)) if !name.is_empty() => (modifier.to_string(), name.to_string()),
+            _ => break,
+        };
+        modifiers |= match modifier.as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(format!("'{}' isn't ctrl, alt or shift", modifier)),
+        };
+        rest = name;
+    }
+    let code = match NAMED.iter().find(|(name, _)| *name == rest) {
+        Some((_, code)) => *code,
+        None => match (rest.chars().next(), rest.chars().count()) {
+            (Some(ch), 1) => KeyCode::Char(ch),
+            _ => match rest.strip_prefix('f').and_then(|n| n.parse().ok()).filter(|n| (1..=12).contains(n)) {
+                Some(n) => KeyCode::F(n),
+                None => return Err(format!("'{}' isn't a key, e.g. enter, ?, f2 or ctrl-h", s)),
+            },
+        },
+    };
+    Ok(Key { code, modifiers })
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (modifier, name) in [(KeyModifiers::CONTROL, "ctrl-"), (KeyModifiers::ALT, "alt-"), (KeyModifiers::SHIFT, "shift-")] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", name)?;
+            }
+        }
+        match (NAMED.iter().find(|(_, code)| *code == self.code), self.code) {
+            (Some((name, _)), _) => write!(f, "{}", name),
+            (None, KeyCode::Char(ch)) => write!(f, "{}", ch),
+            (None, KeyCode::F(n)) => write!(f, "f{}", n),
+            (None, code) => write!(f, "{}", code),
+        }
+    }
+}
+
+/// One key or a list of them, as the config file has them.
+fn keys<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<Key>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    let names = match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(name) => vec![name],
+        OneOrMany::Many(names) => names,
+    };
+    names.iter().map(|name| parse_key(name).map_err(serde::de::Error::custom)).collect()
+}
+
+/// What a key does in the terminal UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Submit,
+    Backspace,
+    Hint,
+    Quit,
+    Keyboard,
+    Undo,
+}
+
+/// The keys of each action. Ctrl-C always quits as well.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keys {
+    #[serde(deserialize_with = "keys")]
+    pub submit: Vec<Key>,
+    #[serde(deserialize_with = "keys")]
+    pub backspace: Vec<Key>,
+    #[serde(deserialize_with = "keys")]
+    pub hint: Vec<Key>,
+    #[serde(deserialize_with = "keys")]
+    pub quit: Vec<Key>,
+    #[serde(deserialize_with = "keys")]
+    pub keyboard: Vec<Key>,
+    #[serde(deserialize_with = "keys")]
+    pub undo: Vec<Key>,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Self {
+            submit: vec![Key::plain(KeyCode::Enter)],
+            backspace: vec![Key::plain(KeyCode::Backspace)],
+            hint: vec![Key::plain(KeyCode::Char('?'))],
+            quit: vec![Key::plain(KeyCode::Esc)],
+            keyboard: vec![Key::plain(KeyCode::Tab)],
+            undo: vec![Key::ctrl('z')],
+        }
+    }
+}
+
+impl Keys {
+    /// Each action with its name, what it does and its keys, in the order
+    /// they're looked up.
+    fn actions(&self) -> [(Action, &'static str, &'static str, &[Key]); 6] {
+        [
+            (Action::Submit, "submit", "guess the word typed", &self.submit),
+            (Action::Backspace, "backspace", "take back the last letter typed", &self.backspace),
+            (Action::Hint, "hint", "reveal a hint", &self.hint),
+            (Action::Quit, "quit", "leave the game, which is saved", &self.quit),
+            (Action::Keyboard, "keyboard", "show or hide the keyboard", &self.keyboard),
+            (Action::Undo, "undo", "take back the last guess, in practice games", &self.undo),
+        ]
+    }
+
+    /// What `event` does, if it's bound. A key bound to more than one action
+    /// does the first.
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        if Key::ctrl('c').matches(event) {
+            return Some(Action::Quit);
+        }
+        self.actions().into_iter().find(|(_, _, _, keys)| keys.iter().any(|key| key.matches(event))).map(|(action, ..)| action)
+    }
+
+    /// What's odd about the bindings: letters that can't be typed anymore,
+    /// and keys bound twice.
+    fn problems(&self) -> Vec<String> {
+        let actions = self.actions();
+        let mut problems = vec![];
+        for (i, (_, name, _, keys)) in actions.iter().enumerate() {
+            for key in keys.iter() {
+                if key.is_letter() {
+                    problems.push(format!("{} is bound to {}, so it can't be typed in a guess", key, name));
+                }
+                if let Some((_, first, ..)) = actions[..i].iter().find(|(_, _, _, earlier)| earlier.contains(key)) {
+                    problems.push(format!("{} is bound to both {} and {}, so it does {}", key, first, name, first));
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// Lists the keys of the terminal UI.
+pub fn run(app: &App) -> Result<()> {
+    let keys = &app.config.keys;
+    if app.json() {
+        for (_, name, description, bound) in keys.actions() {
+            let bound = bound.iter().map(Key::to_string).collect();
+            output::emit(&Event::Binding { action: name, description, keys: bound });
+        }
+        return Ok(());
+    }
+    for (_, name, description, bound) in keys.actions() {
+        let bound: Vec<String> = bound.iter().map(Key::to_string).collect();
+        let bound = match bound.is_empty() {
+            true => "(none)".to_string(),
+            false => bound.join(", "),
+        };
+        println!("{:<10} {:<16} {}", name, bound, description);
+    }
+    println!("Ctrl-C always quits. Change them in the [keys] table of {}.", app.config_path.display());
+    for problem in keys.problems() {
+        eprintln!("Note: {}.", problem);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_names_keys() {
+        assert_eq!(parse_key("Ctrl-H"), Ok(Key::ctrl('h')));
+        assert_eq!(parse_key("f2").unwrap().to_string(), "f2");
+        assert_eq!(parse_key("alt-enter").unwrap().to_string(), "alt-enter");
+        assert_eq!(parse_key("-"), Ok(Key::plain(KeyCode::Char('-'))));
+        assert!(parse_key("hyper-x").is_err());
+        assert!(parse_key("enterr").is_err());
+    }
+
+    #[test]
+    fn looks_up_what_keys_do() {
+        let keys: Keys = toml::from_str("submit = [\"enter\", \"ctrl-j\"]\nhint = \"q\"\n").unwrap();
+        let event = |code, modifiers| KeyEvent::new(code, modifiers);
+        assert_eq!(keys.action(&event(KeyCode::Char('j'), KeyModifiers::CONTROL)), Some(Action::Submit));
+        assert_eq!(keys.action(&event(KeyCode::Char('?'), KeyModifiers::SHIFT)), None);
+        assert_eq!(keys.action(&event(KeyCode::Tab, KeyModifiers::NONE)), Some(Action::Keyboard));
+        assert_eq!(keys.action(&event(KeyCode::Char('c'), KeyModifiers::CONTROL)), Some(Action::Quit));
+        assert_eq!(keys.problems(), ["q is bound to hint, so it can't be typed in a guess"]);
+    }
+}
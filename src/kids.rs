@@ -0,0 +1,27 @@
+//! Wordle for kids: answers they'll know, any five letters allowed as a
+//! guess and more tries to get there.
+
+use crate::{app::App, obscurity};
+
+/// How common kids' answers are, as a percentile of the dictionary by word
+/// frequency.
+static PERCENTILE: u8 = 20;
+
+/// Common words that still aren't for kids.
+static UNSUITABLE: [&str; 16] = [
+    "booze", "crack", "death", "drunk", "dying", "fatal", "flirt", "gross",
+    "kills", "knife", "lusty", "sexed", "sexes", "tipsy", "toxic", "vodka",
+];
+
+/// The practice answers worth picking for kids, out of `words`.
+pub fn answers<'a>(app: &App, words: Vec<&'a String>) -> Vec<&'a String> {
+    let words = words.into_iter().filter(|w| !UNSUITABLE.contains(&w.as_str())).collect();
+    // Kids' games are offline, so the list has to be downloaded already.
+    match obscurity::read_cached_frequencies(&app.cache_dir) {
+        Ok(frequencies) => obscurity::most_common(words, &frequencies, PERCENTILE),
+        Err(e) => {
+            app.degrade(format!("the word frequencies couldn't be read, so kids' words may be uncommon ones ({})", e));
+            words
+        },
+    }
+}
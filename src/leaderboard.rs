@@ -0,0 +1,254 @@
+//! A leaderboard kept in one JSON file that several people add their daily
+//! games to, e.g. on a shared drive or a server everyone logs in to.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::App,
+    cache,
+    cli::{LeaderboardArgs, LeaderboardCommand},
+    date_arg,
+    error::{Context, Result, WordleError},
+    output::{self, Event},
+    progress::{SaveFile, SavedGame},
+    rules::Rules,
+    score,
+    share::ShareLevel,
+};
+
+/// What a loss counts as in the average, one more than the usual six tries.
+static LOSS_GUESSES: usize = 7;
+
+/// One player's game on one day, with only what their share level lets out.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub day: NaiveDate,
+    pub won: bool,
+    /// How many guesses it took, unless the share level leaves it out.
+    #[serde(default)]
+    pub guesses: Option<usize>,
+    /// Whether the game had hints or other help. Entries from before this
+    /// was kept count as clean.
+    #[serde(default)]
+    pub aided: bool,
+    /// The points the game scored, left out with the guesses since the tries
+    /// left would give them away. Entries from before scoring have none.
+    #[serde(default)]
+    pub score: Option<u32>,
+}
+
+/// How one player is doing over every day they added.
+#[derive(Debug, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub played: usize,
+    /// The average over the games whose guesses are known, if any are.
+    pub average: Option<f64>,
+    pub average_score: Option<f64>,
+    pub streak: usize,
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (count, total) = values.fold((0, 0.0), |(count, total), value| (count + 1, total + value));
+    (count > 0).then(|| total / count as f64)
+}
+
+/// The players best first: fewest guesses on average, or the highest score
+/// on average `by_score`, then the longest current streak. A streak counts
+/// only if it reaches `today` or the day before.
+pub fn standings(entries: &[Entry], today: NaiveDate, by_score: bool) -> Vec<Standing> {
+    let mut names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    names.sort();
+    names.dedup();
+
+    let mut standings: Vec<Standing> = names.into_iter().map(|name| {
+        let mut games: Vec<&Entry> = entries.iter().filter(|e| e.name == name).collect();
+        games.sort_by_key(|e| e.day);
+        let average = mean(games.iter().filter_map(|e| if e.won { e.guesses } else { Some(LOSS_GUESSES) }).map(|g| g as f64));
+        let average_score = mean(games.iter().filter_map(|e| e.score).map(f64::from));
+
+        let mut streak = 0;
+        let mut next = Some(today);
+        for game in games.iter().rev() {
+            if streak == 0 && Some(game.day) != next {
+                next = today.checked_sub_days(Days::new(1));
+            }
+            if Some(game.day) != next || !game.won {
+                break;
+            }
+            streak += 1;
+            next = game.day.checked_sub_days(Days::new(1));
+        }
+        Standing { name: name.to_string(), played: games.len(), average, average_score, streak }
+    }).collect();
+    // Players with nothing to rank by go last.
+    standings.sort_by(|a, b| match by_score {
+        true => b.average_score.unwrap_or(f64::NEG_INFINITY).total_cmp(&a.average_score.unwrap_or(f64::NEG_INFINITY)),
+        false => a.average.unwrap_or(f64::INFINITY).total_cmp(&b.average.unwrap_or(f64::INFINITY)),
+    }.then(b.streak.cmp(&a.streak)));
+    standings
+}
+
+fn load(path: &Path) -> Result<Vec<Entry>> {
+    if !path.try_exists().context("checking for the leaderboard")? {
+        return Ok(vec![]);
+    }
+    let json = fs::read_to_string(path).context("reading the leaderboard")?;
+    serde_json::from_str(&json).map_err(|e| WordleError::CorruptSave(path.to_path_buf(), e))
+}
+
+/// The entry of `game` for the leaderboard, with only what `rules.share`
+/// lets out, or `None` if it lets nothing out.
+fn entry(name: String, day: NaiveDate, game: &SavedGame, rules: &Rules) -> Option<Entry> {
+    let tries = rules.tries.saturating_sub(game.hint_tries);
+    let score = score::score(&game.char_guesses, game.won(), tries);
+    let entry = Entry { name, day, won: game.won(), guesses: Some(game.char_guesses.len()), aided: !game.clean(), score: Some(score) };
+    match rules.share {
+        ShareLevel::Full => Some(entry),
+        ShareLevel::NoCount => Some(Entry { guesses: None, score: None, ..entry }),
+        ShareLevel::Outcome => Some(Entry { guesses: None, score: None, aided: false, ..entry }),
+        ShareLevel::None => None,
+    }
+}
+
+/// Adds `name`'s finished game of `day` to the leaderboard, replacing one
+/// they added for that day before.
+fn add(data_dir: &Path, path: &Path, name: String, day: NaiveDate, rules: &Rules) -> Result<()> {
+    let game = match SaveFile::for_day(data_dir, day).load()? {
+        Some(game) if game.finished => game,
+        game => return Err(WordleError::Unfinished(day, game.map_or(0, |g| g.char_guesses.len()))),
+    };
+    let entry = entry(name, day, &game, rules).ok_or(WordleError::SharingOff)?;
+
+    // Everyone sharing the file takes the lock next to it, not their own.
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::create_dir_all(dir).context("creating the leaderboard's dir")?;
+    cache::with_lock(dir, || {
+        let mut entries = load(path)?;
+        entries.retain(|e| e.name != entry.name || e.day != entry.day);
+        entries.push(entry.clone());
+        let json = serde_json::to_string_pretty(&entries).expect("entries serialize");
+        cache::write_atomic(path, json.as_bytes()).context("writing the leaderboard")
+    })?;
+    let result = match (entry.won, entry.guesses) {
+        (true, Some(guesses)) => guesses.to_string(),
+        (true, None) => "win".to_string(),
+        (false, _) => "X".to_string(),
+    };
+    eprintln!("Added {}'s {} for {} to {}.", entry.name, result, day, path.display());
+    Ok(())
+}
+
+fn show(app: &App, path: &Path, clean: bool, by_score: bool) -> Result<()> {
+    let mut entries = load(path)?;
+    if clean {
+        entries.retain(|e| !e.aided);
+    }
+    let standings = standings(&entries, date_arg::today(), by_score);
+    if app.json() {
+        for (i, s) in standings.iter().enumerate() {
+            output::emit(&Event::Standing { rank: i + 1, name: &s.name, played: s.played, average: s.average, average_score: s.average_score, streak: s.streak });
+        }
+        return Ok(());
+    }
+    if standings.is_empty() {
+        println!("Nobody is on the leaderboard yet.");
+        return Ok(());
+    }
+    let width = standings.iter().map(|s| s.name.chars().count()).max().unwrap_or(0).max(4);
+    let or_dash = |average: Option<f64>, places| average.map_or("-".to_string(), |a| format!("{:.*}", places, a));
+    println!("    {:<width$}  Played  Average  Score  Streak", "Name");
+    for (i, s) in standings.iter().enumerate() {
+        println!("{:>2}. {:<width$}  {:>6}  {:>7}  {:>5}  {:>6}", i + 1, s.name, s.played, or_dash(s.average, 2), or_dash(s.average_score, 1), s.streak);
+    }
+    Ok(())
+}
+
+pub fn run(app: &App, args: LeaderboardArgs) -> Result<()> {
+    let path: PathBuf = args.file.or_else(|| app.config.leaderboard.clone()).ok_or(WordleError::NoLeaderboard)?;
+    match args.command {
+        LeaderboardCommand::Add { name, play } => add(&app.data_dir, &path, name, play.day, &Rules::from_args(&app.config, &play)),
+        LeaderboardCommand::Show { clean, by_score } => show(app, &path, clean, by_score),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(name: &str, d: u32, guesses: Option<usize>) -> Entry {
+        Entry {
+            name: name.to_string(),
+            day: NaiveDate::from_ymd_opt(2025, 1, d).unwrap(),
+            won: guesses.is_some(),
+            guesses: Some(guesses.unwrap_or(6)),
+            aided: false,
+            score: Some(guesses.map_or(0, |g| 40 - 5 * g as u32)),
+        }
+    }
+
+    /// A clean win in 2, or an aided one, shared at `share` with `tries`.
+    fn shared(share: ShareLevel, tries: u32, aided: bool) -> Option<Entry> {
+        let row = |guess: &str| guess.chars().zip(crate::grade::grade(guess, "crane")).map(|(ch, kind)| crate::CharGuess::new(ch, kind)).collect();
+        let aids = if aided { vec![crate::progress::Aid::Suggestions] } else { vec![] };
+        let game = SavedGame { char_guesses: vec![row("trace"), row("crane")], finished: true, aids, ..SavedGame::default() };
+        entry("ann".to_string(), NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), &game, &Rules { share, tries, ..Rules::default() })
+    }
+
+    #[test]
+    fn full_shares_the_guesses_and_a_score_for_the_tries() {
+        let entry = shared(ShareLevel::Full, 6, true).unwrap();
+        assert_eq!((entry.won, entry.guesses, entry.aided), (true, Some(2), true));
+        // Kids' 8 tries leave more tries to score.
+        assert!(shared(ShareLevel::Full, 8, true).unwrap().score > entry.score);
+    }
+
+    #[test]
+    fn no_count_leaves_out_the_guesses_and_score() {
+        let entry = shared(ShareLevel::NoCount, 6, true).unwrap();
+        assert_eq!((entry.won, entry.guesses, entry.score, entry.aided), (true, None, None, true));
+    }
+
+    #[test]
+    fn outcome_shares_only_the_win() {
+        let entry = shared(ShareLevel::Outcome, 6, true).unwrap();
+        assert_eq!((entry.won, entry.guesses, entry.score, entry.aided), (true, None, None, false));
+    }
+
+    #[test]
+    fn none_adds_nothing() {
+        assert_eq!(shared(ShareLevel::None, 6, false), None);
+    }
+
+    #[test]
+    fn ranks_by_average_then_streak() {
+        let entries = [
+            played("ann", 1, Some(3)),
+            played("ann", 2, None),
+            played("ann", 3, Some(4)),
+            played("bob", 2, Some(4)),
+            played("bob", 3, Some(4)),
+            played("cat", 1, Some(4)),
+        ];
+        let today = NaiveDate::from_ymd_opt(2025, 1, 4).unwrap();
+        let standings = standings(&entries, today, false);
+        let names: Vec<&str> = standings.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["bob", "cat", "ann"]);
+        assert_eq!(standings[0].streak, 2);
+        assert_eq!(standings[1].streak, 0);
+        assert_eq!(standings[2], Standing { name: "ann".to_string(), played: 3, average: Some(14.0 / 3.0), average_score: Some(45.0 / 3.0), streak: 1 });
+
+        // A genius day puts ann ahead on points.
+        let mut entries = entries;
+        entries[0].score = Some(60);
+        let by_score: Vec<String> = super::standings(&entries, today, true).into_iter().map(|s| s.name).collect();
+        assert_eq!(by_score, ["ann", "bob", "cat"]);
+    }
+}
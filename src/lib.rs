@@ -0,0 +1,1903 @@
+#[cfg(feature = "cli")]
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, IsTerminal},
+    path::Path,
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "cli")]
+use {
+    chrono::NaiveDate,
+    colored::Colorize,
+    serde::{Deserialize, Serialize},
+    rand::{SeedableRng, rngs::StdRng, seq::IndexedRandom},
+    app::{App, LazyClient},
+    constraints::{HardModeViolation, LogicViolation},
+    i18n::tr,
+    cli::{CacheCommand, ChallengeCommand, Cli, ColorChoice, Command, ConfigCommand, DictCommand, PlayArgs, StatsCommand, WordListArgs},
+    error::{Context, Result, WordleError},
+    index::WordIndex,
+    output::Event,
+    secret::SecretWord,
+    source::WordSource,
+    tracing::debug,
+};
+
+pub use grade::{CharGuess, CharGuessKind, Tile, Word, WordError};
+
+// The grading and solver modules are plain computation. Everything that reads
+// files, goes online or parses the command line is the `cli` feature, so the
+// engine alone builds for targets like `wasm32-unknown-unknown`.
+pub mod adversary;
+#[cfg(feature = "cli")]
+pub mod analysis;
+#[cfg(feature = "cli")]
+pub mod anagram;
+#[cfg(feature = "cli")]
+pub mod animation;
+#[cfg(feature = "cli")]
+pub mod app;
+#[cfg(feature = "cli")]
+pub mod archive;
+#[cfg(feature = "cli")]
+pub mod available;
+#[cfg(feature = "cli")]
+pub mod blitz;
+#[cfg(feature = "cli")]
+pub mod bot;
+#[cfg(feature = "cli")]
+pub mod cache;
+#[cfg(feature = "cli")]
+pub mod catchup;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "cli")]
+pub mod chain;
+#[cfg(feature = "cli")]
+pub mod challenge;
+#[cfg(feature = "cli")]
+pub mod compiled;
+#[cfg(feature = "cli")]
+pub mod completions;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod constraints;
+#[cfg(feature = "cli")]
+pub mod control;
+#[cfg(feature = "cli")]
+pub mod continuation;
+#[cfg(feature = "cli")]
+pub mod cross;
+#[cfg(feature = "cli")]
+pub mod daily;
+#[cfg(feature = "cli")]
+pub mod daily_random;
+#[cfg(feature = "cli")]
+pub mod dashboard;
+#[cfg(feature = "cli")]
+pub mod date_arg;
+#[cfg(feature = "cli")]
+pub mod definition;
+#[cfg(feature = "cli")]
+pub mod dict_index;
+#[cfg(feature = "cli")]
+pub mod dictionary;
+#[cfg(feature = "cli")]
+pub mod difficulty;
+#[cfg(feature = "cli")]
+pub mod doctor;
+#[cfg(feature = "cli")]
+pub mod duel;
+#[cfg(feature = "cli")]
+pub mod endscreen;
+#[cfg(feature = "cli")]
+pub mod engine;
+#[cfg(feature = "cli")]
+pub mod error;
+#[cfg(feature = "cli")]
+pub mod export;
+#[cfg(feature = "cli")]
+pub mod gc;
+pub mod grade;
+#[cfg(feature = "cli")]
+pub mod hint;
+#[cfg(feature = "cli")]
+pub mod i18n;
+#[cfg(feature = "cli")]
+pub mod import;
+#[cfg(feature = "cli")]
+pub mod incremental;
+pub mod index;
+pub mod input;
+#[cfg(feature = "cli")]
+pub mod interrupt;
+#[cfg(feature = "cli")]
+pub mod keys;
+#[cfg(feature = "cli")]
+pub mod kids;
+#[cfg(feature = "cli")]
+pub mod leaderboard;
+#[cfg(feature = "cli")]
+pub mod limits;
+#[cfg(feature = "cli")]
+pub mod logging;
+#[cfg(feature = "cli")]
+pub mod man;
+#[cfg(feature = "cli")]
+pub mod mastodon;
+#[cfg(feature = "cli")]
+pub mod menu;
+#[cfg(feature = "cli")]
+pub mod multi;
+#[cfg(feature = "cli")]
+pub mod multiplayer;
+#[cfg(feature = "cli")]
+pub mod notes;
+#[cfg(feature = "cli")]
+pub mod obscurity;
+#[cfg(feature = "cli")]
+pub mod output;
+pub mod partial;
+#[cfg(feature = "cli")]
+pub mod prefetch;
+#[cfg(feature = "cli")]
+pub mod progress;
+#[cfg(feature = "cli")]
+pub mod prompt;
+#[cfg(feature = "cli")]
+pub mod rate_limit;
+#[cfg(feature = "cli")]
+pub mod repeats;
+#[cfg(feature = "cli")]
+pub mod replay;
+#[cfg(feature = "cli")]
+pub mod review;
+#[cfg(feature = "cli")]
+pub mod rules;
+#[cfg(feature = "cli")]
+pub mod score;
+#[cfg(feature = "cli")]
+pub mod scheduler;
+#[cfg(feature = "cli")]
+pub mod scramble;
+#[cfg(feature = "cli")]
+pub mod secret;
+#[cfg(feature = "cli")]
+pub mod serve;
+#[cfg(feature = "cli")]
+pub mod setup;
+#[cfg(feature = "cli")]
+pub mod share;
+#[cfg(feature = "cli")]
+pub mod simulate;
+#[cfg(feature = "cli")]
+pub mod snapshot;
+#[cfg(feature = "cli")]
+pub mod solve;
+#[cfg(feature = "cli")]
+pub mod sound;
+#[cfg(feature = "cli")]
+pub mod source;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "cli")]
+pub mod stats;
+#[cfg(feature = "cli")]
+pub mod storage;
+#[cfg(feature = "cli")]
+pub mod streamer;
+pub mod strategy;
+pub mod suggest;
+#[cfg(feature = "cli")]
+pub mod sync;
+#[cfg(feature = "cli")]
+pub mod terminal;
+#[cfg(feature = "cli")]
+pub mod theme;
+#[cfg(feature = "cli")]
+pub mod transcript;
+#[cfg(feature = "cli")]
+pub mod tui;
+#[cfg(feature = "cli")]
+pub mod watch;
+#[cfg(feature = "cli")]
+pub mod webhook;
+#[cfg(feature = "cli")]
+pub mod wordlebot;
+
+
+#[cfg(feature = "cli")]
+static ALPHABET: [char; 26] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm',
+    'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z'];
+
+/// The keyboard the letters are shown on, a row a line.
+#[cfg(feature = "cli")]
+static KEYBOARD: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Columns left between the board and the keyboard next to it.
+#[cfg(feature = "cli")]
+static KEYBOARD_GAP: usize = 6;
+
+#[cfg(feature = "cli")]
+static DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The word lengths that can be played, the daily wordle always being 5.
+static WORD_LENGTHS: std::ops::RangeInclusive<usize> = 4..=8;
+
+
+/// A day's wordle as NYT describes it. Caches from before the metadata was
+/// kept only have the solution, and so do days read from a response NYT
+/// changed, but the solution is always there.
+#[cfg(feature = "cli")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Puzzle {
+    pub id: Option<u32>,
+    pub solution: SecretWord,
+    pub print_date: Option<String>,
+    pub days_since_launch: Option<u32>,
+    pub editor: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+impl Puzzle {
+    /// The puzzle number of `day` with thousands separators, the way NYT
+    /// shows it. Caches without the number get it from the day.
+    fn number(&self, day: NaiveDate) -> Option<String> {
+        self.days_since_launch.or_else(|| date_arg::puzzle_number(day)).map(thousands)
+    }
+
+    /// The heading of the game, e.g. "Wordle #1,234 · 2025-01-01 by Tracy
+    /// Bennett".
+    fn header(&self, day: NaiveDate) -> String {
+        let mut header = match self.number(day) {
+            Some(number) => format!("Wordle #{} · {}", number, day),
+            None => format!("Wordle {}", day),
+        };
+        if let Some(editor) = &self.editor {
+            header.push_str(&format!(" by {}", editor));
+        }
+        header
+    }
+}
+
+/// A number with thousands separators, the way NYT shows puzzle numbers.
+#[cfg(feature = "cli")]
+fn thousands(n: u32) -> String {
+    let digits = n.to_string();
+    let mut number = String::new();
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            number.push(',');
+        }
+        number.push(ch);
+    }
+    number
+}
+
+/// Reads a user-supplied word list, checking that every word can be played.
+#[cfg(feature = "cli")]
+fn read_word_list(path: &Path) -> Result<HashSet<String>> {
+    let f = File::open(path).context("opening word list")?;
+    let mut words = HashSet::new();
+    for (i, line) in BufReader::new(f).lines().enumerate() {
+        let line = line.context("reading word list")?;
+        let word = line.trim().to_ascii_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        if !WORD_LENGTHS.contains(&word.len()) || !word.chars().all(|ch| ALPHABET.contains(&ch)) {
+            return Err(WordleError::InvalidWord { path: path.to_path_buf(), line: i + 1, word });
+        }
+        words.insert(word);
+    }
+    Ok(words)
+}
+
+#[cfg(feature = "cli")]
+impl WordListArgs {
+    /// The allowed guesses, including any custom answers so they can be won.
+    fn dictionary(&self, cache_dir: &Path, url: &str, offline: bool, client: &LazyClient) -> Result<HashSet<String>> {
+        let mut dictionary = match &self.dictionary {
+            Some(path) => read_word_list(path)?,
+            None => dictionary::read(cache_dir, url, offline, client)?,
+        };
+        if let Some(answers) = self.answers()? {
+            dictionary.extend(answers);
+        }
+        Ok(dictionary)
+    }
+
+    fn answers(&self) -> Result<Option<HashSet<String>>> {
+        self.answers.as_deref().map(read_word_list).transpose()
+    }
+}
+
+/// `text` in a box of lines, for the header above the board, or as it is
+/// on a terminal too narrow for the box.
+#[cfg(feature = "cli")]
+fn boxed(text: &str) -> String {
+    if terminal::width().is_some_and(|columns| columns < text.chars().count() + 4) {
+        return text.to_string();
+    }
+    let line = "─".repeat(text.chars().count() + 2);
+    format!("┌{}┐\n│ {} │\n└{}┘", line, text, line)
+}
+
+/// A duration as minutes and seconds, e.g. 1:05.
+#[cfg(feature = "cli")]
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// How long each guess took, and which took longest, e.g. "Time per guess:
+/// 0:12, 1:42, 0:30, guess 2 took longest".
+#[cfg(feature = "cli")]
+fn time_per_guess(think_times: &[Duration]) -> Option<String> {
+    let (slowest, _) = think_times.iter().enumerate().rev().max_by_key(|&(_, took)| took)?;
+    let times: Vec<String> = think_times.iter().map(|&took| format_duration(took)).collect();
+    Some(match think_times.len() {
+        1 => tr!("time-per-guess", times = times.join(", ")),
+        _ => tr!("time-per-guess-longest", times = times.join(", "), guess = slowest + 1),
+    })
+}
+
+/// The time spent on each guess, in milliseconds.
+#[cfg(feature = "cli")]
+fn think_ms(current_word: &CurrentWord) -> Vec<u128> {
+    progress::think_times(&current_word.guess_times).iter().map(Duration::as_millis).collect()
+}
+
+#[cfg(feature = "cli")]
+fn random_answer(mut words: Vec<&String>, seed: u64) -> Result<String> {
+    words.sort();
+    words.choose(&mut StdRng::seed_from_u64(seed))
+        .map(|w| w.to_string())
+        .ok_or(WordleError::EmptyDictionary)
+}
+
+/// A practice word for games between players, from the answers if given.
+#[cfg(feature = "cli")]
+fn practice_word(app: &App, dictionary: &WordIndex, seed: Option<u64>) -> Result<String> {
+    let words = app.answers()?.unwrap_or(dictionary).iter().filter(|w| w.len() == 5).collect();
+    random_answer(words, seed.unwrap_or_else(rand::random))
+}
+
+#[cfg(feature = "cli")]
+fn write_word_cache(cache_dir: &Path, day: NaiveDate, puzzle: &Puzzle) -> Result<()> {
+    let json = serde_json::to_string(puzzle).expect("a puzzle serializes");
+    let path = cache::word_path(cache_dir, day);
+    let contents = scramble::encode(&path, json.as_bytes()).context("scrambling the cached word")?;
+    cache::write_atomic(&path, &contents).context("writing to word cache file")?;
+    cache::add_day(cache_dir, day)
+}
+
+/// The day's puzzle from the cache, fetching it from `source` the first time.
+#[cfg(feature = "cli")]
+pub fn get_and_write_word(cache_dir: &Path, day: NaiveDate, source: &dyn WordSource) -> Result<Puzzle> {
+    let word_cache_path = cache::word_path(cache_dir, day);
+
+    if word_cache_path.try_exists().context("checking for word cache")? {
+        match read_word_cache(&word_cache_path) {
+            Err(e @ (WordleError::CorruptSave(..) | WordleError::CorruptWord(_))) => {
+                eprintln!("{} Fetching it again.", e);
+            },
+            result => {
+                debug!(%day, "the word was cached");
+                return result;
+            },
+        }
+    }
+
+    debug!(%day, "the word isn't cached, fetching it");
+    let puzzle = source.answer_for(day)?;
+    write_word_cache(cache_dir, day, &puzzle)?;
+    Ok(puzzle)
+}
+
+/// The puzzle in the contents of a cached word: the puzzle as JSON, or
+/// for caches from before the metadata was kept the solution on a line.
+#[cfg(feature = "cli")]
+pub fn parse_word_cache(contents: &str) -> std::result::Result<Puzzle, error::Malformed> {
+    let puzzle = match contents.trim_start().starts_with('{') {
+        true => serde_json::from_str(contents).map_err(error::Malformed::Json)?,
+        false => {
+            let solution = contents.lines().next().unwrap_or("").trim_end().to_string();
+            Puzzle { solution: solution.into(), ..Puzzle::default() }
+        },
+    };
+    match source::is_solution(puzzle.solution.reveal()) {
+        true => Ok(puzzle),
+        false => Err(error::Malformed::NotAWord),
+    }
+}
+
+/// Reads a cached word, failing if it isn't a playable NYT solution rather
+/// than starting a game that can't be won.
+#[cfg(feature = "cli")]
+fn read_word_cache(word_cache_path: &Path) -> Result<Puzzle> {
+    let contents = fs::read_to_string(word_cache_path).context("reading word cache file")?;
+    let plain = scramble::is_plain(&contents);
+    let buf = scramble::decode(word_cache_path, contents).ok_or_else(|| WordleError::CorruptWord(word_cache_path.to_path_buf()))?;
+    let puzzle = parse_word_cache(&buf).map_err(|e| match e {
+        error::Malformed::Json(e) => WordleError::CorruptSave(word_cache_path.to_path_buf(), e),
+        error::Malformed::NotAWord => WordleError::CorruptWord(word_cache_path.to_path_buf()),
+    })?;
+    // Words cached before --encrypt-cache are scrambled as they're read,
+    // which is worth trying but not failing over.
+    if plain && let Ok(scrambled) = scramble::encode(word_cache_path, buf.as_bytes()) {
+        let _ = cache::write_atomic(word_cache_path, &scrambled);
+    }
+    Ok(puzzle)
+}
+
+#[cfg(feature = "cli")]
+#[allow(clippy::enum_variant_names)]
+pub enum InvalidGuessKind {
+    WordTooLong(usize),
+    WordTooShort(usize),
+    /// The position (from 1) and the character that isn't a letter.
+    WordContainsNonLetters(usize, char),
+    /// The closest dictionary words, for a "did you mean".
+    WordNotInDictionary(Vec<String>),
+    WordBreaksHardMode(constraints::HardModeViolation),
+    /// With `--strict-logic`, the clue the guess goes against.
+    WordBreaksLogic(constraints::LogicViolation),
+    /// The day of a game played before that had the guess as its answer.
+    WordWasAnswer(NaiveDate),
+    /// The guess is rarer than `--common-guesses` takes.
+    WordTooObscure,
+}
+
+#[cfg(feature = "cli")]
+impl InvalidGuessKind {
+    fn message(&self) -> String {
+        match self {
+            InvalidGuessKind::WordTooShort(len) => tr!("too-short", length = len),
+            InvalidGuessKind::WordTooLong(len) => tr!("too-long", length = len),
+            InvalidGuessKind::WordContainsNonLetters(position, ch) => tr!("non-letter", ch = ch, position = position),
+            InvalidGuessKind::WordNotInDictionary(close) if close.is_empty() => tr!("not-in-dictionary"),
+            InvalidGuessKind::WordNotInDictionary(close) => tr!("not-in-dictionary-close", words = i18n::or_list(close)),
+            InvalidGuessKind::WordBreaksHardMode(HardModeViolation::MovedCorrectLetter { position, ch }) => {
+                tr!("must-be", position = position + 1, letter = ch.to_ascii_uppercase())
+            },
+            InvalidGuessKind::WordBreaksHardMode(HardModeViolation::MissingRevealedLetter(ch)) => {
+                tr!("must-contain", letter = ch.to_ascii_uppercase())
+            },
+            InvalidGuessKind::WordBreaksLogic(violation) => match violation {
+                LogicViolation::Moved { position, ch, guess } => {
+                    tr!("logic-moved", position = position + 1, letter = ch.to_ascii_uppercase(), guess = guess.to_uppercase())
+                },
+                LogicViolation::Missing { ch, count: 1, guess } => tr!("logic-missing", letter = ch.to_ascii_uppercase(), guess = guess.to_uppercase()),
+                LogicViolation::Missing { ch, count, guess } => {
+                    tr!("logic-missing-many", count = count, letter = ch.to_ascii_uppercase(), guess = guess.to_uppercase())
+                },
+                LogicViolation::Absent { ch, guess } => tr!("logic-absent", letter = ch.to_ascii_uppercase(), guess = guess.to_uppercase()),
+                LogicViolation::TooMany { ch, count, guess } => {
+                    tr!("logic-too-many", count = count, letter = ch.to_ascii_uppercase(), guess = guess.to_uppercase())
+                },
+                LogicViolation::NotThere { position, ch, guess } => {
+                    tr!("logic-not-there", position = position + 1, letter = ch.to_ascii_uppercase(), guess = guess.to_uppercase())
+                },
+            },
+            InvalidGuessKind::WordWasAnswer(day) => tr!("repeat-rejected", day = day),
+            InvalidGuessKind::WordTooObscure => tr!("too-obscure"),
+        }
+    }
+}
+
+#[cfg(feature = "cli")]
+pub enum GuessOutcome {
+    /// The guess was turned away without using a try.
+    InvalidGuess(InvalidGuessKind),
+    Continue,
+    Win,
+    NoTriesLeft,
+    /// The game was already won, lost or given up, so nothing was graded
+    /// and no try was used.
+    GameOver,
+}
+
+#[cfg(feature = "cli")]
+pub struct CurrentWord {
+    correct_answer: SecretWord,
+
+    word_length: usize,
+
+    char_guesses: Vec<Vec<CharGuess>>,
+
+    tries: u32,
+
+    hard_mode: bool,
+
+    /// Whether guesses the feedback rules out are refused.
+    strict_logic: bool,
+
+    /// Whether any letters make a guess, dictionary word or not.
+    any_word: bool,
+
+    /// Whether the first hint defines the answer, if it can be.
+    definition_hints: bool,
+
+    /// Time spent on the game in earlier sessions.
+    played_before: Duration,
+
+    /// When this session started.
+    started: Instant,
+
+    /// How long the game took, once it's over.
+    took: Option<Duration>,
+
+    /// How far into the game each guess was made, one per guess or none if
+    /// the game was resumed from a save without them.
+    guess_times: Vec<Duration>,
+
+    hints: Vec<hint::Hint>,
+
+    /// Tries spent on hints.
+    hint_tries: u32,
+
+    gave_up: bool,
+
+    /// Help other than hints the game had, sorted.
+    aids: Vec<progress::Aid>,
+
+    /// Picks the answer as the game goes, in adversarial games.
+    adversary: Option<adversary::Adversary>,
+
+    /// What becomes of guesses that were the answers of [`Self::seen_answers`].
+    no_repeats: Option<repeats::Repeats>,
+
+    /// The answers of days played before, each with its day.
+    seen_answers: HashMap<String, NaiveDate>,
+
+    /// The only guesses taken, with `--common-guesses`.
+    common_guesses: Option<HashSet<String>>,
+}
+
+#[cfg(feature = "cli")]
+impl CurrentWord {
+    pub fn new(correct_answer: impl Into<SecretWord>, tries: u32) -> Self {
+        let correct_answer = correct_answer.into();
+        let word_length = correct_answer.reveal().chars().count();
+        Self {
+            correct_answer,
+            word_length,
+            tries,
+            char_guesses: vec![],
+            hard_mode: false,
+            strict_logic: false,
+            any_word: false,
+            definition_hints: false,
+            played_before: Duration::ZERO,
+            started: Instant::now(),
+            took: None,
+            guess_times: vec![],
+            hints: vec![],
+            hint_tries: 0,
+            gave_up: false,
+            aids: vec![],
+            adversary: None,
+            no_repeats: None,
+            seen_answers: HashMap::new(),
+            common_guesses: None,
+        }
+    }
+
+    fn resume(&mut self, saved: progress::SavedGame) {
+        self.tries = self.tries.saturating_sub(saved.char_guesses.len() as u32 + saved.hint_tries);
+        self.played_before = saved.elapsed();
+        if saved.guess_ms.len() == saved.char_guesses.len() {
+            self.guess_times = saved.guess_ms.iter().map(|&ms| Duration::from_millis(ms)).collect();
+        }
+        self.char_guesses = saved.char_guesses;
+        self.hints = saved.hints;
+        self.hint_tries = saved.hint_tries;
+        self.aids = saved.aids;
+    }
+
+    fn aid(&mut self, aid: progress::Aid) {
+        if let Err(i) = self.aids.binary_search(&aid) {
+            self.aids.insert(i, aid);
+        }
+    }
+
+    /// How long the game has been played, across sessions.
+    fn elapsed(&self) -> Duration {
+        self.took.unwrap_or_else(|| self.played_before + self.started.elapsed())
+    }
+
+    /// The latest guess, `None` before the first.
+    pub fn current_guess(&self) -> Option<String> {
+        self.char_guesses.last().map(|cgs| cgs.iter().map(|cg| cg.ch).collect())
+    }
+
+    /// The day played before that had `guess` as its answer. Today's answer
+    /// can be one again, so it never counts.
+    fn repeated(&self, guess: &str) -> Option<&NaiveDate> {
+        self.seen_answers.get(guess).filter(|_| self.correct_answer != *guess)
+    }
+
+    /// Grades `guess`, using a try unless it's turned away. Once the game is
+    /// over every guess is [`GuessOutcome::GameOver`], and the tries and
+    /// rows stay as they are.
+    pub fn guess(&mut self, guess: String, dictionary: &WordIndex) -> GuessOutcome {
+        let length = guess.chars().count();
+        let non_letter = guess.chars().enumerate().find(|(_, ch)| !ALPHABET.contains(ch));
+        if self.is_over() {
+            GuessOutcome::GameOver
+        } else if length < self.word_length {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordTooShort(self.word_length))
+        } else if length > self.word_length {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordTooLong(self.word_length))
+        } else if let Some((i, ch)) = non_letter {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordContainsNonLetters(i + 1, ch))
+        } else if !self.any_word && !dictionary.contains(&guess) {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordNotInDictionary(suggest::nearest(&guess, dictionary, 3)))
+        } else if let Some(violation) = self.hard_mode_violation(&guess) {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordBreaksHardMode(violation))
+        } else if let Some(violation) = self.logic_violation(&guess) {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordBreaksLogic(violation))
+        } else if self.no_repeats == Some(repeats::Repeats::Reject) && let Some(&day) = self.repeated(&guess) {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordWasAnswer(day))
+        } else if self.common_guesses.as_ref().is_some_and(|common| !common.contains(&guess)) {
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordTooObscure)
+        } else {
+            if let Some(adversary) = &mut self.adversary {
+                self.correct_answer = adversary.respond(&guess).to_string().into();
+            }
+            self.char_guesses.push(
+                guess.chars()
+                    .zip(grade::grade(&guess, self.correct_answer.reveal()))
+                    .map(|(ch, kind)| CharGuess::new(ch, kind))
+                    .collect()
+            );
+            // Only the row and its colors, not the guess, so a log attached to
+            // a bug report doesn't give the answer away.
+            debug!(row = self.char_guesses.len(), pattern = output::pattern(self.char_guesses.last().expect("a guess was just graded")), "graded");
+            if self.guess_times.len() + 1 == self.char_guesses.len() {
+                self.guess_times.push(self.elapsed());
+            }
+
+            self.tries -= 1;
+            if self.is_over() {
+                self.took = Some(self.elapsed());
+            }
+
+            if self.is_solved() {
+                GuessOutcome::Win
+            } else if self.tries == 0 {
+                GuessOutcome::NoTriesLeft
+            } else {
+                GuessOutcome::Continue
+            }
+        }
+    } 
+
+    /// Takes back the latest guess along with the try it used.
+    fn undo(&mut self) -> Option<Vec<CharGuess>> {
+        let undone = self.char_guesses.pop()?;
+        self.guess_times.truncate(self.char_guesses.len());
+        self.tries += 1;
+        self.took = None;
+        Some(undone)
+    }
+
+    /// Gives the next hint, paying `penalty` tries for it.
+    fn hint(&mut self, answers: &WordIndex, shortlist: usize, penalty: u32) -> std::result::Result<hint::Hint, String> {
+        if penalty >= self.tries {
+            return Err(tr!("hint-too-costly", penalty = penalty));
+        }
+        let hint = hint::next(self, answers, shortlist).ok_or_else(|| tr!("hint-none-left"))?;
+        self.tries -= penalty;
+        self.hint_tries += penalty;
+        self.hints.push(hint.clone());
+        Ok(hint)
+    }
+
+    fn give_up(&mut self) {
+        self.gave_up = true;
+        self.took = Some(self.elapsed());
+    }
+
+    /// Whether the latest guess is the answer, given up on or not.
+    pub fn is_solved(&self) -> bool {
+        self.current_guess().is_some_and(|guess| self.correct_answer == guess)
+    }
+
+    pub fn won(&self) -> bool {
+        !self.gave_up && self.is_solved()
+    }
+
+    /// Whether the game was won, lost or given up.
+    pub fn is_over(&self) -> bool {
+        self.gave_up || self.tries == 0 || self.is_solved()
+    }
+
+    fn hard_mode_violation(&self, guess: &str) -> Option<constraints::HardModeViolation> {
+        if self.hard_mode {
+            constraints::Constraints::from_guesses(&self.char_guesses).violation(guess)
+        } else {
+            None
+        }
+    }
+
+    fn logic_violation(&self, guess: &str) -> Option<constraints::LogicViolation> {
+        self.strict_logic.then(|| constraints::Knowledge::from_guesses(&self.char_guesses).violation(guess)).flatten()
+    }
+
+    fn display_suggestions(&self, answers: &WordIndex, dictionary: &WordIndex) {
+        let suggestions = strategy::suggest(&self.char_guesses, answers, dictionary);
+        let best_overall = suggestions.best_overall.as_deref().unwrap_or("-");
+        if self.hard_mode {
+            let best_legal = suggestions.best_legal.as_deref().unwrap_or("-");
+            println!("{} words left. Best guess: {} (hard mode: {})", suggestions.candidates, best_overall, best_legal);
+        } else {
+            println!("{} words left. Best guess: {}", suggestions.candidates, best_overall);
+        }
+    }
+
+    /// The number the board shows in front of row `i`, e.g. ` 3/6`.
+    pub fn row_number(&self, i: usize) -> String {
+        let total = self.char_guesses.len() + self.tries as usize;
+        format!("{:>w$}/{}", i + 1, total, w = total.to_string().len())
+    }
+
+    /// What the guesses showed about `ch`, the best of it when they differ.
+    pub fn letter_kind(&self, ch: char) -> Option<CharGuessKind> {
+        let rank = |kind: CharGuessKind| match kind {
+            CharGuessKind::NotInWord => 1,
+            CharGuessKind::WrongPlace => 2,
+            CharGuessKind::Correct => 3,
+        };
+        self.char_guesses.iter().flatten().filter(|cg| cg.ch == ch).map(|cg| cg.kind).max_by_key(|&kind| rank(kind))
+    }
+
+    /// The keyboard with each letter colored by what the guesses showed.
+    fn keyboard(&self, theme: &theme::Theme) -> Vec<String> {
+        KEYBOARD.iter().enumerate()
+            .map(|(i, row)| {
+                let keys: Vec<String> = row.chars()
+                    .map(|ch| match self.letter_kind(ch) {
+                        // The theme's marks go in place of the padding.
+                        Some(kind) if theme.tile(kind).marks.is_some() => theme.paint(kind, &ch.to_string()).to_string(),
+                        Some(kind) => theme.paint(kind, &format!(" {} ", ch)).to_string(),
+                        None => format!(" {} ", ch),
+                    })
+                    .collect();
+                format!("{}{}", " ".repeat(i), keys.join(" "))
+            })
+            .collect()
+    }
+
+    /// Shows the board with each row numbered out of the tries, e.g. 3/6, as
+    /// wide as the terminal allows: big tiles fall back to small ones where
+    /// they don't fit, the row numbers go when even those don't, and where
+    /// there's room the keyboard goes next to it.
+    fn display_word(&self, big_tiles: bool, theme: &theme::Theme) {
+        let total = self.char_guesses.len() + self.tries as usize;
+        let number = |i: usize| self.row_number(i);
+        let width = terminal::width();
+        // Six columns a big tile, and the row number after them.
+        let big_fits = width.is_none_or(|columns| columns > self.word_length * 6 + number(0).len());
+        if !big_tiles || !big_fits {
+            let rows = self.rows(theme);
+            let numbered: Vec<String> = rows.iter().enumerate().map(|(i, row)| format!("{} {}", number(i), row)).collect();
+            let board = numbered.iter().map(|row| terminal::visible_width(row)).max().unwrap_or(0);
+            let lines = match width {
+                Some(columns) if columns < board => rows,
+                Some(columns) if columns >= board + KEYBOARD_GAP + terminal::visible_width(&self.keyboard(theme)[0]) => {
+                    terminal::side_by_side(&numbered, &self.keyboard(theme), KEYBOARD_GAP)
+                },
+                _ => numbered,
+            };
+            for line in lines {
+                println!("{}", line);
+            }
+            return;
+        }
+        for i in 0..self.char_guesses.len() {
+            self.display_big_row(i, theme);
+        }
+        for i in self.char_guesses.len()..total {
+            println!("\n{}  {}\n", "  _   ".repeat(self.word_length).trim_end(), number(i));
+            println!();
+        }
+    }
+
+    fn display_big_row(&self, i: usize, theme: &theme::Theme) {
+        for line in 0..3 {
+            for cg in &self.char_guesses[i] {
+                let s = if line == 1 { format!("  {}  ", cg.ch.to_ascii_uppercase()) } else { "     ".to_string() };
+                print!("{} ", theme.paint(cg.kind, &s));
+            }
+            if line == 1 {
+                print!(" {}", self.row_number(i));
+            }
+            println!();
+        }
+        println!();
+    }
+
+    /// Just the guessed row `i`, the way [`Self::display_word`] shows it.
+    fn display_row(&self, i: usize, big_tiles: bool, theme: &theme::Theme) {
+        let big_fits = terminal::width().is_none_or(|columns| columns > self.word_length * 6 + self.row_number(0).len());
+        match big_tiles && big_fits {
+            true => self.display_big_row(i, theme),
+            false => println!("{} {}", self.row_number(i), self.rows(theme)[i]),
+        }
+    }
+
+    /// The board in small tiles, a line a row: the guesses, then a blank row
+    /// for each try left.
+    fn rows(&self, theme: &theme::Theme) -> Vec<String> {
+        let guessed = self.char_guesses.iter()
+            .map(|cgs| cgs.iter().map(|cg| theme.paint(cg.kind, &cg.ch.to_string()).to_string()).collect());
+        let empty = (0..self.tries).map(|_| theme.label(CharGuessKind::NotInWord, "_").repeat(self.word_length));
+        guessed.chain(empty).collect()
+    }
+}
+
+
+/// Runs the whole program for the parsed command line.
+#[cfg(feature = "cli")]
+pub fn run(cli: Cli) -> Result<()> {
+    let Cli { cache_dir, data_dir, color, config, output, timeout, timezone: _, lang, nyt_url, dictionary_url, answers_url, session, no_network, streamer, encrypt_cache, dict_index, verbose, quiet, log_file, word_lists, command, play: play_args } = cli;
+    // Colors can still be forced where the console doesn't seem to take them.
+    let escapes = terminal::prepare();
+    colored::control::set_override(color.should_colorize() && (escapes || color == ColorChoice::Always));
+    logging::init(logging::level(verbose, quiet), log_file.as_deref())?;
+    interrupt::install();
+
+    let config_path = config.unwrap_or_else(config::default_path);
+    let prefetch = match setup::wanted(&config_path, session.as_deref(), &command, output == output::OutputFormat::Json) {
+        true => setup::run(&config_path, lang)?,
+        false => false,
+    };
+    let mut config = match config::Config::load_session(&config_path, session.as_deref()) {
+        // The doctor has a config file that can't be read to report.
+        Err(WordleError::Config(..)) if matches!(command, Some(Command::Doctor)) => config::Config::default(),
+        config => config?,
+    };
+    config.streamer |= streamer;
+    config.nyt_url = nyt_url.or(config.nyt_url);
+    config.dictionary_url = dictionary_url.or(config.dictionary_url);
+    config.answers_url = answers_url.or(config.answers_url);
+    config.dict_index = dict_index.unwrap_or(config.dict_index);
+    i18n::set(lang.or(config.lang).unwrap_or_else(i18n::Lang::from_env));
+    storage::set_backend(config.storage);
+    scramble::set(encrypt_cache || config.encrypt_cache);
+    let cache_dir = cache_dir.or_else(|| config.cache_dir.clone());
+    // A cache dir that was picked by hand keeps the saved games too, unless
+    // they got a dir of their own.
+    let data_dir = data_dir
+        .or_else(|| config.data_dir.clone())
+        .or_else(|| cache_dir.clone())
+        .unwrap_or_else(|| dirs::data_dir().unwrap_or_else(std::env::temp_dir).join("wordle-rs"));
+    let cache_dir = cache_dir.unwrap_or_else(cache::default_dir);
+    let timeout = timeout
+        .or(config.timeout)
+        .map_or(app::DEFAULT_TIMEOUT, std::time::Duration::from_secs);
+    // A session keeps its games and settings apart, and shares the cache.
+    let (session_data_dir, config_path) = match &session {
+        Some(name) => (data_dir.join("sessions").join(name), config::session_path(&config_path, name)),
+        None => (data_dir.clone(), config_path),
+    };
+    let mut app = App::new(cache_dir, session_data_dir, config, config_path, output, LazyClient::new(timeout, no_network), word_lists)?;
+    app.quiet = quiet;
+    // Games saved before there was a data dir were played outside of any session.
+    cache::migrate_results(&app.cache_dir, &data_dir)?;
+    // The game asked for is played even when the catalog can't be had.
+    if prefetch && let Err(e) = dispatch(&app, Command::Fetch(setup::archive())) {
+        eprintln!("{}", e);
+    }
+
+    let result = match command {
+        Some(command) => dispatch(&app, command),
+        None if menu::wanted(&app) => menu::run(&app, play_args),
+        None => dispatch(&app, Command::Play(play_args)),
+    };
+    if !app.quiet {
+        app.report_degradations();
+    }
+    match app.config.streamer {
+        true => result.map_err(|e| streamer::hide_paths(e, &app.cache_dir)),
+        false => result,
+    }
+}
+
+#[cfg(feature = "cli")]
+fn dispatch(app: &App, command: Command) -> Result<()> {
+    limits::enforce(app, &command)?;
+    match command {
+        Command::Play(args) if args.boards > 1 => multi::play(app, args),
+        Command::Play(args) => match (args.saves_dir(&app.data_dir), play(app, args)) {
+            (saves, Err(WordleError::AlreadyFinished(day))) => stats::finished(app, &saves, day),
+            (_, result) => result.map(|_| ()),
+        },
+        Command::Anagram(args) => match (anagram::saves_dir(&app.data_dir), play(app, PlayArgs { anagram: true, ..args })) {
+            (saves, Err(WordleError::AlreadyFinished(day))) => stats::finished(app, &saves, day),
+            (_, result) => result.map(|_| ()),
+        },
+        Command::Daily(args) => daily::run(app, args),
+        Command::Archive(args) => archive::run(app, args),
+        Command::Catchup(args) => catchup::run(app, args),
+        Command::Replay(args) => replay::run(app, args),
+        Command::Fetch(args) if args.dry_run => prefetch::dry_run(&app.cache_dir, &args),
+        Command::Fetch(args) => {
+            if app::local_path(app.nyt_url()).is_none() {
+                app.client.online("fetching wordles")?;
+            }
+            if args.if_stale {
+                return prefetch::if_stale(app);
+            }
+            let rate = args.rate.or(app.config.fetch_rate).unwrap_or(prefetch::DEFAULT_RATE).max(1);
+            prefetch::fetch(&app.cache_dir, app.client.timeout, app.nyt_url(), rate, args)
+        },
+        Command::Watch(args) => watch::run(app, args),
+        Command::InstallScheduler(args) => scheduler::run(app, args),
+        Command::Dict { command: DictCommand::Update } => {
+            dictionary::write(&app.cache_dir, app.dictionary_url(), &app.client)?;
+            dictionary::write_answers(&app.cache_dir, app.answers_url(), &app.client)?;
+            obscurity::write_frequencies(&app.cache_dir, &app.client)
+        },
+        Command::Cache { command: CacheCommand::Gc(args) } => gc::collect(&app.cache_dir, args),
+        Command::Cache { command: CacheCommand::Info } => gc::info(&app.cache_dir),
+        Command::Challenge { command: ChallengeCommand::Create { word } } => challenge::create(app, &word),
+        Command::Challenge { command: ChallengeCommand::Play(args) } => challenge::play(app, *args),
+        Command::Duel(args) => duel::play(app, args),
+        Command::Blitz(args) => blitz::play(app, args),
+        Command::Chain(args) => chain::play(app, args),
+        Command::Cross(args) => cross::play(app, args),
+        Command::Host(args) => multiplayer::host(app, args),
+        Command::Join(args) => multiplayer::join(app, args),
+        Command::Serve(args) => serve::run(app, args),
+        Command::Stats { command, daily_random, anagram, dashboard } => {
+            let saves = match anagram {
+                true => anagram::saves_dir(&app.data_dir),
+                false => daily_random::saves_dir(&app.data_dir, daily_random),
+            };
+            if let Some(StatsCommand::Rebuild { dry_run }) = command {
+                return stats::rebuild(&saves, dry_run);
+            }
+            if dashboard && io::stdin().is_terminal() && io::stdout().is_terminal() && !app.json() {
+                return dashboard::run(&saves);
+            }
+            if dashboard {
+                app.degrade("the dashboard needs an interactive terminal and plain output, so the stats were printed instead");
+            }
+            stats::show(app, &saves)
+        },
+        Command::Status => stats::status(&app.data_dir, app.output),
+        Command::Available(args) => available::run(app, args),
+        Command::Doctor => doctor::run(app),
+        Command::Keys => keys::run(app),
+        Command::Compact => progress::compact(&app.data_dir),
+        Command::Export(args) => export::run(&app.cache_dir, &app.data_dir, args),
+        Command::Import(args) => import::run(&app.data_dir, args),
+        Command::ExportArchive(args) => snapshot::export(app, args),
+        Command::ImportArchive(args) => snapshot::import(app, args),
+        Command::Sync(args) => sync::run(app, args),
+        Command::Leaderboard(args) => leaderboard::run(app, args),
+        Command::Config { command: ConfigCommand::Init } => config::init(&app.config_path),
+        Command::Config { command: ConfigCommand::Setup } => match setup::run(&app.config_path, None)? {
+            true => dispatch(app, Command::Fetch(setup::archive())),
+            false => Ok(()),
+        },
+        Command::Man { dir } => man::run(dir.as_deref()).context("writing the man page"),
+        Command::Completions { shell } => completions::print(shell).context("writing the completion script"),
+        Command::Bot(args) => {
+            let dictionary = app.dictionary(false)?;
+            bot::run(&app.cache_dir, args, app.answers()?.unwrap_or(dictionary), dictionary)
+        },
+        Command::Analyze(args) => {
+            let dictionary = app.dictionary(false)?;
+            analysis::run(args, app.answers()?.unwrap_or(dictionary), dictionary);
+            Ok(())
+        },
+        Command::Continue(args) => continuation::run(app, args),
+        Command::Solve(args) => {
+            let dictionary = app.dictionary(false)?;
+            solve::solve(args, app.answers()?.unwrap_or(dictionary), dictionary);
+            Ok(())
+        },
+    }
+}
+
+/// The theme asked for, else the configured one, else classic. Without
+/// colors a colored theme can't tell the tiles apart, so mono is used.
+#[cfg(feature = "cli")]
+fn pick_theme(app: &App, asked: Option<&str>) -> Result<theme::Theme> {
+    let theme_name = asked.or(app.config.theme.as_deref());
+    let theme = theme::Theme::named(theme_name.unwrap_or("classic"), &app.config.themes)?;
+    if theme.tile(CharGuessKind::Correct).marks.is_none() && !colored::control::SHOULD_COLORIZE.should_colorize() {
+        if let Some(name) = theme_name {
+            app.degrade(format!("colors are off, so the mono theme was used instead of {}", name));
+        }
+        return Ok(theme::Theme::mono());
+    }
+    Ok(theme)
+}
+
+/// Plays a game to the end, or until the input runs out, and returns it.
+#[cfg(feature = "cli")]
+fn play(app: &App, args: PlayArgs) -> Result<CurrentWord> {
+    if let Some(path) = &args.replay_transcript {
+        return transcript::replay(app, path);
+    }
+    let rules = rules::Rules::from_args(&app.config, &args);
+    let define = args.define || app.config.define;
+    let answer_difficulty = args.answer_difficulty || app.config.answer_difficulty;
+    let (current_word, title) = play_game(app, args)?;
+    if current_word.is_over()
+        && let Some(text) = share::share_text(&current_word, &title, rules.share)
+    {
+        if rules.copy
+            && let Err(e) = share::copy(&text)
+        {
+            app.degrade(format!("the result wasn't copied to the clipboard: {}", e));
+        }
+        if let Some(url) = &rules.webhook
+            && let Err(e) = webhook::post(&app.client, url, &text)
+        {
+            app.degrade(format!("the result wasn't posted to the webhook: {}", e));
+        }
+        if let Some(account) = &app.config.mastodon
+            && let Err(e) = mastodon::post(&app.client, account, &text)
+        {
+            app.degrade(format!("the result wasn't posted to Mastodon: {}", e));
+        }
+    }
+    if define && current_word.is_over() {
+        show_definition(app, &current_word);
+    }
+    if answer_difficulty && current_word.is_over() {
+        show_difficulty(app, &current_word)?;
+    }
+    Ok(current_word)
+}
+
+/// Shows how hard the answer was, which like the definition waits for a key
+/// with --streamer when the game was lost.
+#[cfg(feature = "cli")]
+fn show_difficulty(app: &App, current_word: &CurrentWord) -> Result<()> {
+    let word = current_word.correct_answer.reveal();
+    let dictionary = app.dictionary(true)?;
+    let answers: Vec<&String> = app.answers()?.unwrap_or(dictionary).iter().collect();
+    let rating = difficulty::rate(word, &answers);
+    let simulated = bot::Simulated::load(&app.cache_dir);
+    let bot_guesses = simulated.as_ref().and_then(|s| s.guesses.get(word).copied());
+    let average = simulated.as_ref().and_then(bot::Simulated::average);
+    if app.json() {
+        output::emit(&Event::AnswerDifficulty {
+            word,
+            rating: rating.out_of_ten,
+            harder_than: rating.harder_than,
+            rare_letters: &rating.rare_letters,
+            repeated: &rating.repeated,
+            neighbors: &rating.neighbors,
+            bot_guesses: bot_guesses.flatten(),
+            bot_average: average.map(|(average, _)| average),
+        });
+        return Ok(());
+    }
+    let letters = |letters: &[char]| letters.iter().map(|ch| ch.to_ascii_uppercase().to_string()).collect::<Vec<_>>().join(", ");
+    let mut lines = vec![tr!("difficulty", rating = rating.out_of_ten, percent = rating.harder_than)];
+    if !rating.rare_letters.is_empty() {
+        lines.push(tr!("difficulty-rare", letters = letters(&rating.rare_letters)));
+    }
+    if !rating.repeated.is_empty() {
+        lines.push(tr!("difficulty-repeated", letters = letters(&rating.repeated)));
+    }
+    if !rating.neighbors.is_empty() {
+        // The count says how many more there are than the few named.
+        let words = rating.neighbors.iter().take(8).cloned().collect::<Vec<_>>().join(", ");
+        lines.push(tr!("difficulty-neighbors", count = rating.neighbors.len(), words = words));
+    }
+    if let Some((average, days)) = average {
+        let average = format!("{:.2}", average);
+        lines.push(match bot_guesses {
+            Some(Some(guesses)) => tr!("difficulty-bot", guesses = guesses, average = average, days = days),
+            Some(None) => tr!("difficulty-bot-lost", average = average, days = days),
+            None => tr!("difficulty-bot-average", average = average, days = days),
+        });
+    }
+    let text = lines.join("\n");
+    println!();
+    match current_word.won() {
+        true => println!("{}", text),
+        false => streamer::spoiler(app, &text),
+    }
+    Ok(())
+}
+
+/// Shows what the answer means, from the built in dictionary when it can't
+/// be looked up. Losing doesn't show the answer, so with --streamer neither
+/// does this until a key is pressed.
+#[cfg(feature = "cli")]
+fn show_definition(app: &App, current_word: &CurrentWord) {
+    let word = current_word.correct_answer.reveal();
+    let meaning = match definition::look_up(app, word) {
+        Ok(meaning) => meaning,
+        Err(e) => match definition::built_in(word) {
+            Some(meaning) => Some(meaning),
+            None => return app.degrade(format!("the answer wasn't looked up ({})", e)),
+        },
+    };
+    if app.json() {
+        if let Some(m) = &meaning {
+            output::emit(&Event::Definition {
+                word,
+                part_of_speech: m.part_of_speech.as_deref(),
+                definition: &m.definition,
+                origin: m.origin.as_deref(),
+            });
+        }
+        return;
+    }
+    let text = match &meaning {
+        Some(m) => {
+            let mut text = match &m.part_of_speech {
+                Some(part) => tr!("defined-as", word = word, part = part, definition = m.definition),
+                None => tr!("defined", word = word, definition = m.definition),
+            };
+            if let Some(origin) = &m.origin {
+                text.push('\n');
+                text.push_str(&tr!("origin", origin = origin));
+            }
+            text
+        },
+        None => tr!("undefined", word = word),
+    };
+    println!();
+    match current_word.won() {
+        true => println!("{}", text),
+        false => streamer::spoiler(app, &text),
+    }
+}
+
+#[cfg(feature = "cli")]
+fn play_game(app: &App, mut args: PlayArgs) -> Result<(CurrentWord, String)> {
+    let (cache_dir, client) = (app.cache_dir.as_path(), &app.client);
+    let rules = rules::Rules::from_args(&app.config, &args);
+    let theme = pick_theme(app, args.theme.as_deref())?;
+    let dictionary = app.dictionary(args.offline)?;
+    let answers = app.answers()?.unwrap_or(dictionary);
+
+    // The title goes in the share text, the header above the game.
+    let (title, header, answer, adversary) = if args.anagram {
+        let answer = anagram::answer(app, args.day)?;
+        let title = format!("anagram {}", args.day);
+        (title.clone(), format!("Wordle {} · {}", title, anagram::scramble(&answer, args.day)), answer.into(), None)
+    } else if args.offline {
+        let seed = args.seed.unwrap_or_else(rand::random);
+        let mut title = format!("practice #{}", seed);
+        let mut words: Vec<&String> = app.answers()?
+            .unwrap_or(dictionary)
+            .iter()
+            .filter(|w| w.len() == args.length)
+            .collect();
+        if let Some(percentile) = args.obscurity {
+            let frequencies = obscurity::read_cached_frequencies(cache_dir)?;
+            words = obscurity::most_common(words, &frequencies, percentile);
+            title.push_str(&format!(" obscurity {}", percentile));
+        }
+        if let Some(difficulty) = args.difficulty {
+            words = difficulty::filter(words, difficulty, args.length);
+            title.push_str(&format!(" {:?}", difficulty).to_lowercase());
+        }
+        if rules.family_words {
+            words = kids::answers(app, words);
+            title.push_str(" kids");
+        }
+        if args.length != 5 {
+            title.push_str(&format!(" {} letters", args.length));
+        }
+        let adversary = args.adversarial.then(|| {
+            title.push_str(" adversarial");
+            adversary::Adversary::new(words.iter().map(|w| w.to_string()).collect())
+        });
+        (title.clone(), format!("Wordle {}", title), random_answer(words, seed)?.into(), adversary)
+    } else if args.daily_random {
+        let title = format!("random {}", args.day);
+        (title.clone(), format!("Wordle {}", title), daily_random::answer(app, args.day)?.into(), None)
+    } else if args.source() != source::SourceChoice::Nyt {
+        let choice = args.source();
+        let source = choice.open(app)?;
+        if !source.is_available(args.day) {
+            return Err(WordleError::Unavailable(args.day, choice.to_string()));
+        }
+        let title = choice.title(args.day);
+        (title.clone(), format!("Wordle {}", title), source.answer_for(args.day)?.solution, None)
+    } else {
+        let nyt = app.nyt();
+        if !nyt.is_available(args.day) {
+            return Err(WordleError::NotPublished(args.day));
+        }
+        let puzzle = match get_and_write_word(cache_dir, args.day, &nyt) {
+            Ok(puzzle) => puzzle,
+            Err(e @ (WordleError::Network { .. } | WordleError::NoNetwork(_))) => {
+                let Some(day) = progress::unfinished_cached_day(cache_dir, &app.data_dir, args.day)? else { return Err(e) };
+                eprintln!("{}", e);
+                eprintln!("Playing {} from the cache instead.", day);
+                args.day = day;
+                read_word_cache(&cache::word_path(cache_dir, day))?
+            },
+            Err(e) => return Err(e),
+        };
+        let title = puzzle.number(args.day).unwrap_or_else(|| args.day.to_string());
+        (title, puzzle.header(args.day), puzzle.solution, None)
+    };
+    let mut current_word = CurrentWord::new(answer, rules.tries);
+    current_word.hard_mode = rules.hard_mode;
+    current_word.strict_logic = rules.strict_logic;
+    current_word.any_word = rules.any_word;
+    current_word.definition_hints = args.definition_hints;
+    if args.definition_hints && !definition::AVAILABLE {
+        app.degrade("definitions aren't built in, so hints only reveal letters. Build with --features definitions to have them");
+    }
+    current_word.adversary = adversary;
+    if let Some(how) = rules.no_repeats {
+        current_word.no_repeats = Some(how);
+        current_word.seen_answers = repeats::seen(cache_dir, &args.saves_dir(&app.data_dir), args.day)?;
+    }
+    let read_frequencies = || match args.offline {
+        true => obscurity::read_cached_frequencies(cache_dir),
+        false => obscurity::read_frequencies(cache_dir, client),
+    };
+    let frequencies = match rules.obscure_tags || rules.common_guesses.is_some() {
+        true => match read_frequencies() {
+            Ok(frequencies) => Some(frequencies),
+            Err(e) => {
+                app.degrade(format!("the word frequencies couldn't be read, so guesses aren't judged by how common they are ({})", e));
+                None
+            },
+        },
+        false => None,
+    };
+    if let (Some(percentile), Some(frequencies)) = (rules.common_guesses, &frequencies) {
+        let words = dictionary.iter().filter(|w| w.chars().count() == current_word.word_length).collect();
+        current_word.common_guesses = Some(obscurity::most_common(words, frequencies, percentile).into_iter().cloned().collect());
+    }
+    let frequencies = frequencies.filter(|_| rules.obscure_tags);
+
+    // Practice words, words from other sources and replays of finished days
+    // aren't saved.
+    let mut save_file = None;
+    if args.anagram || !args.offline && args.source() == source::SourceChoice::Nyt {
+        let file = progress::SaveFile::for_day(&args.saves_dir(&app.data_dir), args.day);
+        match file.load()? {
+            Some(saved) if saved.finished && args.replay => (),
+            Some(saved) if saved.finished => return Err(WordleError::AlreadyFinished(args.day)),
+            Some(saved) => {
+                current_word.resume(saved);
+                save_file = Some(file);
+                if !current_word.char_guesses.is_empty() {
+                    eprintln!("{}", tr!("resuming"));
+                }
+            },
+            None => save_file = Some(file),
+        }
+    }
+
+    let words = engine::Words { dictionary, answers };
+    let options = engine::Options { undo: args.offline, hint_shortlist: rules.hint_shortlist, hint_penalty: rules.hint_penalty };
+    let mut game = engine::Engine::new(current_word, save_file, options);
+    if let Some(path) = args.record.take() {
+        let transcript = transcript::Transcript::start(&args, &title, &header, &game, options, dictionary);
+        game.record(transcript::Recorder::new(path, transcript)?);
+    }
+
+    // Training plays a strong opener for you, of letters that are all
+    // different so each of them gets a color, and never the answer.
+    if args.training && game.current_word.char_guesses.is_empty() {
+        let frequencies = analysis::Frequencies::of(answers, game.current_word.word_length);
+        let opener = analysis::openers(&frequencies, answers).into_iter()
+            .map(|(word, ..)| word.clone())
+            .find(|word| word != game.current_word.correct_answer.reveal() && word.chars().collect::<HashSet<_>>().len() == word.len());
+        if let Some(opener) = opener {
+            game.current_word.aid(progress::Aid::Training);
+            if let engine::Step::Guessed(GuessOutcome::Continue) = game.play(engine::Move::Guess(opener.clone()), words)? && !app.json() {
+                eprintln!("{}", tr!("training-opener", guess = opener.to_uppercase()));
+            }
+        }
+    }
+
+    // Stopped before it's over, a saved game is saved once more for the time
+    // played since the last move, and how to pick it up is said.
+    let resume = tr!("game-saved", command = args.resume_command());
+    let _saving = game.is_saved().then(|| interrupt::saving(resume.clone()));
+    let left = |game: &engine::Engine| -> Result<()> {
+        if game.is_saved() && !game.is_finished() {
+            game.save()?;
+            eprintln!("{}", resume);
+        }
+        Ok(())
+    };
+
+    // A board started somewhere else is played first, see [`continuation`].
+    for guess in std::mem::take(&mut args.board) {
+        game.play(engine::Move::Guess(guess), words)?;
+    }
+
+    let scoring = args.score || app.config.score;
+    let score = |current_word: &CurrentWord| score::score(&current_word.char_guesses, current_word.won(), rules.tries.saturating_sub(current_word.hint_tries));
+    let show_score = |current_word: &CurrentWord| if scoring {
+        println!("{}", tr!("score", points = score(current_word)));
+    };
+
+    let notes = args.notes || app.config.notes;
+    if args.tui {
+        if io::stdin().is_terminal() && io::stdout().is_terminal() && !app.json() {
+            if rules.casual {
+                app.degrade("the terminal UI doesn't tell how many letters are shared with --casual");
+            }
+            if scoring {
+                app.degrade("the terminal UI doesn't keep score with --score");
+            }
+            if frequencies.is_some() {
+                app.degrade("the terminal UI doesn't tag obscure guesses with --obscure-tags");
+            }
+            if notes {
+                app.degrade("the terminal UI has no notes panel for --notes");
+            }
+            let options = tui::Options { timed: rules.timed, untimed: !rules.timer, masked: app.config.streamer };
+            let control = match &args.control_socket {
+                Some(path) => {
+                    let control = control::Control::bind(path)
+                        .map_err(|e| WordleError::Serve { address: path.display().to_string(), reason: e.to_string() })?;
+                    control.send(&Event::Start { title: &title, header: &header, word_length: game.current_word.word_length, tries: game.current_word.tries });
+                    for row in &game.current_word.char_guesses {
+                        control.send(&Event::guess(row, game.current_word.tries));
+                    }
+                    Some(control)
+                },
+                None => None,
+            };
+            let saves_dir = args.saves_dir(&app.data_dir);
+            // The message line has no room for a banner or more lines.
+            let end_screen = |current_word: &CurrentWord| {
+                Ok(app.config.end_screen.text(current_word, &saves_dir)?.map(|text| text.replace('\n', " ")))
+            };
+            let hooks = tui::Hooks { end_screen: &end_screen, control: control.as_ref() };
+            let finished = tui::play(&mut game, words, &header, &theme, &app.config.keys, options, hooks)?;
+            match finished {
+                true => share::print_share(&game.current_word, &title, rules.share),
+                false => left(&game)?,
+            }
+            return Ok((game.into_word(), title));
+        }
+        app.degrade("the terminal UI needs an interactive terminal and plain output, so plain mode was used");
+        if args.control_socket.is_some() {
+            app.degrade("the control socket is only for the terminal UI, so it wasn't opened");
+        }
+    }
+
+    // Screen readers get each guess in words instead of the board.
+    let accessible = args.accessible || app.config.accessible;
+    let show = |current_word: &CurrentWord| if !accessible {
+        current_word.display_word(rules.big_tiles, &theme);
+    };
+
+    // What a lost game reveals, which screen readers get without the panel.
+    let hold_reveal = args.hold_reveal || app.config.hold_reveal;
+    let reveal_delay = Duration::from_secs(args.reveal_delay.or(app.config.reveal_delay).unwrap_or(0));
+    let lost = |current_word: &CurrentWord| {
+        let answer = current_word.correct_answer.reveal();
+        match accessible {
+            true => streamer::reveal(app, &tr!("word-was", word = answer), hold_reveal, reveal_delay),
+            false => streamer::reveal(app, &review::panel(&current_word.char_guesses, answer), hold_reveal, reveal_delay),
+        }
+    };
+
+    if app.json() {
+        output::emit(&Event::Start {
+            title: &title,
+            header: &header,
+            word_length: game.current_word.word_length,
+            tries: game.current_word.tries,
+        });
+    } else if accessible {
+        println!("{}. {} letters, {} tries.", header, game.current_word.word_length, game.current_word.tries);
+    } else {
+        println!("{}", boxed(&header));
+    }
+    // Given or piped guesses are played without redrawing the board between them.
+    let interactive = args.guesses.is_empty() && io::stdin().is_terminal();
+    // The animations repaint the small tiles of the line typed on.
+    let animate = interactive && io::stdout().is_terminal() && terminal::escapes() && !app.json() && !rules.big_tiles && !args.no_animations && !accessible;
+    // Typing into the board's row needs the small tiles, drawn on a terminal.
+    let incremental = args.incremental && interactive && io::stdout().is_terminal() && terminal::escapes() && !app.json() && !rules.big_tiles && !accessible;
+    if args.incremental && !incremental {
+        app.degrade("typing into the board needs small tiles shown on a terminal, so guesses were typed below it");
+    }
+    // Unless --full-board, the board is shown once and then just the rows
+    // guesses add to it, or all of it again once one is taken back. Typing
+    // into the board needs all of it.
+    let full_board = args.full_board || app.config.full_board || incremental;
+    let mut shown_rows = None;
+    let mut show_new = |current_word: &CurrentWord| match shown_rows.replace(current_word.char_guesses.len()) {
+        // The animation already turned the line typed on into the row.
+        Some(shown) if !full_board && shown <= current_word.char_guesses.len() => if !animate && !accessible {
+            for i in shown..current_word.char_guesses.len() {
+                current_word.display_row(i, rules.big_tiles, &theme);
+            }
+        },
+        _ => show(current_word),
+    };
+    let muted = args.mute || app.config.mute || !interactive || app.json();
+    let sounds = sound::Sounds::new(muted, app.config.volume.unwrap_or(sound::DEFAULT_VOLUME));
+    let mut lines: Box<dyn Iterator<Item = io::Result<String>> + '_> = if !args.guesses.is_empty() {
+        Box::new(std::mem::take(&mut args.guesses).into_iter().map(Ok))
+    } else if interactive {
+        prompt::lines(dictionary, app.config.streamer)
+    } else {
+        Box::new(io::stdin().lines())
+    };
+    // With --show-remaining the counts go down with every guess, and
+    // --wordlebot rates the guesses after them.
+    let wordlebot = args.wordlebot || app.config.wordlebot;
+    let narrowing = |current_word: &CurrentWord| {
+        if args.show_remaining {
+            let remaining = strategy::remaining(&current_word.char_guesses, current_word.word_length, answers, dictionary);
+            if app.json() {
+                for (cgs, counts) in current_word.char_guesses.iter().zip(remaining.windows(2)) {
+                    let guess = cgs.iter().map(|cg| cg.ch).collect();
+                    output::emit(&Event::Narrowed { guess, possible: counts[0], left: counts[1] });
+                }
+            } else {
+                let counts: Vec<String> = remaining.iter().map(usize::to_string).collect();
+                println!("Words remaining: {}", counts.join(" → "));
+            }
+        }
+        if wordlebot {
+            wordlebot::show(app.json(), &current_word.char_guesses, answers, dictionary);
+        }
+    };
+    let timing = |current_word: &CurrentWord| {
+        if rules.timer
+            && let Some(line) = time_per_guess(&progress::think_times(&current_word.guess_times))
+        {
+            println!("{}", line);
+        }
+    };
+    let saves_dir = args.saves_dir(&app.data_dir);
+    let end_screen = |current_word: &CurrentWord| app.config.end_screen.screen(current_word, &saves_dir);
+    let mut legend = true;
+    loop {
+        if interactive && !app.json() && !accessible {
+            println!();
+            // The board goes last when typing into it, so the row being
+            // typed on is a known number of lines up.
+            if !incremental {
+                show_new(&game.current_word);
+            }
+            if !accessible && std::mem::take(&mut legend) {
+                println!("{}", theme.legend());
+            }
+            if rules.timed {
+                println!("{}", format_duration(game.current_word.elapsed()));
+            }
+            if rules.assist && !game.current_word.char_guesses.is_empty() {
+                game.current_word.aid(progress::Aid::Suggestions);
+                game.current_word.display_suggestions(answers, dictionary);
+            }
+            if (notes || args.training) && !game.current_word.char_guesses.is_empty() {
+                for line in notes::Notes::from_guesses(&game.current_word.char_guesses, game.current_word.word_length).lines() {
+                    println!("{}", line.dimmed());
+                }
+            }
+            match incremental {
+                true => show(&game.current_word),
+                false => println!(),
+            }
+        }
+
+        let next = match incremental {
+            true => incremental::read(&game.current_word, &theme, app.config.streamer),
+            false => lines.next(),
+        };
+        let Some(line) = next else {
+            match interactive {
+                true => left(&game)?,
+                false if !app.json() => show(&game.current_word),
+                false => (),
+            }
+            return Ok((game.into_word(), title));
+        };
+        let line = line.map_err(|source| WordleError::Terminal { context: "reading your guess", source })?;
+        if let Some(command) = input::command(&line) {
+            let reject = |reason: String| match app.json() {
+                true => output::emit(&Event::Invalid { guess: line.trim(), reason }),
+                false => println!("{}", reason),
+            };
+            match command {
+                Ok(input::Command::Quit) => {
+                    if interactive {
+                        left(&game)?;
+                    }
+                    return Ok((game.into_word(), title));
+                },
+                Ok(input::Command::Help) if app.json() => output::emit(&Event::Help { commands: &tr!("help") }),
+                Ok(input::Command::Help) => println!("{}", tr!("help")),
+                Ok(input::Command::Hint) => match game.play(engine::Move::Hint, words)? {
+                    engine::Step::Hinted(hint) => match app.json() {
+                        true => output::emit(&Event::Hint { message: hint.message(), hint, tries_left: game.current_word.tries }),
+                        false => println!("{}", hint.message()),
+                    },
+                    engine::Step::Refused(reason) => reject(reason),
+                    _ => unreachable!("a hint is given or refused"),
+                },
+                Ok(input::Command::Undo) => match game.play(engine::Move::Undo, words)? {
+                    engine::Step::Undone(cgs) => {
+                        let guess: String = cgs.iter().map(|cg| cg.ch).collect();
+                        match app.json() {
+                            true => output::emit(&Event::Undo { guess, tries_left: game.current_word.tries }),
+                            false => println!("{}", tr!("took-back", guess = guess)),
+                        }
+                    },
+                    engine::Step::Refused(reason) => reject(reason),
+                    _ => unreachable!("an undo is done or refused"),
+                },
+                // Someone at the terminal is asked first, a stray :giveup
+                // shouldn't spoil the word.
+                Ok(input::Command::GiveUp) if interactive && !app.json() && !streamer::confirm(&tr!("confirm-give-up")) => {
+                    println!("{}", tr!("kept-playing"));
+                },
+                Ok(input::Command::GiveUp) => {
+                    game.play(engine::Move::GiveUp, words)?;
+                    sounds.play(sound::Cue::Loss);
+                    narrowing(&game.current_word);
+                    if app.json() {
+                        output::emit(&Event::Over {
+                            won: false,
+                            answer: game.current_word.correct_answer.reveal(),
+                            guesses: game.current_word.char_guesses.len(),
+                            elapsed_ms: game.current_word.elapsed().as_millis(),
+                            think_ms: think_ms(&game.current_word),
+                            score: scoring.then(|| score(&game.current_word)),
+                            share: share::share_text(&game.current_word, &title, rules.share),
+                        });
+                    } else {
+                        lost(&game.current_word);
+                        show_score(&game.current_word);
+                        timing(&game.current_word);
+                        share::print_share(&game.current_word, &title, rules.share);
+                    }
+                    return Ok((game.into_word(), title));
+                },
+                Err(name) => reject(tr!("unknown-command", name = name)),
+            }
+            continue;
+        }
+        let guess = input::normalize(&line);
+
+        let engine::Step::Guessed(outcome) = game.play(engine::Move::Guess(guess.clone()), words)? else {
+            unreachable!("the game is over once it's finished");
+        };
+        if game.current_word.no_repeats == Some(repeats::Repeats::Warn)
+            && !matches!(outcome, GuessOutcome::InvalidGuess(_))
+            && !app.json()
+            && let Some(day) = game.current_word.repeated(&guess)
+        {
+            println!("{}", tr!("repeat-warning", day = day));
+        }
+        if let Some(frequencies) = &frequencies
+            && !matches!(outcome, GuessOutcome::InvalidGuess(_))
+            && !app.json()
+            && obscurity::is_obscure(&guess, frequencies)
+        {
+            println!("{}", tr!("obscure-guess", guess = guess).dimmed());
+        }
+        if app.json() {
+            match &outcome {
+                GuessOutcome::InvalidGuess(kind) => output::emit(&Event::Invalid { guess: &guess, reason: kind.message() }),
+                _ => output::emit(&Event::guess(game.current_word.char_guesses.last().unwrap(), game.current_word.tries)),
+            }
+            if let GuessOutcome::Win | GuessOutcome::NoTriesLeft = outcome {
+                narrowing(&game.current_word);
+                output::emit(&Event::Over {
+                    won: matches!(outcome, GuessOutcome::Win),
+                    answer: game.current_word.correct_answer.reveal(),
+                    guesses: game.current_word.char_guesses.len(),
+                    elapsed_ms: game.current_word.elapsed().as_millis(),
+                    think_ms: think_ms(&game.current_word),
+                    score: scoring.then(|| score(&game.current_word)),
+                    share: share::share_text(&game.current_word, &title, rules.share),
+                });
+                return Ok((game.into_word(), title));
+            }
+            continue;
+        }
+
+        if animate && !matches!(outcome, GuessOutcome::InvalidGuess(_)) {
+            let prefix = match incremental {
+                true => format!("{} ", game.current_word.row_number(game.current_word.char_guesses.len() - 1)),
+                false => String::new(),
+            };
+            animation::reveal(&prefix, game.current_word.char_guesses.last().unwrap(), &theme);
+        }
+        if accessible && !matches!(outcome, GuessOutcome::InvalidGuess(_)) {
+            let spoken = output::spoken(game.current_word.char_guesses.last().unwrap());
+            match outcome {
+                GuessOutcome::Continue => println!("{}. {} tries left.", spoken, game.current_word.tries),
+                _ => println!("{}.", spoken),
+            }
+        }
+        if rules.casual && let GuessOutcome::Continue = outcome {
+            game.current_word.aid(progress::Aid::Casual);
+            let shared = grade::shared_letters(&guess, game.current_word.correct_answer.reveal());
+            println!("Casual: {} {} shared with the word", shared, if shared == 1 { "letter" } else { "letters" });
+        }
+        if args.show_remaining && let GuessOutcome::Continue = outcome {
+            game.current_word.aid(progress::Aid::ShowRemaining);
+            let remaining = strategy::remaining(&game.current_word.char_guesses, game.current_word.word_length, answers, dictionary);
+            if let [.., before, after] = remaining[..] {
+                println!("{} → {} words remain", before, after);
+            }
+        }
+        if let GuessOutcome::Continue = outcome {
+            show_score(&game.current_word);
+        }
+        match outcome {
+            GuessOutcome::InvalidGuess(_) => sounds.play(sound::Cue::Invalid),
+            GuessOutcome::Win => sounds.play(sound::Cue::Win),
+            GuessOutcome::NoTriesLeft => sounds.play(sound::Cue::Loss),
+            GuessOutcome::Continue if game.current_word.char_guesses.last().unwrap().iter().any(|cg| cg.kind == CharGuessKind::WrongPlace) => {
+                sounds.play(sound::Cue::Present);
+            },
+            GuessOutcome::Continue | GuessOutcome::GameOver => (),
+        }
+        match outcome {
+            GuessOutcome::InvalidGuess(kind) => println!("{}", kind.message()),
+            GuessOutcome::Continue => (),
+            GuessOutcome::GameOver => unreachable!("the game is over once it's finished"),
+            GuessOutcome::Win if animate => {
+                // Typed into the board, the line above the row is the one before it.
+                if !incremental {
+                    animation::jump(game.current_word.char_guesses.last().unwrap(), &theme);
+                }
+                println!();
+                show_new(&game.current_word);
+                match end_screen(&game.current_word)? {
+                    Some(text) => println!("{}", text),
+                    None => println!("{}!", animation::rank(game.current_word.char_guesses.len())),
+                }
+                if rules.timer {
+                    println!("{}", tr!("solved-in", time = format_duration(game.current_word.elapsed())));
+                }
+                show_score(&game.current_word);
+                timing(&game.current_word);
+                narrowing(&game.current_word);
+                share::print_share(&game.current_word, &title, rules.share);
+                return Ok((game.into_word(), title));
+            },
+            GuessOutcome::Win => {
+                show_new(&game.current_word);
+                match end_screen(&game.current_word)? {
+                    Some(text) => println!("{}", text),
+                    None => println!("{}", tr!("won")),
+                }
+                if rules.timer {
+                    println!("{}", tr!("solved-in", time = format_duration(game.current_word.elapsed())));
+                }
+                show_score(&game.current_word);
+                timing(&game.current_word);
+                narrowing(&game.current_word);
+                share::print_share(&game.current_word, &title, rules.share);
+                return Ok((game.into_word(), title));
+            },
+            GuessOutcome::NoTriesLeft => {
+                show_new(&game.current_word);
+                // A configured one goes after the answer, so a {word} in it
+                // doesn't give away what --hold-reveal holds back.
+                let text = end_screen(&game.current_word)?;
+                if text.is_none() {
+                    println!("{}", tr!("lost"));
+                }
+                lost(&game.current_word);
+                if let Some(text) = text {
+                    println!("{}", text);
+                }
+                println!("{}", tr!("played-for", time = format_duration(game.current_word.elapsed())));
+                show_score(&game.current_word);
+                timing(&game.current_word);
+                narrowing(&game.current_word);
+                share::print_share(&game.current_word, &title, rules.share);
+                return Ok((game.into_word(), title));
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cli"))]
+mod tests {
+    use super::*;
+
+    fn dictionary(words: &[&str]) -> WordIndex {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn numbers_a_cached_puzzle_without_one_from_its_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let puzzle = Puzzle { solution: "crane".to_string().into(), ..Puzzle::default() };
+        assert_eq!(puzzle.number(day).as_deref(), Some("1,943"));
+        assert_eq!(puzzle.header(day), "Wordle #1,943 · 2026-10-14");
+    }
+
+    #[test]
+    fn describes_guesses_in_words() {
+        let mut current_word = CurrentWord::new("crane".to_string(), 6);
+        current_word.guess("trace".to_string(), &dictionary(&["trace"]));
+        assert_eq!(
+            output::spoken(&current_word.char_guesses[0]),
+            "T not in the word, R correct, A correct, C in the word but in the wrong spot, E correct",
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_guesses_without_using_a_try() {
+        let dictionary = dictionary(&["crane", "slate"]);
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        let outcomes = [
+            word.guess("cran".to_string(), &dictionary),
+            word.guess("cranes".to_string(), &dictionary),
+            word.guess("cr4ne".to_string(), &dictionary),
+            word.guess("zzzzz".to_string(), &dictionary),
+            word.guess("crème".to_string(), &dictionary),
+        ];
+        assert!(matches!(outcomes[0], GuessOutcome::InvalidGuess(InvalidGuessKind::WordTooShort(5))));
+        assert!(matches!(outcomes[1], GuessOutcome::InvalidGuess(InvalidGuessKind::WordTooLong(5))));
+        assert!(matches!(outcomes[2], GuessOutcome::InvalidGuess(InvalidGuessKind::WordContainsNonLetters(3, '4'))));
+        assert!(matches!(outcomes[3], GuessOutcome::InvalidGuess(InvalidGuessKind::WordNotInDictionary(_))));
+        // Counted in letters rather than bytes.
+        assert!(matches!(outcomes[4], GuessOutcome::InvalidGuess(InvalidGuessKind::WordContainsNonLetters(3, 'è'))));
+        assert_eq!(word.tries, 6);
+        assert!(word.char_guesses.is_empty());
+    }
+
+    #[test]
+    fn kids_can_guess_any_letters() {
+        let dictionary = dictionary(&["crane"]);
+        let mut word = CurrentWord::new("crane".to_string(), 8);
+        word.any_word = true;
+        assert!(matches!(word.guess("zzzzz".to_string(), &dictionary), GuessOutcome::Continue));
+        assert!(matches!(word.guess("cr4ne".to_string(), &dictionary), GuessOutcome::InvalidGuess(_)));
+    }
+
+    #[test]
+    fn counts_the_answers_each_guess_leaves() {
+        let dictionary = dictionary(&["crane", "crate", "slate", "pride", "abbey", "cranes"]);
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        word.guess("slate".to_string(), &dictionary);
+        word.guess("crate".to_string(), &dictionary);
+        assert_eq!(strategy::remaining(&word.char_guesses, 5, &dictionary, &dictionary), [5, 1, 1]);
+    }
+
+    #[test]
+    fn hard_mode_rejects_guesses_that_drop_revealed_letters() {
+        let dictionary = dictionary(&["crane", "slate", "pious"]);
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        word.hard_mode = true;
+        assert!(matches!(word.guess("slate".to_string(), &dictionary), GuessOutcome::Continue));
+        assert!(matches!(
+            word.guess("pious".to_string(), &dictionary),
+            GuessOutcome::InvalidGuess(InvalidGuessKind::WordBreaksHardMode(_)),
+        ));
+    }
+
+    #[test]
+    fn no_repeats_rejects_earlier_answers_but_today_s() {
+        let dictionary = dictionary(&["crane", "slate", "pious"]);
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        word.no_repeats = Some(repeats::Repeats::Reject);
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        word.seen_answers = HashMap::from([("slate".to_string(), day), ("crane".to_string(), day)]);
+        assert!(matches!(word.guess("slate".to_string(), &dictionary), GuessOutcome::InvalidGuess(InvalidGuessKind::WordWasAnswer(d)) if d == day));
+        assert!(matches!(word.guess("pious".to_string(), &dictionary), GuessOutcome::Continue));
+        assert!(matches!(word.guess("crane".to_string(), &dictionary), GuessOutcome::Win));
+    }
+
+    #[test]
+    fn common_guesses_turn_rare_words_away() {
+        let dictionary = dictionary(&["crane", "slate", "aalii"]);
+        let frequencies = HashMap::from([("crane".to_string(), 900), ("slate".to_string(), 40)]);
+        let words = dictionary.iter().collect();
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        word.common_guesses = Some(obscurity::most_common(words, &frequencies, 60).into_iter().cloned().collect());
+        assert!(matches!(word.guess("aalii".to_string(), &dictionary), GuessOutcome::InvalidGuess(InvalidGuessKind::WordTooObscure)));
+        assert!(matches!(word.guess("slate".to_string(), &dictionary), GuessOutcome::Continue));
+        assert!(obscurity::is_obscure("aalii", &frequencies) && !obscurity::is_obscure("slate", &frequencies));
+    }
+
+    #[test]
+    fn ends_on_a_win_or_the_last_try() {
+        let dictionary = dictionary(&["crane", "slate"]);
+        let mut won = CurrentWord::new("crane".to_string(), 6);
+        assert!(matches!(won.guess("slate".to_string(), &dictionary), GuessOutcome::Continue));
+        assert!(matches!(won.guess("crane".to_string(), &dictionary), GuessOutcome::Win));
+        assert!(won.is_over());
+
+        let mut lost = CurrentWord::new("crane".to_string(), 2);
+        assert!(matches!(lost.guess("slate".to_string(), &dictionary), GuessOutcome::Continue));
+        assert!(matches!(lost.guess("slate".to_string(), &dictionary), GuessOutcome::NoTriesLeft));
+        assert!(lost.is_over());
+    }
+
+    #[test]
+    fn guesses_after_the_end_change_nothing() {
+        let dictionary = dictionary(&["crane", "slate"]);
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        assert_eq!(word.current_guess(), None);
+        assert!(!word.is_solved() && !word.is_over());
+        word.guess("crane".to_string(), &dictionary);
+        assert!(matches!(word.guess("slate".to_string(), &dictionary), GuessOutcome::GameOver));
+        assert_eq!((word.tries, word.char_guesses.len(), word.current_guess().as_deref()), (5, 1, Some("crane")));
+
+        let mut lost = CurrentWord::new("crane".to_string(), 1);
+        lost.guess("slate".to_string(), &dictionary);
+        assert!(matches!(lost.guess("crane".to_string(), &dictionary), GuessOutcome::GameOver));
+        assert_eq!(lost.tries, 0);
+        assert!(matches!(CurrentWord::new("crane".to_string(), 0).guess("crane".to_string(), &dictionary), GuessOutcome::GameOver));
+    }
+
+    #[test]
+    fn undo_gives_back_the_try() {
+        let dictionary = dictionary(&["crane", "slate"]);
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        assert!(word.undo().is_none());
+        word.guess("slate".to_string(), &dictionary);
+        let undone = word.undo().unwrap();
+        assert_eq!(undone.iter().map(|cg| cg.ch).collect::<String>(), "slate");
+        assert_eq!(word.tries, 6);
+        assert!(word.char_guesses.is_empty());
+    }
+
+    #[test]
+    fn hints_get_more_telling() {
+        use hint::Hint;
+
+        let dictionary = dictionary(&["crane", "slate", "brine", "drape", "chace"]);
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        word.guess("slate".to_string(), &dictionary);
+        assert_eq!(word.hint(&dictionary, 2, 0), Ok(Hint::Letter('c')));
+        assert_eq!(word.hint(&dictionary, 2, 0), Ok(Hint::Position(0, 'c')));
+        assert_eq!(word.hint(&dictionary, 2, 0), Ok(Hint::Candidates(vec!["chace".to_string(), "crane".to_string()])));
+        assert_eq!(word.hint(&dictionary, 2, 0), Ok(Hint::Position(1, 'r')));
+        assert_eq!(word.hint(&dictionary, 2, 0), Ok(Hint::Position(3, 'n')));
+        assert!(word.hint(&dictionary, 2, 0).is_err());
+    }
+
+    #[test]
+    fn hints_can_cost_tries() {
+        let dictionary = dictionary(&["crane"]);
+        let mut word = CurrentWord::new("crane".to_string(), 3);
+        assert!(word.hint(&dictionary, 3, 2).is_ok());
+        assert_eq!(word.tries, 1);
+        assert!(word.hint(&dictionary, 3, 2).is_err());
+        assert_eq!(word.tries, 1);
+    }
+
+    #[test]
+    fn giving_up_ends_the_game() {
+        let mut word = CurrentWord::new("crane".to_string(), 6);
+        word.give_up();
+        assert!(word.is_over());
+        assert!(word.took.is_some());
+    }
+
+    #[test]
+    fn rejects_cached_words_that_cannot_be_played() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2025-01-01");
+        fs::write(&path, "crane\n").unwrap();
+        assert_eq!(read_word_cache(&path).unwrap().solution.reveal(), "crane");
+        fs::write(&path, "cr").unwrap();
+        assert!(matches!(read_word_cache(&path), Err(WordleError::CorruptWord(_))));
+        fs::write(&path, "{\"solution\":").unwrap();
+        assert!(matches!(read_word_cache(&path), Err(WordleError::CorruptSave(..))));
+    }
+}
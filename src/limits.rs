@@ -0,0 +1,155 @@
+//! Play limits set in the config file, for a family computer: how many
+//! practice games can be started a day, and hours with no playing at all.
+//! The practice games started are counted in the data dir, next to the
+//! saved games.
+
+use std::{fs, path::Path};
+
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::App,
+    cache,
+    cli::{ChallengeCommand, Command},
+    date_arg,
+    error::{Context, Result, WordleError},
+    i18n::tr,
+};
+
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// How many practice games can be started a day.
+    pub offline_games_per_day: Option<u32>,
+
+    /// When no game can be started.
+    pub no_play: Option<Hours>,
+}
+
+/// A span of the day like 21:00-07:00, which goes over midnight when it
+/// ends before it starts.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(try_from = "String")]
+pub struct Hours {
+    pub from: NaiveTime,
+    pub until: NaiveTime,
+}
+
+impl TryFrom<String> for Hours {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, String> {
+        let invalid = || format!("'{}' isn't a span of hours, e.g. 21:00-07:00", s);
+        let (from, until) = s.split_once('-').ok_or_else(invalid)?;
+        let time = |t: &str| NaiveTime::parse_from_str(t.trim(), "%H:%M").map_err(|_| invalid());
+        Ok(Hours { from: time(from)?, until: time(until)? })
+    }
+}
+
+impl Hours {
+    fn contains(self, time: NaiveTime) -> bool {
+        match self.from <= self.until {
+            true => self.from <= time && time < self.until,
+            false => time >= self.from || time < self.until,
+        }
+    }
+}
+
+/// The practice games started on `day`, as kept in [`cache::limits_path`].
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct Played {
+    day: Option<NaiveDate>,
+    offline_games: u32,
+}
+
+/// What a command plays, as far as the limits go.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Game {
+    Daily,
+    Practice,
+}
+
+fn game(command: &Command) -> Option<Game> {
+    match command {
+        Command::Play(args) if args.replay_transcript.is_some() => None,
+        Command::Play(args) | Command::Anagram(args) if args.offline => Some(Game::Practice),
+        Command::Continue(args) if args.advice => None,
+        Command::Blitz(_) | Command::Chain(_) | Command::Continue(_) | Command::Duel(_) => Some(Game::Practice),
+        Command::Cross(args) if args.seed.is_some() => Some(Game::Practice),
+        Command::Play(_)
+        | Command::Anagram(_)
+        | Command::Daily(_)
+        | Command::Archive(_)
+        | Command::Catchup(_)
+        | Command::Replay(_)
+        | Command::Challenge { command: ChallengeCommand::Play(_) }
+        | Command::Cross(_)
+        | Command::Host(_)
+        | Command::Join(_) => Some(Game::Daily),
+        _ => None,
+    }
+}
+
+/// Counts a practice game started on `today`, unless `most` were already.
+/// Returns whether it could be.
+fn count_practice(data_dir: &Path, today: NaiveDate, most: u32) -> Result<bool> {
+    let path = cache::limits_path(data_dir);
+    cache::with_lock(data_dir, || {
+        let mut played: Played = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| WordleError::CorruptSave(path.clone(), e))?,
+            Err(_) => Played::default(),
+        };
+        if played.day != Some(today) {
+            played = Played { day: Some(today), offline_games: 0 };
+        }
+        if played.offline_games >= most {
+            return Ok(false);
+        }
+        played.offline_games += 1;
+        let json = serde_json::to_string(&played).expect("the count serializes");
+        cache::write_atomic(&path, json.as_bytes()).context("writing the games played today")?;
+        Ok(true)
+    })
+}
+
+/// Refuses `command` when it would play past the limits, with a message for
+/// whoever is at the keyboard.
+pub fn enforce(app: &App, command: &Command) -> Result<()> {
+    let Some(game) = game(command) else { return Ok(()) };
+    let limits = app.config.limits;
+    let now = date_arg::now();
+    if let Some(hours) = limits.no_play && hours.contains(now.time()) {
+        let (from, until) = (hours.from.format("%H:%M").to_string(), hours.until.format("%H:%M").to_string());
+        return Err(WordleError::PlayLimit(tr!("limit-hours", from = from, until = until)));
+    }
+    if game == Game::Practice
+        && let Some(most) = limits.offline_games_per_day
+        && !count_practice(&app.data_dir, now.date(), most)?
+    {
+        return Err(WordleError::PlayLimit(tr!("limit-practice", games = most)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_to_the_limits() {
+        let hours = Hours::try_from("21:00-07:00".to_string()).unwrap();
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        assert!(hours.contains(at(23, 30)) && hours.contains(at(6, 59)));
+        assert!(!hours.contains(at(7, 0)) && !hours.contains(at(12, 0)));
+        assert!(Hours::try_from("12:00-13:00".to_string()).unwrap().contains(at(12, 30)));
+        assert!(Hours::try_from("after dinner".to_string()).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        assert!(count_practice(dir.path(), day(1), 2).unwrap());
+        assert!(count_practice(dir.path(), day(1), 2).unwrap());
+        assert!(!count_practice(dir.path(), day(1), 2).unwrap());
+        assert!(count_practice(dir.path(), day(2), 2).unwrap());
+    }
+}
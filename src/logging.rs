@@ -0,0 +1,50 @@
+//! What the program does behind the board, for telling why a fetch failed
+//! or a word got the colors it did. Logs go to stderr, or a file with
+//! `--log-file`, so the game on stdout stays the same whatever the verbosity.
+
+use std::{fs::OpenOptions, io, path::Path, sync::Mutex};
+
+use tracing::level_filters::LevelFilter;
+
+use crate::error::{Context, Result};
+
+/// The most detailed logs shown for `-v` counted `verbose` times, or only
+/// errors when `quiet`. Without either, warnings are shown too.
+pub fn level(verbose: u8, quiet: bool) -> LevelFilter {
+    match (quiet, verbose) {
+        (true, _) => LevelFilter::ERROR,
+        (false, 0) => LevelFilter::WARN,
+        (false, 1) => LevelFilter::INFO,
+        (false, 2) => LevelFilter::DEBUG,
+        (false, _) => LevelFilter::TRACE,
+    }
+}
+
+/// Starts writing the logs of `level` and above, appending them to `file` if
+/// one is given. A file gets the time of each line as well, since it's read
+/// after the fact.
+pub fn init(level: LevelFilter, file: Option<&Path>) -> Result<()> {
+    let builder = tracing_subscriber::fmt().with_max_level(level).with_target(false);
+    // Only the first run in a process gets to set it, which matters to tests.
+    let _ = match file {
+        Some(path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path).context("opening the log file")?;
+            builder.with_writer(Mutex::new(file)).try_init()
+        },
+        None => builder.without_time().with_writer(io::stderr).try_init(),
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_verbosity_up_from_warnings() {
+        assert_eq!(level(0, false), LevelFilter::WARN);
+        assert_eq!(level(2, false), LevelFilter::DEBUG);
+        assert_eq!(level(5, false), LevelFilter::TRACE);
+        assert_eq!(level(2, true), LevelFilter::ERROR);
+    }
+}
@@ -0,0 +1,71 @@
+//! The man pages, made from the command line definition so they can't fall
+//! behind it.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use clap::CommandFactory;
+use clap_mangen::Man;
+use roff::{Roff, bold, roman};
+
+use crate::cli::Cli;
+
+/// Files wordle-rs reads and writes, with where they are on Linux. The
+/// dirs follow the XDG variables, and the platform's own dirs elsewhere.
+static FILES: [(&str, &str); 3] = [
+    ("~/.config/wordle-rs/config.toml", "Defaults for the flags, see `config init`."),
+    ("~/.cache/wordle-rs/", "Downloaded words, the dictionary and the answer list. Safe to delete, it's downloaded again."),
+    ("~/.local/share/wordle-rs/", "Saved games and duels, which the stats are made from."),
+];
+
+static ENVIRONMENT: [(&str, &str); 5] = [
+    ("NO_COLOR", "Turns colors off when set, unless --color always is given."),
+    ("TERM", "Colors are off when it is dumb."),
+    ("HTTPS_PROXY, HTTP_PROXY, NO_PROXY", "The proxy to download through."),
+    ("XDG_CONFIG_HOME, XDG_CACHE_HOME, XDG_DATA_HOME", "Where the config file, the cache dir and the data dir are."),
+    ("COMPLETE", "Set by the completion scripts when they call back, see `completions`."),
+];
+
+fn section(roff: &mut Roff, title: &str, items: &[(&str, &str)]) {
+    roff.control("SH", [title]);
+    for (name, description) in items {
+        roff.control("TP", []).text([bold(*name)]).text([roman(*description)]);
+    }
+}
+
+/// Writes the page of wordle-rs itself, with the files and environment
+/// sections clap doesn't know about.
+fn render(man: &Man, w: &mut dyn Write) -> io::Result<()> {
+    man.render_title(w)?;
+    man.render_name_section(w)?;
+    man.render_synopsis_section(w)?;
+    man.render_description_section(w)?;
+    man.render_options_section(w)?;
+    man.render_subcommands_section(w)?;
+    man.render_extra_section(w)?;
+    let mut roff = Roff::new();
+    section(&mut roff, "FILES", &FILES);
+    section(&mut roff, "ENVIRONMENT", &ENVIRONMENT);
+    roff.to_writer(w)?;
+    man.render_version_section(w)
+}
+
+/// Prints the man page, or with `dir` writes it there along with a page for
+/// every subcommand, as wordle-rs.1, wordle-rs-play.1 and so on.
+pub fn run(dir: Option<&Path>) -> io::Result<()> {
+    let mut command = Cli::command().disable_help_subcommand(true);
+    command.build();
+    let Some(dir) = dir else {
+        return render(&Man::new(command), &mut io::stdout());
+    };
+    fs::create_dir_all(dir)?;
+    for subcommand in command.get_subcommands().filter(|s| !s.is_hide_set()) {
+        Man::new(subcommand.clone()).generate_to(dir)?;
+    }
+    let man = Man::new(command);
+    let mut file = fs::File::create(dir.join(man.get_filename()))?;
+    render(&man, &mut file)
+}
@@ -0,0 +1,61 @@
+//! Posting finished games to Mastodon, as a status of the share text.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::LazyClient,
+    error::{Context, Result},
+};
+
+/// The account to post as, from the config file. The token is an access
+/// token of an app of the account's that may write statuses.
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Mastodon {
+    /// The account's server, e.g. `https://mastodon.social`.
+    pub instance: String,
+    pub token: String,
+}
+
+/// Leaves the token out, so a config printed for debugging doesn't hand
+/// the account over.
+impl fmt::Debug for Mastodon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mastodon").field("instance", &self.instance).field("token", &"<hidden>").finish()
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct Status<'a> {
+    status: &'a str,
+}
+
+fn statuses_url(instance: &str) -> String {
+    format!("{}/api/v1/statuses", instance.trim_end_matches('/'))
+}
+
+/// Posts `text` as a status of `account`'s. Like the webhook's, it's only
+/// ever share text.
+pub fn post(client: &LazyClient, account: &Mastodon, text: &str) -> Result<()> {
+    let url = statuses_url(&account.instance);
+    client.send_with(|client| client.post(&url).bearer_auth(&account.token).json(&Status { status: text }), "posting the result to Mastodon")?
+        .error_for_status()
+        .context("posting the result to Mastodon")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posts_to_the_accounts_server() {
+        assert_eq!(statuses_url("https://mastodon.social/"), "https://mastodon.social/api/v1/statuses");
+        let json = serde_json::to_string(&Status { status: "Wordle 1,292 1/6" }).unwrap();
+        assert_eq!(json, r#"{"status":"Wordle 1,292 1/6"}"#);
+        let account = Mastodon { instance: "https://mastodon.social".to_string(), token: "s3cret".to_string() };
+        assert!(!format!("{:?}", account).contains("s3cret"));
+    }
+}
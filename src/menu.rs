@@ -0,0 +1,142 @@
+//! The menu shown when the program is started from a terminal without any
+//! arguments, for people who'd rather not learn the subcommands first.
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+    process,
+};
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::App,
+    cli::{Command, PlayArgs},
+    config,
+    error::{Context, Result, WordleError},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Item {
+    Today,
+    Random,
+    Archive,
+    Stats,
+    Settings,
+    Quit,
+}
+
+static ITEMS: [Item; 6] = [Item::Today, Item::Random, Item::Archive, Item::Stats, Item::Settings, Item::Quit];
+
+impl Item {
+    fn label(self) -> &'static str {
+        match self {
+            Item::Today => "Play today's wordle",
+            Item::Random => "Play a random word",
+            Item::Archive => "Play an earlier day",
+            Item::Stats => "Stats",
+            Item::Settings => "Settings",
+            Item::Quit => "Quit",
+        }
+    }
+}
+
+/// Whether to show the menu: nothing was asked for on the command line and
+/// someone is at the terminal to pick from it.
+pub fn wanted(app: &App) -> bool {
+    env::args_os().len() == 1 && io::stdin().is_terminal() && io::stdout().is_terminal() && !app.json()
+}
+
+fn terminal_error(source: io::Error) -> WordleError {
+    WordleError::Terminal { context: "showing the menu", source }
+}
+
+fn draw(f: &mut Frame, selected: usize) {
+    let mut lines = vec![Line::from("Wordle in Rust"), Line::from("")];
+    for (i, item) in ITEMS.iter().enumerate() {
+        let line = Line::from(format!("{} {}. {}", if i == selected { '>' } else { ' ' }, i + 1, item.label()));
+        lines.push(match i == selected {
+            true => line.style(Style::new().add_modifier(Modifier::REVERSED)),
+            false => line,
+        });
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("Pick with the arrows and enter, or by number. Esc quits."));
+    f.render_widget(Paragraph::new(lines), f.area());
+}
+
+/// The item picked, moving between them with the arrow keys or j and k.
+fn pick(terminal: &mut DefaultTerminal, mut selected: usize) -> io::Result<(Item, usize)> {
+    loop {
+        terminal.draw(|f| draw(f, selected))?;
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.checked_sub(1).unwrap_or(ITEMS.len() - 1),
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1) % ITEMS.len(),
+            KeyCode::Enter => return Ok((ITEMS[selected], selected)),
+            KeyCode::Char(ch) if let Some(n) = ch.to_digit(10) && (1..=ITEMS.len()).contains(&(n as usize)) => {
+                return Ok((ITEMS[n as usize - 1], n as usize - 1));
+            },
+            KeyCode::Esc | KeyCode::Char('q') => return Ok((Item::Quit, selected)),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok((Item::Quit, selected)),
+            _ => (),
+        }
+    }
+}
+
+/// Opens the config file in `$VISUAL` or `$EDITOR`, writing the commented
+/// one first if there's none yet. Without an editor it only says where the
+/// file is.
+fn settings(app: &App) -> Result<()> {
+    if !app.config_path.try_exists().context("checking for the config file")? {
+        config::init(&app.config_path)?;
+    }
+    let Some(editor) = env::var_os("VISUAL").or_else(|| env::var_os("EDITOR")) else {
+        println!("The settings are in {}. Set $EDITOR to edit them from here.", app.config_path.display());
+        return Ok(());
+    };
+    process::Command::new(editor).arg(&app.config_path).status().context("opening the config file")?;
+    println!("Changed settings take effect the next time wordle-rs starts.");
+    Ok(())
+}
+
+/// Shows the menu and runs what's picked from it, coming back to it after
+/// each until Quit.
+pub fn run(app: &App, args: PlayArgs) -> Result<()> {
+    let mut selected = 0;
+    loop {
+        let mut terminal = ratatui::init();
+        let picked = pick(&mut terminal, selected);
+        ratatui::restore();
+        let (item, at) = picked.map_err(terminal_error)?;
+        selected = at;
+
+        let result = match item {
+            Item::Today => crate::dispatch(app, Command::Play(args.clone())),
+            Item::Random => crate::dispatch(app, Command::Play(PlayArgs { offline: true, ..args.clone() })),
+            Item::Archive => crate::dispatch(app, Command::Archive(args.clone())),
+            Item::Stats => crate::dispatch(app, Command::Stats { command: None, daily_random: false, anagram: false, dashboard: false }),
+            Item::Settings => settings(app),
+            Item::Quit => return Ok(()),
+        };
+        // The other items can still be picked past a play limit.
+        match result {
+            Err(WordleError::PlayLimit(why)) => println!("{}", why),
+            result => result?,
+        }
+        println!();
+        println!("Press enter to go back to the menu.");
+        if io::stdin().lines().next().is_none() {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Dordle, Quordle and the like: every guess is played on several boards at
+//! once, each with its own answer.
+
+use std::io::{self, IsTerminal};
+
+use rand::{SeedableRng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::{
+    CurrentWord, GuessOutcome,
+    app::App,
+    cli::PlayArgs,
+    error::{Result, WordleError},
+    i18n::tr,
+    index::WordIndex,
+    input, pick_theme, prompt,
+    share::ShareLevel,
+    streamer, terminal,
+    theme::Theme,
+};
+
+/// How many boards are shown next to each other before wrapping, when the
+/// terminal's width isn't known.
+static BOARDS_PER_LINE: usize = 4;
+
+/// Columns between boards next to each other.
+static BOARD_GAP: usize = 4;
+
+/// How many boards `board` columns wide fit next to each other in `columns`.
+fn boards_per_line(columns: Option<usize>, board: usize) -> usize {
+    match columns {
+        Some(columns) => ((columns + BOARD_GAP) / (board + BOARD_GAP)).max(1),
+        None => BOARDS_PER_LINE,
+    }
+}
+
+/// Boards that share every guess. A board that is solved stops taking
+/// guesses, the others each use up a try.
+pub struct MultiGame {
+    pub boards: Vec<CurrentWord>,
+}
+
+impl MultiGame {
+    pub fn new(answers: Vec<String>, tries: u32) -> Self {
+        Self { boards: answers.into_iter().map(|answer| CurrentWord::new(answer, tries)).collect() }
+    }
+
+    /// Plays `guess` on every board that isn't solved yet. Every board checks
+    /// a guess the same way, so one that isn't allowed is caught by the first
+    /// before any board takes it.
+    pub fn guess(&mut self, guess: String, dictionary: &WordIndex) -> GuessOutcome {
+        let mut outcome = GuessOutcome::Continue;
+        for board in self.boards.iter_mut().filter(|b| !b.is_over()) {
+            match board.guess(guess.clone(), dictionary) {
+                invalid @ GuessOutcome::InvalidGuess(_) => return invalid,
+                GuessOutcome::NoTriesLeft => outcome = GuessOutcome::NoTriesLeft,
+                _ => (),
+            }
+        }
+        if self.boards.iter().all(CurrentWord::is_solved) {
+            GuessOutcome::Win
+        } else {
+            outcome
+        }
+    }
+
+    /// Shows the boards in as many lines of them as the terminal's width
+    /// takes.
+    fn display(&self, theme: &Theme) {
+        let board = self.boards[0].rows(theme).iter().map(|row| terminal::visible_width(row)).max().unwrap_or(0);
+        for boards in self.boards.chunks(boards_per_line(terminal::width(), board)) {
+            let rows: Vec<Vec<String>> = boards.iter().map(|b| b.rows(theme)).collect();
+            for i in 0..rows[0].len() {
+                println!("{}", rows.iter().map(|r| r[i].as_str()).collect::<Vec<_>>().join(&" ".repeat(BOARD_GAP)));
+            }
+            println!();
+        }
+    }
+
+    /// How many guesses each board took, X for the unsolved ones.
+    fn scores(&self) -> String {
+        self.boards.iter()
+            .map(|b| if b.is_solved() { b.char_guesses.len().to_string() } else { "X".to_string() })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Plays a practice game on `--boards` boards, with one more try per board
+/// than a single wordle unless told otherwise.
+pub fn play(app: &App, mut args: PlayArgs) -> Result<()> {
+    let theme = pick_theme(app, args.theme.as_deref())?;
+    let dictionary = app.dictionary(true)?;
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut words: Vec<&String> = app.answers()?
+        .unwrap_or(dictionary)
+        .iter()
+        .filter(|w| w.len() == args.length)
+        .collect();
+    words.sort();
+    let picked: Vec<String> = words.sample(&mut StdRng::seed_from_u64(seed), args.boards).map(|w| w.to_string()).collect();
+    if picked.len() < args.boards {
+        return Err(WordleError::EmptyDictionary);
+    }
+    let tries = args.max_guesses.or(app.config.max_guesses).unwrap_or(5 + args.boards as u32);
+    let mut game = MultiGame::new(picked, tries);
+    let title = format!("practice #{} x{}", seed, args.boards);
+    println!("Wordle {}", title);
+
+    let interactive = args.guesses.is_empty() && io::stdin().is_terminal();
+    let mut lines: Box<dyn Iterator<Item = io::Result<String>> + '_> = if !args.guesses.is_empty() {
+        Box::new(std::mem::take(&mut args.guesses).into_iter().map(Ok))
+    } else if interactive {
+        prompt::lines(dictionary, app.config.streamer)
+    } else {
+        Box::new(io::stdin().lines())
+    };
+    loop {
+        if interactive {
+            println!();
+            game.display(&theme);
+        }
+        let Some(line) = lines.next() else {
+            if !interactive {
+                game.display(&theme);
+            }
+            return Ok(());
+        };
+        let line = line.map_err(|source| WordleError::Terminal { context: "reading your guess", source })?;
+        let outcome = game.guess(input::normalize(&line), dictionary);
+        match outcome {
+            GuessOutcome::InvalidGuess(kind) => println!("{}", kind.message()),
+            GuessOutcome::Continue | GuessOutcome::GameOver => (),
+            GuessOutcome::Win | GuessOutcome::NoTriesLeft => {
+                game.display(&theme);
+                match outcome {
+                    GuessOutcome::Win => println!("{}", tr!("won")),
+                    _ => {
+                        println!("{}", tr!("lost"));
+                        let answers: Vec<&str> = game.boards.iter().map(|b| b.correct_answer.reveal()).collect();
+                        streamer::spoiler(app, &format!("The words were {}.", answers.join(", ")));
+                    },
+                }
+                if args.share.or(app.config.share) != Some(ShareLevel::None) {
+                    println!("\nWordle {} {}", title, game.scores());
+                }
+                return Ok(());
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_go_to_the_unsolved_boards() {
+        let dictionary: WordIndex = ["crane", "slate", "pride"].iter().map(|w| w.to_string()).collect();
+        let mut game = MultiGame::new(vec!["crane".to_string(), "slate".to_string()], 3);
+        assert!(matches!(game.guess("crane".to_string(), &dictionary), GuessOutcome::Continue));
+        assert!(matches!(game.guess("nope".to_string(), &dictionary), GuessOutcome::InvalidGuess(_)));
+        assert!(matches!(game.guess("slate".to_string(), &dictionary), GuessOutcome::Win));
+        assert_eq!(game.boards[0].char_guesses.len(), 1);
+        assert_eq!(game.boards[1].char_guesses.len(), 2);
+        assert_eq!(game.scores(), "1 2");
+    }
+
+    #[test]
+    fn wraps_boards_to_the_width() {
+        assert_eq!(boards_per_line(None, 15), 4);
+        assert_eq!(boards_per_line(Some(80), 15), 4);
+        assert_eq!(boards_per_line(Some(33), 15), 1);
+        assert_eq!(boards_per_line(Some(34), 15), 2);
+        assert_eq!(boards_per_line(Some(10), 15), 1);
+    }
+}
@@ -0,0 +1,272 @@
+//! Racing someone on another machine. The host picks the word and both
+//! players guess it at once, seeing only the colors of each other's rows as
+//! they come in.
+//!
+//! The wire protocol is one JSON [`Message`] per line over TCP. The joining
+//! side says hello first, the host answers with its own hello and the word,
+//! then both send a row after every guess and one last message once they're
+//! done. The word is sent in the clear, so this is for playing with friends.
+//!
+//! The host still keeps the other side honest: whoever doesn't say hello, or
+//! sends rows faster than `--max-guess-rate` or ones that can't be, is counted
+//! against `--max-invalid` and turned away for `--ban-secs` past it.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{IpAddr, TcpListener, TcpStream},
+    sync::mpsc,
+    thread,
+    time::Instant,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CurrentWord, GuessOutcome,
+    app::App,
+    cli::{HostArgs, JoinArgs},
+    error::{Result, WordleError},
+    index::WordIndex,
+    input, output, pick_theme, practice_word,
+    rate_limit::{Limiter, Verdict},
+    secret::SecretWord,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    Hello { name: String },
+    Start { answer: SecretWord, tries: u32 },
+    /// The colors of a guess as g/y/b letters, see [`output::pattern`].
+    Row { pattern: String },
+    Done { won: bool, guesses: usize, elapsed_ms: u64 },
+}
+
+impl Message {
+    /// What kind of message it is, for errors, which mustn't show a Start's
+    /// word.
+    fn kind(&self) -> &'static str {
+        match self {
+            Message::Hello { .. } => "Hello",
+            Message::Start { .. } => "Start",
+            Message::Row { .. } => "Row",
+            Message::Done { .. } => "Done",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Won,
+    Lost,
+    Draw,
+}
+
+/// Who was first to solve it, given how long each player took if they did.
+fn outcome(mine: Option<u64>, theirs: Option<u64>) -> Outcome {
+    match (mine, theirs) {
+        (Some(mine), Some(theirs)) if mine == theirs => Outcome::Draw,
+        (Some(mine), Some(theirs)) => if mine < theirs { Outcome::Won } else { Outcome::Lost },
+        (Some(_), None) => Outcome::Won,
+        (None, Some(_)) => Outcome::Lost,
+        (None, None) => Outcome::Draw,
+    }
+}
+
+fn peer_error(context: &'static str) -> impl Fn(io::Error) -> WordleError {
+    move |source| WordleError::Peer { context, source }
+}
+
+fn send(stream: &mut TcpStream, message: &Message) -> Result<()> {
+    let mut line = serde_json::to_string(message).expect("messages serialize");
+    line.push('\n');
+    stream.write_all(line.as_bytes()).map_err(peer_error("sending to the other player"))
+}
+
+fn receive(reader: &mut BufReader<TcpStream>) -> Result<Message> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).map_err(peer_error("hearing from the other player"))? == 0 {
+        return Err(WordleError::Peer {
+            context: "hearing from the other player",
+            source: io::ErrorKind::UnexpectedEof.into(),
+        });
+    }
+    // The line itself isn't told, it may have the word in it.
+    serde_json::from_str(&line).map_err(|_| WordleError::Protocol("a line that isn't a message".to_string()))
+}
+
+/// Waits on `--port` for one player to join, then races them.
+pub fn host(app: &App, args: HostArgs) -> Result<()> {
+    let dictionary = app.dictionary(true)?;
+    let answer = SecretWord::new(practice_word(app, dictionary, args.seed)?);
+    let tries = app.config.max_guesses.unwrap_or(6);
+
+    let listener = TcpListener::bind(("0.0.0.0", args.port)).map_err(peer_error("waiting for a player"))?;
+    eprintln!("Waiting for someone to join on port {}.", args.port);
+    let mut limiter = Limiter::new(args.limits);
+    let (mut stream, reader, address, other) = loop {
+        let (stream, address) = listener.accept().map_err(peer_error("waiting for a player"))?;
+        if limiter.check(address.ip(), Instant::now()) != Verdict::Allowed {
+            continue;
+        }
+        let mut reader = BufReader::new(stream.try_clone().map_err(peer_error("waiting for a player"))?);
+        match receive(&mut reader) {
+            Ok(Message::Hello { name }) => break (stream, reader, address, name),
+            _ => {
+                eprintln!("{} didn't say hello, waiting for someone else.", address);
+                limiter.invalid(address.ip(), Instant::now());
+            },
+        }
+    };
+    eprintln!("{} joined from {}.", other, address);
+    send(&mut stream, &Message::Hello { name: args.name.unwrap_or_else(|| "host".to_string()) })?;
+    send(&mut stream, &Message::Start { answer: answer.clone(), tries })?;
+    race(app, dictionary, stream, reader, other, answer, tries, Some((limiter, address.ip())))
+}
+
+/// Joins a game someone is hosting and races them.
+pub fn join(app: &App, args: JoinArgs) -> Result<()> {
+    let dictionary = app.dictionary(true)?;
+    let mut stream = TcpStream::connect(&args.address).map_err(peer_error("joining the game"))?;
+    let mut reader = BufReader::new(stream.try_clone().map_err(peer_error("joining the game"))?);
+    send(&mut stream, &Message::Hello { name: args.name.unwrap_or_else(|| "guest".to_string()) })?;
+    let (other, answer, tries) = match (receive(&mut reader)?, receive(&mut reader)?) {
+        (Message::Hello { name }, Message::Start { answer, tries }) => (name, answer, tries),
+        (hello, start) => return Err(WordleError::Protocol(format!("expected Hello then Start, got {} then {}", hello.kind(), start.kind()))),
+    };
+    eprintln!("Joined {}'s game.", other);
+    race(app, dictionary, stream, reader, other, answer, tries, None)
+}
+
+/// Whether `pattern` could be the colors of a guess at a word of `length`.
+fn possible_row(pattern: &str, length: usize) -> bool {
+    pattern.chars().count() == length && pattern.chars().all(|ch| matches!(ch, 'g' | 'y' | 'b'))
+}
+
+/// Races the other player, keeping them to `limits` when hosting.
+#[allow(clippy::too_many_arguments)]
+fn race(
+    app: &App,
+    dictionary: &WordIndex,
+    mut stream: TcpStream,
+    mut reader: BufReader<TcpStream>,
+    other: String,
+    answer: SecretWord,
+    tries: u32,
+    mut limits: Option<(Limiter<IpAddr>, IpAddr)>,
+) -> Result<()> {
+    let theme = pick_theme(app, None)?;
+    let length = answer.reveal().chars().count();
+    let mut current_word = CurrentWord::new(answer, tries);
+
+    // The other player's rows are shown as soon as they arrive, in between
+    // your own guesses.
+    let (sender, messages) = mpsc::channel();
+    let name = other.clone();
+    thread::spawn(move || {
+        let mut rows = 0;
+        loop {
+            let message = receive(&mut reader);
+            if let (Some((limiter, address)), Ok(Message::Row { pattern })) = (limits.as_mut(), &message) {
+                let now = Instant::now();
+                let allowed = rows < tries as usize && possible_row(pattern, length) && limiter.guess(*address, now) == Verdict::Allowed;
+                if allowed {
+                    rows += 1;
+                } else if limiter.invalid(*address, now) {
+                    // Like running out of input, being turned away is giving up.
+                    println!("{} sent too many rows that can't be and is out.", name);
+                    let _ = sender.send(Ok(Message::Done { won: false, guesses: rows, elapsed_ms: 0 }));
+                    break;
+                } else {
+                    continue;
+                }
+            }
+            match &message {
+                Ok(Message::Row { pattern }) => println!("{}: {}", name, pattern_squares(pattern)),
+                Ok(Message::Done { won: true, guesses, .. }) => println!("{} solved it in {}.", name, guesses),
+                Ok(Message::Done { won: false, .. }) => println!("{} is out of tries.", name),
+                _ => (),
+            }
+            let done = !matches!(message, Ok(Message::Row { .. }));
+            if sender.send(message).is_err() || done {
+                break;
+            }
+        }
+    });
+
+    println!("Racing {} on the same word, {} tries each.", other, tries);
+    let mut lines = io::stdin().lines();
+    let won = loop {
+        println!();
+        current_word.display_word(false, &theme);
+        println!();
+
+        // Running out of input counts as giving up.
+        let Some(line) = lines.next() else { break false };
+        let line = line.map_err(|source| WordleError::Terminal { context: "reading your guess", source })?;
+        let outcome = current_word.guess(input::normalize(&line), dictionary);
+        if let GuessOutcome::InvalidGuess(kind) = outcome {
+            println!("{}", kind.message());
+            continue;
+        }
+        let pattern = output::pattern(current_word.char_guesses.last().unwrap());
+        send(&mut stream, &Message::Row { pattern })?;
+        match outcome {
+            GuessOutcome::Win => break true,
+            GuessOutcome::NoTriesLeft => break false,
+            _ => (),
+        }
+    };
+    let elapsed_ms = current_word.elapsed().as_millis() as u64;
+    send(&mut stream, &Message::Done { won, guesses: current_word.char_guesses.len(), elapsed_ms })?;
+
+    let theirs = loop {
+        match messages.recv() {
+            Ok(Ok(Message::Done { won, elapsed_ms, .. })) => break won.then_some(elapsed_ms),
+            Ok(Ok(Message::Row { .. })) => (),
+            Ok(Ok(message)) => return Err(WordleError::Protocol(format!("expected Row or Done, got {}", message.kind()))),
+            Ok(Err(e)) => return Err(e),
+            Err(_) => return Err(WordleError::Protocol("the connection closed".to_string())),
+        }
+    };
+    match outcome(won.then_some(elapsed_ms), theirs) {
+        Outcome::Won => println!("You win! The word was {}.", current_word.correct_answer.reveal()),
+        Outcome::Lost => println!("{} wins! The word was {}.", other, current_word.correct_answer.reveal()),
+        Outcome::Draw => println!("It's a draw, the word was {}.", current_word.correct_answer.reveal()),
+    }
+    Ok(())
+}
+
+fn pattern_squares(pattern: &str) -> String {
+    pattern.chars().map(|ch| match ch {
+        'g' => '🟩',
+        'y' => '🟨',
+        _ => '⬛',
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_one_json_line_each() {
+        let line = serde_json::to_string(&Message::Row { pattern: "gybbb".to_string() }).unwrap();
+        assert_eq!(line, r#"{"type":"row","pattern":"gybbb"}"#);
+        assert_eq!(serde_json::from_str::<Message>(&line).unwrap(), Message::Row { pattern: "gybbb".to_string() });
+        let start = Message::Start { answer: SecretWord::new("crane".to_string()), tries: 6 };
+        assert_eq!(serde_json::to_string(&start).unwrap(), r#"{"type":"start","answer":"crane","tries":6}"#);
+        assert!(!format!("{:?}", start).contains("crane"));
+        assert!(possible_row("gybbb", 5));
+        assert!(!possible_row("gybb", 5) && !possible_row("gybbx", 5));
+    }
+
+    #[test]
+    fn the_faster_solve_wins() {
+        assert_eq!(outcome(Some(10), Some(20)), Outcome::Won);
+        assert_eq!(outcome(Some(30), Some(20)), Outcome::Lost);
+        assert_eq!(outcome(None, Some(20)), Outcome::Lost);
+        assert_eq!(outcome(Some(30), None), Outcome::Won);
+        assert_eq!(outcome(None, None), Outcome::Draw);
+    }
+}
@@ -0,0 +1,120 @@
+//! `--notes`: what the guesses so far tell about each spot and letter, the
+//! scratchpad players otherwise keep on paper. It's worked out from the
+//! colors alone, so it gives nothing away the board doesn't.
+
+use std::collections::BTreeMap;
+
+use crate::{CharGuess, CharGuessKind, i18n::tr};
+
+fn list(letters: impl Iterator<Item = String>) -> String {
+    letters.collect::<Vec<_>>().join(", ")
+}
+
+/// What is known about one spot of the word.
+#[derive(Debug, PartialEq)]
+pub enum Spot {
+    Is(char),
+    /// The letters that were tried there and aren't it, leaving out the
+    /// ones not in the word at all.
+    IsNot(Vec<char>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Notes {
+    pub spots: Vec<Spot>,
+    /// Letters in the word that aren't all placed yet, with how many of
+    /// them there are at least.
+    pub unplaced: Vec<(char, usize)>,
+    pub absent: Vec<char>,
+}
+
+impl Notes {
+    pub fn from_guesses(char_guesses: &[Vec<CharGuess>], word_length: usize) -> Self {
+        let mut greens: Vec<Option<char>> = vec![None; word_length];
+        let mut not_here: Vec<Vec<char>> = vec![vec![]; word_length];
+        let mut least: BTreeMap<char, usize> = BTreeMap::new();
+        let mut grayed: Vec<char> = vec![];
+        for row in char_guesses {
+            let mut counts: BTreeMap<char, usize> = BTreeMap::new();
+            for (i, cg) in row.iter().enumerate().take(word_length) {
+                match cg.kind {
+                    CharGuessKind::Correct => greens[i] = Some(cg.ch),
+                    _ if !not_here[i].contains(&cg.ch) => not_here[i].push(cg.ch),
+                    _ => (),
+                }
+                match cg.kind {
+                    CharGuessKind::NotInWord => grayed.push(cg.ch),
+                    _ => *counts.entry(cg.ch).or_default() += 1,
+                }
+            }
+            for (ch, count) in counts {
+                let at_least = least.entry(ch).or_default();
+                *at_least = (*at_least).max(count);
+            }
+        }
+
+        let mut absent: Vec<char> = grayed.into_iter().filter(|ch| !least.contains_key(ch)).collect();
+        absent.sort();
+        absent.dedup();
+        let spots = greens.into_iter().zip(not_here)
+            .map(|(green, mut tried)| match green {
+                Some(ch) => Spot::Is(ch),
+                None => {
+                    tried.retain(|ch| !absent.contains(ch));
+                    tried.sort();
+                    Spot::IsNot(tried)
+                },
+            })
+            .collect::<Vec<_>>();
+        let unplaced = least.into_iter()
+            .filter(|&(ch, count)| spots.iter().filter(|spot| **spot == Spot::Is(ch)).count() < count)
+            .collect();
+        Self { spots, unplaced, absent }
+    }
+
+    /// The notes as lines to print under the board.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![tr!("notes")];
+        for (i, spot) in self.spots.iter().enumerate() {
+            let note = match spot {
+                Spot::Is(ch) => ch.to_ascii_uppercase().to_string(),
+                Spot::IsNot(tried) if tried.is_empty() => "?".to_string(),
+                Spot::IsNot(tried) => tr!("notes-not", letters = list(tried.iter().map(char::to_string))),
+            };
+            lines.push(format!("  {}  {}", i + 1, note));
+        }
+        if !self.unplaced.is_empty() {
+            let unplaced = list(self.unplaced.iter().map(|&(ch, count)| match count {
+                1 => ch.to_string(),
+                count => format!("{} ×{}", ch, count),
+            }));
+            lines.push(format!("  {}", tr!("notes-unplaced", letters = unplaced)));
+        }
+        if !self.absent.is_empty() {
+            lines.push(format!("  {}", tr!("notes-absent", letters = list(self.absent.iter().map(char::to_string)))));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grade;
+
+    #[test]
+    fn works_out_the_spots_and_letters() {
+        let rows: Vec<Vec<CharGuess>> = ["arise", "terse"].iter()
+            .map(|guess| guess.chars().zip(grade::grade(guess, "eerie")).map(|(ch, kind)| CharGuess::new(ch, kind)).collect())
+            .collect();
+        let notes = Notes::from_guesses(&rows[..1], 5);
+        assert_eq!(notes.spots, [Spot::IsNot(vec![]), Spot::IsNot(vec!['r']), Spot::IsNot(vec!['i']), Spot::IsNot(vec![]), Spot::Is('e')]);
+        assert_eq!(notes.unplaced, [('i', 1), ('r', 1)]);
+        assert_eq!(notes.absent, ['a', 's']);
+
+        let notes = Notes::from_guesses(&rows, 5);
+        assert_eq!(notes.spots[..3], [Spot::IsNot(vec![]), Spot::Is('e'), Spot::Is('r')]);
+        assert_eq!(notes.unplaced, [('i', 1)]);
+        assert_eq!(notes.absent, ['a', 's', 't']);
+    }
+}
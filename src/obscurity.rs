@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{
+    app::LazyClient,
+    cache,
+    error::{Context, Result, WordleError},
+};
+
+static FREQUENCIES_URL: &str = "https://raw.githubusercontent.com/hermitdave/FrequencyWords/master/content/2018/en/en_50k.txt";
+
+/// Named obscurity thresholds, as percentiles of the dictionary by word frequency.
+static PRESETS: [(&str, u8); 4] = [("easy", 20), ("medium", 50), ("hard", 80), ("any", 100)];
+
+/// Parses an obscurity threshold, either a percentile from 1 to 100 or one of
+/// the named presets.
+pub fn parse_obscurity(s: &str) -> std::result::Result<u8, String> {
+    if let Some((_, percentile)) = PRESETS.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)) {
+        return Ok(*percentile);
+    }
+    match s.parse::<u8>() {
+        Ok(percentile @ 1..=100) => Ok(percentile),
+        _ => {
+            let names: Vec<&str> = PRESETS.iter().map(|(name, _)| *name).collect();
+            Err(format!("expected a percentile from 1 to 100 or one of {}", names.join(", ")))
+        },
+    }
+}
+
+/// Downloads the word frequency list into the cache.
+pub fn write_frequencies(cache_dir: &Path, client: &LazyClient) -> Result<()> {
+    let text = client.send(FREQUENCIES_URL, "downloading word frequencies")?
+        .error_for_status()
+        .and_then(|r| r.text())
+        .context("downloading word frequencies")?;
+    cache::write_atomic(&cache::frequencies_path(cache_dir), text.as_bytes()).context("writing word frequencies file")
+}
+
+/// Reads how often each word is used, from `<word> <count>` lines. The list
+/// is downloaded the first time it's needed.
+pub fn read_frequencies(cache_dir: &Path, client: &LazyClient) -> Result<HashMap<String, u64>> {
+    if !cache::frequencies_path(cache_dir).try_exists().context("opening word frequencies file")? {
+        write_frequencies(cache_dir, client)?;
+    }
+    read_cached_frequencies(cache_dir)
+}
+
+/// Like [`read_frequencies`], for `--offline`, which never downloads it.
+pub fn read_cached_frequencies(cache_dir: &Path) -> Result<HashMap<String, u64>> {
+    let f = match File::open(cache::frequencies_path(cache_dir)) {
+        Ok(f) => f,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(WordleError::NoFrequencies),
+        Err(e) => return Err(e).context("opening word frequencies file"),
+    };
+
+    let mut frequencies = HashMap::new();
+    for line in BufReader::new(f).lines() {
+        let line = line.context("reading word frequencies file")?;
+        if let Some((word, count)) = line.split_once(' ')
+            && let Ok(count) = count.trim().parse()
+        {
+            frequencies.insert(word.to_string(), count);
+        }
+    }
+    Ok(frequencies)
+}
+
+/// Whether `word` is too rare to be in the frequency list at all, which is
+/// tagged as obscure when it's guessed.
+pub fn is_obscure(word: &str, frequencies: &HashMap<String, u64>) -> bool {
+    !frequencies.contains_key(word)
+}
+
+/// The `percentile` most common percent of the words. Words missing from the
+/// frequency list count as the most obscure.
+pub fn most_common<'a>(
+    mut words: Vec<&'a String>,
+    frequencies: &HashMap<String, u64>,
+    percentile: u8,
+) -> Vec<&'a String> {
+    words.sort_by_key(|w| (std::cmp::Reverse(frequencies.get(*w).copied().unwrap_or(0)), *w));
+    let keep = (words.len() * percentile as usize).div_ceil(100);
+    words.truncate(keep.max(1));
+    words
+}
@@ -0,0 +1,208 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{CharGuess, CharGuessKind, error::WordleError, hint::Hint};
+
+/// How results are written to stdout.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// For people
+    #[default]
+    Plain,
+    /// One JSON object per line, for scripts
+    Json,
+}
+
+/// Something that happened, written as one line of JSON with its kind in
+/// the `event` field.
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    Start {
+        title: &'a str,
+        header: &'a str,
+        word_length: usize,
+        tries: u32,
+    },
+    Guess {
+        guess: String,
+        /// The colors in the same g/y/b letters that `solve` takes.
+        pattern: String,
+        tries_left: u32,
+    },
+    Invalid {
+        guess: &'a str,
+        reason: String,
+    },
+    Undo {
+        guess: String,
+        tries_left: u32,
+    },
+    Hint {
+        hint: Hint,
+        message: String,
+        tries_left: u32,
+    },
+    Help {
+        commands: &'a str,
+    },
+    Over {
+        won: bool,
+        answer: &'a str,
+        guesses: usize,
+        elapsed_ms: u128,
+        /// How long each guess took, empty if they weren't all timed.
+        think_ms: Vec<u128>,
+        /// The points scored, with --score.
+        score: Option<u32>,
+        share: Option<String>,
+    },
+    AnswerDifficulty {
+        word: &'a str,
+        /// From 1 to 10.
+        rating: u8,
+        harder_than: u8,
+        rare_letters: &'a [char],
+        repeated: &'a [char],
+        neighbors: &'a [String],
+        /// What the last `bot` run took on it, if it played and won it.
+        bot_guesses: Option<usize>,
+        bot_average: Option<f64>,
+    },
+    Definition {
+        word: &'a str,
+        part_of_speech: Option<&'a str>,
+        definition: &'a str,
+        origin: Option<&'a str>,
+    },
+    Narrowed {
+        guess: String,
+        possible: usize,
+        left: usize,
+    },
+    /// A guess gone over with --wordlebot.
+    Rated {
+        guess: &'a str,
+        skill: u32,
+        luck: Option<u32>,
+        before: usize,
+        after: usize,
+        best: Option<&'a str>,
+    },
+    Stats {
+        played: usize,
+        won: usize,
+        win_percentage: usize,
+        current_streak: usize,
+        max_streak: usize,
+        assisted: usize,
+        clean: usize,
+        training: usize,
+        distribution: &'a [usize],
+        fastest: Vec<Solve>,
+        median_think_ms: Option<u128>,
+        best_openers: Vec<Opener<'a>>,
+        strong_openers: Vec<StrongOpener<'a>>,
+        average_score: Option<f64>,
+    },
+    Standing {
+        rank: usize,
+        name: &'a str,
+        played: usize,
+        average: Option<f64>,
+        average_score: Option<f64>,
+        streak: usize,
+    },
+    Available {
+        day: NaiveDate,
+        published: bool,
+        number: Option<u32>,
+        editor: Option<&'a str>,
+    },
+    Status {
+        day: NaiveDate,
+        finished: bool,
+        won: bool,
+        guesses: usize,
+        elapsed_ms: u64,
+    },
+    Binding {
+        action: &'a str,
+        description: &'a str,
+        keys: Vec<String>,
+    },
+    Check {
+        name: &'a str,
+        /// pass, fail or skip.
+        status: &'a str,
+        detail: &'a str,
+        hint: Option<&'a str>,
+    },
+    Error {
+        message: String,
+        exit_code: i32,
+    },
+}
+
+#[derive(Serialize, Debug)]
+pub struct Solve {
+    pub day: NaiveDate,
+    pub elapsed_ms: u128,
+    pub guesses: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Opener<'a> {
+    pub word: &'a str,
+    pub played: usize,
+    pub win_percentage: usize,
+    pub average_guesses: Option<f64>,
+}
+
+/// An answer that makes a good opener, with the greens and yellows it's
+/// expected to get.
+#[derive(Serialize, Debug)]
+pub struct StrongOpener<'a> {
+    pub word: &'a str,
+    pub reveals: f64,
+}
+
+impl Event<'_> {
+    pub fn guess(cgs: &[CharGuess], tries_left: u32) -> Self {
+        Event::Guess {
+            guess: cgs.iter().map(|cg| cg.ch).collect(),
+            pattern: pattern(cgs),
+            tries_left,
+        }
+    }
+
+    pub fn error(e: &WordleError) -> Self {
+        Event::Error { message: e.to_string(), exit_code: e.exit_code() }
+    }
+}
+
+/// The colors of a guess as g/y/b letters, without the guessed letters.
+pub fn pattern(cgs: &[CharGuess]) -> String {
+    cgs.iter().map(|cg| match cg.kind {
+        CharGuessKind::NotInWord => 'b',
+        CharGuessKind::WrongPlace => 'y',
+        CharGuessKind::Correct => 'g',
+    }).collect()
+}
+
+/// A guess in words, for screen readers, e.g. "C correct, R in the word but
+/// in the wrong spot, A not in the word".
+pub fn spoken(cgs: &[CharGuess]) -> String {
+    cgs.iter().map(|cg| {
+        let kind = match cg.kind {
+            CharGuessKind::NotInWord => "not in the word",
+            CharGuessKind::WrongPlace => "in the word but in the wrong spot",
+            CharGuessKind::Correct => "correct",
+        };
+        format!("{} {}", cg.ch.to_ascii_uppercase(), kind)
+    }).collect::<Vec<_>>().join(", ")
+}
+
+pub fn emit(event: &Event) {
+    println!("{}", serde_json::to_string(event).expect("events always serialize"));
+}
@@ -0,0 +1,57 @@
+use crate::{
+    CharGuess,
+    index::WordIndex,
+    strategy::{self, Feedback},
+};
+
+/// What can be worked out from feedback where some guesses are unknown.
+pub struct Analysis<'a> {
+    /// The answers that every row can be explained for, sorted.
+    pub answers: Vec<&'a String>,
+    /// For each row with an unknown guess, the fewest and the most dictionary
+    /// words that could have been guessed there, over all possible answers.
+    pub bounds: Vec<(usize, usize)>,
+    /// How many answer and guess sequences produce exactly this feedback.
+    pub sequences: u128,
+}
+
+/// Finds the answers that fit the known rows, and for which every unknown
+/// row has at least one dictionary word that grades to its colors.
+pub fn analyze<'a>(rows: &[Feedback], dictionary: &'a WordIndex) -> Analysis<'a> {
+    let known: Vec<Vec<CharGuess>> = rows.iter().filter_map(|row| match row {
+        Feedback::Known(cgs) => Some(cgs.clone()),
+        Feedback::Unknown(_) => None,
+    }).collect();
+    let unknown: Vec<(usize, u32)> = rows.iter().filter_map(|row| match row {
+        Feedback::Known(_) => None,
+        Feedback::Unknown(kinds) => Some((kinds.len(), strategy::kinds_code(kinds.iter().copied()))),
+    }).collect();
+
+    let mut analysis = Analysis { answers: vec![], bounds: vec![(usize::MAX, 0); unknown.len()], sequences: 0 };
+    for answer in strategy::candidates(&known, dictionary) {
+        let mut fitting = vec![0; unknown.len()];
+        for guess in dictionary {
+            let code = strategy::pattern_code(guess.as_bytes(), answer.as_bytes());
+            for (n, &(len, pattern)) in fitting.iter_mut().zip(&unknown) {
+                if guess.len() == len && code == pattern {
+                    *n += 1;
+                }
+            }
+        }
+        if fitting.contains(&0) {
+            continue;
+        }
+
+        analysis.answers.push(answer);
+        analysis.sequences += fitting.iter().map(|&n| n as u128).product::<u128>();
+        for ((min, max), &n) in analysis.bounds.iter_mut().zip(&fitting) {
+            *min = (*min).min(n);
+            *max = (*max).max(n);
+        }
+    }
+
+    if analysis.answers.is_empty() {
+        analysis.bounds.fill((0, 0));
+    }
+    analysis
+}
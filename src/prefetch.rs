@@ -0,0 +1,298 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    time::Duration,
+};
+
+use chrono::{NaiveDate, SecondsFormat, Utc};
+use futures_util::{StreamExt, stream::FuturesUnordered};
+use indicatif::{ProgressBar, ProgressStyle};
+use tokio::time::Instant;
+use tracing::debug;
+
+use crate::{
+    Puzzle,
+    app::{self, App, FIRST_BACKOFF, MAX_RETRY_AFTER, RETRIES, USER_AGENT, retry_after},
+    cli::FetchArgs,
+    date_arg, dictionary, get_and_write_word,
+    error::{Context, Result, WordleError},
+    cache,
+    source::{self, puzzle_url},
+    write_word_cache,
+};
+
+/// How many days are downloaded at the same time.
+static CONCURRENCY: usize = 8;
+
+/// How many downloads are started a second when neither `--rate` nor the
+/// config file say, slow enough not to look like an attack.
+pub static DEFAULT_RATE: u32 = 4;
+
+/// How big the fetch log gets before it's moved to `fetch.log.1`, replacing
+/// the one there.
+static LOG_LIMIT: u64 = 64 * 1024;
+
+/// The day of the first wordle, before which NYT has nothing.
+pub static FIRST_DAY: NaiveDate = NaiveDate::from_ymd_opt(2021, 6, 19).expect("a valid date");
+
+/// Fetches the days `args` asks for from the NYT API at `base_url`, at most
+/// `rate` a second.
+pub fn fetch(cache_dir: &Path, timeout: Duration, base_url: &str, rate: u32, args: FetchArgs) -> Result<()> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("starting the download runtime")?
+        .block_on(fetch_days(cache_dir, timeout, base_url, rate, args))
+}
+
+async fn download(client: &reqwest::Client, base_url: &str, day: NaiveDate) -> Result<Puzzle> {
+    let context = "fetching the word from NYT";
+    let url = puzzle_url(base_url, day);
+    if let Some(path) = app::local_path(&url) {
+        return source::read_mirrored(&path, day);
+    }
+    debug!(%url, "sending a request");
+    let response = client.get(&url).send().await.context(context)?;
+    debug!(%url, status = %response.status(), "got a response");
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(WordleError::RateLimited { context, wait: retry_after(response.headers()) });
+    }
+    let body = response.text().await.context(context)?;
+    source::parse_response(&body, day)
+}
+
+async fn fetch_day(client: &reqwest::Client, base_url: &str, cache_dir: &Path, day: NaiveDate, start: Instant) -> (NaiveDate, Result<()>) {
+    tokio::time::sleep_until(start).await;
+    let mut backoff = FIRST_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        match download(client, base_url, day).await {
+            Err(WordleError::Network { .. }) if attempt < RETRIES => {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            },
+            Err(WordleError::RateLimited { wait, .. }) if attempt < RETRIES && wait <= MAX_RETRY_AFTER => {
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            },
+            Ok(puzzle) => return (day, write_word_cache(cache_dir, day, &puzzle)),
+            Err(e) => return (day, Err(e)),
+        }
+    }
+}
+
+/// The days `args` asks for, in the order they are fetched. Without `--to`
+/// the range is open, which is counted here as going up to today.
+struct Range {
+    first: NaiveDate,
+    last: Option<NaiveDate>,
+    backwards: bool,
+}
+
+impl Range {
+    fn new(args: &FetchArgs) -> Self {
+        let backwards = args.to.is_some_and(|to| to < args.from);
+        let first = if backwards { args.from } else { args.from.max(FIRST_DAY) };
+        Self { first, last: args.to.map(|to| to.max(FIRST_DAY)), backwards }
+    }
+
+    fn contains(&self, day: NaiveDate) -> bool {
+        match (self.backwards, self.last) {
+            (true, Some(last)) => day >= last,
+            (false, Some(last)) => day <= last,
+            (_, None) => true,
+        }
+    }
+
+    fn after(&self, day: NaiveDate) -> Option<NaiveDate> {
+        if self.backwards { day.pred_opt() } else { day.succ_opt() }
+    }
+
+    /// How many days are in the range, up to today if it is open.
+    fn len(&self) -> u64 {
+        let last = self.last.unwrap_or_else(date_arg::today);
+        let days = if self.backwards { self.first - last } else { last - self.first };
+        (days.num_days() + 1).max(0) as u64
+    }
+
+    fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let last = self.last.unwrap_or_else(date_arg::today);
+        let (from, to) = if self.backwards { (last, self.first) } else { (self.first, last) };
+        from.iter_days().take_while(move |&day| day <= to)
+    }
+}
+
+/// Reports how many days of `args` aren't cached yet, without downloading
+/// anything.
+pub fn dry_run(cache_dir: &Path, args: &FetchArgs) -> Result<()> {
+    let range = Range::new(args);
+    let mut missing = 0;
+    for day in range.days() {
+        if !cache::word_path(cache_dir, day).try_exists().context("checking for word cache")? {
+            missing += 1;
+        }
+    }
+    println!("{} days would be fetched, {} are already cached.", missing, range.len() - missing);
+    Ok(())
+}
+
+/// Fetches every day from `--from` to `--to`, walking backwards when `--to`
+/// is the earlier one. Without `--to` it keeps going until NYT has nothing
+/// published. Up to [`CONCURRENCY`] days are in flight at once, started at
+/// most `rate` a second. A day NYT answers with 429 is tried again after the
+/// pause it asks for. Every day is cached as soon as it's downloaded,
+/// so an interrupted fetch picks up where it stopped when run again.
+///
+/// On a terminal progress is shown as a bar, otherwise each day is reported
+/// as it finishes, so they may be printed out of order.
+async fn fetch_days(cache_dir: &Path, timeout: Duration, base_url: &str, rate: u32, args: FetchArgs) -> Result<()> {
+    let client = reqwest::Client::builder().timeout(timeout).user_agent(USER_AGENT).build().unwrap_or_default();
+    let range = Range::new(&args);
+    let period = Duration::from_secs(1) / rate;
+    let mut in_flight = FuturesUnordered::new();
+    let mut next_day = Some(range.first);
+    let mut next_start = Instant::now();
+    let mut first_unpublished: Option<NaiveDate> = None;
+    let mut failed = vec![];
+    let (mut downloaded, mut cached) = (0, 0);
+
+    if args.from < FIRST_DAY || args.to.is_some_and(|to| to < FIRST_DAY) {
+        eprintln!("The first wordle was on {}, nothing before it is fetched.", FIRST_DAY);
+    }
+    eprintln!("Wordle prefetch requested! Starting from {}.", range.first);
+    let bar = ProgressBar::new(range.len()).with_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} days, {eta} left").expect("the template is valid"),
+    );
+    let report = |line: String| if bar.is_hidden() { eprintln!("{}", line) };
+    loop {
+        while in_flight.len() < CONCURRENCY
+            && let Some(day) = next_day
+            && range.contains(day)
+            && (args.to.is_some() || first_unpublished.is_none())
+        {
+            next_day = range.after(day);
+            if cache::word_path(cache_dir, day).try_exists().context("checking for word cache")? {
+                cached += 1;
+                bar.inc(1);
+                report(format!("{}: Already cached ({} done)", day, downloaded + cached));
+                continue;
+            }
+            next_start = next_start.max(Instant::now());
+            in_flight.push(fetch_day(&client, base_url, cache_dir, day, next_start));
+            next_start += period;
+        }
+
+        let Some((day, result)) = in_flight.next().await else { break };
+        bar.inc(1);
+        match result {
+            Ok(()) => {
+                downloaded += 1;
+                report(format!("{}: Successfully fetched the word ({} done)", day, downloaded + cached));
+            },
+            Err(WordleError::NotPublished(_)) => {
+                if first_unpublished.is_none_or(|d| day < d) {
+                    first_unpublished = Some(day);
+                }
+            },
+            // One day that can't be reached shouldn't stop a long range.
+            Err(e @ (WordleError::Network { .. } | WordleError::RateLimited { .. })) if args.to.is_some() => {
+                bar.suspend(|| eprintln!("{}: {}", day, e));
+                failed.push(day);
+            },
+            Err(e) => {
+                bar.abandon();
+                return Err(e);
+            },
+        }
+    }
+    bar.finish_and_clear();
+
+    eprintln!("Prefetch done.");
+    if let Some(day) = first_unpublished {
+        eprintln!("No word from NYtimes for {} yet.", day);
+    }
+    if downloaded + cached == 0 {
+        eprintln!("No days were prefetched. This is rare. You probably set a custom --from too far into the future.");
+    } else {
+        eprintln!("{} days fetched, {} already cached.", downloaded, cached);
+    }
+    if !failed.is_empty() {
+        failed.sort();
+        eprintln!("{} days couldn't be downloaded, fetch them again to retry: {}", failed.len(), failed.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", "));
+    }
+    Ok(())
+}
+
+/// Appends `line` to the fetch log with the time, first moving the log out
+/// of the way once it's grown past [`LOG_LIMIT`].
+fn log(cache_dir: &Path, line: &str) -> Result<()> {
+    let path = cache::fetch_log_path(cache_dir);
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > LOG_LIMIT) {
+        fs::rename(&path, path.with_extension("log.1")).context("rotating the fetch log")?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path).context("opening the fetch log")?;
+    writeln!(file, "{} {}", Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true), line).context("writing the fetch log")
+}
+
+/// What [`if_stale`] did, one thing a line, or the reason it stopped.
+fn fetch_stale(app: &App, done: &mut Vec<String>) -> Result<()> {
+    let tomorrow = date_arg::today().succ_opt().expect("tomorrow is a day");
+    if !cache::word_path(&app.cache_dir, tomorrow).try_exists().context("checking for word cache")? {
+        match get_and_write_word(&app.cache_dir, tomorrow, &app.nyt()) {
+            Ok(_) => done.push(format!("Fetched the word of {}.", tomorrow)),
+            // It's fetched on the next run, once NYT has it.
+            Err(WordleError::NotPublished(_) | WordleError::Unavailable(..)) => (),
+            Err(e) => return Err(e),
+        }
+    }
+    if !cache::dictionary_path(&app.cache_dir).try_exists().context("checking for dictionary")? {
+        dictionary::write(&app.cache_dir, app.dictionary_url(), &app.client)?;
+        done.push("Downloaded the dictionary.".to_string());
+    }
+    match dictionary::refresh(&app.cache_dir, app.dictionary_url(), app.dictionary_ttl(), &app.client)? {
+        (0, 0) => (),
+        (added, removed) => done.push(format!("The dictionary was updated: {} words added, {} removed.", added, removed)),
+    }
+    Ok(())
+}
+
+/// `fetch --if-stale`, run by cron or a systemd timer: fetches tomorrow's
+/// word unless it's cached, and checks the dictionary for changes once it's
+/// due. What was done goes to the fetch log as one line, and to stderr
+/// unless `--quiet`. When there was nothing to do, nothing is written at all.
+pub fn if_stale(app: &App) -> Result<()> {
+    let mut done = vec![];
+    let result = fetch_stale(app, &mut done);
+    if let Err(e) = &result {
+        done.push(format!("Failed: {}", e));
+    }
+    if !done.is_empty() {
+        log(&app.cache_dir, &done.join(" "))?;
+    }
+    if !app.quiet && result.is_ok() {
+        for line in &done {
+            eprintln!("{}", line);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_the_fetch_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache::fetch_log_path(dir.path());
+        log(dir.path(), "Fetched the word of 2025-01-02.").unwrap();
+        assert!(fs::read_to_string(&path).unwrap().ends_with("Z Fetched the word of 2025-01-02.\n"));
+
+        fs::write(&path, vec![b'x'; LOG_LIMIT as usize + 1]).unwrap();
+        log(dir.path(), "Failed: no network").unwrap();
+        assert_eq!(fs::metadata(dir.path().join("fetch.log.1")).unwrap().len(), LOG_LIMIT + 1);
+        assert!(fs::read_to_string(&path).unwrap().ends_with("Z Failed: no network\n"));
+    }
+}
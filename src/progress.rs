@@ -0,0 +1,520 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CharGuess, CharGuessKind, CurrentWord, DATE_FORMAT, cache,
+    error::{Context, Result, WordleError},
+    hint::Hint,
+    storage::{self, Storage},
+};
+
+/// Help a game had beyond hints, recorded so stats and leaderboards can tell
+/// clean games apart. Undo and replays need no record: they're only allowed
+/// in games that aren't saved.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Aid {
+    /// The solver's suggestions were shown, with `--assist`.
+    Suggestions,
+    /// The answers left were counted, with `--show-remaining`.
+    ShowRemaining,
+    /// How many letters each guess shares with the answer was told, with `--casual`.
+    Casual,
+    /// The first row was played by the computer, with `--training`.
+    Training,
+}
+
+impl Aid {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aid::Suggestions => "suggestions",
+            Aid::ShowRemaining => "show_remaining",
+            Aid::Casual => "casual",
+            Aid::Training => "training",
+        }
+    }
+}
+
+/// A day's game as saved after every guess.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SavedGame {
+    #[serde(with = "packed")]
+    pub char_guesses: Vec<Vec<CharGuess>>,
+    pub finished: bool,
+    /// How long the game has been played, in milliseconds.
+    #[serde(default)]
+    pub elapsed_ms: u64,
+    /// What `:hint` revealed, in the order it was asked for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<Hint>,
+    /// Tries paid for the hints.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hint_tries: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aids: Vec<Aid>,
+    /// How far into the game each guess was made, in milliseconds. Empty for
+    /// games saved before guesses were timed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guess_ms: Vec<u64>,
+    /// When the game was last saved, which `sync` goes by when two devices
+    /// have the same day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved_at: Option<DateTime<Utc>>,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+/// Saves each guess as its word and a [`crate::strategy::pattern_code`] of
+/// the feedback, instead of a struct per letter. Games saved in the old,
+/// unpacked form still load.
+mod packed {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    use crate::{CharGuess, WORD_LENGTHS, strategy};
+
+    #[derive(Serialize, Deserialize)]
+    struct PackedRow(String, u32);
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Rows {
+        Packed(Vec<PackedRow>),
+        Full(Vec<Vec<CharGuess>>),
+    }
+
+    pub fn serialize<S: Serializer>(char_guesses: &[Vec<CharGuess>], serializer: S) -> Result<S::Ok, S::Error> {
+        char_guesses.iter()
+            .map(|cgs| PackedRow(
+                cgs.iter().map(|cg| cg.ch).collect(),
+                strategy::kinds_code(cgs.iter().map(|cg| cg.kind)),
+            ))
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    /// Every guess has to be a 4 to 8 letter word, or question marks for
+    /// a game imported from share text, for the game to load.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec<CharGuess>>, D::Error> {
+        let char_guesses: Vec<Vec<CharGuess>> = match Rows::deserialize(deserializer)? {
+            Rows::Full(char_guesses) => char_guesses,
+            Rows::Packed(rows) => rows.into_iter().map(|PackedRow(word, code)| {
+                let kinds = strategy::decode_kinds(code, word.chars().count());
+                word.chars().zip(kinds).map(|(ch, kind)| CharGuess::new(ch, kind)).collect()
+            }).collect(),
+        };
+        let word = |cgs: &Vec<CharGuess>| WORD_LENGTHS.contains(&cgs.len()) && cgs.iter().all(|cg| cg.ch.is_ascii_lowercase() || cg.ch == '?');
+        match char_guesses.iter().all(word) {
+            true => Ok(char_guesses),
+            false => Err(D::Error::custom("a saved guess isn't a 4 to 8 letter word")),
+        }
+    }
+}
+
+impl SavedGame {
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    /// Whether hints were used without paying tries for them, which stats
+    /// count apart.
+    pub fn assisted(&self) -> bool {
+        !self.hints.is_empty() && self.hint_tries == 0
+    }
+
+    /// Whether the first row was played by the computer.
+    pub fn training(&self) -> bool {
+        self.aids.contains(&Aid::Training)
+    }
+
+    /// Whether the game was played without hints or any other help.
+    pub fn clean(&self) -> bool {
+        self.hints.is_empty() && self.aids.is_empty()
+    }
+
+    /// How long was spent on each guess, or nothing if the guesses weren't
+    /// all timed.
+    pub fn think_times(&self) -> Vec<Duration> {
+        match self.guess_ms.len() == self.char_guesses.len() {
+            true => think_times(&self.guess_ms.iter().map(|&ms| Duration::from_millis(ms)).collect::<Vec<_>>()),
+            false => vec![],
+        }
+    }
+
+    /// Finished games count as won if the last guess was all correct, so a
+    /// game given up on early is lost.
+    pub fn won(&self) -> bool {
+        self.char_guesses
+            .last()
+            .is_some_and(|cgs| cgs.iter().all(|cg| cg.kind == CharGuessKind::Correct))
+    }
+}
+
+/// The time between one guess and the one before it, given how far into
+/// the game each was made.
+pub fn think_times(guess_times: &[Duration]) -> Vec<Duration> {
+    let mut before = Duration::ZERO;
+    guess_times.iter().map(|&at| at.saturating_sub(std::mem::replace(&mut before, at))).collect()
+}
+
+/// Every saved daily game, oldest first.
+pub fn saved_games(data_dir: &Path) -> Result<Vec<(NaiveDate, SavedGame)>> {
+    storage::open(data_dir)?.games()
+}
+
+/// The cached days that haven't been finished yet, oldest first.
+pub fn unfinished_cached_days(cache_dir: &Path, data_dir: &Path) -> Result<Vec<NaiveDate>> {
+    let storage = storage::open(data_dir)?;
+    let mut days = vec![];
+    for day in cache::cached_days(cache_dir)? {
+        if !storage.load(day)?.is_some_and(|game| game.finished) {
+            days.push(day);
+        }
+    }
+    Ok(days)
+}
+
+/// The latest cached day other than `asked` that isn't finished yet, to play
+/// when the asked day can't be downloaded.
+pub fn unfinished_cached_day(cache_dir: &Path, data_dir: &Path, asked: NaiveDate) -> Result<Option<NaiveDate>> {
+    Ok(unfinished_cached_days(cache_dir, data_dir)?.into_iter().rev().find(|&day| day != asked))
+}
+
+/// Where a day's game is saved, so it can be resumed after quitting.
+pub struct SaveFile {
+    data_dir: PathBuf,
+    day: NaiveDate,
+}
+
+impl SaveFile {
+    pub fn for_day(data_dir: &Path, day: NaiveDate) -> Self {
+        Self { data_dir: data_dir.to_path_buf(), day }
+    }
+
+    pub fn load(&self) -> Result<Option<SavedGame>> {
+        storage::open(&self.data_dir)?.load(self.day)
+    }
+
+    pub fn save(&self, current_word: &CurrentWord) -> Result<()> {
+        let game = SavedGame {
+            char_guesses: current_word.char_guesses.clone(),
+            finished: current_word.is_over(),
+            elapsed_ms: current_word.elapsed().as_millis() as u64,
+            hints: current_word.hints.clone(),
+            hint_tries: current_word.hint_tries,
+            aids: current_word.aids.clone(),
+            guess_ms: current_word.guess_times.iter().map(|t| t.as_millis() as u64).collect(),
+            saved_at: Some(Utc::now()),
+        };
+        storage::open(&self.data_dir)?.save(self.day, &game)
+    }
+}
+
+/// Saves games played elsewhere, leaving days that already have a game
+/// alone. Returns how many were saved.
+pub fn import(data_dir: &Path, games: Vec<(NaiveDate, SavedGame)>) -> Result<usize> {
+    let storage = storage::open(data_dir)?;
+    let mut imported = 0;
+    for (day, game) in games {
+        if storage.load(day)?.is_none() {
+            storage.save(day, &game)?;
+            imported += 1;
+        }
+    }
+    Ok(imported)
+}
+
+/// Makes the saved games take less space.
+pub fn compact(data_dir: &Path) -> Result<()> {
+    storage::open(data_dir)?.compact()
+}
+
+/// Fixes what would throw the stats off in the saved games, or only lists
+/// it if `dry_run`.
+pub fn rebuild(data_dir: &Path, today: NaiveDate, dry_run: bool) -> Result<Vec<Problem>> {
+    storage::open(data_dir)?.rebuild(today, dry_run)
+}
+
+/// The default storage: a JSON file a day in the data dir's results dir.
+pub struct JsonFiles {
+    data_dir: PathBuf,
+}
+
+impl JsonFiles {
+    pub fn new(data_dir: &Path) -> Self {
+        Self { data_dir: data_dir.to_path_buf() }
+    }
+
+    fn path(&self, day: NaiveDate) -> PathBuf {
+        cache::results_dir(&self.data_dir).join(format!("{}.json", day.format(DATE_FORMAT)))
+    }
+
+    fn read(path: &Path) -> Result<Option<SavedGame>> {
+        if !path.try_exists().context("checking for saved game")? {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(path).context("reading saved game")?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| WordleError::CorruptSave(path.to_path_buf(), e))
+    }
+
+    fn write(&self, day: NaiveDate, game: &SavedGame) -> Result<()> {
+        let path = self.path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).context("creating saved games dir")?;
+        }
+        let json = serde_json::to_string(game).map_err(|e| WordleError::CorruptSave(path.clone(), e))?;
+        cache::write_atomic(&path, json.as_bytes()).context("writing saved game")
+    }
+
+    /// The files of the results dir named by a day, with the day.
+    fn files(&self) -> Result<Vec<(NaiveDate, PathBuf)>> {
+        let dir = cache::results_dir(&self.data_dir);
+        if !dir.try_exists().context("checking for saved games")? {
+            return Ok(vec![]);
+        }
+        let mut files = vec![];
+        for entry in fs::read_dir(&dir).context("listing saved games")? {
+            let path = entry.context("listing saved games")?.path();
+            if let Some(day) = path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| NaiveDate::parse_from_str(stem, DATE_FORMAT).ok())
+                .filter(|_| path.extension().is_some_and(|ext| ext == "json"))
+            {
+                files.push((day, path));
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl Storage for JsonFiles {
+    fn load(&self, day: NaiveDate) -> Result<Option<SavedGame>> {
+        Self::read(&self.path(day))
+    }
+
+    fn save(&self, day: NaiveDate, game: &SavedGame) -> Result<()> {
+        cache::with_lock(&self.data_dir, || self.write(day, game))
+    }
+
+    fn games(&self) -> Result<Vec<(NaiveDate, SavedGame)>> {
+        let mut games = vec![];
+        for (day, path) in self.files()? {
+            if let Some(game) = Self::read(&path)? {
+                games.push((day, game));
+            }
+        }
+        games.sort_by_key(|(day, _)| *day);
+        Ok(games)
+    }
+
+    /// Rewrites every saved game in the packed form.
+    fn compact(&self) -> Result<()> {
+        cache::with_lock(&self.data_dir, || {
+            let (mut before, mut after) = (0, 0);
+            let games = self.games()?;
+            for (day, game) in &games {
+                let path = self.path(*day);
+                before += fs::metadata(&path).context("reading saved game")?.len();
+                self.write(*day, game)?;
+                after += fs::metadata(&path).context("reading saved game")?.len();
+            }
+            eprintln!("{} saved games compacted, {} bytes down to {}.", games.len(), before, after);
+            Ok(())
+        })
+    }
+
+    /// Goes over every file in the results dir: files that aren't games are
+    /// moved aside, and of several games for one day the one played furthest
+    /// is kept.
+    fn rebuild(&self, today: NaiveDate, dry_run: bool) -> Result<Vec<Problem>> {
+        cache::with_lock(&self.data_dir, || {
+            let mut problems = vec![];
+            let mut days: BTreeMap<NaiveDate, Vec<(PathBuf, SavedGame)>> = BTreeMap::new();
+            for (day, path) in self.files()? {
+                let json = fs::read_to_string(&path).context("reading saved game")?;
+                match serde_json::from_str(&json) {
+                    Ok(game) => days.entry(day).or_default().push((path, game)),
+                    Err(_) => {
+                        if !dry_run {
+                            let mut aside = path.clone().into_os_string();
+                            aside.push(".corrupt");
+                            fs::rename(&path, aside).context("moving a corrupt saved game aside")?;
+                        }
+                        problems.push(Problem::Corrupt(path));
+                    },
+                }
+            }
+
+            for (day, mut games) in days {
+                let canonical = self.path(day);
+                games.sort_by_key(|(path, game)| (Reverse(game.progress()), *path != canonical));
+                let mut games = games.into_iter();
+                let (path, mut game) = games.next().expect("each day has a game");
+                let mut fixes = game.repair(day);
+                let rewrite = !fixes.is_empty() || path != canonical;
+                for (duplicate, _) in games {
+                    if !dry_run && duplicate != canonical {
+                        fs::remove_file(&duplicate).context("removing a duplicate saved game")?;
+                    }
+                    fixes.push(Problem::Duplicate(day, duplicate));
+                }
+                if day > today {
+                    fixes.push(Problem::Future(day));
+                }
+                if !dry_run && rewrite {
+                    self.write(day, &game)?;
+                    if path != canonical {
+                        fs::remove_file(&path).context("removing a duplicate saved game")?;
+                    }
+                }
+                problems.extend(fixes);
+            }
+            Ok(problems)
+        })
+    }
+}
+
+/// Something wrong with the saved games that stats are tallied from.
+#[derive(Debug, PartialEq)]
+pub enum Problem {
+    /// The file couldn't be read as a game, and is moved aside to `.corrupt`
+    /// so the rest can be.
+    Corrupt(PathBuf),
+    /// Another file had a game for the same day, and the one played further
+    /// is kept.
+    Duplicate(NaiveDate, PathBuf),
+    /// Guesses came after the answer was found, and are dropped.
+    PastWin(NaiveDate, usize),
+    /// The answer was found but the game wasn't marked finished.
+    Unfinished(NaiveDate),
+    /// The guesses' times don't add up, and are dropped.
+    GuessTimes(NaiveDate),
+    /// The game is saved for a day that hasn't come yet, which only a clock
+    /// set wrong makes. It's left for you to remove.
+    Future(NaiveDate),
+}
+
+impl Problem {
+    pub fn fixable(&self) -> bool {
+        !matches!(self, Problem::Future(_))
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Corrupt(path) => write!(f, "{} isn't a saved game", path.display()),
+            Problem::Duplicate(day, path) => write!(f, "{} is a second game for {}", path.display(), day),
+            Problem::PastWin(day, extra) => write!(f, "{} has {} guesses after the answer was found", day, extra),
+            Problem::Unfinished(day) => write!(f, "{} was solved but not marked finished", day),
+            Problem::GuessTimes(day) => write!(f, "{} has guess times that don't add up", day),
+            Problem::Future(day) => write!(f, "{} hasn't come yet, so its game can't be right", day),
+        }
+    }
+}
+
+impl SavedGame {
+    /// Puts right what can't have happened in a game of `day`, giving what
+    /// was wrong.
+    pub fn repair(&mut self, day: NaiveDate) -> Vec<Problem> {
+        let mut problems = vec![];
+        let solved = self.char_guesses.iter().position(|row| row.iter().all(|cg| cg.kind == CharGuessKind::Correct));
+        if let Some(solved) = solved {
+            let extra = self.char_guesses.len() - solved - 1;
+            if extra > 0 {
+                self.char_guesses.truncate(solved + 1);
+                self.guess_ms.truncate(solved + 1);
+                problems.push(Problem::PastWin(day, extra));
+            }
+            if !self.finished {
+                self.finished = true;
+                problems.push(Problem::Unfinished(day));
+            }
+        }
+        let in_order = self.guess_ms.is_sorted() && self.guess_ms.last().is_none_or(|&last| last <= self.elapsed_ms);
+        if !self.guess_ms.is_empty() && (self.guess_ms.len() != self.char_guesses.len() || !in_order) {
+            self.guess_ms.clear();
+            problems.push(Problem::GuessTimes(day));
+        }
+        problems
+    }
+
+    /// How far the game got, to pick between games saved for the same day.
+    fn progress(&self) -> (bool, usize, u64) {
+        (self.finished, self.char_guesses.len(), self.elapsed_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn games_with_help_are_not_clean() {
+        let old: SavedGame = serde_json::from_str(r#"{"char_guesses":[["crane",242]],"finished":true}"#).unwrap();
+        assert!(old.clean());
+
+        let aided = SavedGame { aids: vec![Aid::ShowRemaining], ..SavedGame::default() };
+        let json = serde_json::to_string(&aided).unwrap();
+        assert!(json.contains(r#""aids":["show_remaining"]"#));
+        assert!(!serde_json::from_str::<SavedGame>(&json).unwrap().clean());
+    }
+
+    #[test]
+    fn rebuilding_fixes_what_would_throw_stats_off() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = cache::results_dir(dir.path());
+        fs::create_dir_all(&results).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        fs::write(results.join("2025-01-01.json"), r#"{"char_guesses":[["slate",0]],"finished":false}"#).unwrap();
+        fs::write(results.join("2025-1-1.json"), r#"{"char_guesses":[["slate",0],["crane",242]],"finished":true}"#).unwrap();
+        fs::write(results.join("2025-01-02.json"), r#"{"char_guesses":[["crane",242],["slate",0]],"finished":false,"guess_ms":[9,5]}"#).unwrap();
+        fs::write(results.join("2025-01-03.json"), "{").unwrap();
+
+        let problems = rebuild(dir.path(), day(2), false).unwrap();
+        assert_eq!(problems, [
+            Problem::Corrupt(results.join("2025-01-03.json")),
+            Problem::Duplicate(day(1), results.join("2025-01-01.json")),
+            Problem::PastWin(day(2), 1),
+            Problem::Unfinished(day(2)),
+            Problem::GuessTimes(day(2)),
+        ]);
+        let games = saved_games(dir.path()).unwrap();
+        assert_eq!(games.iter().map(|(day, game)| (*day, game.char_guesses.len(), game.won())).collect::<Vec<_>>(), [(day(1), 2, true), (day(2), 1, true)]);
+        assert!(results.join("2025-01-03.json.corrupt").exists() && !results.join("2025-1-1.json").exists());
+        assert!(rebuild(dir.path(), day(2), false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn saved_guesses_have_to_be_words() {
+        for json in [
+            r#"{"char_guesses":[["",0]],"finished":true}"#,
+            r#"{"char_guesses":[["abcdefghijklmnopqrstuvwxyz",4294967295]],"finished":true}"#,
+            r#"{"char_guesses":[["CRANE",242]],"finished":true}"#,
+            r#"{"char_guesses":[[{"ch":"é","kind":"Correct"}]],"finished":true}"#,
+        ] {
+            assert!(serde_json::from_str::<SavedGame>(json).is_err(), "{}", json);
+        }
+        assert!(serde_json::from_str::<SavedGame>(r#"{"char_guesses":[["?????",242]],"finished":true}"#).is_ok());
+    }
+
+    #[test]
+    fn times_each_guess_from_the_one_before() {
+        let timed: SavedGame = serde_json::from_str(r#"{"char_guesses":[["slate",0],["crane",242]],"finished":true,"guess_ms":[12000,114000]}"#).unwrap();
+        assert_eq!(timed.think_times(), [Duration::from_secs(12), Duration::from_secs(102)]);
+
+        let untimed: SavedGame = serde_json::from_str(r#"{"char_guesses":[["crane",242]],"finished":true}"#).unwrap();
+        assert!(untimed.think_times().is_empty());
+    }
+}
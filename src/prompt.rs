@@ -0,0 +1,127 @@
+//! Reading guesses on a terminal through a line editor, so arrows, Ctrl-U
+//! and the like work the same in every shell, earlier guesses are a press of
+//! up away and tab completes a word from the dictionary.
+
+use std::{borrow::Cow, io};
+
+use rustyline::{
+    Context, Editor, Helper,
+    completion::Completer,
+    error::ReadlineError,
+    highlight::{CmdKind, Highlighter},
+    hint::Hinter,
+    history::DefaultHistory,
+    validate::Validator,
+};
+
+use crate::index::WordIndex;
+
+/// The names `:` commands can be completed to.
+static COMMANDS: [&str; 5] = [":quit", ":giveup", ":hint", ":undo", ":help"];
+
+/// Completes a guess from the dictionary words it is the start of.
+struct Guesses<'a> {
+    dictionary: &'a WordIndex,
+    /// Whether what's typed shows as stars, for `--streamer`. Completing
+    /// would show the words, so it's off then too.
+    masked: bool,
+}
+
+impl Guesses<'_> {
+    fn candidates(&self, prefix: &str) -> Vec<String> {
+        if prefix.starts_with(':') {
+            return COMMANDS.iter().filter(|c| c.starts_with(prefix)).map(|c| c.to_string()).collect();
+        }
+        // Every word would be too many to be of use.
+        if prefix.is_empty() {
+            return vec![];
+        }
+        let mut words: Vec<String> = self.dictionary.iter().filter(|w| w.starts_with(prefix)).cloned().collect();
+        words.sort();
+        words
+    }
+}
+
+impl Completer for Guesses<'_> {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let typed = &line[..pos];
+        let start = typed.len() - typed.trim_start().len();
+        if self.masked {
+            return Ok((start, vec![]));
+        }
+        Ok((start, self.candidates(&typed[start..].to_lowercase())))
+    }
+}
+
+impl Hinter for Guesses<'_> {
+    type Hint = String;
+}
+
+impl Highlighter for Guesses<'_> {
+    fn highlight<'l>(&self, line: &'l str, _: usize) -> Cow<'l, str> {
+        match self.masked {
+            true => Cow::Owned("*".repeat(line.chars().count())),
+            false => Cow::Borrowed(line),
+        }
+    }
+
+    fn highlight_char(&self, _: &str, _: usize, _: CmdKind) -> bool {
+        self.masked
+    }
+}
+
+impl Validator for Guesses<'_> {}
+
+impl Helper for Guesses<'_> {}
+
+/// The lines typed at the prompt. Ctrl-D and Ctrl-C end them, which stops
+/// the game like running out of input does.
+struct Lines<'a> {
+    editor: Editor<Guesses<'a>, DefaultHistory>,
+}
+
+impl Iterator for Lines<'_> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.editor.readline("") {
+            Ok(line) => {
+                if !line.trim().is_empty() {
+                    let _ = self.editor.add_history_entry(line.as_str());
+                }
+                Some(Ok(line))
+            },
+            Err(ReadlineError::Eof | ReadlineError::Interrupted) => None,
+            Err(ReadlineError::Io(e)) => Some(Err(e)),
+            Err(e) => Some(Err(io::Error::other(e))),
+        }
+    }
+}
+
+/// Lines from the line editor, or straight from stdin when the terminal
+/// can't be edited on. `masked` hides what's typed until enter is pressed.
+pub fn lines(dictionary: &WordIndex, masked: bool) -> Box<dyn Iterator<Item = io::Result<String>> + '_> {
+    match Editor::new() {
+        Ok(mut editor) => {
+            editor.set_helper(Some(Guesses { dictionary, masked }));
+            Box::new(Lines { editor })
+        },
+        Err(_) => Box::new(io::stdin().lines()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_words_and_commands() {
+        let dictionary: WordIndex = ["crane", "crate", "slate"].map(String::from).into_iter().collect();
+        let guesses = Guesses { dictionary: &dictionary, masked: false };
+        assert_eq!(guesses.candidates("cra"), ["crane", "crate"]);
+        assert_eq!(guesses.candidates(":u"), [":undo"]);
+        assert!(guesses.candidates("").is_empty());
+    }
+}
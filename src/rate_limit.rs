@@ -0,0 +1,124 @@
+//! Keeping a client of `serve` or `host` from flooding it or trying words
+//! until one sticks: each may guess so many times a minute, and one that
+//! keeps sending what isn't a guess is turned away for a while.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::cli::LimitArgs;
+
+/// The window the guesses of `--max-guess-rate` are counted over.
+const WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct Client {
+    /// When the guesses of the last minute came in, oldest first.
+    recent: VecDeque<Instant>,
+    invalid: u32,
+    banned_until: Option<Instant>,
+}
+
+/// Whether a client may go on.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Allowed,
+    /// It's guessing too fast, and may again after this long.
+    TooFast(Duration),
+    /// It's turned away for this much longer.
+    Banned(Duration),
+}
+
+impl Verdict {
+    /// How long until the client may try again, if it may not now.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Verdict::Allowed => None,
+            Verdict::TooFast(wait) | Verdict::Banned(wait) => Some(*wait),
+        }
+    }
+}
+
+/// What each client, told apart by `K`, has been up to.
+pub struct Limiter<K> {
+    limits: LimitArgs,
+    clients: HashMap<K, Client>,
+}
+
+impl<K: Hash + Eq> Limiter<K> {
+    pub fn new(limits: LimitArgs) -> Self {
+        Limiter { limits, clients: HashMap::new() }
+    }
+
+    fn client(&mut self, key: K, now: Instant) -> &mut Client {
+        let client = self.clients.entry(key).or_default();
+        if client.banned_until.is_some_and(|until| until <= now) {
+            *client = Client::default();
+        }
+        while client.recent.front().is_some_and(|&at| now.duration_since(at) >= WINDOW) {
+            client.recent.pop_front();
+        }
+        client
+    }
+
+    /// Whether `key` is turned away at the moment.
+    pub fn check(&mut self, key: K, now: Instant) -> Verdict {
+        match self.client(key, now).banned_until {
+            Some(until) => Verdict::Banned(until - now),
+            None => Verdict::Allowed,
+        }
+    }
+
+    /// Counts a guess of `key`'s, if it may make one.
+    pub fn guess(&mut self, key: K, now: Instant) -> Verdict {
+        let per_minute = self.limits.max_guess_rate as usize;
+        let client = self.client(key, now);
+        if let Some(until) = client.banned_until {
+            return Verdict::Banned(until - now);
+        }
+        if client.recent.len() >= per_minute {
+            let oldest = *client.recent.front().expect("the limit is at least one");
+            return Verdict::TooFast(WINDOW - now.duration_since(oldest));
+        }
+        client.recent.push_back(now);
+        Verdict::Allowed
+    }
+
+    /// Counts something of `key`'s that wasn't a guess, turning it away once
+    /// there have been `--max-invalid` of them. Returns whether it was.
+    pub fn invalid(&mut self, key: K, now: Instant) -> bool {
+        let (max, ban) = (self.limits.max_invalid, Duration::from_secs(self.limits.ban_secs));
+        let client = self.client(key, now);
+        client.invalid += 1;
+        if client.invalid >= max && client.banned_until.is_none() {
+            client.banned_until = Some(now + ban);
+        }
+        client.banned_until.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slows_down_then_bans_a_client() {
+        let mut limiter = Limiter::new(LimitArgs { max_guess_rate: 2, max_invalid: 3, ban_secs: 60 });
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        assert_eq!(limiter.guess("a", at(0)), Verdict::Allowed);
+        assert_eq!(limiter.guess("a", at(10)), Verdict::Allowed);
+        assert_eq!(limiter.guess("a", at(20)), Verdict::TooFast(Duration::from_secs(40)));
+        assert_eq!(limiter.guess("b", at(20)), Verdict::Allowed);
+        assert_eq!(limiter.guess("a", at(60)), Verdict::Allowed);
+
+        assert!(!limiter.invalid("a", at(61)) && !limiter.invalid("a", at(62)));
+        assert!(limiter.invalid("a", at(63)));
+        assert_eq!(limiter.check("a", at(93)), Verdict::Banned(Duration::from_secs(30)));
+        assert_eq!(limiter.guess("b", at(93)), Verdict::Allowed);
+        assert_eq!(limiter.check("a", at(123)), Verdict::Allowed);
+        assert!(!limiter.invalid("a", at(124)));
+    }
+}
@@ -0,0 +1,50 @@
+//! `--no-repeats`: guessing the answer of a day you already played is
+//! pointed out, or not allowed, so openers and guesses keep changing.
+
+use std::{collections::HashMap, path::Path};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{cache, error::Result, progress, read_word_cache};
+
+#[derive(clap::ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Repeats {
+    /// Say that the guess was an answer, and take it anyway
+    Warn,
+    /// Take another guess instead
+    Reject,
+}
+
+/// The answers of the days before `day` that have a saved game in
+/// `saves_dir`, each with its day. They're looked up in the word cache, so
+/// days whose word isn't cached anymore are left out.
+pub fn seen(cache_dir: &Path, saves_dir: &Path, day: NaiveDate) -> Result<HashMap<String, NaiveDate>> {
+    let answers = progress::saved_games(saves_dir)?.into_iter()
+        .filter(|(played, _)| *played < day)
+        .filter_map(|(played, _)| Some((read_word_cache(&cache::word_path(cache_dir, played)).ok()?.solution.into_revealed(), played)))
+        .collect();
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::progress::SavedGame;
+
+    #[test]
+    fn finds_the_answers_of_earlier_days_played() {
+        let dir = tempfile::tempdir().unwrap();
+        cache::prepare(dir.path()).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        for (d, word) in [(1, "crane"), (2, "slate"), (3, "pious"), (4, "adieu")] {
+            fs::write(cache::word_path(dir.path(), day(d)), word).unwrap();
+        }
+        progress::import(dir.path(), [1, 3, 4].map(|d| (day(d), SavedGame::default())).into()).unwrap();
+        let seen = seen(dir.path(), dir.path(), day(4)).unwrap();
+        assert_eq!(seen, HashMap::from([("crane".to_string(), day(1)), ("pious".to_string(), day(3))]));
+    }
+}
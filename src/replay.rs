@@ -0,0 +1,72 @@
+//! Showing a finished game to someone else, a row at a time, the way it was
+//! played.
+
+use std::{
+    io::{self, IsTerminal},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    animation, boxed, cache,
+    app::App,
+    cli::ReplayArgs,
+    daily_random,
+    error::{Result, WordleError},
+    format_duration, pick_theme,
+    progress::SaveFile,
+    read_word_cache,
+};
+
+/// Parses `--delay`, a number of seconds that can't be negative.
+pub fn parse_delay(s: &str) -> std::result::Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds >= 0.0 => Ok(seconds),
+        _ => Err(format!("'{}' isn't a number of seconds", s)),
+    }
+}
+
+/// Plays back the saved game of `args.day`, turning each row over after
+/// `--delay` seconds or, with `--step`, when enter is pressed.
+pub fn run(app: &App, args: ReplayArgs) -> Result<()> {
+    let saves = daily_random::saves_dir(&app.data_dir, args.daily_random);
+    let game = match SaveFile::for_day(&saves, args.day).load()? {
+        Some(game) if game.finished => game,
+        game => return Err(WordleError::Unfinished(args.day, game.map_or(0, |g| g.char_guesses.len()))),
+    };
+    let theme = pick_theme(app, args.theme.as_deref())?;
+    let header = match read_word_cache(&cache::word_path(&app.cache_dir, args.day)) {
+        Ok(puzzle) if !args.daily_random => puzzle.header(args.day),
+        _ => format!("Wordle {}", args.day),
+    };
+
+    println!("{}", boxed(&header));
+    let animate = io::stdout().is_terminal();
+    let mut enter = io::stdin().lines();
+    for (i, cgs) in game.char_guesses.iter().enumerate() {
+        if i > 0 {
+            match args.step {
+                // Once the input runs out the rest is shown without waiting.
+                true => _ = enter.next(),
+                false => thread::sleep(Duration::from_secs_f64(args.delay)),
+            }
+        }
+        let letters: String = cgs.iter().map(|cg| cg.ch).collect();
+        if animate {
+            println!("{}", letters);
+            animation::reveal("", cgs, &theme);
+        } else {
+            println!("{}", cgs.iter().map(|cg| theme.paint(cg.kind, &cg.ch.to_string()).to_string()).collect::<String>());
+        }
+    }
+
+    let guesses = game.char_guesses.len();
+    match game.won() {
+        true => println!("Solved in {} guesses, taking {}.", guesses, format_duration(game.elapsed())),
+        false => println!("Not solved after {} guesses, playing for {}.", guesses, format_duration(game.elapsed())),
+    }
+    if !game.hints.is_empty() {
+        println!("{} hints were used.", game.hints.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Going over a lost game: the answer above each guess, with which letters
+//! were in the right spot and which of the answer's letters were never
+//! tried, so there's something to learn from it.
+
+use colored::Colorize;
+
+use crate::{CharGuess, CharGuessKind, i18n::tr};
+
+/// For each letter of `row`, whether it's the answer's letter there.
+pub fn marks(row: &[CharGuess]) -> Vec<bool> {
+    row.iter().map(|cg| cg.kind == CharGuessKind::Correct).collect()
+}
+
+/// The letters of `answer` that none of the guesses had, in its order and
+/// each once.
+pub fn untried(char_guesses: &[Vec<CharGuess>], answer: &str) -> Vec<char> {
+    let mut untried: Vec<char> = vec![];
+    for ch in answer.chars() {
+        let tried = char_guesses.iter().flatten().any(|cg| cg.ch == ch);
+        if !tried && !untried.contains(&ch) {
+            untried.push(ch);
+        }
+    }
+    untried
+}
+
+/// The panel shown after a loss, which gives the answer away. Letters that
+/// were never tried are underlined in the answer, and listed below for
+/// when there's no color.
+pub fn panel(char_guesses: &[Vec<CharGuess>], answer: &str) -> String {
+    let untried = untried(char_guesses, answer);
+    let mut lines = vec![tr!("word-was", word = answer)];
+    let letters: Vec<String> = answer.chars()
+        .map(|ch| match untried.contains(&ch) {
+            true => format!("{}", ch.to_string().bold().underline()),
+            false => ch.to_string(),
+        })
+        .collect();
+    lines.push(format!("  {}", letters.join("  ")));
+    for row in char_guesses {
+        let tiles: Vec<String> = row.iter().zip(marks(row))
+            .map(|(cg, right)| match right {
+                true => format!("{}{}", cg.ch, "✓".green()),
+                false => format!("{}{}", cg.ch, "✗".red()),
+            })
+            .collect();
+        lines.push(format!("  {}", tiles.join(" ")));
+    }
+    if !untried.is_empty() {
+        let letters: Vec<String> = untried.iter().map(char::to_string).collect();
+        lines.push(tr!("never-tried", letters = letters.join(", ")));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grade;
+
+    #[test]
+    fn marks_the_guesses_and_finds_untried_letters() {
+        let rows: Vec<Vec<CharGuess>> = ["slate", "trace"].iter()
+            .map(|guess| guess.chars().zip(grade::grade(guess, "crane")).map(|(ch, kind)| CharGuess::new(ch, kind)).collect())
+            .collect();
+        assert_eq!(marks(&rows[0]), [false, false, true, false, true]);
+        assert_eq!(untried(&rows, "crane"), ['n']);
+        assert_eq!(untried(&rows[..1], "eerie"), ['r', 'i']);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{cli::PlayArgs, config::Config, hint, repeats::Repeats, share::ShareLevel};
+
+/// The settings a game is played with.
+///
+/// Built in layers: the defaults, then the config file, then any preset asked
+/// for on the command line, then the other flags given there, so whatever
+/// was typed beats the config file.
+pub struct Rules {
+    pub tries: u32,
+    pub share: ShareLevel,
+    pub big_tiles: bool,
+    pub hard_mode: bool,
+    /// Whether guesses the feedback so far rules out are refused.
+    pub strict_logic: bool,
+    /// What becomes of guesses that were answers of days played before.
+    pub no_repeats: Option<Repeats>,
+    /// Whether rare guesses are tagged as obscure.
+    pub obscure_tags: bool,
+    /// How common a guess has to be to be taken, as a percentile of the
+    /// dictionary by word frequency.
+    pub common_guesses: Option<u8>,
+    pub assist: bool,
+    /// Whether guesses don't have to be in the dictionary.
+    pub any_word: bool,
+    /// Tries each hint costs. Free hints mark the game as assisted instead.
+    pub hint_penalty: u32,
+    /// How many words the shortlist hint names.
+    pub hint_shortlist: usize,
+    /// Whether the share text is copied to the clipboard once the game is over.
+    pub copy: bool,
+    /// Where to post the share text once the game is over.
+    pub webhook: Option<String>,
+    /// Whether practice answers are only common words fit for kids.
+    pub family_words: bool,
+    /// Whether each guess is followed by how many of its letters are in the
+    /// answer, with `--casual`.
+    pub casual: bool,
+    /// Whether how long the game took is told at the end.
+    pub timer: bool,
+    /// Whether a running timer shows while playing, with `--timed`.
+    pub timed: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self { tries: 6, share: ShareLevel::Full, big_tiles: false, hard_mode: false, strict_logic: false, no_repeats: None, obscure_tags: false, common_guesses: None, assist: false, any_word: false, hint_penalty: 0, hint_shortlist: hint::DEFAULT_SHORTLIST, copy: false, webhook: None, family_words: false, casual: false, timer: true, timed: false }
+    }
+}
+
+impl Rules {
+    pub fn from_args(config: &Config, args: &PlayArgs) -> Self {
+        let mut rules = Self::default();
+        if let Some(tries) = config.max_guesses {
+            rules.tries = tries;
+        }
+        if let Some(share) = config.share {
+            rules.share = share;
+        }
+        rules.hard_mode |= config.hard;
+        rules.strict_logic |= config.strict_logic;
+        rules.no_repeats = config.no_repeats.or(rules.no_repeats);
+        rules.obscure_tags |= config.obscure_tags;
+        rules.common_guesses = config.common_guesses.or(rules.common_guesses);
+        rules.assist |= config.assist;
+        rules.copy |= config.copy;
+        if let Some(penalty) = config.hint_penalty {
+            rules.hint_penalty = penalty;
+        }
+        if let Some(shortlist) = config.hint_shortlist {
+            rules.hint_shortlist = shortlist;
+        }
+
+        if args.simple {
+            rules.simple();
+        }
+        if args.kids {
+            rules.tries = 8;
+            rules.any_word = true;
+            rules.family_words = true;
+        }
+        if let Some(tries) = args.max_guesses {
+            rules.tries = tries;
+        }
+        if let Some(share) = args.share {
+            rules.share = share;
+        }
+        rules.hard_mode |= args.hard;
+        rules.strict_logic |= args.strict_logic;
+        rules.no_repeats = args.no_repeats.or(rules.no_repeats);
+        rules.obscure_tags |= args.obscure_tags;
+        rules.common_guesses = args.common_guesses.or(rules.common_guesses);
+        rules.assist |= args.assist;
+        rules.casual |= args.casual;
+        rules.copy |= args.copy;
+        if args.timed {
+            rules.timed = true;
+            rules.timer = true;
+        }
+        rules.webhook = args.post_webhook.clone().or_else(|| config.webhook.clone());
+        rules
+    }
+
+    /// Turns this into a gentler game for young kids, whatever the config
+    /// file says: 8 tries, the kids' common words as practice answers, the
+    /// casual clue after each guess, no hard rules, no timer, big tiles and
+    /// nothing shared.
+    fn simple(&mut self) {
+        self.tries = 8;
+        self.family_words = true;
+        self.casual = true;
+        self.hard_mode = false;
+        self.strict_logic = false;
+        self.timer = false;
+        self.timed = false;
+        self.big_tiles = true;
+        self.share = ShareLevel::None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::cli::Cli;
+
+    #[test]
+    fn presets_beat_the_config_file() {
+        let config = Config { max_guesses: Some(5), share: Some(ShareLevel::Full), hard: true, ..Config::default() };
+        let rules = |args: &[&str]| Rules::from_args(&config, &Cli::parse_from([&["wordle-rs"], args].concat()).play);
+        let simple = rules(&["--simple"]);
+        assert_eq!((simple.tries, simple.share, simple.hard_mode), (8, ShareLevel::None, false));
+        assert!(simple.family_words && simple.casual && simple.big_tiles && !simple.timer);
+        assert!(rules(&["--simple", "--hard"]).hard_mode);
+        assert_eq!(rules(&["--offline", "--kids"]).tries, 8);
+        assert_eq!(rules(&["--simple", "--max-guesses", "4"]).tries, 4);
+        assert_eq!(rules(&[]).tries, 5);
+    }
+}
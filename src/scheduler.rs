@@ -0,0 +1,235 @@
+//! `install-scheduler`: a systemd user timer on Linux, or a launchd agent on
+//! macOS, running `fetch --if-stale --quiet` every day so tomorrow's word is
+//! always there to play offline.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use chrono::{NaiveTime, Timelike};
+
+use crate::{
+    app::App,
+    cli::InstallSchedulerArgs,
+    config,
+    error::{Context, Result, WordleError},
+};
+
+/// What the systemd units are called, with `.service` and `.timer`.
+static UNIT: &str = "wordle-rs-fetch";
+
+/// The launchd label, and the name of its plist.
+static LABEL: &str = "io.github.emanueljg.wordle-rs.fetch";
+
+/// `--at` as an hour and a minute of the day, e.g. 04:30.
+pub fn parse_time(s: &str) -> std::result::Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(s, "%H:%M").map_err(|_| format!("'{}' isn't a time of day, e.g. 04:30", s))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Scheduler {
+    Systemd,
+    Launchd,
+}
+
+impl Scheduler {
+    fn here() -> Result<Self> {
+        match std::env::consts::OS {
+            "linux" => Ok(Scheduler::Systemd),
+            "macos" => Ok(Scheduler::Launchd),
+            os => Err(WordleError::NoScheduler(os)),
+        }
+    }
+
+    /// The files to write, with what goes in them.
+    fn files(self, command: &[String], at: NaiveTime) -> Result<Vec<(PathBuf, String)>> {
+        let missing = || WordleError::NoScheduler("a computer without a home dir");
+        Ok(match self {
+            Scheduler::Systemd => {
+                let dir = dirs::config_dir().ok_or_else(missing)?.join("systemd").join("user");
+                vec![
+                    (dir.join(format!("{}.service", UNIT)), service(command)),
+                    (dir.join(format!("{}.timer", UNIT)), timer(at)),
+                ]
+            },
+            Scheduler::Launchd => {
+                let dir = dirs::home_dir().ok_or_else(missing)?.join("Library").join("LaunchAgents");
+                vec![(dir.join(format!("{}.plist", LABEL)), plist(command, at))]
+            },
+        })
+    }
+
+    /// The commands that get it to pick up the written files, or forget them.
+    fn load(self, files: &[(PathBuf, String)], enable: bool) -> Vec<Vec<String>> {
+        let timer = format!("{}.timer", UNIT);
+        let plist = files[0].0.display().to_string();
+        let args: Vec<Vec<&str>> = match (self, enable) {
+            (Scheduler::Systemd, true) => vec![vec!["systemctl", "--user", "daemon-reload"], vec!["systemctl", "--user", "enable", "--now", &timer]],
+            (Scheduler::Systemd, false) => vec![vec!["systemctl", "--user", "disable", "--now", &timer], vec!["systemctl", "--user", "daemon-reload"]],
+            (Scheduler::Launchd, true) => vec![vec!["launchctl", "load", "-w", &plist]],
+            (Scheduler::Launchd, false) => vec![vec!["launchctl", "unload", "-w", &plist]],
+        };
+        args.into_iter().map(|args| args.into_iter().map(str::to_string).collect()).collect()
+    }
+}
+
+/// `word` quoted for an `ExecStart=` line, where `%` starts a specifier.
+fn systemd_quoted(word: &str) -> String {
+    format!("\"{}\"", word.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%"))
+}
+
+fn service(command: &[String]) -> String {
+    let exec = command.iter().map(|word| systemd_quoted(word)).collect::<Vec<_>>().join(" ");
+    format!(
+        "[Unit]\n\
+         Description=Fetch tomorrow's wordle ahead of time\n\
+         Wants=network-online.target\n\
+         After=network-online.target\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         ExecStart={}\n",
+        exec,
+    )
+}
+
+/// A run missed while the computer was off is made up when it's back on,
+/// and the time is spread a little so not everyone asks NYT at once.
+fn timer(at: NaiveTime) -> String {
+    format!(
+        "[Unit]\n\
+         Description=Fetch tomorrow's wordle ahead of time every day\n\
+         \n\
+         [Timer]\n\
+         OnCalendar=*-*-* {}\n\
+         Persistent=true\n\
+         RandomizedDelaySec=15min\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n",
+        at.format("%H:%M:00"),
+    )
+}
+
+fn xml_escaped(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn plist(command: &[String], at: NaiveTime) -> String {
+    let arguments: String = command.iter().map(|word| format!("        <string>{}</string>\n", xml_escaped(word))).collect();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n\
+         <dict>\n\
+         \x20   <key>Label</key>\n\
+         \x20   <string>{}</string>\n\
+         \x20   <key>ProgramArguments</key>\n\
+         \x20   <array>\n\
+         {}\
+         \x20   </array>\n\
+         \x20   <key>StartCalendarInterval</key>\n\
+         \x20   <dict>\n\
+         \x20       <key>Hour</key>\n\
+         \x20       <integer>{}</integer>\n\
+         \x20       <key>Minute</key>\n\
+         \x20       <integer>{}</integer>\n\
+         \x20   </dict>\n\
+         </dict>\n\
+         </plist>\n",
+        LABEL,
+        arguments,
+        at.hour(),
+        at.minute(),
+    )
+}
+
+/// The quiet prefetch, run with this wordle-rs and the cache dir and config
+/// file it uses now.
+fn command(app: &App) -> Result<Vec<String>> {
+    let exe = std::env::current_exe().context("finding the wordle-rs program")?;
+    let mut command = vec![exe.display().to_string(), "--cache-dir".to_string(), app.cache_dir.display().to_string()];
+    if app.config_path != config::default_path() {
+        command.extend(["--config".to_string(), app.config_path.display().to_string()]);
+    }
+    command.extend(["fetch", "--if-stale", "--quiet"].map(str::to_string));
+    Ok(command)
+}
+
+/// Runs `commands`, telling what's left to run by hand when one of them
+/// fails. Returns whether they all ran.
+fn run_all(commands: &[Vec<String>]) -> bool {
+    for (i, args) in commands.iter().enumerate() {
+        let ran = process::Command::new(&args[0]).args(&args[1..]).status();
+        if !ran.is_ok_and(|status| status.success()) {
+            let left: Vec<String> = commands[i..].iter().map(|args| format!("  {}", args.join(" "))).collect();
+            eprintln!("Running `{}` failed, run this yourself to finish:\n{}", args.join(" "), left.join("\n"));
+            return false;
+        }
+    }
+    true
+}
+
+fn remove(files: &[(PathBuf, String)], scheduler: Scheduler) -> Result<()> {
+    if !files.iter().any(|(path, _)| path.exists()) {
+        eprintln!("The prefetch wasn't scheduled.");
+        return Ok(());
+    }
+    run_all(&scheduler.load(files, false));
+    for (path, _) in files {
+        if path.exists() {
+            fs::remove_file(path).context("removing the scheduler file")?;
+        }
+    }
+    eprintln!("The daily prefetch was removed.");
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("creating the scheduler dir")?;
+    }
+    fs::write(path, contents).context("writing the scheduler file")?;
+    eprintln!("Wrote {}", path.display());
+    Ok(())
+}
+
+pub fn run(app: &App, args: InstallSchedulerArgs) -> Result<()> {
+    let scheduler = Scheduler::here()?;
+    let files = scheduler.files(&command(app)?, args.at)?;
+    if args.remove {
+        return remove(&files, scheduler);
+    }
+    if args.print {
+        for (path, contents) in &files {
+            println!("# {}\n{}", path.display(), contents);
+        }
+        return Ok(());
+    }
+    for (path, contents) in &files {
+        write(path, contents)?;
+    }
+    if run_all(&scheduler.load(&files, true)) {
+        eprintln!("Tomorrow's word will be fetched every day at {}, see fetch.log in the cache dir for how it went.", args.at.format("%H:%M"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_the_prefetch_into_units() {
+        let command = ["/opt/my wordle/wordle-rs", "--cache-dir", "/tmp/100%", "fetch", "--if-stale", "--quiet"].map(str::to_string);
+        let at = parse_time("04:30").unwrap();
+        assert!(service(&command).contains(r#"ExecStart="/opt/my wordle/wordle-rs" "--cache-dir" "/tmp/100%%" "fetch""#));
+        assert!(timer(at).contains("OnCalendar=*-*-* 04:30:00\n"));
+        let plist = plist(&command, at);
+        assert!(plist.contains("        <string>/opt/my wordle/wordle-rs</string>\n"));
+        assert!(plist.contains("<key>Hour</key>\n        <integer>4</integer>"));
+        assert!(parse_time("4pm").is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! Points for a game, for a finer measure than the guesses it took: every
+//! green and yellow tile of every row scores, and solving it scores more the
+//! more tries were left.
+
+use crate::{CharGuess, CharGuessKind};
+
+/// What a green tile scores.
+static GREEN: u32 = 3;
+
+/// What a yellow tile scores.
+static YELLOW: u32 = 1;
+
+/// What solving it scores for each try left, counting the one it was solved in.
+static SOLVED_BONUS: u32 = 5;
+
+/// The points of one row's tiles.
+pub fn tiles(row: &[CharGuess]) -> u32 {
+    row.iter()
+        .map(|cg| match cg.kind {
+            CharGuessKind::Correct => GREEN,
+            CharGuessKind::WrongPlace => YELLOW,
+            CharGuessKind::NotInWord => 0,
+        })
+        .sum()
+}
+
+/// The points of a game that had `tries` for guesses, with the bonus if it
+/// was `won`.
+pub fn score(char_guesses: &[Vec<CharGuess>], won: bool, tries: u32) -> u32 {
+    let tiles: u32 = char_guesses.iter().map(|row| tiles(row)).sum();
+    let left = tries.saturating_sub(char_guesses.len() as u32) + 1;
+    match won {
+        true => tiles + SOLVED_BONUS * left,
+        false => tiles,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grade;
+
+    fn rows(guesses: &[&str], answer: &str) -> Vec<Vec<CharGuess>> {
+        guesses.iter()
+            .map(|guess| guess.chars().zip(grade::grade(guess, answer)).map(|(ch, kind)| CharGuess::new(ch, kind)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn scores_tiles_and_the_tries_left() {
+        // Against crane, trace has 3 greens and a yellow.
+        let won = rows(&["trace", "crane"], "crane");
+        assert_eq!(tiles(&won[0]), 3 * GREEN + YELLOW);
+        assert_eq!(score(&won, true, 6), 10 + 15 + 5 * SOLVED_BONUS);
+        assert_eq!(score(&rows(&["crane"], "crane"), true, 6), 15 + 6 * SOLVED_BONUS);
+        assert_eq!(score(&rows(&["pious"], "crane"), false, 6), 0);
+    }
+}
@@ -0,0 +1,107 @@
+//! `--encrypt-cache`: the cached words are written scrambled with a key
+//! made on this computer, so listing or `cat`-ing the cache dir doesn't
+//! spoil the days `fetch` got ahead of time. The key sits next to the words,
+//! so it keeps answers from being read by accident, not from anyone set on
+//! reading them.
+//!
+//! Scrambled files are read back whether or not the flag is given, and with
+//! it the plain ones are scrambled as they're read.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+/// What a scrambled file starts with, to tell it from a plain one.
+static MAGIC: &str = "wordle-rs scrambled 1\n";
+
+static KEY_LEN: usize = 32;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Makes cached words be written scrambled.
+pub fn set(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Where the key of the word at `path` is kept.
+fn key_path(path: &Path) -> PathBuf {
+    path.with_file_name(".key")
+}
+
+/// The key of the word at `path`, made the first time if `create`.
+fn key(path: &Path, create: bool) -> io::Result<Option<Vec<u8>>> {
+    let key_path = key_path(path);
+    match fs::read(&key_path) {
+        Ok(key) if key.len() == KEY_LEN => return Ok(Some(key)),
+        Ok(_) => return Ok(None),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        Err(_) if !create => return Ok(None),
+        Err(_) => (),
+    }
+    let key: [u8; KEY_LEN] = rand::random();
+    let mut options = fs::File::options();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    match options.open(&key_path) {
+        Ok(mut file) => io::Write::write_all(&mut file, &key).map(|_| Some(key.to_vec())),
+        // Another wordle-rs made one first.
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(fs::read(&key_path).ok().filter(|key| key.len() == KEY_LEN)),
+        Err(e) => Err(e),
+    }
+}
+
+fn mix(bytes: &[u8], key: &[u8]) -> Vec<u8> {
+    bytes.iter().enumerate().map(|(i, b)| b ^ key[i % key.len()] ^ (i / key.len()) as u8).collect()
+}
+
+/// Whether `contents` are a plain word that should be scrambled.
+pub fn is_plain(contents: &str) -> bool {
+    enabled() && !contents.starts_with(MAGIC)
+}
+
+/// What to write to `path` for `contents`: them scrambled if that's on,
+/// else as they are.
+pub fn encode(path: &Path, contents: &[u8]) -> io::Result<Vec<u8>> {
+    if !enabled() {
+        return Ok(contents.to_vec());
+    }
+    let key = key(path, true)?.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "the cache key isn't a key"))?;
+    Ok(format!("{}{}", MAGIC, STANDARD.encode(mix(contents, &key))).into_bytes())
+}
+
+/// The contents of the file at `path` unscrambled, or `None` if they're
+/// scrambled with a key that's gone.
+pub fn decode(path: &Path, contents: String) -> Option<String> {
+    let Some(scrambled) = contents.strip_prefix(MAGIC) else { return Some(contents) };
+    let key = key(path, false).ok()??;
+    let bytes = STANDARD.decode(scrambled.trim()).ok()?;
+    String::from_utf8(mix(&bytes, &key)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrambles_words_only_its_key_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2025-01-01");
+        let key = key(&path, true).unwrap().unwrap();
+        let scrambled = format!("{}{}", MAGIC, STANDARD.encode(mix(br#"{"solution":"crane"}"#, &key)));
+        assert!(!scrambled.contains("crane"));
+        assert_eq!(decode(&path, scrambled.clone()).as_deref(), Some(r#"{"solution":"crane"}"#));
+        assert_eq!(decode(&path, "crane".to_string()).as_deref(), Some("crane"));
+
+        fs::remove_file(key_path(&path)).unwrap();
+        assert_eq!(decode(&path, scrambled), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Keeping the answer out of logs, panics and `{:?}`, so a crash report or a
+//! `-vv` run pasted into an issue doesn't spoil the day for anyone.
+
+use std::{fmt, panic};
+
+use serde::{Deserialize, Serialize};
+
+/// The answer of a game. It shows as `<hidden>` wherever it's formatted, and
+/// the word itself only comes out through [`SecretWord::reveal`], at the
+/// points where the player gets to see it or it's graded against.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct SecretWord(String);
+
+impl SecretWord {
+    pub fn new(word: String) -> Self {
+        Self(word)
+    }
+
+    pub fn reveal(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_revealed(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for SecretWord {
+    fn from(word: String) -> Self {
+        Self(word)
+    }
+}
+
+impl PartialEq<str> for SecretWord {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<String> for SecretWord {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl fmt::Debug for SecretWord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretWord(<hidden>)")
+    }
+}
+
+impl fmt::Display for SecretWord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<hidden>")
+    }
+}
+
+/// Makes panics say only where they happened. What they were about can be a
+/// request, a response or a guess with the answer in it; `RUST_BACKTRACE`
+/// shows it all for whoever is debugging.
+pub fn hide_panics() {
+    if std::env::var_os("RUST_BACKTRACE").is_some() {
+        return;
+    }
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map_or_else(String::new, |l| format!(" at {}:{}", l.file(), l.line()));
+        eprintln!("wordle-rs crashed{}. Run it again with RUST_BACKTRACE=1 to see why, which can give the answer away.", location);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hides_the_word_unless_revealed() {
+        let word = SecretWord::new("crane".to_string());
+        assert_eq!(format!("{} {:?}", word, word), "<hidden> SecretWord(<hidden>)");
+        assert_eq!(word.reveal(), "crane");
+        assert_eq!(serde_json::to_string(&word).unwrap(), "\"crane\"");
+        assert!(word == *"crane");
+    }
+}
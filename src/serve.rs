@@ -0,0 +1,281 @@
+//! A small JSON API over HTTP, so web pages, launcher extensions and the like
+//! can play through the same engine as the terminal.
+//!
+//! - `POST /games` starts a game, or picks up the saved one: the day's wordle
+//!   by default, `{"day": "2025-01-01"}` for another day or
+//!   `{"practice": true, "seed": 7}` for a practice word.
+//! - `GET /games/<id>` is the board, with the game's `phase`: `ready`,
+//!   `in_progress`, `won` or `lost`.
+//! - `POST /games/<id>/guesses` with `{"guess": "crane"}` plays a guess.
+//! - `GET /stats` is the stats of the finished daily games.
+//!
+//! Daily games are saved after every guess like in the terminal, so they can
+//! be carried on there or after a restart. Practice games only last as long
+//! as the server.
+//!
+//! Each address may only guess `--max-guess-rate` times a minute, and one
+//! sending `--max-invalid` guesses that aren't words or go to no game is
+//! turned away for `--ban-secs`. Either way it's told `429 Too Many Requests`
+//! with a `Retry-After`.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
+    time::{Duration, Instant},
+};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    CurrentWord, GuessOutcome,
+    app::App,
+    cli::ServeArgs,
+    date_arg,
+    engine::{self, Engine, Move, Step, Words},
+    error::{Result, WordleError},
+    get_and_write_word, input, output, practice_word,
+    progress::{self, SaveFile},
+    rate_limit::{Limiter, Verdict},
+    share::{self, ShareLevel},
+    stats::Stats,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route<'a> {
+    NewGame,
+    Board(&'a str),
+    Guess(&'a str),
+    Stats,
+}
+
+fn route<'a>(method: &Method, path: &'a str) -> Option<Route<'a>> {
+    let parts: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, parts.as_slice()) {
+        (Method::Post, ["games"]) => Some(Route::NewGame),
+        (Method::Get, ["games", id]) => Some(Route::Board(id)),
+        (Method::Post, ["games", id, "guesses"]) => Some(Route::Guess(id)),
+        (Method::Get, ["stats"]) => Some(Route::Stats),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct NewGame {
+    day: Option<String>,
+    practice: bool,
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Guess {
+    guess: String,
+}
+
+struct Game {
+    engine: Engine,
+    title: String,
+    header: String,
+}
+
+impl Game {
+    fn board(&self, id: &str) -> Value {
+        let current_word = &self.engine.current_word;
+        let over = self.engine.is_finished();
+        let rows: Vec<Value> = current_word.char_guesses.iter().map(|cgs| json!({
+            "guess": cgs.iter().map(|cg| cg.ch).collect::<String>(),
+            "pattern": output::pattern(cgs),
+        })).collect();
+        json!({
+            "id": id,
+            "header": self.header,
+            "word_length": current_word.word_length,
+            "tries_left": current_word.tries,
+            "rows": rows,
+            "phase": self.engine.phase().name(),
+            "over": over,
+            "won": current_word.is_solved(),
+            "answer": over.then_some(current_word.correct_answer.reveal()),
+            "share": over.then(|| share::share_text(current_word, &self.title, ShareLevel::Full)).flatten(),
+        })
+    }
+}
+
+/// An error as an HTTP status and a JSON body saying what went wrong.
+fn error(status: u16, message: impl Into<String>) -> (u16, Value) {
+    (status, json!({ "error": message.into() }))
+}
+
+fn status_of(e: &WordleError) -> u16 {
+    match e {
+        WordleError::NotPublished(_) => 404,
+        WordleError::Network { .. } | WordleError::NoNetwork(_) => 502,
+        _ => 500,
+    }
+}
+
+struct Games<'a> {
+    app: &'a App,
+    games: HashMap<String, Game>,
+}
+
+impl Games<'_> {
+    fn start(&mut self, body: &str) -> Result<std::result::Result<String, String>> {
+        let app = self.app;
+        let asked: NewGame = match body.trim() {
+            "" => NewGame::default(),
+            body => match serde_json::from_str(body) {
+                Ok(asked) => asked,
+                Err(e) => return Ok(Err(e.to_string())),
+            },
+        };
+        let tries = app.config.max_guesses.unwrap_or(6);
+
+        let (id, game) = if asked.practice {
+            let seed = asked.seed.unwrap_or_else(rand::random);
+            let id = format!("practice-{}", seed);
+            if self.games.contains_key(&id) {
+                return Ok(Ok(id));
+            }
+            let answer = practice_word(app, app.dictionary(true)?, Some(seed))?;
+            let title = format!("practice #{}", seed);
+            let game = Game {
+                engine: Engine::new(CurrentWord::new(answer, tries), None, engine::Options::default()),
+                header: format!("Wordle {}", title),
+                title,
+            };
+            (id, game)
+        } else {
+            let day: NaiveDate = match asked.day.as_deref().map(date_arg::parse_day) {
+                None => date_arg::today(),
+                Some(Ok(day)) => day,
+                Some(Err(e)) => return Ok(Err(e)),
+            };
+            let id = day.to_string();
+            if self.games.contains_key(&id) {
+                return Ok(Ok(id));
+            }
+            let puzzle = get_and_write_word(&app.cache_dir, day, &app.nyt())?;
+            let mut current_word = CurrentWord::new(puzzle.solution.clone(), tries);
+            current_word.hard_mode = app.config.hard;
+            let save_file = SaveFile::for_day(&app.data_dir, day);
+            if let Some(saved) = save_file.load()? {
+                current_word.resume(saved);
+            }
+            let game = Game {
+                engine: Engine::new(current_word, Some(save_file), engine::Options::default()),
+                title: puzzle.number(day).unwrap_or_else(|| id.clone()),
+                header: puzzle.header(day),
+            };
+            (id, game)
+        };
+        self.games.insert(id.clone(), game);
+        Ok(Ok(id))
+    }
+
+    fn guess(&mut self, id: &str, body: &str) -> Result<(u16, Value)> {
+        let dictionary = self.app.dictionary(false)?;
+        let words = Words { dictionary, answers: self.app.answers()?.unwrap_or(dictionary) };
+        let Some(game) = self.games.get_mut(id) else { return Ok(error(404, format!("No game {}.", id))) };
+        let guess = match serde_json::from_str::<Guess>(body) {
+            Ok(Guess { guess }) => input::normalize(&guess),
+            Err(e) => return Ok(error(400, e.to_string())),
+        };
+        match game.engine.play(Move::Guess(guess), words)? {
+            Step::Over => Ok(error(409, "The game is over.")),
+            Step::Guessed(GuessOutcome::InvalidGuess(kind)) => Ok(error(422, kind.message())),
+            _ => Ok((200, game.board(id))),
+        }
+    }
+
+    fn handle(&mut self, route: Route, body: &str) -> Result<(u16, Value)> {
+        match route {
+            Route::NewGame => Ok(match self.start(body)? {
+                Ok(id) => (201, self.games[&id].board(&id)),
+                Err(reason) => error(400, reason),
+            }),
+            Route::Board(id) => Ok(match self.games.get(id) {
+                Some(game) => (200, game.board(id)),
+                None => error(404, format!("No game {}.", id)),
+            }),
+            Route::Guess(id) => self.guess(id, body),
+            Route::Stats => {
+                let stats = Stats::from_games(&progress::saved_games(&self.app.data_dir)?, date_arg::today());
+                Ok((200, serde_json::to_value(stats.event()).expect("stats serialize")))
+            },
+        }
+    }
+}
+
+fn respond(request: Request, status: u16, body: &Value, retry_after: Option<Duration>) {
+    let header = Header::from_bytes("Content-Type", "application/json").expect("the header is valid");
+    let mut response = Response::from_string(body.to_string()).with_status_code(status).with_header(header);
+    if let Some(wait) = retry_after {
+        // Rounded up, so a client waiting that long is let in.
+        let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        response.add_header(Header::from_bytes("Retry-After", secs.to_string()).expect("the header is valid"));
+    }
+    // The client hanging up before the answer is its own problem.
+    let _ = request.respond(response);
+}
+
+/// Answers requests on `--bind` until killed.
+pub fn run(app: &App, args: ServeArgs) -> Result<()> {
+    let server = Server::http(&args.bind).map_err(|e| WordleError::Serve { address: args.bind.clone(), reason: e.to_string() })?;
+    eprintln!("Serving the API on http://{}.", args.bind);
+    let mut games = Games { app, games: HashMap::new() };
+    let mut limiter = Limiter::new(args.limits);
+    for mut request in server.incoming_requests() {
+        let client = request.remote_addr().map_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED), |address| address.ip());
+        let mut body = String::new();
+        if request.as_reader().read_to_string(&mut body).is_err() {
+            respond(request, 400, &json!({ "error": "The body isn't UTF-8." }), None);
+            continue;
+        }
+        let url = request.url().to_string();
+        let path = url.split('?').next().unwrap_or_default();
+        let route = route(request.method(), path);
+        let guessing = matches!(route, Some(Route::Guess(_)));
+        let verdict = match guessing {
+            true => limiter.guess(client, Instant::now()),
+            false => limiter.check(client, Instant::now()),
+        };
+        if let Some(wait) = verdict.retry_after() {
+            let message = match verdict {
+                Verdict::Banned(_) => "Too many invalid guesses, try again later.",
+                _ => "Too many guesses, slow down.",
+            };
+            respond(request, 429, &json!({ "error": message }), Some(wait));
+            continue;
+        }
+        let (status, json) = match route {
+            Some(route) => games.handle(route, &body).unwrap_or_else(|e| error(status_of(&e), e.to_string())),
+            None => error(404, format!("No {} {}.", request.method(), path)),
+        };
+        // A guess that isn't JSON, isn't a word or is for no game is what
+        // trying words or game ids until one sticks looks like.
+        if guessing && matches!(status, 400 | 404 | 422) {
+            limiter.invalid(client, Instant::now());
+        }
+        respond(request, status, &json, None);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_requests_by_method_and_path() {
+        assert_eq!(route(&Method::Post, "/games"), Some(Route::NewGame));
+        assert_eq!(route(&Method::Get, "/games/2025-01-01"), Some(Route::Board("2025-01-01")));
+        assert_eq!(route(&Method::Post, "/games/practice-7/guesses/"), Some(Route::Guess("practice-7")));
+        assert_eq!(route(&Method::Get, "/stats"), Some(Route::Stats));
+        assert_eq!(route(&Method::Get, "/games"), None);
+        assert_eq!(route(&Method::Delete, "/games/2025-01-01"), None);
+    }
+}
@@ -0,0 +1,154 @@
+//! The questions asked the first time wordle-rs is played, with no config
+//! file yet, to write one: the language, the look of the tiles, hard mode,
+//! where things are kept and whether to download the back catalog.
+
+use std::{
+    collections::HashMap,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    cache,
+    cli::{Command, FetchArgs},
+    config,
+    error::{Result, WordleError},
+    i18n::{self, Lang, tr},
+    prefetch,
+    theme::Theme,
+};
+
+/// What was answered.
+#[derive(Debug, PartialEq)]
+struct Answers {
+    lang: Lang,
+    theme: String,
+    hard: bool,
+    cache_dir: PathBuf,
+    data_dir: PathBuf,
+    prefetch: bool,
+}
+
+fn default_data_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(std::env::temp_dir).join("wordle-rs")
+}
+
+/// Whether to ask before a run of `command`: there's no config file at
+/// `path`, and someone is at the terminal to answer for a game.
+pub fn wanted(path: &Path, session: Option<&str>, command: &Option<Command>, json: bool) -> bool {
+    let playing = matches!(command, None | Some(Command::Play(_) | Command::Daily(_)));
+    playing && session.is_none() && !json && !path.exists() && io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Asks `question`, returning the answer or `default` for none. At the end
+/// of the input every question after is answered with its default.
+fn ask(lines: &mut dyn Iterator<Item = io::Result<String>>, question: &str, default: &str) -> Result<String> {
+    print!("{} [{}]: ", question, default);
+    let _ = io::stdout().flush();
+    match lines.next() {
+        Some(line) => {
+            let line = line.map_err(|source| WordleError::Terminal { context: "reading your answer", source })?;
+            Ok(Some(line.trim()).filter(|answer| !answer.is_empty()).unwrap_or(default).to_string())
+        },
+        None => {
+            println!();
+            Ok(default.to_string())
+        },
+    }
+}
+
+/// Asks until the answer is one `parse` takes.
+fn ask_until<T>(lines: &mut dyn Iterator<Item = io::Result<String>>, question: &str, default: &str, parse: impl Fn(&str) -> Option<T>) -> Result<T> {
+    loop {
+        let answer = ask(lines, question, default)?;
+        match parse(&answer) {
+            Some(value) => return Ok(value),
+            None => println!("{}", tr!("setup-invalid", answer = answer)),
+        }
+    }
+}
+
+fn yes(answer: &str) -> Option<bool> {
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" | "j" | "ja" => Some(true),
+        "n" | "no" | "nej" => Some(false),
+        _ => None,
+    }
+}
+
+/// Asks the questions, the language first so the others are asked in it.
+/// The language isn't asked when `lang` was picked already.
+fn interview(lines: &mut dyn Iterator<Item = io::Result<String>>, lang: Option<Lang>) -> Result<Answers> {
+    let lang = match lang {
+        Some(lang) => lang,
+        None => {
+            let locale = Lang::from_env().to_possible_value().expect("languages have names").get_name().to_string();
+            ask_until(lines, "Language / Språk: en, sv", &locale, |answer| <Lang as ValueEnum>::from_str(answer, true).ok())?
+        },
+    };
+    i18n::set(lang);
+    println!("{}", tr!("setup-intro"));
+    let theme = ask_until(lines, &tr!("setup-theme"), "classic", |answer| {
+        Theme::named(answer, &HashMap::new()).ok().map(|_| answer.to_string())
+    })?;
+    let hard = ask_until(lines, &tr!("setup-hard"), &tr!("no"), yes)?;
+    let cache_dir = PathBuf::from(ask(lines, &tr!("setup-cache-dir"), &cache::default_dir().to_string_lossy())?);
+    let data_dir = PathBuf::from(ask(lines, &tr!("setup-data-dir"), &default_data_dir().to_string_lossy())?);
+    let prefetch = ask_until(lines, &tr!("setup-prefetch"), &tr!("no"), yes)?;
+    Ok(Answers { lang, theme, hard, cache_dir, data_dir, prefetch })
+}
+
+/// The settings that differ from what's assumed without them.
+fn settings(answers: &Answers) -> Vec<(&'static str, toml::Value)> {
+    let name = answers.lang.to_possible_value().expect("languages have names").get_name().to_string();
+    let path = |dir: &Path| toml::Value::String(dir.to_string_lossy().into_owned());
+    let mut settings = vec![("lang", toml::Value::String(name))];
+    if answers.theme != "classic" {
+        settings.push(("theme", toml::Value::String(answers.theme.clone())));
+    }
+    if answers.hard {
+        settings.push(("hard", toml::Value::Boolean(true)));
+    }
+    if answers.cache_dir != cache::default_dir() {
+        settings.push(("cache_dir", path(&answers.cache_dir)));
+    }
+    if answers.data_dir != default_data_dir() {
+        settings.push(("data_dir", path(&answers.data_dir)));
+    }
+    settings
+}
+
+/// Asks the questions at the terminal and writes the config file at `path`.
+/// Returns whether the back catalog should be downloaded.
+pub fn run(path: &Path, lang: Option<Lang>) -> Result<bool> {
+    let answers = interview(&mut io::stdin().lines(), lang)?;
+    config::write(path, &settings(&answers))?;
+    println!("{}\n", tr!("setup-wrote", path = path.display()));
+    Ok(answers.prefetch)
+}
+
+/// The fetch of every wordle from the first to today's.
+pub fn archive() -> FetchArgs {
+    FetchArgs { from: prefetch::FIRST_DAY, to: None, rate: None, dry_run: false, if_stale: false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asks_again_until_the_answer_will_do() {
+        let answers = ["neon", "mono", "y", "", "/tmp/games", "n"];
+        let mut lines = answers.iter().map(|line| Ok(line.to_string()));
+        let answers = interview(&mut lines, Some(Lang::En)).unwrap();
+        assert_eq!((answers.theme.as_str(), answers.hard, answers.prefetch), ("mono", true, false));
+        let keys: Vec<&str> = settings(&answers).iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, ["lang", "theme", "hard", "data_dir"]);
+
+        let defaults = interview(&mut std::iter::empty(), Some(Lang::En)).unwrap();
+        assert_eq!((defaults.theme.as_str(), defaults.hard), ("classic", false));
+        assert_eq!(settings(&defaults).len(), 1);
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::{self, IsTerminal, Write};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::{CharGuess, CharGuessKind, CurrentWord};
+
+/// How much of a finished game is allowed to leave the machine.
+///
+/// The clipboard gets the text of [`share_text`], the webhook and Mastodon
+/// post it, and leaderboard entries leave out what it does, so this one setting decides what all of
+/// them get to see.
+#[derive(clap::ValueEnum, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShareLevel {
+    /// The emoji grid and the guess count
+    Full,
+    /// The emoji grid without the guess count
+    NoCount,
+    /// Only whether the word was solved
+    Outcome,
+    /// Nothing at all
+    None,
+}
+
+fn emoji(kind: CharGuessKind) -> char {
+    match kind {
+        CharGuessKind::NotInWord => '⬛',
+        CharGuessKind::WrongPlace => '🟨',
+        CharGuessKind::Correct => '🟩',
+    }
+}
+
+/// The guesses as rows of colored squares, without their letters.
+pub fn grid(char_guesses: &[Vec<CharGuess>]) -> String {
+    char_guesses
+        .iter()
+        .map(|cgs| cgs.iter().map(|cg| emoji(cg.kind)).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The spoiler-free text describing a finished game, or `None` if the level
+/// doesn't allow sharing anything.
+pub fn share_text(current_word: &CurrentWord, title: &str, level: ShareLevel) -> Option<String> {
+    let won = current_word.char_guesses
+        .last()
+        .is_some_and(|cgs| cgs.iter().all(|cg| cg.kind == CharGuessKind::Correct));
+    let grid = grid(&current_word.char_guesses);
+    let total = current_word.char_guesses.len() as u32 + current_word.tries + current_word.hint_tries;
+
+    match level {
+        ShareLevel::Full => {
+            let score = if won { current_word.char_guesses.len().to_string() } else { "X".to_string() };
+            Some(format!("Wordle {} {}/{}\n\n{}", title, score, total, grid))
+        },
+        ShareLevel::NoCount => Some(format!("Wordle {}\n\n{}", title, grid)),
+        ShareLevel::Outcome => Some(format!("Wordle {} {}", title, if won { "solved" } else { "not solved" })),
+        ShareLevel::None => None,
+    }
+}
+
+pub fn print_share(current_word: &CurrentWord, title: &str, level: ShareLevel) {
+    if let Some(text) = share_text(current_word, title, level) {
+        println!("\n{}", text);
+    }
+}
+
+/// The escape sequence asking the terminal to put `text` on the clipboard.
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+/// Copies `text` to the clipboard through the terminal, which works over ssh
+/// too. Terminals that don't take OSC 52 ignore it.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    if !stdout.is_terminal() {
+        return Err(io::Error::other("the output isn't a terminal"));
+    }
+    stdout.write_all(osc52(text).as_bytes())?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asks_the_terminal_to_copy() {
+        assert_eq!(osc52("Wordle 1,292 1/6"), "\x1b]52;c;V29yZGxlIDEsMjkyIDEvNg==\x07");
+    }
+}
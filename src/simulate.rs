@@ -0,0 +1,110 @@
+//! Playing a strategy against many answers at once, for the bot and for
+//! measuring solvers. The answers are played in parallel a chunk at a
+//! time, so results come out in order while the rest are still played.
+
+use rayon::prelude::*;
+
+use crate::{
+    CharGuess, grade,
+    index::WordIndex,
+    strategy,
+};
+
+/// How many answers are played in parallel before their results are given.
+static CHUNK: usize = 64;
+
+/// Picks guesses from the feedback so far.
+pub trait Strategy: Sync {
+    /// The next guess, or `None` to give up.
+    fn guess(&self, char_guesses: &[Vec<CharGuess>]) -> Option<String>;
+}
+
+impl<F: Fn(&[Vec<CharGuess>]) -> Option<String> + Sync> Strategy for F {
+    fn guess(&self, char_guesses: &[Vec<CharGuess>]) -> Option<String> {
+        self(char_guesses)
+    }
+}
+
+/// The best guess [`strategy::rank`] finds, with the opener ranked once up
+/// front since it doesn't depend on the answer.
+pub struct Ranked<'a> {
+    answers: &'a WordIndex,
+    dictionary: &'a WordIndex,
+    kind: strategy::Strategy,
+    opener: Option<String>,
+}
+
+impl<'a> Ranked<'a> {
+    pub fn new(answers: &'a WordIndex, dictionary: &'a WordIndex, kind: strategy::Strategy) -> Self {
+        let opener = strategy::rank(&[], answers, dictionary, kind).first().map(|(w, _)| w.to_string());
+        Self { answers, dictionary, kind, opener }
+    }
+}
+
+impl Strategy for Ranked<'_> {
+    fn guess(&self, char_guesses: &[Vec<CharGuess>]) -> Option<String> {
+        match char_guesses.is_empty() {
+            true => self.opener.clone(),
+            false => strategy::rank(char_guesses, self.answers, self.dictionary, self.kind).first().map(|(w, _)| w.to_string()),
+        }
+    }
+}
+
+/// How a strategy did against one answer.
+#[derive(Debug, PartialEq)]
+pub struct Outcome {
+    pub answer: String,
+    pub guesses: Vec<String>,
+    pub won: bool,
+}
+
+impl Outcome {
+    /// How many guesses the answer took, if it was found.
+    pub fn guess_count(&self) -> Option<usize> {
+        self.won.then_some(self.guesses.len())
+    }
+}
+
+/// Plays `answer` with `strategy` until it's found, the tries run out or
+/// the strategy gives up.
+pub fn play(strategy: &impl Strategy, answer: &str, tries: u32) -> Outcome {
+    let mut char_guesses: Vec<Vec<CharGuess>> = vec![];
+    let mut guesses = vec![];
+    let mut won = false;
+    while !won && guesses.len() < tries as usize {
+        let Some(guess) = strategy.guess(&char_guesses) else { break };
+        char_guesses.push(guess.chars().zip(grade::grade(&guess, answer)).map(|(ch, kind)| CharGuess::new(ch, kind)).collect());
+        won = guess == answer;
+        guesses.push(guess);
+    }
+    Outcome { answer: answer.to_string(), guesses, won }
+}
+
+/// Plays every one of `answers` with `strategy`, giving how each went in
+/// the order of `answers`.
+pub fn run<'a, S: Strategy>(strategy: &'a S, answers: &'a [String], tries: u32) -> impl Iterator<Item = Outcome> + 'a {
+    answers.chunks(CHUNK).flat_map(move |chunk| {
+        chunk.par_iter().map(|answer| play(strategy, answer, tries)).collect::<Vec<_>>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn plays_every_answer_in_order() {
+        let words: Vec<String> = ["crane", "crate", "slate", "pious", "adieu"].map(str::to_string).into();
+        let index = WordIndex::new(words.iter().cloned().collect::<HashSet<_>>());
+        let ranked = Ranked::new(&index, &index, strategy::Strategy::Frequency);
+        let outcomes: Vec<Outcome> = run(&ranked, &words, 6).collect();
+        assert_eq!(outcomes.iter().map(|o| o.answer.as_str()).collect::<Vec<_>>(), ["crane", "crate", "slate", "pious", "adieu"]);
+        assert!(outcomes.iter().all(|o| o.won && o.guesses.last() == Some(&o.answer)));
+
+        let stubborn = |_: &[Vec<CharGuess>]| Some("slate".to_string());
+        assert_eq!(play(&stubborn, "crane", 3).guess_count(), None);
+        assert_eq!(play(&stubborn, "slate", 3).guess_count(), Some(1));
+    }
+}
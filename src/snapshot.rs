@@ -0,0 +1,193 @@
+//! `export-archive` and `import-archive`: the cached words, and the word
+//! lists, in one `.tar.zst` file, so a computer that can't reach NYT can be
+//! given the whole back catalog at once.
+//!
+//! The words are written plain whatever `--encrypt-cache` says, since the
+//! key doesn't go with them, and scrambled again as they're imported if it's
+//! on there.
+
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    DATE_FORMAT, cache,
+    app::App,
+    cli::{ExportArchiveArgs, ImportArchiveArgs},
+    dictionary,
+    error::{Context, Result},
+    parse_word_cache, read_word_cache, write_word_cache,
+};
+
+/// How hard the archive is compressed, from zstd's 1 to 22.
+static LEVEL: i32 = 19;
+
+/// The biggest file read from an archive, far bigger than any word list.
+static MAX_ENTRY: u64 = 16 * 1024 * 1024;
+
+static DICTIONARY: &str = "dictionary/words.txt";
+static ANSWERS: &str = "dictionary/answers.txt";
+
+/// What an archive held, and what of it was imported.
+#[derive(Debug, Default, PartialEq)]
+struct Imported {
+    days: usize,
+    already_cached: usize,
+    left_out: usize,
+    word_lists: usize,
+}
+
+fn word_name(day: NaiveDate) -> String {
+    format!("words/{}.json", day.format(DATE_FORMAT))
+}
+
+fn word_day(name: &str) -> Option<NaiveDate> {
+    let day = name.strip_prefix("words/")?.strip_suffix(".json")?;
+    NaiveDate::parse_from_str(day, DATE_FORMAT).ok()
+}
+
+fn append(builder: &mut tar::Builder<impl Write>, name: &str, contents: &[u8]) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, name, contents)
+}
+
+/// Writes the cached words of `days`, and the word lists there are, to
+/// `out`. Returns how many days were written.
+fn write_archive(cache_dir: &Path, days: &[NaiveDate], out: impl Write) -> Result<usize> {
+    let encoder = zstd::Encoder::new(out, LEVEL).context("compressing the archive")?;
+    let mut builder = tar::Builder::new(encoder);
+    let mut written = 0;
+    for &day in days {
+        let puzzle = match read_word_cache(&cache::word_path(cache_dir, day)) {
+            Ok(puzzle) => puzzle,
+            Err(e) => {
+                eprintln!("{} Left out of the archive.", e);
+                continue;
+            },
+        };
+        let json = serde_json::to_string(&puzzle).expect("a puzzle serializes");
+        append(&mut builder, &word_name(day), json.as_bytes()).context("writing the archive")?;
+        written += 1;
+    }
+    for (name, path) in [(DICTIONARY, cache::dictionary_path(cache_dir)), (ANSWERS, cache::answers_path(cache_dir))] {
+        if path.try_exists().context("checking for the word lists")? {
+            append(&mut builder, name, &fs::read(&path).context("reading the word lists")?).context("writing the archive")?;
+        }
+    }
+    builder.into_inner().context("writing the archive")?.finish().context("compressing the archive")?;
+    Ok(written)
+}
+
+/// Caches the words of the archive in `input` that aren't cached yet, and
+/// its word lists when there are none.
+fn read_archive(cache_dir: &Path, input: impl Read) -> Result<Imported> {
+    let decoder = zstd::Decoder::new(input).context("decompressing the archive")?;
+    let mut archive = tar::Archive::new(decoder);
+    let mut imported = Imported::default();
+    for entry in archive.entries().context("reading the archive")? {
+        let entry = entry.context("reading the archive")?;
+        let name = entry.path().context("reading the archive")?.to_string_lossy().into_owned();
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let mut contents = String::new();
+        let read = entry.take(MAX_ENTRY).read_to_string(&mut contents);
+        let word_list = match name.as_str() {
+            n if n == DICTIONARY => Some(cache::dictionary_path(cache_dir)),
+            n if n == ANSWERS => Some(cache::answers_path(cache_dir)),
+            _ => None,
+        };
+        match (word_day(&name), word_list) {
+            _ if read.is_err() => {
+                eprintln!("{} in the archive isn't text, it was left out.", name);
+                imported.left_out += 1;
+            },
+            (Some(day), _) if cache::word_path(cache_dir, day).try_exists().context("checking for word cache")? => imported.already_cached += 1,
+            (Some(day), _) => match parse_word_cache(&contents) {
+                Ok(puzzle) => {
+                    write_word_cache(cache_dir, day, &puzzle)?;
+                    imported.days += 1;
+                },
+                Err(e) => {
+                    eprintln!("The word of {} in the archive can't be played, {}. It was left out.", day, e);
+                    imported.left_out += 1;
+                },
+            },
+            // A downloaded list is likely newer than the archive's.
+            (None, Some(path)) if path.try_exists().context("checking for the word lists")? => (),
+            (None, Some(_)) if name == DICTIONARY => {
+                dictionary::import(cache_dir, &contents)?;
+                imported.word_lists += 1;
+            },
+            (None, Some(_)) => {
+                dictionary::import_answers(cache_dir, &contents)?;
+                imported.word_lists += 1;
+            },
+            (None, None) => {
+                eprintln!("{} isn't something wordle-rs keeps, it was left out.", name);
+                imported.left_out += 1;
+            },
+        }
+    }
+    Ok(imported)
+}
+
+pub fn export(app: &App, args: ExportArchiveArgs) -> Result<()> {
+    let days: Vec<NaiveDate> = cache::cached_days(&app.cache_dir)?
+        .into_iter()
+        .filter(|day| args.from.is_none_or(|from| *day >= from) && args.to.is_none_or(|to| *day <= to))
+        .collect();
+    let file = File::create(&args.file).context("creating the archive")?;
+    let written = write_archive(&app.cache_dir, &days, io::BufWriter::new(file))?;
+    eprintln!("Wrote the words of {} days to {}.", written, args.file.display());
+    Ok(())
+}
+
+pub fn import(app: &App, args: ImportArchiveArgs) -> Result<()> {
+    let file = File::open(&args.file).context("opening the archive")?;
+    let imported = read_archive(&app.cache_dir, io::BufReader::new(file))?;
+    eprintln!("Imported the words of {} days, {} were already cached.", imported.days, imported.already_cached);
+    if imported.word_lists > 0 {
+        eprintln!("Imported {} word lists.", imported.word_lists);
+    }
+    if imported.left_out > 0 {
+        eprintln!("{} files of the archive were left out.", imported.left_out);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Puzzle, secret::SecretWord};
+
+    #[test]
+    fn imports_what_was_exported() {
+        let (from, to) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        cache::prepare(from.path()).unwrap();
+        cache::prepare(to.path()).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        for (d, word) in [(1, "crane"), (2, "slate")] {
+            let puzzle = Puzzle { id: Some(d), solution: SecretWord::new(word.to_string()), print_date: None, days_since_launch: None, editor: None };
+            write_word_cache(from.path(), day(d), &puzzle).unwrap();
+        }
+        write_word_cache(to.path(), day(2), &read_word_cache(&cache::word_path(from.path(), day(2))).unwrap()).unwrap();
+        dictionary::import(from.path(), "crane\nslate\n").unwrap();
+
+        let mut archive = vec![];
+        assert_eq!(write_archive(from.path(), &[day(1), day(2)], &mut archive).unwrap(), 2);
+        let imported = read_archive(to.path(), archive.as_slice()).unwrap();
+        assert_eq!(imported, Imported { days: 1, already_cached: 1, left_out: 0, word_lists: 1 });
+        assert_eq!(read_word_cache(&cache::word_path(to.path(), day(1))).unwrap().solution.reveal(), "crane");
+        assert_eq!(fs::read_to_string(cache::dictionary_path(to.path())).unwrap(), "crane\nslate\n");
+        assert!(read_archive(to.path(), b"not an archive".as_slice()).is_err());
+    }
+}
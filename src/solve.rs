@@ -0,0 +1,62 @@
+use crate::{
+    CharGuess,
+    cli::SolveArgs,
+    constraints::Constraints,
+    index::WordIndex,
+    partial,
+    strategy::{self, Feedback, Strategy},
+};
+
+/// How many possible answers are listed in full.
+static LISTED_CANDIDATES: usize = 20;
+
+pub fn solve(args: SolveArgs, answers: &WordIndex, dictionary: &WordIndex) {
+    let char_guesses: Vec<Vec<CharGuess>> = args.feedback.iter().filter_map(|f| match f {
+        Feedback::Known(cgs) => Some(cgs.clone()),
+        Feedback::Unknown(_) => None,
+    }).collect();
+
+    let candidates = if char_guesses.len() == args.feedback.len() {
+        strategy::likely_candidates(&char_guesses, answers, dictionary)
+    } else {
+        let analysis = partial::analyze(&args.feedback, dictionary);
+        println!("{} ways to have played this.", analysis.sequences);
+        for (i, (min, max)) in analysis.bounds.iter().enumerate() {
+            println!("Unknown guess {}: {} to {} words fit.", i + 1, min, max);
+        }
+        analysis.answers
+    };
+    match candidates.len() {
+        0 => {
+            println!("No word in the dictionary matches that feedback. Double-check the patterns.");
+            return;
+        },
+        1 => {
+            println!("The answer is {}.", candidates[0]);
+            return;
+        },
+        n => println!("{} possible answers.", n),
+    }
+    if candidates.len() <= LISTED_CANDIDATES {
+        let listed: Vec<&str> = candidates.iter().map(|w| w.as_str()).collect();
+        println!("{}", listed.join(", "));
+    }
+
+    let constraints = Constraints::from_guesses(&char_guesses);
+    let ranked = strategy::rank_candidates(candidates.clone(), dictionary, args.strategy);
+    let unit = match args.strategy {
+        Strategy::Frequency => "",
+        Strategy::Entropy => " bits",
+    };
+
+    println!();
+    println!("Best guesses{}:", if args.hard { " in hard mode" } else { "" });
+    for (i, (word, score)) in ranked.iter()
+        .filter(|(w, _)| !args.hard || constraints.allows(w))
+        .take(args.top)
+        .enumerate()
+    {
+        let possible = if candidates.binary_search(word).is_ok() { " (possible answer)" } else { "" };
+        println!("{:>3}. {}  {:.2}{}{}", i + 1, word, score, unit, possible);
+    }
+}
@@ -0,0 +1,75 @@
+//! Short tones for what happens in a game, when built with the `audio`
+//! feature. Without it, or without a sound device, nothing is played.
+
+/// Something in a game that has a sound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cue {
+    /// A guess that wasn't accepted.
+    Invalid,
+    /// A guess with a letter in the wrong place.
+    Present,
+    Win,
+    Loss,
+}
+
+pub static DEFAULT_VOLUME: f32 = 0.3;
+
+/// The notes of a cue, as their pitch in Hz and length in milliseconds.
+pub fn notes(cue: Cue) -> &'static [(f32, u64)] {
+    match cue {
+        Cue::Invalid => &[(196.0, 140)],
+        Cue::Present => &[(880.0, 60)],
+        Cue::Win => &[(523.25, 110), (659.25, 110), (783.99, 260)],
+        Cue::Loss => &[(392.0, 180), (311.13, 320)],
+    }
+}
+
+pub struct Sounds {
+    #[cfg(feature = "audio")]
+    output: Option<(rodio::MixerDeviceSink, rodio::Player)>,
+}
+
+impl Sounds {
+    /// Sounds at `volume`, from 0 to 1, or none when `muted`.
+    #[cfg(feature = "audio")]
+    pub fn new(muted: bool, volume: f32) -> Self {
+        if muted {
+            return Self { output: None };
+        }
+        let output = rodio::DeviceSinkBuilder::open_default_sink().ok().map(|mut sink| {
+            sink.log_on_drop(false);
+            let player = rodio::Player::connect_new(sink.mixer());
+            player.set_volume(volume.clamp(0.0, 1.0));
+            (sink, player)
+        });
+        Self { output }
+    }
+
+    #[cfg(not(feature = "audio"))]
+    pub fn new(_muted: bool, _volume: f32) -> Self {
+        Self {}
+    }
+
+    /// Starts playing `cue` after whatever is still playing.
+    pub fn play(&self, cue: Cue) {
+        #[cfg(feature = "audio")]
+        if let Some((_, player)) = &self.output {
+            use rodio::Source;
+            for &(pitch, ms) in notes(cue) {
+                player.append(rodio::source::SineWave::new(pitch).take_duration(std::time::Duration::from_millis(ms)));
+            }
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = cue;
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Drop for Sounds {
+    /// Lets the last cue finish, as the game is over right after a win.
+    fn drop(&mut self) {
+        if let Some((_, player)) = &self.output {
+            player.sleep_until_end();
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Datelike, NaiveDate, Utc};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use crate::{
+    DATE_FORMAT, Puzzle,
+    app::{self, App, LazyClient, Validators},
+    cache,
+    error::{Context, Malformed, Result, WordleError},
+    prefetch::FIRST_DAY,
+    random_answer, read_word_cache,
+    secret::SecretWord,
+};
+
+/// Where NYT serves `<day>.json` for every published wordle.
+pub static NYT_URL: &str = "https://www.nytimes.com/svc/wordle/v2";
+
+/// Where the wordle of each day comes from when it isn't cached yet.
+pub trait WordSource {
+    /// The puzzle of `day`.
+    fn answer_for(&self, day: NaiveDate) -> Result<Puzzle>;
+
+    /// Whether `day` can have a puzzle, without fetching it.
+    fn is_available(&self, day: NaiveDate) -> bool;
+}
+
+/// The `--source` to play from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourceChoice {
+    Nyt,
+    /// A file with a line like `2025-01-01 crane` for each day.
+    File(PathBuf),
+    /// A word a day picked from the answers by the seed and the date.
+    Seed(u64),
+    /// The same word every day, for testing and demos.
+    Word(String),
+}
+
+impl SourceChoice {
+    /// What the game is called in the share text, which mustn't give a fixed
+    /// word away.
+    pub fn title(&self, day: NaiveDate) -> String {
+        match self {
+            SourceChoice::Nyt => day.to_string(),
+            SourceChoice::File(path) => format!("{} {}", path.file_stem().unwrap_or_default().display(), day),
+            SourceChoice::Seed(seed) => format!("seed {} {}", seed, day),
+            SourceChoice::Word(_) => format!("custom {}", day),
+        }
+    }
+
+    pub fn open<'a>(&self, app: &'a App) -> Result<Box<dyn WordSource + 'a>> {
+        Ok(match self {
+            SourceChoice::Nyt => Box::new(app.nyt()),
+            SourceChoice::File(path) => Box::new(WordFile::read(path)?),
+            SourceChoice::Seed(seed) => {
+                let words = app.answers()?.unwrap_or(app.dictionary(true)?).iter().filter(|w| w.len() == 5).cloned().collect();
+                Box::new(Seeded { seed: *seed, words })
+            },
+            SourceChoice::Word(word) => Box::new(Fixed(word.clone())),
+        })
+    }
+}
+
+impl fmt::Display for SourceChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SourceChoice::Nyt => write!(f, "NYT's wordles"),
+            SourceChoice::File(path) => write!(f, "{}", path.display()),
+            SourceChoice::Seed(seed) => write!(f, "the words of seed {}", seed),
+            SourceChoice::Word(_) => write!(f, "the fixed word"),
+        }
+    }
+}
+
+/// Parses `--source`: nyt, file:PATH, seed:N or word:WORD.
+pub fn parse_source(s: &str) -> std::result::Result<SourceChoice, String> {
+    match s.split_once(':') {
+        None if s == "nyt" => Ok(SourceChoice::Nyt),
+        Some(("file", path)) if !path.is_empty() => Ok(SourceChoice::File(PathBuf::from(path))),
+        Some(("seed", seed)) => seed.parse().map(SourceChoice::Seed).map_err(|_| format!("'{}' isn't a seed", seed)),
+        Some(("word", word)) => parse_word(word).map(SourceChoice::Word),
+        _ => Err("expected nyt, file:PATH, seed:N or word:WORD".to_string()),
+    }
+}
+
+/// Parses `--word`, a 4 to 8 letter word in any case.
+pub fn parse_word(s: &str) -> std::result::Result<String, String> {
+    let word = s.to_ascii_lowercase();
+    match (4..=8).contains(&word.len()) && word.bytes().all(|b| b.is_ascii_lowercase()) {
+        true => Ok(word),
+        false => Err(format!("'{}' isn't a 4 to 8 letter word", s)),
+    }
+}
+
+/// Whether `word` can be a daily wordle's solution, 5 lowercase letters.
+pub fn is_solution(word: &str) -> bool {
+    word.len() == 5 && word.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+/// How much of a response that didn't parse is logged.
+static LOGGED_BODY: usize = 2_000;
+
+/// What the solution has been called, or could be if NYT renames it.
+static SOLUTION_NAMES: [&str; 3] = ["solution", "answer", "word"];
+
+/// A day as version 2 of the API, the one [`NYT_URL`] is, describes it.
+/// Fields NYT adds are ignored, and the ones it might rename are taken under
+/// their likely new names too.
+#[derive(Deserialize)]
+struct V2 {
+    id: Option<u32>,
+    #[serde(alias = "answer", alias = "word")]
+    solution: SecretWord,
+    #[serde(alias = "date")]
+    print_date: Option<String>,
+    #[serde(alias = "puzzle_number", alias = "number")]
+    days_since_launch: Option<u32>,
+    #[serde(alias = "author")]
+    editor: Option<String>,
+}
+
+impl From<V2> for Puzzle {
+    fn from(v2: V2) -> Self {
+        Self { id: v2.id, solution: v2.solution, print_date: v2.print_date, days_since_launch: v2.days_since_launch, editor: v2.editor }
+    }
+}
+
+/// At most [`LOGGED_BODY`] characters of `body`.
+fn logged(body: &str) -> &str {
+    body.char_indices().nth(LOGGED_BODY).map_or(body, |(end, _)| &body[..end])
+}
+
+/// The first word called one of [`SOLUTION_NAMES`] anywhere in `value`.
+fn find_solution(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(fields) => fields.iter().find_map(|(name, field)| match field {
+            Value::String(word) if SOLUTION_NAMES.contains(&name.as_str()) => parse_word(word).ok(),
+            field => find_solution(field),
+        }),
+        Value::Array(values) => values.iter().find_map(find_solution),
+        _ => None,
+    }
+}
+
+/// The puzzle of `day` in NYT's response `body`. When the response doesn't
+/// look the way it used to, the solution is looked for on its own, so the day
+/// can still be played without the rest. The body is logged, with -vv when
+/// the solution was found since it's in there.
+///
+/// No response can make it panic, and one with a solution that isn't a word
+/// is [`WordleError::Unplayable`].
+pub fn parse_response(body: &str, day: NaiveDate) -> Result<Puzzle> {
+    let value: Value = match serde_json::from_str(body) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!(error = %e, body = logged(body), "NYT's response isn't JSON");
+            return Err(WordleError::Parse(e));
+        },
+    };
+    if value.get("status").and_then(Value::as_str) == Some("ERROR") {
+        return Err(WordleError::NotPublished(day));
+    }
+    let e = match V2::deserialize(&value) {
+        Ok(v2) if !is_solution(v2.solution.reveal()) => return Err(WordleError::Unplayable(day, Malformed::NotAWord)),
+        Ok(v2) => return Ok(v2.into()),
+        Err(e) => e,
+    };
+    match find_solution(&value).filter(|solution| is_solution(solution)) {
+        Some(solution) => {
+            warn!(error = %e, "NYT's response has changed, so only the solution was read from it");
+            debug!(body = logged(body), "the response");
+            Ok(Puzzle { solution: solution.into(), ..Puzzle::default() })
+        },
+        None => {
+            warn!(error = %e, body = logged(body), "NYT's response has no solution in it");
+            Err(WordleError::Parse(e))
+        },
+    }
+}
+
+pub fn puzzle_url(base_url: &str, day: NaiveDate) -> String {
+    format!("{}/{}.json", base_url.trim_end_matches('/'), day.format(DATE_FORMAT))
+}
+
+/// The puzzle of `day` from a mirror of the API on disk, which is missing
+/// the days that weren't copied to it.
+pub(crate) fn read_mirrored(path: &Path, day: NaiveDate) -> Result<Puzzle> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(WordleError::Unavailable(day, path.display().to_string())),
+        Err(e) => return Err(e).context("reading the mirrored wordle"),
+    };
+    parse_response(&json, day)
+}
+
+/// What NYT said about a day the last time it was asked for it, and whether
+/// the day was out then.
+#[derive(Serialize, Deserialize, Default)]
+struct Asked {
+    #[serde(flatten)]
+    validators: Validators,
+    published: bool,
+}
+
+impl Asked {
+    /// What was kept about `day`, nothing if it's no use: a day that was out
+    /// but whose word isn't cached anymore has to be downloaded again.
+    fn load(cache_dir: &Path, day: NaiveDate) -> Self {
+        let asked: Self = fs::read_to_string(cache::headers_path(cache_dir, day))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        match asked.published && read_word_cache(&cache::word_path(cache_dir, day)).is_err() {
+            true => Self::default(),
+            false => asked,
+        }
+    }
+
+    fn save(&self, cache_dir: &Path, day: NaiveDate) -> Result<()> {
+        let path = cache::headers_path(cache_dir, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).context("creating the headers dir")?;
+        }
+        let json = serde_json::to_string(self).expect("headers serialize");
+        cache::write_atomic(&path, json.as_bytes()).context("writing what NYT said about a day")
+    }
+}
+
+/// What NYT said about `day` last time, to ask it whether the day changed.
+pub fn validators(cache_dir: &Path, day: NaiveDate) -> Validators {
+    Asked::load(cache_dir, day).validators
+}
+
+/// The NYT API, or anything that answers like it: a mirror, or a `file://`
+/// directory of the same `<day>.json` files.
+pub struct Nyt<'a> {
+    pub client: &'a LazyClient,
+    pub base_url: String,
+    /// Where what NYT said about each day is kept, so asking about a day again
+    /// can be answered with 304 Not Modified instead of all of it.
+    pub cache_dir: Option<&'a Path>,
+}
+
+impl<'a> Nyt<'a> {
+    pub fn new(client: &'a LazyClient, base_url: &str) -> Self {
+        Self { client, base_url: base_url.to_string(), cache_dir: None }
+    }
+
+    /// Asks for `day` with what NYT said about it last time, keeping what it
+    /// says now. Not Modified means the cached word is still right, or the day
+    /// is still not out.
+    fn ask(&self, url: &str, cache_dir: &Path, day: NaiveDate) -> Result<Puzzle> {
+        let mut asked = Asked::load(cache_dir, day);
+        let response = self.client.send_with(|c| asked.validators.ask(c.get(url)), "fetching the word from NYT")?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            debug!(%day, published = asked.published, "NYT said the day didn't change");
+            asked.validators.checked = Some(Utc::now());
+            asked.save(cache_dir, day)?;
+            return match asked.published {
+                true => read_word_cache(&cache::word_path(cache_dir, day)),
+                false => Err(WordleError::NotPublished(day)),
+            };
+        }
+        let validators = Validators::from_response(&response);
+        let body = response.text().context("reading the word from NYT")?;
+        let puzzle = parse_response(&body, day);
+        let published = match &puzzle {
+            Ok(_) => true,
+            Err(WordleError::NotPublished(_)) => false,
+            Err(_) => return puzzle,
+        };
+        Asked { validators, published }.save(cache_dir, day)?;
+        puzzle
+    }
+}
+
+impl WordSource for Nyt<'_> {
+    fn answer_for(&self, day: NaiveDate) -> Result<Puzzle> {
+        let url = puzzle_url(&self.base_url, day);
+        if let Some(path) = app::local_path(&url) {
+            return read_mirrored(&path, day);
+        }
+        if let Some(cache_dir) = self.cache_dir {
+            return self.ask(&url, cache_dir, day);
+        }
+        let body = self.client.send(&url, "fetching the word from NYT")?
+            .text()
+            .context("reading the word from NYT")?;
+        parse_response(&body, day)
+    }
+
+    fn is_available(&self, day: NaiveDate) -> bool {
+        day >= FIRST_DAY
+    }
+}
+
+/// Words for days from a file, e.g. for a club with a list of its own.
+pub struct WordFile {
+    path: PathBuf,
+    words: HashMap<NaiveDate, String>,
+}
+
+impl WordFile {
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).context("reading the word file")?;
+        let mut words = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = line.split_once(char::is_whitespace).and_then(|(day, word)| {
+                Some((NaiveDate::parse_from_str(day, DATE_FORMAT).ok()?, parse_word(word.trim()).ok()?))
+            });
+            let Some((day, word)) = entry else {
+                return Err(WordleError::InvalidWordFile { path: path.to_path_buf(), line: i + 1, text: line.to_string() });
+            };
+            words.insert(day, word);
+        }
+        Ok(Self { path: path.to_path_buf(), words })
+    }
+}
+
+impl WordSource for WordFile {
+    fn answer_for(&self, day: NaiveDate) -> Result<Puzzle> {
+        let solution = self.words.get(&day).ok_or_else(|| WordleError::Unavailable(day, self.path.display().to_string()))?;
+        Ok(Puzzle { solution: solution.clone().into(), ..Puzzle::default() })
+    }
+
+    fn is_available(&self, day: NaiveDate) -> bool {
+        self.words.contains_key(&day)
+    }
+}
+
+/// A word a day that anyone with the same seed and answers gets too.
+pub struct Seeded {
+    seed: u64,
+    words: Vec<String>,
+}
+
+impl WordSource for Seeded {
+    fn answer_for(&self, day: NaiveDate) -> Result<Puzzle> {
+        let seed = self.seed ^ (day.num_days_from_ce() as u64).wrapping_mul(0x9e3779b97f4a7c15);
+        let solution = random_answer(self.words.iter().collect(), seed)?;
+        Ok(Puzzle { solution: solution.into(), ..Puzzle::default() })
+    }
+
+    fn is_available(&self, _: NaiveDate) -> bool {
+        !self.words.is_empty()
+    }
+}
+
+/// The same word whatever the day.
+pub struct Fixed(pub String);
+
+impl WordSource for Fixed {
+    fn answer_for(&self, _: NaiveDate) -> Result<Puzzle> {
+        Ok(Puzzle { solution: self.0.clone().into(), ..Puzzle::default() })
+    }
+
+    fn is_available(&self, _: NaiveDate) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_sources() {
+        assert_eq!(parse_source("nyt"), Ok(SourceChoice::Nyt));
+        assert_eq!(parse_source("file:club.txt"), Ok(SourceChoice::File(PathBuf::from("club.txt"))));
+        assert_eq!(parse_source("seed:7"), Ok(SourceChoice::Seed(7)));
+        assert_eq!(parse_source("word:CRANE"), Ok(SourceChoice::Word("crane".to_string())));
+        assert!(parse_source("word:cr4ne").is_err());
+        assert!(parse_source("seed:x").is_err());
+        assert!(parse_source("gist").is_err());
+    }
+
+    #[test]
+    fn reads_a_word_a_day_from_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("club.txt");
+        fs::write(&path, "2025-01-01 crane\n\n2025-01-02  Slate\n").unwrap();
+        let file = WordFile::read(&path).unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        assert_eq!(file.answer_for(day).unwrap().solution.reveal(), "slate");
+        assert!(!file.is_available(day.succ_opt().unwrap()));
+
+        fs::write(&path, "2025-01-01 crane\ntomorrow slate\n").unwrap();
+        assert!(matches!(WordFile::read(&path), Err(WordleError::InvalidWordFile { line: 2, .. })));
+    }
+
+    #[test]
+    fn reads_the_solution_of_responses_that_changed() {
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let puzzle = parse_response(r#"{"id":1,"solution":"crane","days_since_launch":1292,"editor":"Tracy Bennett","hint":"birds"}"#, day).unwrap();
+        assert_eq!((puzzle.solution.reveal(), puzzle.days_since_launch), ("crane", Some(1292)));
+        assert_eq!(parse_response(r#"{"answer":"crane","puzzle_number":1292}"#, day).unwrap().days_since_launch, Some(1292));
+
+        let puzzle = parse_response(r#"{"id":"a1","puzzle":{"answer":"CRANE"}}"#, day).unwrap();
+        assert_eq!((puzzle.solution.reveal(), puzzle.id), ("crane", None));
+
+        assert!(matches!(parse_response(r#"{"status":"ERROR","errors":["Not Found"],"results":[]}"#, day), Err(WordleError::NotPublished(_))));
+        assert!(matches!(parse_response(r#"{"id":1}"#, day), Err(WordleError::Parse(_))));
+        assert!(matches!(parse_response("<html>", day), Err(WordleError::Parse(_))));
+    }
+
+    mod properties {
+        use proptest::prelude::*;
+
+        use super::*;
+
+        /// Responses that look a little like NYT's, with anything as the
+        /// solution.
+        fn responses() -> impl Strategy<Value = String> {
+            prop_oneof![
+                any::<String>(),
+                any::<String>().prop_map(|solution| serde_json::json!({ "id": 1, "solution": solution }).to_string()),
+                any::<String>().prop_map(|solution| serde_json::json!({ "data": [{ "answer": solution }] }).to_string()),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn only_words_come_out_of_a_response(body in responses()) {
+                let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+                if let Ok(puzzle) = parse_response(&body, day) {
+                    prop_assert!(is_solution(puzzle.solution.reveal()));
+                }
+            }
+
+            #[test]
+            fn only_words_come_out_of_a_cached_word(contents in responses()) {
+                if let Ok(puzzle) = crate::parse_word_cache(&contents) {
+                    prop_assert!(is_solution(puzzle.solution.reveal()));
+                }
+            }
+        }
+    }
+}
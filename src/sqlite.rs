@@ -0,0 +1,164 @@
+//! Saved games in an SQLite database, with `storage = "sqlite"`. Each game
+//! is kept as the same JSON as its file would have, next to columns for
+//! how it went so the database can also be queried by hand.
+
+use std::{fs, path::{Path, PathBuf}, time::Duration};
+
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::{
+    DATE_FORMAT, cache,
+    error::{Context, Result, WordleError},
+    progress::{JsonFiles, Problem, SavedGame},
+    storage::Storage,
+};
+
+/// How long to wait for another wordle-rs writing to the database.
+static BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+static SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS games (
+        day TEXT PRIMARY KEY,
+        finished INTEGER NOT NULL,
+        won INTEGER NOT NULL,
+        guesses INTEGER NOT NULL,
+        game TEXT NOT NULL
+    );
+";
+
+fn database(context: &'static str) -> impl FnOnce(rusqlite::Error) -> WordleError {
+    move |e| WordleError::Database { context, reason: e.to_string() }
+}
+
+pub struct Sqlite {
+    path: PathBuf,
+    connection: Connection,
+}
+
+impl Sqlite {
+    /// Opens the database of `data_dir`. While it has no games, the ones
+    /// saved as JSON files are copied in, and left where they are.
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        let path = cache::database_path(data_dir);
+        fs::create_dir_all(data_dir).context("creating data dir")?;
+        let connection = Connection::open(&path).map_err(database("opening the saved games database"))?;
+        connection.busy_timeout(BUSY_TIMEOUT).map_err(database("opening the saved games database"))?;
+        connection.execute_batch(SCHEMA).map_err(database("setting up the saved games database"))?;
+        let sqlite = Self { path, connection };
+        let empty = sqlite.connection.query_row("SELECT COUNT(*) FROM games", [], |row| row.get::<_, i64>(0))
+            .map_err(database("reading saved games"))? == 0;
+        if empty {
+            let games = JsonFiles::new(data_dir).games()?;
+            if !games.is_empty() {
+                let transaction = sqlite.connection.unchecked_transaction().map_err(database("copying saved games"))?;
+                for (day, game) in &games {
+                    sqlite.save(*day, game)?;
+                }
+                transaction.commit().map_err(database("copying saved games"))?;
+                eprintln!("Copied {} saved games into {}.", games.len(), sqlite.path.display());
+            }
+        }
+        Ok(sqlite)
+    }
+
+    fn parse(&self, day: &str, json: &str) -> Result<(NaiveDate, SavedGame)> {
+        let day = NaiveDate::parse_from_str(day, DATE_FORMAT)
+            .map_err(|e| WordleError::Database { context: "reading saved games", reason: format!("'{}' isn't a day: {}", day, e) })?;
+        let game = serde_json::from_str(json).map_err(|e| WordleError::CorruptSave(self.path.clone(), e))?;
+        Ok((day, game))
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<(NaiveDate, SavedGame)>> {
+        let mut statement = self.connection.prepare(sql).map_err(database("reading saved games"))?;
+        let rows = statement.query_map(params, |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(database("reading saved games"))?;
+        let mut games = vec![];
+        for row in rows {
+            let (day, json) = row.map_err(database("reading saved games"))?;
+            games.push(self.parse(&day, &json)?);
+        }
+        Ok(games)
+    }
+}
+
+impl Storage for Sqlite {
+    fn load(&self, day: NaiveDate) -> Result<Option<SavedGame>> {
+        let day = day.format(DATE_FORMAT).to_string();
+        let json: Option<String> = self.connection
+            .query_row("SELECT game FROM games WHERE day = ?1", params![day], |row| row.get(0))
+            .optional()
+            .map_err(database("reading saved game"))?;
+        json.map(|json| self.parse(&day, &json).map(|(_, game)| game)).transpose()
+    }
+
+    fn save(&self, day: NaiveDate, game: &SavedGame) -> Result<()> {
+        let json = serde_json::to_string(game).map_err(|e| WordleError::CorruptSave(self.path.clone(), e))?;
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO games (day, finished, won, guesses, game) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![day.format(DATE_FORMAT).to_string(), game.finished, game.finished && game.won(), game.char_guesses.len() as i64, json],
+            )
+            .map_err(database("writing saved game"))?;
+        Ok(())
+    }
+
+    fn games(&self) -> Result<Vec<(NaiveDate, SavedGame)>> {
+        self.query("SELECT day, game FROM games ORDER BY day", [])
+    }
+
+    fn games_between(&self, first: NaiveDate, last: NaiveDate) -> Result<Vec<(NaiveDate, SavedGame)>> {
+        let (first, last) = (first.format(DATE_FORMAT).to_string(), last.format(DATE_FORMAT).to_string());
+        self.query("SELECT day, game FROM games WHERE day BETWEEN ?1 AND ?2 ORDER BY day", params![first, last])
+    }
+
+    /// Gives the space of removed and rewritten games back.
+    fn compact(&self) -> Result<()> {
+        let before = fs::metadata(&self.path).context("reading the saved games database")?.len();
+        self.connection.execute_batch("VACUUM").map_err(database("compacting the saved games database"))?;
+        let after = fs::metadata(&self.path).context("reading the saved games database")?.len();
+        eprintln!("The saved games database was compacted, {} bytes down to {}.", before, after);
+        Ok(())
+    }
+
+    /// A day has one game at most here, so it's only the games themselves
+    /// that can be wrong.
+    fn rebuild(&self, today: NaiveDate, dry_run: bool) -> Result<Vec<Problem>> {
+        let mut problems = vec![];
+        let transaction = self.connection.unchecked_transaction().map_err(database("fixing saved games"))?;
+        for (day, mut game) in self.games()? {
+            let fixes = game.repair(day);
+            if !dry_run && !fixes.is_empty() {
+                self.save(day, &game)?;
+            }
+            problems.extend(fixes);
+            if day > today {
+                problems.push(Problem::Future(day));
+            }
+        }
+        transaction.commit().map_err(database("fixing saved games"))?;
+        Ok(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharGuess, CharGuessKind};
+
+    #[test]
+    fn copies_json_games_in_and_queries_days() {
+        let dir = tempfile::tempdir().unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let won = SavedGame { char_guesses: vec!["crane".chars().map(|ch| CharGuess::new(ch, CharGuessKind::Correct)).collect()], finished: true, ..SavedGame::default() };
+        JsonFiles::new(dir.path()).save(day(1), &won).unwrap();
+
+        let sqlite = Sqlite::open(dir.path()).unwrap();
+        sqlite.save(day(3), &SavedGame::default()).unwrap();
+        assert!(sqlite.load(day(1)).unwrap().is_some_and(|game| game.won()));
+        assert!(sqlite.load(day(2)).unwrap().is_none());
+        let days: Vec<NaiveDate> = sqlite.games_between(day(2), day(9)).unwrap().into_iter().map(|(day, _)| day).collect();
+        assert_eq!(days, [day(3)]);
+        assert_eq!(Sqlite::open(dir.path()).unwrap().games().unwrap().len(), 2);
+    }
+}
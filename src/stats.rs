@@ -0,0 +1,395 @@
+use std::{collections::HashMap, path::Path, time::Duration};
+
+use chrono::{Days, NaiveDate};
+
+use crate::{
+    analysis::{self, Frequencies},
+    app::App,
+    date_arg,
+    error::{Result, WordleError},
+    format_duration,
+    index::WordIndex,
+    output::{self, Event, OutputFormat, Solve},
+    progress::{self, SaveFile, SavedGame},
+    rules::Rules,
+    score, share,
+};
+
+/// How many of the fastest solves are listed.
+static PERSONAL_BESTS: usize = 5;
+
+/// How many openers are listed, of yours and of the answer list's.
+static OPENERS: usize = 5;
+
+/// How often an opener has to have been played to count among your best.
+static OPENER_MIN_PLAYED: usize = 3;
+
+/// How a first guess did over the games it opened.
+#[derive(Debug, PartialEq)]
+pub struct Opener {
+    pub word: String,
+    pub played: usize,
+    pub won: usize,
+    /// Guesses over the won games.
+    pub guesses: usize,
+}
+
+impl Opener {
+    pub fn win_percentage(&self) -> usize {
+        (self.won * 100).checked_div(self.played).unwrap_or(0)
+    }
+
+    pub fn average_guesses(&self) -> Option<f64> {
+        (self.won > 0).then(|| self.guesses as f64 / self.won as f64)
+    }
+}
+
+pub struct Stats {
+    pub played: usize,
+    pub won: usize,
+    pub current_streak: usize,
+    pub max_streak: usize,
+    /// How many finished games used hints.
+    pub assisted: usize,
+    /// How many finished games had no hints or other help.
+    pub clean: usize,
+    /// How many finished games were played with `--training`.
+    pub training: usize,
+    /// How many games were won in 1, 2, 3... guesses.
+    pub distribution: Vec<usize>,
+    /// The fastest timed wins, with how many guesses they took.
+    pub fastest: Vec<(NaiveDate, Duration, usize)>,
+    /// The median time spent on a guess, over the finished games that timed
+    /// their guesses.
+    pub median_think: Option<Duration>,
+    /// Every first guess of the finished games, the ones played most first.
+    /// Games imported from share texts have no letters and are left out.
+    pub openers: Vec<Opener>,
+    /// The answers that reveal the most as openers, with the greens and
+    /// yellows they're expected to get. Empty until [`Stats::rank_openers`].
+    pub strong_openers: Vec<(String, f64)>,
+    /// The greens and yellows your most played opener is expected to get.
+    pub usual_opener_reveals: Option<f64>,
+    /// The points of the finished games, as [`score::score`] gives them.
+    pub score: u32,
+}
+
+impl Stats {
+    /// Tallies finished games (oldest first). A streak is a run of wins on
+    /// consecutive days, and only counts as current if it reaches `today` or
+    /// the day before.
+    pub fn from_games(games: &[(NaiveDate, SavedGame)], today: NaiveDate) -> Self {
+        let mut stats = Stats { played: 0, won: 0, current_streak: 0, max_streak: 0, assisted: 0, clean: 0, training: 0, distribution: vec![], fastest: vec![], median_think: None, openers: vec![], strong_openers: vec![], usual_opener_reveals: None, score: 0 };
+        let mut think_times = vec![];
+        let mut openers: HashMap<String, Opener> = HashMap::new();
+        let mut streak = 0;
+        let mut last_day: Option<NaiveDate> = None;
+
+        for (day, game) in games.iter().filter(|(_, game)| game.finished) {
+            stats.played += 1;
+            stats.score += score::score(&game.char_guesses, game.won(), Rules::default().tries.saturating_sub(game.hint_tries));
+            think_times.extend(game.think_times());
+            if game.assisted() {
+                stats.assisted += 1;
+            }
+            if game.clean() {
+                stats.clean += 1;
+            }
+            if game.training() {
+                stats.training += 1;
+            }
+            let first: String = game.char_guesses.first().map(|row| row.iter().map(|cg| cg.ch).collect()).unwrap_or_default();
+            // A training game's opener wasn't yours.
+            let mut opener = match first.is_empty() || first.contains('?') || game.training() {
+                true => None,
+                false => Some(openers.entry(first.clone()).or_insert_with(|| Opener { word: first, played: 0, won: 0, guesses: 0 })),
+            };
+            if let Some(opener) = &mut opener {
+                opener.played += 1;
+            }
+            let follows_last = last_day.and_then(|d| d.checked_add_days(Days::new(1))) == Some(*day);
+            if game.won() {
+                stats.won += 1;
+                streak = if follows_last { streak + 1 } else { 1 };
+                let guesses = game.char_guesses.len();
+                if stats.distribution.len() < guesses {
+                    stats.distribution.resize(guesses, 0);
+                }
+                stats.distribution[guesses - 1] += 1;
+                if let Some(opener) = opener {
+                    opener.won += 1;
+                    opener.guesses += guesses;
+                }
+                if game.elapsed_ms > 0 {
+                    stats.fastest.push((*day, game.elapsed(), guesses));
+                }
+            } else {
+                streak = 0;
+            }
+            stats.max_streak = stats.max_streak.max(streak);
+            last_day = Some(*day);
+        }
+
+        stats.fastest.sort_by_key(|(day, took, _)| (*took, *day));
+        stats.fastest.truncate(PERSONAL_BESTS);
+        think_times.sort();
+        stats.median_think = think_times.get(think_times.len() / 2).copied();
+        stats.openers = openers.into_values().collect();
+        stats.openers.sort_by(|a, b| b.played.cmp(&a.played).then_with(|| a.word.cmp(&b.word)));
+
+        let yesterday = today.checked_sub_days(Days::new(1));
+        if last_day == Some(today) || last_day == yesterday {
+            stats.current_streak = streak;
+        }
+        stats
+    }
+
+    pub fn win_percentage(&self) -> usize {
+        (self.won * 100).checked_div(self.played).unwrap_or(0)
+    }
+
+    pub fn average_score(&self) -> Option<f64> {
+        (self.played > 0).then(|| self.score as f64 / self.played as f64)
+    }
+
+    /// The openers played often enough to tell, the ones winning most and
+    /// then in the fewest guesses first.
+    pub fn best_openers(&self) -> Vec<&Opener> {
+        let mut best: Vec<&Opener> = self.openers.iter().filter(|o| o.played >= OPENER_MIN_PLAYED).collect();
+        best.sort_by(|a, b| {
+            b.win_percentage().cmp(&a.win_percentage())
+                .then_with(|| a.average_guesses().unwrap_or(f64::MAX).total_cmp(&b.average_guesses().unwrap_or(f64::MAX)))
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        best.truncate(OPENERS);
+        best
+    }
+
+    /// Works out which answers make the strongest openers, and how your most
+    /// played one compares.
+    pub fn rank_openers(&mut self, answers: &WordIndex) {
+        let length = self.openers.first().map_or(5, |o| o.word.len());
+        let frequencies = Frequencies::of(answers, length);
+        self.strong_openers = analysis::openers(&frequencies, answers).into_iter()
+            .take(OPENERS)
+            .map(|(word, greens, yellows)| (word.clone(), greens + yellows))
+            .collect();
+        self.usual_opener_reveals = self.openers.first().map(|o| {
+            let (greens, yellows) = frequencies.expected(&o.word);
+            greens + yellows
+        });
+    }
+
+    pub fn event(&self) -> Event<'_> {
+        Event::Stats {
+            played: self.played,
+            won: self.won,
+            win_percentage: self.win_percentage(),
+            current_streak: self.current_streak,
+            max_streak: self.max_streak,
+            assisted: self.assisted,
+            clean: self.clean,
+            training: self.training,
+            distribution: &self.distribution,
+            fastest: self.fastest.iter()
+                .map(|&(day, took, guesses)| Solve { day, elapsed_ms: took.as_millis(), guesses })
+                .collect(),
+            median_think_ms: self.median_think.map(|took| took.as_millis()),
+            best_openers: self.best_openers().into_iter()
+                .map(|o| output::Opener { word: &o.word, played: o.played, win_percentage: o.win_percentage(), average_guesses: o.average_guesses() })
+                .collect(),
+            strong_openers: self.strong_openers.iter()
+                .map(|(word, reveals)| output::StrongOpener { word, reveals: *reveals })
+                .collect(),
+            average_score: self.average_score(),
+        }
+    }
+
+    /// Prints the stats, with the average score if `scoring`.
+    pub fn display(&self, scoring: bool) {
+        println!("Played  Win %  Current streak  Max streak");
+        println!("{:>6}  {:>5}  {:>14}  {:>10}", self.played, self.win_percentage(), self.current_streak, self.max_streak);
+        if self.assisted > 0 {
+            println!("{} of them played with hints", self.assisted);
+        }
+        if self.clean < self.played {
+            println!("{} of them played clean, without hints, suggestions or counts of what's left", self.clean);
+        }
+        if self.training > 0 {
+            println!("{} of them training games, with the first row played for you", self.training);
+        }
+
+        if scoring && let Some(average) = self.average_score() {
+            println!("Average score: {:.1}", average);
+        }
+        if let Some(took) = self.median_think {
+            println!("Median time per guess: {}", format_duration(took));
+        }
+
+        if !self.distribution.is_empty() {
+            println!();
+            println!("Guess distribution");
+            let most = self.distribution.iter().copied().max().unwrap_or(0).max(1);
+            for (i, &count) in self.distribution.iter().enumerate() {
+                println!("{} | {} {}", i + 1, "#".repeat(count * 30 / most), count);
+            }
+        }
+
+        if !self.fastest.is_empty() {
+            println!();
+            println!("Fastest solves");
+            for (i, (day, took, guesses)) in self.fastest.iter().enumerate() {
+                println!("{}. {}  {:>5}  in {} guesses", i + 1, day, format_duration(*took), guesses);
+            }
+        }
+        self.display_openers();
+    }
+
+    fn display_openers(&self) {
+        let best = self.best_openers();
+        if !best.is_empty() {
+            println!();
+            println!("Your best openers");
+            for (i, opener) in best.iter().enumerate() {
+                let average = opener.average_guesses().map_or_else(|| "-".to_string(), |a| format!("{:.1}", a));
+                println!("{}. {}  {:>3} played  {:>3}% won  {} guesses on average", i + 1, opener.word, opener.played, opener.win_percentage(), average);
+            }
+        }
+        if self.strong_openers.is_empty() {
+            return;
+        }
+        println!();
+        let words: Vec<&str> = self.strong_openers.iter().map(|(word, _)| word.as_str()).collect();
+        println!("Strong openers from the answer list: {}", words.join(", "));
+        if let (Some(usual), Some(reveals), Some((strongest, most))) = (self.openers.first(), self.usual_opener_reveals, self.strong_openers.first())
+            && reveals < *most
+            && !words.contains(&usual.word.as_str())
+        {
+            println!(
+                "Your usual opener, {}, gets {:.1} greens and yellows on average. Opening with {} gets {:.1}.",
+                usual.word, reveals, strongest, most,
+            );
+        }
+    }
+}
+
+fn status_event(day: NaiveDate, game: Option<&SavedGame>) -> Event<'static> {
+    Event::Status {
+        day,
+        finished: game.is_some_and(|g| g.finished),
+        won: game.is_some_and(|g| g.finished && g.won()),
+        guesses: game.map_or(0, |g| g.char_guesses.len()),
+        elapsed_ms: game.map_or(0, |g| g.elapsed_ms),
+    }
+}
+
+/// Shows the grid of a day that was already finished, and the stats, in
+/// place of playing it again. The grid has no letters so it doesn't give
+/// the answer away to someone looking over your shoulder.
+pub fn finished(app: &App, data_dir: &Path, day: NaiveDate) -> Result<()> {
+    let format = app.output;
+    let game = SaveFile::for_day(data_dir, day).load()?.unwrap_or_default();
+    match format {
+        OutputFormat::Json => output::emit(&status_event(day, Some(&game))),
+        OutputFormat::Plain => {
+            let outcome = match game.won() {
+                true => format!("solved it in {}", game.char_guesses.len()),
+                false => "didn't solve it".to_string(),
+            };
+            println!("You already played the wordle for {} and {}. Pass --replay to play it again.", day, outcome);
+            println!();
+            println!("{}", share::grid(&game.char_guesses));
+            println!();
+        },
+    }
+    show(app, data_dir)
+}
+
+/// Prints how today's game went, failing if it isn't finished.
+pub fn status(data_dir: &Path, format: OutputFormat) -> Result<()> {
+    let today = date_arg::today();
+    let game = SaveFile::for_day(data_dir, today).load()?;
+    if format == OutputFormat::Json {
+        output::emit(&status_event(today, game.as_ref()));
+    }
+    match game {
+        Some(game) if game.finished => {
+            let outcome = match game.won() {
+                true => format!("won in {}", game.char_guesses.len()),
+                false => "lost".to_string(),
+            };
+            if format == OutputFormat::Plain {
+                println!("Wordle {}: {} ({})", today, outcome, format_duration(game.elapsed()));
+            }
+            Ok(())
+        },
+        game => Err(WordleError::Unfinished(today, game.map_or(0, |g| g.char_guesses.len()))),
+    }
+}
+
+/// Prints the stats of the games in `data_dir`, with the strong openers of
+/// the answer list when there is one.
+pub fn show(app: &App, data_dir: &Path) -> Result<()> {
+    let games = progress::saved_games(data_dir)?;
+    let mut stats = Stats::from_games(&games, date_arg::today());
+    if app.has_answers() && let Some(answers) = app.answers()? {
+        stats.rank_openers(answers);
+    }
+    match app.output {
+        OutputFormat::Plain => stats.display(app.config.score),
+        OutputFormat::Json => output::emit(&stats.event()),
+    }
+    Ok(())
+}
+
+/// Fixes the saved games in `data_dir`, saying what was wrong.
+pub fn rebuild(data_dir: &Path, dry_run: bool) -> Result<()> {
+    let problems = progress::rebuild(data_dir, date_arg::today(), dry_run)?;
+    for problem in &problems {
+        match (dry_run, problem.fixable()) {
+            (true, true) => eprintln!("Would fix: {}.", problem),
+            (false, true) => eprintln!("Fixed: {}.", problem),
+            (_, false) => eprintln!("Note: {}.", problem),
+        }
+    }
+    let fixable = problems.iter().filter(|p| p.fixable()).count();
+    match (fixable, dry_run) {
+        (0, _) => eprintln!("The saved games are in order."),
+        (n, true) => eprintln!("{} problems found. Run it without --dry-run to fix them.", n),
+        (n, false) => eprintln!("{} problems fixed.", n),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharGuess, CharGuessKind};
+
+    fn game(day: u64, rows: &[&str], won: bool) -> (NaiveDate, SavedGame) {
+        let kind = |last| if won && last { CharGuessKind::Correct } else { CharGuessKind::NotInWord };
+        let char_guesses = rows.iter().enumerate()
+            .map(|(i, row)| row.chars().map(|ch| CharGuess::new(ch, kind(i + 1 == rows.len()))).collect())
+            .collect();
+        (NaiveDate::from_ymd_opt(2025, 1, 1).unwrap() + Days::new(day), SavedGame { char_guesses, finished: true, ..SavedGame::default() })
+    }
+
+    #[test]
+    fn ranks_openers_played_often_enough_by_how_they_did() {
+        let games = [
+            game(0, &["crane", "slate"], true),
+            game(1, &["crane", "plant", "blunt"], true),
+            game(2, &["crane", "x", "x", "x", "x", "x"], false),
+            game(3, &["adieu", "touch"], true),
+            game(4, &["adieu", "mouth", "south"], true),
+            game(5, &["adieu", "youth"], true),
+            game(6, &["soare"], true),
+            game(7, &["?????", "?????"], true),
+        ];
+        let stats = Stats::from_games(&games, NaiveDate::from_ymd_opt(2025, 1, 8).unwrap());
+        let played: Vec<(&str, usize)> = stats.openers.iter().map(|o| (o.word.as_str(), o.played)).collect();
+        assert_eq!(played, [("adieu", 3), ("crane", 3), ("soare", 1)]);
+        let best: Vec<(&str, usize, Option<f64>)> = stats.best_openers().iter().map(|o| (o.word.as_str(), o.win_percentage(), o.average_guesses())).collect();
+        assert_eq!(best, [("adieu", 100, Some(7.0 / 3.0)), ("crane", 66, Some(2.5))]);
+    }
+}
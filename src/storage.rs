@@ -0,0 +1,66 @@
+//! Where saved games are kept. By default it's a JSON file a day in the
+//! data dir, which is easy to read, back up and sync. With the `sqlite`
+//! feature and `storage = "sqlite"` in the config file they're kept in a
+//! database instead, which answers questions about a range of days without
+//! reading years of files.
+
+use std::{path::Path, sync::OnceLock};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{
+    error::Result,
+    progress::{JsonFiles, Problem, SavedGame},
+};
+
+/// Keeps the saved games of one data dir, a game a day.
+pub trait Storage {
+    fn load(&self, day: NaiveDate) -> Result<Option<SavedGame>>;
+
+    fn save(&self, day: NaiveDate, game: &SavedGame) -> Result<()>;
+
+    /// Every saved game, oldest first.
+    fn games(&self) -> Result<Vec<(NaiveDate, SavedGame)>>;
+
+    /// The saved games from `first` to `last`, oldest first.
+    fn games_between(&self, first: NaiveDate, last: NaiveDate) -> Result<Vec<(NaiveDate, SavedGame)>> {
+        Ok(self.games()?.into_iter().filter(|(day, _)| (first..=last).contains(day)).collect())
+    }
+
+    /// Makes the saved games take less space.
+    fn compact(&self) -> Result<()>;
+
+    /// Fixes what would throw the stats off, or only lists it if `dry_run`.
+    /// Days after `today` are pointed out.
+    fn rebuild(&self, today: NaiveDate, dry_run: bool) -> Result<Vec<Problem>>;
+}
+
+#[derive(clap::ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    /// A JSON file a day
+    #[default]
+    Json,
+    /// An SQLite database, for builds with the sqlite feature
+    Sqlite,
+}
+
+/// The backend picked in the config file.
+static BACKEND: OnceLock<Backend> = OnceLock::new();
+
+/// Makes [`open`] use `backend`. Only the first call counts.
+pub fn set_backend(backend: Backend) {
+    let _ = BACKEND.set(backend);
+}
+
+/// The saved games of `data_dir`, in the backend picked.
+pub fn open(data_dir: &Path) -> Result<Box<dyn Storage>> {
+    match BACKEND.get().copied().unwrap_or_default() {
+        Backend::Json => Ok(Box::new(JsonFiles::new(data_dir))),
+        #[cfg(feature = "sqlite")]
+        Backend::Sqlite => Ok(Box::new(crate::sqlite::Sqlite::open(data_dir)?)),
+        #[cfg(not(feature = "sqlite"))]
+        Backend::Sqlite => Err(crate::error::WordleError::NoSqlite),
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{CharGuess, CharGuessKind, WORD_LENGTHS, constraints::Constraints, index::WordIndex};
+
+/// How guesses are ranked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Strategy {
+    /// Prefer letters that split the remaining answers evenly
+    Frequency,
+    /// Prefer guesses with the most expected information (slower)
+    Entropy,
+}
+
+/// How many of the best frequency-ranked guesses get their entropy computed.
+static ENTROPY_POOL: usize = 500;
+
+/// The next guesses worth trying, given the feedback so far.
+pub struct Suggestions {
+    /// The most informative guess in the whole dictionary.
+    pub best_overall: Option<String>,
+    /// The most informative guess that hard mode allows.
+    pub best_legal: Option<String>,
+    /// How many dictionary words are still possible answers.
+    pub candidates: usize,
+}
+
+/// The grade of `guess` against `answer` packed into a base-3 number (one
+/// digit per letter: 0 gray, 1 yellow, 2 green), computed without allocating
+/// so it can be run over whole dictionaries.
+pub fn pattern_code(guess: &[u8], answer: &[u8]) -> u32 {
+    let mut left = [0u8; 26];
+    let mut digits = [0u8; 16];
+    for (i, (&g, &a)) in guess.iter().zip(answer).enumerate().take(digits.len()) {
+        if g == a {
+            digits[i] = 2;
+        } else if let Some(count) = left.get_mut(a.wrapping_sub(b'a') as usize) {
+            *count += 1;
+        }
+    }
+    for (i, &g) in guess.iter().enumerate().take(digits.len()) {
+        if digits[i] != 2
+            && let Some(count) = left.get_mut(g.wrapping_sub(b'a') as usize)
+            && *count > 0
+        {
+            digits[i] = 1;
+            *count -= 1;
+        }
+    }
+    digits.iter().take(guess.len()).rev().fold(0, |code, &d| code * 3 + d as u32)
+}
+
+/// The [`pattern_code`] of feedback that was already graded.
+pub fn kinds_code(kinds: impl DoubleEndedIterator<Item = CharGuessKind>) -> u32 {
+    kinds.rev().fold(0, |code, kind| code * 3 + match kind {
+        CharGuessKind::NotInWord => 0,
+        CharGuessKind::WrongPlace => 1,
+        CharGuessKind::Correct => 2,
+    })
+}
+
+/// The feedback of `len` letters that [`kinds_code`] packed into `code`.
+pub fn decode_kinds(mut code: u32, len: usize) -> Vec<CharGuessKind> {
+    (0..len).map(|_| {
+        let kind = match code % 3 {
+            0 => CharGuessKind::NotInWord,
+            1 => CharGuessKind::WrongPlace,
+            _ => CharGuessKind::Correct,
+        };
+        code /= 3;
+        kind
+    }).collect()
+}
+
+fn row_code(cgs: &[CharGuess]) -> u32 {
+    kinds_code(cgs.iter().map(|cg| cg.kind))
+}
+
+/// Whether `word` would have produced exactly the feedback seen so far.
+pub fn is_consistent(word: &str, char_guesses: &[Vec<CharGuess>]) -> bool {
+    char_guesses.iter().all(|cgs| {
+        let guess: String = cgs.iter().map(|cg| cg.ch).collect();
+        guess.len() == word.len() && pattern_code(guess.as_bytes(), word.as_bytes()) == row_code(cgs)
+    })
+}
+
+/// The dictionary words that are still possible answers, sorted.
+pub fn candidates<'a>(char_guesses: &[Vec<CharGuess>], dictionary: &'a WordIndex) -> Vec<&'a String> {
+    dictionary.fitting(char_guesses)
+}
+
+/// One graded guess, as typed on the command line.
+#[derive(Clone, Debug)]
+pub enum Feedback {
+    Known(Vec<CharGuess>),
+    /// Only the colors are known, e.g. from a shared emoji grid.
+    Unknown(Vec<CharGuessKind>),
+}
+
+/// Parses one row of feedback written as `guess=pattern`, e.g. `crane=bgybb`,
+/// where `g` is green, `y` is yellow and `b` (or `x`, `.`) is gray. A guess of
+/// `?` means the colors are known but not the letters.
+pub fn parse_feedback(row: &str) -> Result<Feedback, String> {
+    let Some((word, pattern)) = row.split_once('=') else {
+        return Err(format!("expected <guess>=<pattern>, e.g. crane=bgybb, got '{}'", row));
+    };
+    let word = word.trim().to_ascii_lowercase();
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let kinds = pattern.chars().map(|p| match p {
+        'g' => Ok(CharGuessKind::Correct),
+        'y' => Ok(CharGuessKind::WrongPlace),
+        'b' | 'x' | '.' => Ok(CharGuessKind::NotInWord),
+        _ => Err(format!("unknown feedback '{}', use g, y or b", p)),
+    }).collect::<Result<Vec<_>, _>>()?;
+
+    if !WORD_LENGTHS.contains(&kinds.len()) {
+        return Err(format!("'{}' isn't feedback for a 4 to 8 letter word", pattern));
+    }
+    if word == "?" {
+        return Ok(Feedback::Unknown(kinds));
+    }
+    if word.chars().count() != kinds.len() {
+        return Err(format!("'{}' and '{}' have different lengths", word, pattern));
+    }
+    word.chars().zip(kinds).map(|(ch, kind)| {
+        if !ch.is_ascii_lowercase() {
+            return Err(format!("'{}' is not a letter", ch));
+        }
+        Ok(CharGuess::new(ch, kind))
+    }).collect::<Result<_, _>>().map(Feedback::Known)
+}
+
+/// Scores a guess by how evenly its letters split the remaining candidates,
+/// so a letter found in every candidate (or none) is worth nothing.
+fn letter_frequency_score(word: &str, frequencies: &[usize; 26], candidates: usize, is_candidate: bool) -> f64 {
+    let mut seen = [false; 26];
+    let mut score = 0;
+    for ch in word.chars().filter(char::is_ascii_lowercase) {
+        let i = (ch as u8 - b'a') as usize;
+        if !seen[i] {
+            seen[i] = true;
+            score += frequencies[i].min(candidates - frequencies[i]);
+        }
+    }
+    // Break ties towards words that could win outright.
+    (score * 2 + is_candidate as usize) as f64
+}
+
+/// The expected number of bits of information the guess reveals about the
+/// candidates, plus a small bonus for being able to win outright.
+fn entropy_score(word: &str, candidates: &[&String], is_candidate: bool) -> f64 {
+    let mut buckets: HashMap<u32, usize> = HashMap::new();
+    for answer in candidates {
+        *buckets.entry(pattern_code(word.as_bytes(), answer.as_bytes())).or_default() += 1;
+    }
+    let total = candidates.len() as f64;
+    let entropy: f64 = buckets.values().map(|&n| {
+        let p = n as f64 / total;
+        -p * p.log2()
+    }).sum();
+    entropy + if is_candidate { 1.0 / total } else { 0.0 }
+}
+
+fn rank_by_frequency<'a>(candidates: &[&String], dictionary: &'a WordIndex) -> Vec<(&'a String, f64)> {
+    let mut frequencies = [0; 26];
+    for word in candidates {
+        let mut seen = [false; 26];
+        for ch in word.chars().filter(char::is_ascii_lowercase) {
+            let i = (ch as u8 - b'a') as usize;
+            if !seen[i] {
+                seen[i] = true;
+                frequencies[i] += 1;
+            }
+        }
+    }
+
+    let candidate_set: HashSet<&String> = candidates.iter().copied().collect();
+    let mut scored: Vec<(&String, f64)> = dictionary.iter()
+        .map(|w| (w, letter_frequency_score(w, &frequencies, candidates.len(), candidate_set.contains(w))))
+        .collect();
+    sort_scored(&mut scored);
+    scored
+}
+
+fn sort_scored(scored: &mut [(&String, f64)]) {
+    scored.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then(a.cmp(b)));
+}
+
+/// The [`candidates`] among the likely answers, or among all allowed guesses
+/// when none of the likely answers fit, as NYT sometimes picks a word that
+/// isn't on the list.
+pub fn likely_candidates<'a>(
+    char_guesses: &[Vec<CharGuess>],
+    answers: &'a WordIndex,
+    dictionary: &'a WordIndex,
+) -> Vec<&'a String> {
+    let likely = candidates(char_guesses, answers);
+    match likely.is_empty() {
+        true => candidates(char_guesses, dictionary),
+        false => likely,
+    }
+}
+
+/// How many likely answers of `length` letters were possible before the
+/// first guess and after each one, to show how much every guess narrowed
+/// them down.
+pub fn remaining(char_guesses: &[Vec<CharGuess>], length: usize, answers: &WordIndex, dictionary: &WordIndex) -> Vec<usize> {
+    let before = answers.iter().filter(|w| w.chars().count() == length).count();
+    let after = (1..=char_guesses.len()).map(|n| likely_candidates(&char_guesses[..n], answers, dictionary).len());
+    std::iter::once(before).chain(after).collect()
+}
+
+/// Every dictionary word worth guessing next, best first, with its score.
+///
+/// With two or fewer candidates left the candidates themselves are the best
+/// guesses. Entropy is only computed for the [`ENTROPY_POOL`] best guesses by
+/// letter frequency, which keeps it fast on the full dictionary.
+pub fn rank<'a>(
+    char_guesses: &[Vec<CharGuess>],
+    answers: &'a WordIndex,
+    dictionary: &'a WordIndex,
+    strategy: Strategy,
+) -> Vec<(&'a String, f64)> {
+    rank_candidates(likely_candidates(char_guesses, answers, dictionary), dictionary, strategy)
+}
+
+/// Like [`rank`], for candidates that were narrowed down some other way.
+pub fn rank_candidates<'a>(
+    candidates: Vec<&'a String>,
+    dictionary: &'a WordIndex,
+    strategy: Strategy,
+) -> Vec<(&'a String, f64)> {
+    if candidates.len() <= 2 {
+        return candidates.into_iter().map(|w| (w, 1.0)).collect();
+    }
+
+    let by_frequency = rank_by_frequency(&candidates, dictionary);
+    match strategy {
+        Strategy::Frequency => by_frequency,
+        Strategy::Entropy => {
+            let candidate_set: HashSet<&String> = candidates.iter().copied().collect();
+            let mut scored: Vec<(&String, f64)> = by_frequency.into_iter()
+                .take(ENTROPY_POOL)
+                .map(|(w, _)| (w, entropy_score(w, &candidates, candidate_set.contains(w))))
+                .collect();
+            sort_scored(&mut scored);
+            scored
+        },
+    }
+}
+
+pub fn suggest(char_guesses: &[Vec<CharGuess>], answers: &WordIndex, dictionary: &WordIndex) -> Suggestions {
+    let ranked = rank(char_guesses, answers, dictionary, Strategy::Frequency);
+    let constraints = Constraints::from_guesses(char_guesses);
+
+    Suggestions {
+        best_overall: ranked.first().map(|(w, _)| w.to_string()),
+        best_legal: ranked.iter().find(|(w, _)| constraints.allows(w)).map(|(w, _)| w.to_string()),
+        candidates: likely_candidates(char_guesses, answers, dictionary).len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn feedback_that_parses_packs_and_unpacks(row in "[a-z?]{0,12}=[gyb.x]{0,12}|\\PC*") {
+            if let Ok(Feedback::Known(cgs)) = parse_feedback(&row) {
+                let kinds: Vec<CharGuessKind> = cgs.iter().map(|cg| cg.kind).collect();
+                prop_assert_eq!(decode_kinds(kinds_code(kinds.iter().copied()), kinds.len()), kinds);
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Playing on stream without giving the answer away to the people watching.
+
+use std::{
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use ratatui::crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+
+use crate::{app::App, error::WordleError, i18n::tr, terminal};
+
+/// The key pressed to uncover a spoiler, or `None` for Esc or a terminal that
+/// can't be read a key from.
+fn read_key() -> io::Result<Option<KeyCode>> {
+    enable_raw_mode()?;
+    let key = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => break Ok(key.code),
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        }
+    };
+    disable_raw_mode()?;
+    Ok(Some(key?).filter(|&code| code != KeyCode::Esc))
+}
+
+/// Prints `line`, which gives the answer away. With `--streamer` it's
+/// covered until a key is pressed, and stays covered when there's no one at
+/// the terminal to press one.
+pub fn spoiler(app: &App, line: &str) {
+    if !app.config.streamer {
+        println!("{}", line);
+        return;
+    }
+    print!("{}", tr!("spoiler"));
+    let _ = io::stdout().flush();
+    let uncovered = io::stdin().is_terminal() && matches!(read_key(), Ok(Some(_)));
+    uncover(uncovered, line);
+}
+
+fn uncover(uncovered: bool, line: &str) {
+    match (uncovered, terminal::escapes()) {
+        // Clears the cover so only the line is left.
+        (true, true) => println!("\r\x1b[2K{}", line),
+        (true, false) => println!("\n{}", line),
+        (false, _) => println!(),
+    }
+}
+
+/// Prints `line`, the answer of a game that was lost, as [`spoiler`] does.
+/// For someone at the terminal it's shown `delay` later, and with `hold`
+/// only once they press a key for it, so no one looking on sees it first.
+pub fn reveal(app: &App, line: &str, hold: bool, delay: Duration) {
+    let at_terminal = io::stdin().is_terminal();
+    if at_terminal && !delay.is_zero() {
+        eprintln!("{}", tr!("revealing-in", seconds = delay.as_secs()));
+        thread::sleep(delay);
+    }
+    if app.config.streamer || !hold || !at_terminal {
+        return spoiler(app, line);
+    }
+    print!("{}", tr!("hold-reveal"));
+    let _ = io::stdout().flush();
+    // Esc still shows it, the word is what a lost game ends on.
+    uncover(read_key().is_ok(), line);
+}
+
+/// Asks `question` and waits for a key, taking y (or j) as a yes.
+pub fn confirm(question: &str) -> bool {
+    print!("{} ", question);
+    let _ = io::stdout().flush();
+    let yes = matches!(read_key(), Ok(Some(KeyCode::Char('y' | 'Y' | 'j' | 'J'))));
+    println!("{}", if yes { tr!("yes") } else { tr!("no") });
+    yes
+}
+
+/// `path` with the cache dir in it shortened to `<cache>`.
+fn hidden(path: &Path, cache_dir: &Path) -> PathBuf {
+    path.strip_prefix(cache_dir).map_or_else(|_| path.to_path_buf(), |rest| Path::new("<cache>").join(rest))
+}
+
+/// `e` without the path of the cached word it's about, which is where the
+/// answer can be read.
+pub fn hide_paths(e: WordleError, cache_dir: &Path) -> WordleError {
+    match e {
+        WordleError::CorruptWord(path) => WordleError::CorruptWord(hidden(&path, cache_dir)),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hides_where_the_cache_is() {
+        let e = hide_paths(WordleError::CorruptWord(PathBuf::from("/home/me/.cache/wordle-rs/words/2025-01-01")), Path::new("/home/me/.cache/wordle-rs"));
+        assert_eq!(e.to_string(), "The cached word in <cache>/words/2025-01-01 is not a 5 letter word.");
+    }
+}
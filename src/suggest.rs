@@ -0,0 +1,59 @@
+//! "Did you mean" suggestions for guesses that aren't in the dictionary.
+
+use crate::index::WordIndex;
+
+/// Words further than this many edits from a guess aren't worth suggesting.
+static MAX_DISTANCE: usize = 2;
+
+/// How many letters have to be inserted, removed or changed to turn `a`
+/// into `b`, counting two neighbouring letters swapped as one change since
+/// that's the typo people make most.
+pub fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let above = &rows[i - 1];
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (above[j] + 1).min(row[j - 1] + 1).min(above[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Up to `n` dictionary words closest to `guess`, nearest first and
+/// alphabetically among words equally far.
+pub fn nearest(guess: &str, dictionary: &WordIndex, n: usize) -> Vec<String> {
+    let mut close: Vec<(usize, &String)> = dictionary.iter()
+        .map(|word| (distance(guess, word), word))
+        .filter(|&(d, _)| d <= MAX_DISTANCE)
+        .collect();
+    close.sort();
+    close.into_iter().take(n).map(|(_, word)| word.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_swaps_as_one_edit() {
+        assert_eq!(distance("crane", "crane"), 0);
+        assert_eq!(distance("crnae", "crane"), 1);
+        assert_eq!(distance("crame", "crane"), 1);
+        assert_eq!(distance("slate", "crane"), 3);
+    }
+
+    #[test]
+    fn suggests_the_closest_words_first() {
+        let dictionary: WordIndex = ["crane", "crate", "trace", "slate", "crank"].map(str::to_string).into_iter().collect();
+        assert_eq!(nearest("crame", &dictionary, 3), ["crane", "crate", "crank"]);
+        assert_eq!(nearest("xxxxx", &dictionary, 3), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,266 @@
+//! `sync`: keeping the saved games of several computers the same through a
+//! git repository or a WebDAV folder, so streaks follow you from one to the
+//! other. Stats are tallied from the saved games, so they follow too.
+//!
+//! The remote has one file with every game. For a day saved on both sides
+//! the one saved last is kept, and games saved before there was a sync are
+//! all older than any saved since.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use chrono::NaiveDate;
+use reqwest::{StatusCode, Url, header};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::App,
+    cli::SyncArgs,
+    error::{Context, Result, WordleError},
+    progress::SavedGame,
+    storage,
+};
+
+/// The file on the remote with every game.
+static FILE: &str = "wordle-rs-games.json";
+
+/// How many times to start over when another computer synced in between.
+static ATTEMPTS: usize = 3;
+
+#[derive(Serialize, Deserialize, Default)]
+struct Games {
+    games: BTreeMap<NaiveDate, SavedGame>,
+}
+
+/// Where the games are synced to.
+#[derive(Debug, PartialEq)]
+enum Remote {
+    /// A folder on a WebDAV server, e.g. Nextcloud.
+    WebDav(Url),
+    /// A git repository, cloned into the cache dir.
+    Git(String),
+}
+
+impl Remote {
+    /// An http(s) URL is taken as a WebDAV folder unless it ends in `.git`,
+    /// and anything else as what `git clone` takes.
+    fn parse(remote: &str) -> Self {
+        match Url::parse(remote) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && !url.path().ends_with(".git") => Remote::WebDav(url),
+            _ => Remote::Git(remote.to_string()),
+        }
+    }
+}
+
+/// `remote` with any password in it hidden, to show.
+fn shown(remote: &str) -> String {
+    match Url::parse(remote) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some("***"));
+            url.to_string()
+        },
+        _ => remote.to_string(),
+    }
+}
+
+/// `remote` is the [`shown`] one.
+fn failed(remote: &str, reason: impl ToString) -> WordleError {
+    WordleError::Sync { remote: remote.to_string(), reason: reason.to_string() }
+}
+
+fn same(a: &SavedGame, b: &SavedGame) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// Whether `theirs` was saved after `ours`. Without save times the finished
+/// game, or else the longer one, counts as the later.
+fn newer(theirs: &SavedGame, ours: &SavedGame) -> bool {
+    let key = |game: &SavedGame| (game.saved_at, game.finished, game.char_guesses.len());
+    key(theirs) > key(ours)
+}
+
+/// Adds the local games the remote should have to it, and returns the days
+/// to take from it and how many were added.
+fn merge(local: &BTreeMap<NaiveDate, SavedGame>, remote: &mut Games) -> (Vec<NaiveDate>, usize) {
+    let mut pulled = vec![];
+    let mut pushed = 0;
+    for (day, ours) in local {
+        match remote.games.get(day) {
+            Some(theirs) if same(theirs, ours) => (),
+            Some(theirs) if newer(theirs, ours) => pulled.push(*day),
+            _ => {
+                remote.games.insert(*day, ours.clone());
+                pushed += 1;
+            },
+        }
+    }
+    pulled.extend(remote.games.keys().filter(|day| !local.contains_key(day)));
+    pulled.sort();
+    (pulled, pushed)
+}
+
+/// Where the git remote is cloned.
+fn clone_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("sync")
+}
+
+fn git(dir: &Path, remote: &str, args: &[&str]) -> Result<String> {
+    let output = process::Command::new("git").arg("-C").arg(dir).args(args).output()
+        .map_err(|e| failed(remote, format!("couldn't run git: {}", e)))?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        false => Err(failed(remote, String::from_utf8_lossy(&output.stderr).trim())),
+    }
+}
+
+/// The clone of `url`, made or brought up to date with what was pushed.
+fn pull(cache_dir: &Path, url: &str, remote: &str) -> Result<PathBuf> {
+    let dir = clone_dir(cache_dir);
+    let cloned = dir.join(".git").exists() && git(&dir, remote, &["remote", "get-url", "origin"]).is_ok_and(|origin| origin == url);
+    if !cloned {
+        if dir.exists() {
+            fs::remove_dir_all(&dir).context("removing the old sync clone")?;
+        }
+        fs::create_dir_all(cache_dir).context("creating cache dir")?;
+        git(cache_dir, remote, &["clone", "--quiet", url, "sync"])?;
+        return Ok(dir);
+    }
+    git(&dir, remote, &["fetch", "--quiet", "origin"])?;
+    // Nothing was pushed yet when the remote has no branch.
+    if git(&dir, remote, &["rev-parse", "--verify", "--quiet", "@{upstream}"]).is_ok() {
+        git(&dir, remote, &["reset", "--quiet", "--hard", "@{upstream}"])?;
+    }
+    Ok(dir)
+}
+
+fn parse(remote: &str, json: &str) -> Result<Games> {
+    serde_json::from_str(json).map_err(|e| failed(remote, format!("{} isn't a list of games: {}", FILE, e)))
+}
+
+fn to_json(games: &Games) -> String {
+    serde_json::to_string_pretty(games).unwrap_or_default()
+}
+
+/// Syncs with the clone of a git remote. Returns false when someone pushed
+/// first, so it has to be done again.
+fn sync_git(app: &App, url: &str, remote: &str, sync: &mut dyn FnMut(&mut Games) -> Result<bool>) -> Result<bool> {
+    let dir = pull(&app.cache_dir, url, remote)?;
+    let path = dir.join(FILE);
+    let mut games = match fs::read_to_string(&path) {
+        Ok(json) => parse(remote, &json)?,
+        Err(_) => Games::default(),
+    };
+    if !sync(&mut games)? {
+        return Ok(true);
+    }
+    fs::write(&path, to_json(&games)).context("writing the games to sync")?;
+    git(&dir, remote, &["add", FILE])?;
+    git(&dir, remote, &["commit", "--quiet", "-m", "Sync wordle-rs games"])?;
+    match git(&dir, remote, &["push", "--quiet", "--set-upstream", "origin", "HEAD"]) {
+        Ok(_) => Ok(true),
+        Err(WordleError::Sync { reason, .. }) if reason.contains("[rejected]") => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Syncs with a WebDAV folder. Returns false when someone put theirs there
+/// first, so it has to be done again.
+fn sync_webdav(app: &App, url: &Url, remote: &str, sync: &mut dyn FnMut(&mut Games) -> Result<bool>) -> Result<bool> {
+    let mut url = url.clone();
+    let user = url.username().to_string();
+    let password = url.password().map(str::to_string);
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    let url = url.join(FILE).map_err(|e| failed(remote, e))?;
+    let auth = |request: reqwest::blocking::RequestBuilder| match user.is_empty() {
+        true => request,
+        false => request.basic_auth(&user, password.as_deref()),
+    };
+
+    let response = app.client.send_with(|client| auth(client.get(url.clone())), "getting the synced games")?;
+    let (mut games, etag) = match response.status() {
+        StatusCode::NOT_FOUND => (Games::default(), None),
+        status if status.is_success() => {
+            let etag = response.headers().get(header::ETAG).cloned();
+            (parse(remote, &response.text().context("getting the synced games")?)?, etag)
+        },
+        status => return Err(failed(remote, status)),
+    };
+    if !sync(&mut games)? {
+        return Ok(true);
+    }
+    let body = to_json(&games);
+    let response = app.client.send_with(
+        |client| {
+            let request = auth(client.put(url.clone())).body(body.clone());
+            match &etag {
+                Some(etag) => request.header(header::IF_MATCH, etag.clone()),
+                None => request.header(header::IF_NONE_MATCH, "*"),
+            }
+        },
+        "putting the synced games",
+    )?;
+    match response.status() {
+        StatusCode::PRECONDITION_FAILED => Ok(false),
+        status if status.is_success() => Ok(true),
+        status => Err(failed(remote, status)),
+    }
+}
+
+pub fn run(app: &App, args: SyncArgs) -> Result<()> {
+    let url = args.remote.or_else(|| app.config.sync_remote.clone()).ok_or(WordleError::NoSyncRemote)?;
+    let remote = shown(&url);
+    app.client.online("syncing saved games")?;
+    let storage = storage::open(&app.data_dir)?;
+    for _ in 0..ATTEMPTS {
+        let local: BTreeMap<NaiveDate, SavedGame> = storage.games()?.into_iter().collect();
+        let (mut pulled, mut pushed) = (0, 0);
+        let mut sync = |games: &mut Games| {
+            let (days, added) = merge(&local, games);
+            for day in &days {
+                storage.save(*day, &games.games[day])?;
+            }
+            (pulled, pushed) = (days.len(), added);
+            Ok(added > 0)
+        };
+        let synced = match Remote::parse(&url) {
+            Remote::WebDav(url) => sync_webdav(app, &url, &remote, &mut sync)?,
+            Remote::Git(url) => sync_git(app, &url, &remote, &mut sync)?,
+        };
+        if !synced {
+            eprintln!("Another computer synced at the same time, trying again.");
+            continue;
+        }
+        eprintln!("Synced with {}: {} games pulled, {} pushed.", remote, pulled, pushed);
+        return Ok(());
+    }
+    Err(failed(&remote, "other computers kept syncing at the same time"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn keeps_the_game_saved_last() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let saved = |hour| SavedGame { saved_at: Utc.with_ymd_and_hms(2025, 1, 5, hour, 0, 0).single(), ..SavedGame::default() };
+        let local = BTreeMap::from([(day(1), saved(9)), (day(2), saved(9)), (day(3), saved(9))]);
+        let mut remote = Games { games: BTreeMap::from([(day(1), saved(8)), (day(2), saved(10)), (day(3), saved(9)), (day(4), saved(9))]) };
+        assert_eq!(merge(&local, &mut remote), (vec![day(2), day(4)], 1));
+        assert_eq!(remote.games[&day(1)].saved_at, saved(9).saved_at);
+
+        assert_eq!(Remote::parse("https://cloud.example.com/dav/wordle"), Remote::WebDav(Url::parse("https://cloud.example.com/dav/wordle").unwrap()));
+        assert_eq!(Remote::parse("https://github.com/me/games.git"), Remote::Git("https://github.com/me/games.git".to_string()));
+        assert_eq!(Remote::parse("git@github.com:me/games.git"), Remote::Git("git@github.com:me/games.git".to_string()));
+    }
+}
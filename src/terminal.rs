@@ -0,0 +1,102 @@
+//! What differs between the terminals of each platform, kept in one place.
+//!
+//! Colors, the animations and clearing the screen are all escape codes,
+//! which a Windows console only understands once virtual terminal processing
+//! is turned on and an old one can't understand at all.
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static ESCAPES: AtomicBool = AtomicBool::new(true);
+
+/// How many lines push the screen out of sight where it can't be cleared.
+static SCROLL_AWAY: usize = 50;
+
+/// Gets the terminal ready for escape codes, turning on virtual terminal
+/// processing on Windows. Returns whether they can be used.
+pub fn prepare() -> bool {
+    #[cfg(windows)]
+    let escapes = colored::control::set_virtual_terminal(true).is_ok();
+    #[cfg(not(windows))]
+    let escapes = true;
+    ESCAPES.store(escapes, Ordering::Relaxed);
+    escapes
+}
+
+/// Whether escape codes work, as found by [`prepare`]. Without them the
+/// animations are left out and screens scrolled away instead of cleared.
+pub fn escapes() -> bool {
+    ESCAPES.load(Ordering::Relaxed)
+}
+
+/// Clears the screen and puts the cursor at the top, or where that can't be
+/// done pushes what was on it out of sight.
+pub fn clear() {
+    match escapes() {
+        true => print!("\x1b[2J\x1b[H"),
+        false => print!("{}", "\n".repeat(SCROLL_AWAY)),
+    }
+}
+
+/// How many columns the terminal has, or `None` when the output isn't going
+/// to one, in which case it's laid out the same whatever the width.
+pub fn width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    ratatui::crossterm::terminal::size().ok()
+        .map(|(columns, _)| columns as usize)
+        .filter(|&columns| columns > 0)
+        .or_else(|| env::var("COLUMNS").ok()?.parse().ok())
+}
+
+/// How many columns `s` takes up, leaving out its escape codes.
+pub fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => {
+                // Colors end in a letter, e.g. \x1b[42m.
+                for ch in chars.by_ref() {
+                    if ch.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            },
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+/// The lines of `left` with the lines of `right` next to them, `gap`
+/// columns after the widest of `left`.
+pub fn side_by_side(left: &[String], right: &[String], gap: usize) -> Vec<String> {
+    let widest = left.iter().map(|line| visible_width(line)).max().unwrap_or(0);
+    (0..left.len().max(right.len()))
+        .map(|i| {
+            let line = left.get(i).map_or("", String::as_str);
+            match right.get(i) {
+                Some(other) => format!("{}{}{}", line, " ".repeat(widest - visible_width(line) + gap), other),
+                None => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_up_colored_columns() {
+        assert_eq!(visible_width("\x1b[42m a \x1b[0m b"), 5);
+        let left = ["\x1b[42mab\x1b[0m".to_string(), "abcd".to_string()];
+        let right = ["x".to_string(), "y".to_string(), "z".to_string()];
+        assert_eq!(side_by_side(&left, &right, 1), ["\x1b[42mab\x1b[0m   x", "abcd y", "     z"]);
+    }
+}
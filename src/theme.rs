@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    CharGuessKind,
+    error::{Result, WordleError},
+};
+
+/// How one kind of graded tile looks.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct TileStyle {
+    #[serde(deserialize_with = "color")]
+    pub background: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub text: Option<Color>,
+    /// Put around the letter, so tiles can be told apart without colors.
+    pub marks: Option<[char; 2]>,
+}
+
+/// How graded tiles look, everywhere a grid is shown.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub not_in_word: TileStyle,
+    pub wrong_place: TileStyle,
+    pub correct: TileStyle,
+}
+
+/// Parses a color name like `green` or `bright black`, or `#rrggbb`.
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<Color>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    if let Some(hex) = name.strip_prefix('#')
+        && hex.len() == 6
+        && let Ok(rgb) = u32::from_str_radix(hex, 16)
+    {
+        return Ok(Some(Color::TrueColor { r: (rgb >> 16) as u8, g: (rgb >> 8) as u8, b: rgb as u8 }));
+    }
+    name.parse()
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("unknown color '{}', use a name like green or #rrggbb", name)))
+}
+
+fn colored_tile(background: Color) -> TileStyle {
+    TileStyle { background: Some(background), text: Some(Color::Black), marks: None }
+}
+
+impl Theme {
+    pub fn classic() -> Self {
+        Self {
+            not_in_word: colored_tile(Color::BrightBlack),
+            wrong_place: colored_tile(Color::Yellow),
+            correct: colored_tile(Color::Green),
+        }
+    }
+
+    /// Orange and blue instead of green and yellow, like the official
+    /// colorblind mode.
+    pub fn high_contrast() -> Self {
+        Self {
+            not_in_word: colored_tile(Color::BrightBlack),
+            wrong_place: colored_tile(Color::TrueColor { r: 0x85, g: 0xc0, b: 0xf9 }),
+            correct: colored_tile(Color::TrueColor { r: 0xf5, g: 0x79, b: 0x3a }),
+        }
+    }
+
+    /// No colors, only marks: `[a]` is correct and `(a)` is in the wrong place.
+    pub fn mono() -> Self {
+        let marked = |marks| TileStyle { background: None, text: None, marks: Some(marks) };
+        Self {
+            not_in_word: marked([' ', ' ']),
+            wrong_place: marked(['(', ')']),
+            correct: marked(['[', ']']),
+        }
+    }
+
+    /// Finds a theme by name, among the built-in ones and those from the
+    /// config file.
+    pub fn named(name: &str, custom: &HashMap<String, Theme>) -> Result<Self> {
+        match name {
+            "classic" => Ok(Self::classic()),
+            "high-contrast" => Ok(Self::high_contrast()),
+            "mono" => Ok(Self::mono()),
+            _ => custom.get(name).cloned().ok_or_else(|| WordleError::UnknownTheme(name.to_string())),
+        }
+    }
+
+    pub fn tile(&self, kind: CharGuessKind) -> &TileStyle {
+        match kind {
+            CharGuessKind::NotInWord => &self.not_in_word,
+            CharGuessKind::WrongPlace => &self.wrong_place,
+            CharGuessKind::Correct => &self.correct,
+        }
+    }
+
+    /// The text of a tile with its marks, if the theme has any.
+    pub fn label(&self, kind: CharGuessKind, s: &str) -> String {
+        match self.tile(kind).marks {
+            Some([left, right]) => format!("{}{}{}", left, s, right),
+            None => s.to_string(),
+        }
+    }
+
+    /// One line telling what each kind of tile means.
+    pub fn legend(&self) -> String {
+        [
+            (CharGuessKind::Correct, "right spot"),
+            (CharGuessKind::WrongPlace, "in the word, wrong spot"),
+            (CharGuessKind::NotInWord, "not in the word"),
+        ].iter()
+            .map(|&(kind, meaning)| format!("{} {}", self.paint(kind, " "), meaning))
+            .collect::<Vec<_>>()
+            .join("   ")
+    }
+
+    pub fn paint(&self, kind: CharGuessKind, s: &str) -> ColoredString {
+        let tile = self.tile(kind);
+        let mut painted = self.label(kind, s).normal();
+        if let Some(background) = tile.background {
+            painted = painted.on_color(background);
+        }
+        if let Some(text) = tile.text {
+            painted = painted.color(text);
+        }
+        painted
+    }
+}
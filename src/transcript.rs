@@ -0,0 +1,325 @@
+//! `--record FILE`: everything needed to play a game again the same way,
+//! for attaching to a bug report. The answer and where it came from, the
+//! rules, a hash of the dictionary and every move with what came of it, the
+//! file written again after each move like a save.
+//!
+//! `--replay-transcript FILE` plays the moves again with nothing typed, and
+//! tells which of them went differently this time, exiting with 11 if any
+//! did. Settings that read more files, like `--no-repeats`, aren't recorded.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CurrentWord, GuessOutcome, boxed, cache,
+    app::App,
+    cli::PlayArgs,
+    engine::{self, Engine, Move, Step},
+    error::{Context, Result, WordleError},
+    hint::Hint,
+    i18n::{self, Lang},
+    index::WordIndex,
+    output, pick_theme,
+    secret::SecretWord,
+};
+
+/// The version of the file, for telling transcripts of a later one.
+const VERSION: u32 = 1;
+
+/// A move, as it was typed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "move", rename_all = "snake_case")]
+pub enum Recorded {
+    Guess { guess: String },
+    Hint,
+    Undo,
+    GiveUp,
+}
+
+impl Recorded {
+    pub fn of(mv: &Move) -> Self {
+        match mv {
+            Move::Guess(guess) => Recorded::Guess { guess: guess.clone() },
+            Move::Hint => Recorded::Hint,
+            Move::Undo => Recorded::Undo,
+            Move::GiveUp => Recorded::GiveUp,
+        }
+    }
+
+    fn to_move(&self) -> Move {
+        match self {
+            Recorded::Guess { guess } => Move::Guess(guess.clone()),
+            Recorded::Hint => Move::Hint,
+            Recorded::Undo => Move::Undo,
+            Recorded::GiveUp => Move::GiveUp,
+        }
+    }
+}
+
+impl fmt::Display for Recorded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Recorded::Guess { guess } => write!(f, "{}", guess),
+            Recorded::Hint => write!(f, ":hint"),
+            Recorded::Undo => write!(f, ":undo"),
+            Recorded::GiveUp => write!(f, ":giveup"),
+        }
+    }
+}
+
+/// What came of a move. Rows are g/y/b letters, see [`output::pattern`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Outcome {
+    Graded { pattern: String },
+    Won { pattern: String },
+    Lost { pattern: String },
+    Invalid { reason: String },
+    Hinted { hint: Hint },
+    Undone { pattern: String },
+    GaveUp,
+    Refused { reason: String },
+    Over,
+}
+
+impl Outcome {
+    pub fn of(step: &Step, current_word: &CurrentWord) -> Self {
+        let last = || current_word.char_guesses.last().map(|cgs| output::pattern(cgs)).unwrap_or_default();
+        match step {
+            Step::Guessed(GuessOutcome::Continue) => Outcome::Graded { pattern: last() },
+            Step::Guessed(GuessOutcome::Win) => Outcome::Won { pattern: last() },
+            Step::Guessed(GuessOutcome::NoTriesLeft) => Outcome::Lost { pattern: last() },
+            Step::Guessed(GuessOutcome::InvalidGuess(kind)) => Outcome::Invalid { reason: kind.message() },
+            Step::Guessed(GuessOutcome::GameOver) | Step::Over => Outcome::Over,
+            Step::Hinted(hint) => Outcome::Hinted { hint: hint.clone() },
+            Step::Undone(row) => Outcome::Undone { pattern: output::pattern(row) },
+            Step::GaveUp => Outcome::GaveUp,
+            Step::Refused(reason) => Outcome::Refused { reason: reason.clone() },
+        }
+    }
+
+    /// Whether this came of a move that `recorded` came of, where with
+    /// `same_lang` off the wording of why doesn't count.
+    fn matches(&self, recorded: &Outcome, same_lang: bool) -> bool {
+        match (self, recorded) {
+            (Outcome::Invalid { .. }, Outcome::Invalid { .. }) | (Outcome::Refused { .. }, Outcome::Refused { .. }) if !same_lang => true,
+            _ => self == recorded,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Graded { pattern } => write!(f, "{}", pattern),
+            Outcome::Won { pattern } => write!(f, "{}, won", pattern),
+            Outcome::Lost { pattern } => write!(f, "{}, lost", pattern),
+            Outcome::Invalid { reason } => write!(f, "turned away: {}", reason),
+            Outcome::Hinted { hint } => write!(f, "{}", hint.message()),
+            Outcome::Undone { pattern } => write!(f, "took back {}", pattern),
+            Outcome::GaveUp => write!(f, "gave up"),
+            Outcome::Refused { reason } => write!(f, "refused: {}", reason),
+            Outcome::Over => write!(f, "the game is over"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Entry {
+    #[serde(flatten)]
+    pub mv: Recorded,
+    pub outcome: Outcome,
+}
+
+/// How many words the dictionary has, and an FNV-1a hash of them in order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Stamp {
+    pub words: usize,
+    pub hash: String,
+}
+
+impl Stamp {
+    pub fn of(dictionary: &WordIndex) -> Self {
+        let mut words: Vec<&String> = dictionary.iter().collect();
+        words.sort_unstable();
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in words.iter().flat_map(|word| word.bytes().chain([b'\n'])) {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+        Stamp { words: words.len(), hash: format!("{:016x}", hash) }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Transcript {
+    pub version: u32,
+    /// The wordle-rs that recorded it.
+    pub recorded_by: String,
+    pub day: NaiveDate,
+    /// Where the answer came from, e.g. practice or NYT's wordles.
+    pub source: String,
+    pub title: String,
+    pub header: String,
+    pub answer: SecretWord,
+    pub tries: u32,
+    pub hard_mode: bool,
+    pub strict_logic: bool,
+    pub any_word: bool,
+    pub undo: bool,
+    pub hint_shortlist: usize,
+    pub hint_penalty: u32,
+    pub lang: Lang,
+    pub dictionary: Stamp,
+    /// The guesses of a saved game picked up, and the tries its hints took.
+    pub resumed: Vec<String>,
+    pub resumed_hint_tries: u32,
+    pub moves: Vec<Entry>,
+}
+
+impl Transcript {
+    /// The transcript of `game` as it is now, before the first move of the
+    /// recording.
+    pub fn start(args: &PlayArgs, title: &str, header: &str, game: &Engine, options: engine::Options, dictionary: &WordIndex) -> Self {
+        let current_word = &game.current_word;
+        let source = match () {
+            _ if args.anagram => "anagram".to_string(),
+            _ if args.offline => "practice".to_string(),
+            _ if args.daily_random => "daily random".to_string(),
+            _ => args.source().to_string(),
+        };
+        Transcript {
+            version: VERSION,
+            recorded_by: env!("CARGO_PKG_VERSION").to_string(),
+            day: args.day,
+            source,
+            title: title.to_string(),
+            header: header.to_string(),
+            answer: current_word.correct_answer.clone(),
+            tries: current_word.tries + current_word.char_guesses.len() as u32 + current_word.hint_tries,
+            hard_mode: current_word.hard_mode,
+            strict_logic: current_word.strict_logic,
+            any_word: current_word.any_word,
+            undo: options.undo,
+            hint_shortlist: options.hint_shortlist,
+            hint_penalty: options.hint_penalty,
+            lang: i18n::current(),
+            dictionary: Stamp::of(dictionary),
+            resumed: current_word.char_guesses.iter().map(|cgs| cgs.iter().map(|cg| cg.ch).collect()).collect(),
+            resumed_hint_tries: current_word.hint_tries,
+            moves: vec![],
+        }
+    }
+}
+
+/// A transcript being recorded to a file.
+pub struct Recorder {
+    path: PathBuf,
+    transcript: Transcript,
+}
+
+impl Recorder {
+    /// Starts recording `transcript` to `path`, writing it right away.
+    pub fn new(path: PathBuf, transcript: Transcript) -> Result<Self> {
+        let recorder = Recorder { path, transcript };
+        recorder.write()?;
+        Ok(recorder)
+    }
+
+    pub fn push(&mut self, entry: Entry) -> Result<()> {
+        self.transcript.moves.push(entry);
+        self.write()
+    }
+
+    fn write(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.transcript).expect("transcripts serialize");
+        cache::write_atomic(&self.path, json.as_bytes()).context("writing the transcript")
+    }
+}
+
+/// Plays the moves of the transcript at `path` again, showing what came of
+/// each and the board at the end.
+pub fn replay(app: &App, path: &Path) -> Result<CurrentWord> {
+    let json = fs::read_to_string(path).context("reading the transcript")?;
+    let transcript: Transcript = serde_json::from_str(&json).map_err(|e| WordleError::CorruptSave(path.to_path_buf(), e))?;
+    if transcript.version > VERSION {
+        app.degrade(format!("the transcript is from wordle-rs {}, so some of it may not be understood", transcript.recorded_by));
+    }
+    let dictionary = app.dictionary(true)?;
+    let answers = app.answers()?.unwrap_or(dictionary);
+    let stamp = Stamp::of(dictionary);
+    if stamp != transcript.dictionary {
+        app.degrade(format!(
+            "the dictionary has {} words and hash {}, not the {} and {} recorded, so guesses may be taken differently",
+            stamp.words, stamp.hash, transcript.dictionary.words, transcript.dictionary.hash
+        ));
+    }
+    let same_lang = transcript.lang == i18n::current();
+    if !same_lang {
+        app.degrade("the transcript was recorded in another language, so the reasons moves were turned away aren't compared");
+    }
+
+    let mut current_word = CurrentWord::new(transcript.answer.clone(), transcript.tries);
+    current_word.hard_mode = transcript.hard_mode;
+    current_word.strict_logic = transcript.strict_logic;
+    current_word.any_word = transcript.any_word;
+    for guess in &transcript.resumed {
+        current_word.guess(guess.clone(), dictionary);
+    }
+    current_word.tries = current_word.tries.saturating_sub(transcript.resumed_hint_tries);
+    current_word.hint_tries = transcript.resumed_hint_tries;
+    let options = engine::Options { undo: transcript.undo, hint_shortlist: transcript.hint_shortlist, hint_penalty: transcript.hint_penalty };
+    let mut game = Engine::new(current_word, None, options);
+    let words = engine::Words { dictionary, answers };
+
+    println!("{}", boxed(&transcript.header));
+    println!("Recorded by wordle-rs {}, from {} for {}.", transcript.recorded_by, transcript.source, transcript.day);
+    let mut diverged = 0;
+    for (i, entry) in transcript.moves.iter().enumerate() {
+        let step = game.play(entry.mv.to_move(), words)?;
+        let outcome = Outcome::of(&step, &game.current_word);
+        println!("{:>3}. {}: {}", i + 1, entry.mv, outcome);
+        if !outcome.matches(&entry.outcome, same_lang) {
+            diverged += 1;
+            println!("     recorded: {}", entry.outcome);
+        }
+    }
+    println!();
+    game.current_word.display_word(false, &pick_theme(app, None)?);
+    match diverged {
+        0 => {
+            println!("All {} moves went as recorded.", transcript.moves.len());
+            Ok(game.into_word())
+        },
+        diverged => Err(WordleError::Diverged(diverged)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_read_back_as_written() {
+        let entry = Entry { mv: Recorded::Guess { guess: "crane".to_string() }, outcome: Outcome::Graded { pattern: "gybbb".to_string() } };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"move":"guess","guess":"crane","outcome":{"result":"graded","pattern":"gybbb"}}"#);
+        assert_eq!(serde_json::from_str::<Entry>(&json).unwrap(), entry);
+        let hint = Entry { mv: Recorded::Hint, outcome: Outcome::Hinted { hint: Hint::Letter('c') } };
+        assert_eq!(serde_json::from_str::<Entry>(&serde_json::to_string(&hint).unwrap()).unwrap(), hint);
+
+        let invalid = Outcome::Invalid { reason: "Not in the word list.".to_string() };
+        assert!(invalid.matches(&Outcome::Invalid { reason: "Inte i ordlistan.".to_string() }, false));
+        assert!(!invalid.matches(&Outcome::Invalid { reason: "Inte i ordlistan.".to_string() }, true));
+
+        let dictionary: WordIndex = ["slate", "crane"].iter().map(|w| w.to_string()).collect();
+        let reordered: WordIndex = ["crane", "slate"].iter().map(|w| w.to_string()).collect();
+        let other: WordIndex = ["crane", "pride"].iter().map(|w| w.to_string()).collect();
+        assert_eq!(Stamp::of(&dictionary), Stamp::of(&reordered));
+        assert_ne!(Stamp::of(&dictionary), Stamp::of(&other));
+    }
+}
@@ -0,0 +1,421 @@
+use std::{io, thread, time::Duration};
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    CharGuessKind, CurrentWord, GuessOutcome, KEYBOARD, format_duration, input,
+    control::Control,
+    engine::{Engine, Move, Step, Words},
+    error::{Result, WordleError},
+    i18n::tr,
+    keys::{Action, Keys},
+    output,
+    theme::Theme,
+    think_ms,
+};
+
+
+static REVEAL_DELAY: Duration = Duration::from_millis(150);
+static BOUNCE_DELAY: Duration = Duration::from_millis(90);
+static SHAKE_DELAY: Duration = Duration::from_millis(60);
+
+/// How often the timer is redrawn.
+static TICK: Duration = Duration::from_millis(250);
+
+/// How many columns it takes for the keyboard to go next to the grid.
+static WIDE_COLUMNS: u16 = 100;
+
+#[derive(Default)]
+struct Screen {
+    input: String,
+    message: String,
+
+    /// How many tiles of the latest guess are revealed, while it is animating.
+    revealing: Option<usize>,
+
+    /// The tile of the latest guess that is lifted by the win animation.
+    bounce: Option<usize>,
+
+    /// Horizontal offset of the latest guess during the loss animation.
+    shake: i8,
+
+    /// Whether the keyboard was hidden with its key.
+    keyboard_hidden: bool,
+
+    options: Options,
+}
+
+/// How the terminal UI plays.
+#[derive(Clone, Copy, Default)]
+pub struct Options {
+    /// Whether the elapsed time is shown and kept ticking.
+    pub timed: bool,
+
+    /// Whether the time isn't told at the end either, for `--simple`.
+    pub untimed: bool,
+
+    /// Whether the letters being typed show as dots, for `--streamer`.
+    pub masked: bool,
+}
+
+/// Plays the word in an alternate screen, redrawing the grid in place.
+///
+/// Returns whether the game was played to the end rather than quit.
+pub fn play(game: &mut Engine, words: Words, title: &str, theme: &Theme, keys: &Keys, options: Options, hooks: Hooks) -> Result<bool> {
+    let mut terminal = ratatui::init();
+    let screen = Screen { options, ..Screen::default() };
+    let result = run(&mut terminal, screen, game, words, title, theme, keys, hooks);
+    ratatui::restore();
+    result
+}
+
+fn terminal_error(source: io::Error) -> WordleError {
+    WordleError::Terminal { context: "running the terminal UI", source }
+}
+
+/// What a finished game says instead of the usual.
+pub type EndMessage<'a> = dyn Fn(&CurrentWord) -> Result<Option<String>> + 'a;
+
+/// What the terminal UI is hooked up to besides the keyboard.
+#[derive(Clone, Copy)]
+pub struct Hooks<'a> {
+    pub end_screen: &'a EndMessage<'a>,
+
+    /// Where the game is mirrored and guesses come in from, with
+    /// `--control-socket`.
+    pub control: Option<&'a Control>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run(
+    terminal: &mut DefaultTerminal,
+    mut screen: Screen,
+    game: &mut Engine,
+    words: Words,
+    title: &str,
+    theme: &Theme,
+    keys: &Keys,
+    hooks: Hooks,
+) -> Result<bool> {
+    let send = |event: &output::Event| if let Some(control) = hooks.control {
+        control.send(event);
+    };
+    loop {
+        terminal.draw(|f| draw(f, &game.current_word, &screen, title, theme)).map_err(terminal_error)?;
+
+        // A guess from the control socket is played like one typed in.
+        let (guess, typed) = match hooks.control.and_then(Control::guess) {
+            Some(guess) => (input::normalize(&guess), false),
+            None => {
+                if (screen.options.timed || hooks.control.is_some()) && !event::poll(TICK).map_err(terminal_error)? {
+                    continue;
+                }
+
+                let Event::Key(key) = event::read().map_err(terminal_error)? else { continue };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                let submitted = match keys.action(&key) {
+                    Some(Action::Quit) => return Ok(false),
+                    Some(Action::Undo) => {
+                        screen.message = match game.play(Move::Undo, words)? {
+                            Step::Undone(row) => {
+                                send(&output::Event::Undo { guess: row.iter().map(|cg| cg.ch).collect(), tries_left: game.current_word.tries });
+                                String::new()
+                            },
+                            Step::Refused(reason) => reason,
+                            _ => String::new(),
+                        };
+                        None
+                    },
+                    Some(Action::Hint) => {
+                        screen.message = match game.play(Move::Hint, words)? {
+                            Step::Hinted(hint) => {
+                                let message = hint.message();
+                                send(&output::Event::Hint { hint, message: message.clone(), tries_left: game.current_word.tries });
+                                message
+                            },
+                            Step::Refused(reason) => reason,
+                            _ => String::new(),
+                        };
+                        None
+                    },
+                    Some(Action::Keyboard) => {
+                        screen.keyboard_hidden = !screen.keyboard_hidden;
+                        None
+                    },
+                    None => {
+                        if let KeyCode::Char(ch) = key.code && let Some(letter) = input::letter(ch) {
+                            if screen.input.len() < game.current_word.word_length {
+                                screen.input.push(letter);
+                            }
+                            screen.message.clear();
+                        }
+                        None
+                    },
+                    Some(Action::Backspace) => {
+                        screen.input.pop();
+                        screen.message.clear();
+                        None
+                    },
+                    Some(Action::Submit) => Some(std::mem::take(&mut screen.input)),
+                };
+                match submitted {
+                    Some(guess) => (guess, true),
+                    None => continue,
+                }
+            },
+        };
+
+        let Step::Guessed(outcome) = game.play(Move::Guess(guess.clone()), words)? else { continue };
+        let current_word = &game.current_word;
+        match &outcome {
+            GuessOutcome::InvalidGuess(kind) => send(&output::Event::Invalid { guess: &guess, reason: kind.message() }),
+            _ => send(&output::Event::guess(current_word.char_guesses.last().expect("a guess was just graded"), current_word.tries)),
+        }
+        if let GuessOutcome::Win | GuessOutcome::NoTriesLeft = outcome {
+            send(&output::Event::Over {
+                won: current_word.won(),
+                answer: current_word.correct_answer.reveal(),
+                guesses: current_word.char_guesses.len(),
+                elapsed_ms: current_word.elapsed().as_millis(),
+                think_ms: think_ms(current_word),
+                score: None,
+                share: None,
+            });
+        }
+        match outcome {
+            GuessOutcome::InvalidGuess(kind) => {
+                if typed {
+                    screen.input = guess;
+                }
+                screen.message = kind.message();
+            },
+            GuessOutcome::Continue => reveal(terminal, current_word, &mut screen, title, theme).map_err(terminal_error)?,
+            GuessOutcome::GameOver => unreachable!("the game is over once it's finished"),
+            GuessOutcome::Win => {
+                reveal(terminal, current_word, &mut screen, title, theme).map_err(terminal_error)?;
+                bounce(terminal, current_word, &mut screen, title, theme).map_err(terminal_error)?;
+                let message = (hooks.end_screen)(current_word)?.unwrap_or_else(|| tr!("won"));
+                return finish(terminal, current_word, &mut screen, title, theme, &message).map_err(terminal_error);
+            },
+            GuessOutcome::NoTriesLeft => {
+                reveal(terminal, current_word, &mut screen, title, theme).map_err(terminal_error)?;
+                shake(terminal, current_word, &mut screen, title, theme).map_err(terminal_error)?;
+                let message = (hooks.end_screen)(current_word)?.unwrap_or_else(|| tr!("lost"));
+                return finish(terminal, current_word, &mut screen, title, theme, &message).map_err(terminal_error);
+            },
+        }
+    }
+}
+
+fn reveal(terminal: &mut DefaultTerminal, current_word: &CurrentWord, screen: &mut Screen, title: &str, theme: &Theme) -> io::Result<()> {
+    for i in 0..=current_word.word_length {
+        screen.revealing = Some(i);
+        terminal.draw(|f| draw(f, current_word, screen, title, theme))?;
+        thread::sleep(REVEAL_DELAY);
+    }
+    screen.revealing = None;
+    Ok(())
+}
+
+fn bounce(terminal: &mut DefaultTerminal, current_word: &CurrentWord, screen: &mut Screen, title: &str, theme: &Theme) -> io::Result<()> {
+    for i in 0..current_word.word_length {
+        screen.bounce = Some(i);
+        terminal.draw(|f| draw(f, current_word, screen, title, theme))?;
+        thread::sleep(BOUNCE_DELAY);
+    }
+    screen.bounce = None;
+    Ok(())
+}
+
+fn shake(terminal: &mut DefaultTerminal, current_word: &CurrentWord, screen: &mut Screen, title: &str, theme: &Theme) -> io::Result<()> {
+    for offset in [-1, 1, -1, 1, -1, 1, 0] {
+        screen.shake = offset;
+        terminal.draw(|f| draw(f, current_word, screen, title, theme))?;
+        thread::sleep(SHAKE_DELAY);
+    }
+    Ok(())
+}
+
+fn finish(
+    terminal: &mut DefaultTerminal,
+    current_word: &CurrentWord,
+    screen: &mut Screen,
+    title: &str,
+    theme: &Theme,
+    message: &str,
+) -> io::Result<bool> {
+    screen.message = match screen.options.untimed {
+        true => format!("{} {}", message, tr!("press-any-key")),
+        false => format!("{} {} {}", message, format_duration(current_word.elapsed()), tr!("press-any-key")),
+    };
+    terminal.draw(|f| draw(f, current_word, screen, title, theme))?;
+    loop {
+        if let Event::Key(key) = event::read()? && key.kind == KeyEventKind::Press {
+            return Ok(true);
+        }
+    }
+}
+
+fn color(color: colored::Color) -> Color {
+    match color {
+        colored::Color::Black => Color::Black,
+        colored::Color::Red => Color::Red,
+        colored::Color::Green => Color::Green,
+        colored::Color::Yellow => Color::Yellow,
+        colored::Color::Blue => Color::Blue,
+        colored::Color::Magenta => Color::Magenta,
+        colored::Color::Cyan => Color::Cyan,
+        colored::Color::White => Color::Gray,
+        colored::Color::BrightBlack => Color::DarkGray,
+        colored::Color::BrightRed => Color::LightRed,
+        colored::Color::BrightGreen => Color::LightGreen,
+        colored::Color::BrightYellow => Color::LightYellow,
+        colored::Color::BrightBlue => Color::LightBlue,
+        colored::Color::BrightMagenta => Color::LightMagenta,
+        colored::Color::BrightCyan => Color::LightCyan,
+        colored::Color::BrightWhite => Color::White,
+        colored::Color::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+        colored::Color::AnsiColor(n) => Color::Indexed(n),
+    }
+}
+
+fn tile_style(theme: &Theme, kind: CharGuessKind) -> Style {
+    let tile = theme.tile(kind);
+    let mut style = Style::new().add_modifier(Modifier::BOLD);
+    if let Some(background) = tile.background {
+        style = style.bg(color(background));
+    }
+    if let Some(text) = tile.text {
+        style = style.fg(color(text));
+    }
+    style
+}
+
+fn tile(ch: char, style: Style) -> Span<'static> {
+    Span::styled(format!(" {} ", ch.to_ascii_uppercase()), style)
+}
+
+/// A graded tile, with the theme's marks in place of the padding.
+fn graded_tile(theme: &Theme, ch: char, kind: CharGuessKind) -> Span<'static> {
+    let label = match theme.tile(kind).marks {
+        Some(_) => theme.label(kind, &ch.to_ascii_uppercase().to_string()),
+        None => format!(" {} ", ch.to_ascii_uppercase()),
+    };
+    Span::styled(label, tile_style(theme, kind))
+}
+
+fn grid_line(tiles: Vec<Span<'static>>, shake: i8) -> Line<'static> {
+    let mut spans = vec![];
+    if shake > 0 {
+        spans.push(Span::raw("  "));
+    }
+    for (i, t) in tiles.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(t);
+    }
+    if shake < 0 {
+        spans.push(Span::raw("  "));
+    }
+    Line::from(spans)
+}
+
+fn grid(current_word: &CurrentWord, screen: &Screen, theme: &Theme) -> Vec<Line<'static>> {
+    let typed_style = Style::new().fg(Color::White).bg(Color::Indexed(238)).add_modifier(Modifier::BOLD);
+    let empty_style = Style::new().bg(Color::Indexed(236));
+    let guesses = &current_word.char_guesses;
+    let mut lines = vec![];
+
+    for (row, cgs) in guesses.iter().enumerate() {
+        let latest = row + 1 == guesses.len();
+        let tiles = cgs.iter().enumerate().map(|(i, cg)| {
+            match (latest, screen.revealing, screen.bounce) {
+                (true, Some(revealed), _) if i >= revealed => tile(cg.ch, typed_style),
+                (true, _, Some(lifted)) if i == lifted => {
+                    graded_tile(theme, cg.ch, cg.kind).patch_style(Modifier::REVERSED)
+                },
+                _ => graded_tile(theme, cg.ch, cg.kind),
+            }
+        }).collect();
+        lines.push(grid_line(tiles, if latest { screen.shake } else { 0 }));
+    }
+
+    for row in 0..current_word.tries {
+        let tiles = (0..current_word.word_length).map(|i| {
+            match screen.input.chars().nth(i) {
+                Some(_) if row == 0 && screen.options.masked => tile('•', typed_style),
+                Some(ch) if row == 0 => tile(ch, typed_style),
+                _ => tile(' ', empty_style),
+            }
+        }).collect();
+        lines.push(grid_line(tiles, 0));
+    }
+
+    lines
+}
+
+fn keyboard(current_word: &CurrentWord, theme: &Theme) -> Vec<Line<'static>> {
+    KEYBOARD.iter().map(|row| {
+        let keys = row.chars().map(|ch| match current_word.letter_kind(ch) {
+            Some(kind) => graded_tile(theme, ch, kind),
+            None => tile(ch, Style::new().fg(Color::White).bg(Color::Indexed(240))),
+        }).collect();
+        grid_line(keys, 0)
+    }).collect()
+}
+
+/// Lays the screen out for its size: on a wide one the keyboard goes next
+/// to the grid instead of under it, and on a short one the blank lines
+/// between rows go.
+fn draw(frame: &mut Frame, current_word: &CurrentWord, screen: &Screen, title: &str, theme: &Theme) {
+    let grid = grid(current_word, screen, theme);
+    let rows = grid.len() as u16;
+    let area = frame.area();
+    let wide = area.width >= WIDE_COLUMNS && !screen.keyboard_hidden;
+    let body = |grid: u16, keyboard: u16| if wide { grid.max(keyboard) } else { grid + keyboard };
+    let spacious = body(rows * 2, 7) + 4 <= area.height;
+    let (grid_height, keyboard_height) = if spacious { (rows * 2, 7) } else { (rows, 4) };
+    let [title_area, body_area, message_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(body(grid_height, keyboard_height)),
+        Constraint::Length(2),
+    ]).areas(area);
+    let [grid_area, keyboard_area] = match wide {
+        true => Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body_area),
+        false => Layout::vertical([Constraint::Length(grid_height), Constraint::Length(keyboard_height)]).areas(body_area),
+    };
+
+    let title = match screen.options.timed {
+        true => format!("{}  {}", title, format_duration(current_word.elapsed())),
+        false => title.to_string(),
+    };
+    let title = Line::from(title).style(Style::new().add_modifier(Modifier::BOLD));
+    frame.render_widget(Paragraph::new(title).centered(), title_area);
+
+    let spaced = |lines: Vec<Line<'static>>| match spacious {
+        true => lines.into_iter().flat_map(|l| [l, Line::raw("")]).collect::<Vec<_>>(),
+        false => lines,
+    };
+    frame.render_widget(Paragraph::new(spaced(grid)).centered(), grid_area);
+    if !screen.keyboard_hidden {
+        let mut keys = match wide {
+            true => vec![],
+            false => vec![Line::raw("")],
+        };
+        keys.extend(spaced(keyboard(current_word, theme)));
+        frame.render_widget(Paragraph::new(keys).centered(), keyboard_area);
+    }
+
+    frame.render_widget(Paragraph::new(screen.message.as_str()).centered(), message_area);
+}
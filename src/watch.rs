@@ -0,0 +1,85 @@
+//! Waiting for a wordle to come out, for people who play at midnight.
+//!
+//! Nothing is asked of NYT before the day starts where you are. From then on
+//! it's checked every `--interval` minutes until the puzzle is there.
+
+use std::{cmp, thread, time::Duration};
+
+use chrono::{Days, NaiveDate};
+
+use crate::{
+    app::App,
+    cache,
+    cli::WatchArgs,
+    date_arg,
+    error::{Result, WordleError},
+    get_and_write_word,
+    source::WordSource,
+};
+
+pub static DEFAULT_INTERVAL: u64 = 5;
+
+/// The first day from today that isn't in the cache yet.
+fn next_uncached(app: &App) -> NaiveDate {
+    let mut day = date_arg::today();
+    while cache::word_path(&app.cache_dir, day).exists() {
+        day = day + Days::new(1);
+    }
+    day
+}
+
+/// Tells the desktop, and the terminal, that the wordle is out.
+fn announce(app: &App, title: &str) {
+    let summary = format!("{} is out", title);
+    println!("{}.", summary);
+    #[cfg(feature = "notifications")]
+    if let Err(e) = notify_rust::Notification::new()
+        .appname("wordle-rs")
+        .summary(&summary)
+        .body("Run wordle-rs to play it.")
+        .show()
+    {
+        app.degrade(format!("the desktop notification couldn't be shown: {}", e));
+    }
+    #[cfg(not(feature = "notifications"))]
+    app.degrade("desktop notifications weren't built in. Build with the notifications feature to get them");
+}
+
+/// Waits for the day's wordle, announcing it once it's published, and with
+/// `--forever` goes on to the next day.
+pub fn run(app: &App, args: WatchArgs) -> Result<()> {
+    app.client.online("watching for new wordles")?;
+    let interval = Duration::from_secs(args.interval * 60);
+    let nyt = app.nyt();
+    let mut day = args.day.unwrap_or_else(|| next_uncached(app));
+    eprintln!("Watching for the wordle of {}.", day);
+    loop {
+        // Sleeping in steps keeps the wait right across suspends and clock changes.
+        let until = date_arg::until(day);
+        if !until.is_zero() {
+            thread::sleep(cmp::min(until, interval));
+            continue;
+        }
+        let fetched = match args.prefetch {
+            true => get_and_write_word(&app.cache_dir, day, &nyt),
+            false => nyt.answer_for(day),
+        };
+        match fetched {
+            Ok(puzzle) => {
+                announce(app, &puzzle.number(day).map_or_else(|| format!("The wordle of {}", day), |n| format!("Wordle #{}", n)));
+                if !args.forever {
+                    return Ok(());
+                }
+                day = day + Days::new(1);
+                eprintln!("Watching for the wordle of {}.", day);
+            },
+            Err(WordleError::NotPublished(_)) => thread::sleep(interval),
+            Err(WordleError::RateLimited { wait, .. }) => thread::sleep(cmp::max(wait, interval)),
+            Err(e @ WordleError::Network { .. }) => {
+                eprintln!("{}. Trying again in {} minutes.", e, args.interval);
+                thread::sleep(interval);
+            },
+            Err(e) => return Err(e),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Posting finished games to a chat, for friend groups comparing results.
+
+use serde::Serialize;
+
+use crate::{
+    app::LazyClient,
+    error::{Context, Result},
+};
+
+/// The message body. Discord reads `content` and Slack reads `text`, and
+/// both ignore the other, so one body works for either.
+#[derive(Serialize, Debug)]
+struct Payload<'a> {
+    content: &'a str,
+    text: &'a str,
+}
+
+/// Posts `text` to the webhook at `url`. Only share text is ever passed in,
+/// so the answer and the guessed letters never leave the machine.
+pub fn post(client: &LazyClient, url: &str, text: &str) -> Result<()> {
+    let payload = Payload { content: text, text };
+    client.send_with(|client| client.post(url).json(&payload), "posting the result")?
+        .error_for_status()
+        .context("posting the result")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_body_for_discord_and_slack() {
+        let json = serde_json::to_string(&Payload { content: "Wordle 1,292 1/6", text: "Wordle 1,292 1/6" }).unwrap();
+        assert_eq!(json, r#"{"content":"Wordle 1,292 1/6","text":"Wordle 1,292 1/6"}"#);
+    }
+}
@@ -0,0 +1,120 @@
+//! `--wordlebot`: going over a finished game with the solver, like NYT's
+//! WordleBot. Each guess gets a skill rating, for how much it could be
+//! expected to narrow the possible answers down next to the solver's pick,
+//! and a luck rating, for how its feedback turned out next to what it could
+//! have been.
+
+use std::collections::HashMap;
+
+use crate::{
+    CharGuess, CharGuessKind,
+    index::WordIndex,
+    output::{self, Event},
+    strategy::{self, Strategy},
+};
+
+/// How one guess did.
+#[derive(Debug, PartialEq)]
+pub struct Rating {
+    pub guess: String,
+    /// From 0 to 99, the share of the solver pick's expected narrowing the
+    /// guess got.
+    pub skill: u32,
+    /// From 0 to 99, how many of the possible feedbacks would have left
+    /// more answers. `None` once there was only the answer left.
+    pub luck: Option<u32>,
+    /// The possible answers before the guess and after it.
+    pub before: usize,
+    pub after: usize,
+    /// What the solver would have guessed.
+    pub best: Option<String>,
+}
+
+/// How many of `candidates` are expected to be left after guessing `guess`,
+/// counting a solve as none left, with how many each answer would leave.
+fn left_by_answer(guess: &str, candidates: &[&String]) -> (f64, Vec<usize>) {
+    let codes: Vec<u32> = candidates.iter().map(|answer| strategy::pattern_code(guess.as_bytes(), answer.as_bytes())).collect();
+    let mut buckets: HashMap<u32, usize> = HashMap::new();
+    for &code in &codes {
+        *buckets.entry(code).or_default() += 1;
+    }
+    let left: Vec<usize> = candidates.iter().zip(&codes)
+        .map(|(answer, code)| if answer.as_str() == guess { 0 } else { buckets[code] })
+        .collect();
+    let expected = left.iter().sum::<usize>() as f64 / candidates.len().max(1) as f64;
+    (expected, left)
+}
+
+/// Rates every guess of a game, each against the answers still possible
+/// when it was made.
+pub fn rate(char_guesses: &[Vec<CharGuess>], answers: &WordIndex, dictionary: &WordIndex) -> Vec<Rating> {
+    (0..char_guesses.len())
+        .map(|i| {
+            let history = &char_guesses[..i];
+            let guess: String = char_guesses[i].iter().map(|cg| cg.ch).collect();
+            let candidates = strategy::likely_candidates(history, answers, dictionary);
+            let after = strategy::likely_candidates(&char_guesses[..=i], answers, dictionary).len();
+            let best = strategy::rank_candidates(candidates.clone(), dictionary, Strategy::Entropy).first().map(|(w, _)| w.to_string());
+            let (yours, left) = left_by_answer(&guess, &candidates);
+            let possible = candidates.len() as f64;
+            let most = possible - best.as_deref().map_or(possible, |best| left_by_answer(best, &candidates).0);
+            let skill = match most > 0.0 {
+                true => (99.0 * (possible - yours) / most).round().clamp(0.0, 99.0) as u32,
+                false => 99,
+            };
+            let left_now = if char_guesses[i].iter().all(|cg| cg.kind == CharGuessKind::Correct) { 0 } else { after };
+            let luck = (candidates.len() > 1).then(|| {
+                let worse = left.iter().filter(|&&n| n > left_now).count() as f64;
+                let even = left.iter().filter(|&&n| n == left_now).count() as f64;
+                (99.0 * (worse + even / 2.0) / possible).round() as u32
+            });
+            Rating { guess, skill, luck, before: candidates.len(), after, best }
+        })
+        .collect()
+}
+
+/// Prints the ratings as a table, or as events with `--output json`.
+pub fn show(json: bool, char_guesses: &[Vec<CharGuess>], answers: &WordIndex, dictionary: &WordIndex) {
+    let ratings = rate(char_guesses, answers, dictionary);
+    if json {
+        for r in &ratings {
+            output::emit(&Event::Rated { guess: &r.guess, skill: r.skill, luck: r.luck, before: r.before, after: r.after, best: r.best.as_deref() });
+        }
+        return;
+    }
+    println!();
+    println!("Guess  Skill  Luck  Answers left   Solver's pick");
+    for r in &ratings {
+        let luck = r.luck.map_or_else(|| "-".to_string(), |luck| luck.to_string());
+        let left = format!("{} → {}", r.before, r.after);
+        let best = match r.best.as_deref() {
+            Some(best) if best == r.guess => "the same".to_string(),
+            best => best.unwrap_or("-").to_string(),
+        };
+        println!("{:<5}  {:>5}  {:>4}  {:<13}  {}", r.guess, r.skill, luck, left, best);
+    }
+    if let Some(average) = (!ratings.is_empty()).then(|| ratings.iter().map(|r| r.skill).sum::<u32>() / ratings.len() as u32) {
+        println!("Average skill: {}", average);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::grade;
+
+    #[test]
+    fn rates_guesses_against_the_solver() {
+        let words: HashSet<String> = ["crane", "drape", "grape", "crate", "slate"].map(str::to_string).into();
+        let index = WordIndex::new(words);
+        let rows: Vec<Vec<CharGuess>> = ["slate", "grape"].iter()
+            .map(|guess| guess.chars().zip(grade::grade(guess, "crane")).map(|(ch, kind)| CharGuess::new(ch, kind)).collect())
+            .collect();
+        let ratings = rate(&rows, &index, &index);
+        assert_eq!(ratings.iter().map(|r| (r.before, r.after)).collect::<Vec<_>>(), [(5, 3), (3, 1)]);
+        assert!(ratings[0].skill < 99);
+        assert_eq!(ratings[1].skill, 99);
+    }
+}
@@ -0,0 +1,230 @@
+use std::{fs, time::Duration};
+
+use chrono::NaiveDate;
+use clap::Parser;
+use httpmock::{Method::GET, MockServer};
+
+use wordle_rs::{
+    app::{LazyClient, RETRIES, USER_AGENT},
+    cache,
+    cli::{Cli, FetchArgs},
+    dictionary,
+    error::WordleError,
+    get_and_write_word, prefetch,
+    source::Nyt,
+};
+
+static TIMEOUT: Duration = Duration::from_secs(5);
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 1, d).unwrap()
+}
+
+fn puzzle_json(solution: &str) -> String {
+    format!(r#"{{"id":1,"solution":"{}","print_date":"2025-01-01","days_since_launch":1292,"editor":"Tracy Bennett"}}"#, solution)
+}
+
+static NOT_PUBLISHED: &str = r#"{"status":"ERROR","errors":["Not Found"],"results":[]}"#;
+
+#[test]
+fn fetches_a_day_once_and_then_reads_it_from_the_cache() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/2025-01-01.json");
+        then.status(200).header("content-type", "application/json").body(puzzle_json("crane"));
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    let client = LazyClient::new(TIMEOUT, false);
+    let nyt = Nyt::new(&client, &server.base_url());
+
+    for _ in 0..2 {
+        let puzzle = get_and_write_word(dir.path(), day(1), &nyt).unwrap();
+        assert_eq!(puzzle.solution.reveal(), "crane");
+        assert_eq!(puzzle.editor.as_deref(), Some("Tracy Bennett"));
+    }
+    mock.assert_calls(1);
+    assert_eq!(cache::cached_days(dir.path()).unwrap(), vec![day(1)]);
+}
+
+#[test]
+fn refetches_a_corrupt_cached_word() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/2025-01-01.json");
+        then.status(200).body(puzzle_json("crane"));
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    fs::write(cache::word_path(dir.path(), day(1)), "cr").unwrap();
+    let client = LazyClient::new(TIMEOUT, false);
+    let nyt = Nyt::new(&client, &server.base_url());
+
+    assert_eq!(get_and_write_word(dir.path(), day(1), &nyt).unwrap().solution.reveal(), "crane");
+    mock.assert_calls(1);
+}
+
+#[test]
+fn reports_days_that_are_not_published() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/2025-01-01.json");
+        then.status(200).body(NOT_PUBLISHED);
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    let client = LazyClient::new(TIMEOUT, false);
+    let nyt = Nyt::new(&client, &server.base_url());
+
+    let result = get_and_write_word(dir.path(), day(1), &nyt);
+    assert!(matches!(result, Err(WordleError::NotPublished(d)) if d == day(1)));
+    assert!(cache::cached_days(dir.path()).unwrap().is_empty());
+}
+
+#[test]
+fn asks_again_about_a_day_with_what_nyt_said_last_time() {
+    let server = MockServer::start();
+    let unchanged = server.mock(|when, then| {
+        when.method(GET).path("/2025-01-01.json").header("if-none-match", "\"v1\"");
+        then.status(304);
+    });
+    let first = server.mock(|when, then| {
+        when.method(GET).path("/2025-01-01.json").header_missing("if-none-match");
+        then.status(200).header("etag", "\"v1\"").body(NOT_PUBLISHED);
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    let client = LazyClient::new(TIMEOUT, false);
+    let nyt = Nyt { cache_dir: Some(dir.path()), ..Nyt::new(&client, &server.base_url()) };
+
+    for _ in 0..2 {
+        assert!(matches!(get_and_write_word(dir.path(), day(1), &nyt), Err(WordleError::NotPublished(_))));
+    }
+    first.assert_calls(1);
+    unchanged.assert_calls(1);
+    assert!(cache::headers_path(dir.path(), day(1)).exists());
+}
+
+#[test]
+fn refuses_to_fetch_without_the_network() {
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    let client = LazyClient::new(TIMEOUT, true);
+    let nyt = Nyt::new(&client, "http://127.0.0.1:9");
+
+    let result = get_and_write_word(dir.path(), day(1), &nyt);
+    assert!(matches!(result, Err(WordleError::NoNetwork(_))));
+}
+
+#[test]
+fn reads_days_and_word_lists_from_a_mirror_on_disk() {
+    let mirror = tempfile::tempdir().unwrap();
+    fs::write(mirror.path().join("2025-01-01.json"), puzzle_json("crane")).unwrap();
+    fs::write(mirror.path().join("words.txt"), "crane\nslate\n").unwrap();
+    let base_url = reqwest::Url::from_directory_path(mirror.path()).unwrap().to_string();
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    let client = LazyClient::new(TIMEOUT, true);
+    let nyt = Nyt::new(&client, &base_url);
+
+    assert_eq!(get_and_write_word(dir.path(), day(1), &nyt).unwrap().solution.reveal(), "crane");
+    assert!(matches!(get_and_write_word(dir.path(), day(2), &nyt), Err(WordleError::Unavailable(..))));
+    dictionary::write(dir.path(), &format!("{}words.txt", base_url), &client).unwrap();
+    assert_eq!(fs::read_to_string(cache::dictionary_path(dir.path())).unwrap(), "crane\nslate\n");
+}
+
+#[test]
+fn says_who_is_asking() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/2025-01-01.json").header("user-agent", USER_AGENT);
+        then.status(200).body(puzzle_json("crane"));
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    let client = LazyClient::new(TIMEOUT, false);
+    let nyt = Nyt::new(&client, &server.base_url());
+
+    assert_eq!(get_and_write_word(dir.path(), day(1), &nyt).unwrap().solution.reveal(), "crane");
+    mock.assert_calls(1);
+}
+
+#[test]
+fn waits_as_long_as_nyt_asks_when_rate_limited() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/2025-01-01.json");
+        then.status(429).header("retry-after", "0");
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+    let client = LazyClient::new(TIMEOUT, false);
+    let nyt = Nyt::new(&client, &server.base_url());
+
+    let result = get_and_write_word(dir.path(), day(1), &nyt);
+    assert!(matches!(result, Err(WordleError::RateLimited { .. })));
+    mock.assert_calls(RETRIES as usize + 1);
+}
+
+#[test]
+fn prefetch_stops_at_the_first_unpublished_day() {
+    let server = MockServer::start();
+    for (d, solution) in [(1, "crane"), (2, "slate")] {
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/2025-01-{:02}.json", d));
+            then.status(200).body(puzzle_json(solution));
+        });
+    }
+    let unpublished = server.mock(|when, then| {
+        when.method(GET).path_matches(r"^/2025-01-(0[3-9]|[12][0-9]|3[01])\.json$");
+        then.status(200).body(NOT_PUBLISHED);
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+
+    prefetch::fetch(dir.path(), TIMEOUT, &server.base_url(), 100, FetchArgs { from: day(1), to: None, rate: None, dry_run: false, if_stale: false }).unwrap();
+    assert_eq!(cache::cached_days(dir.path()).unwrap(), vec![day(1), day(2)]);
+    assert!(unpublished.calls() >= 1);
+
+    // Days that are cached aren't downloaded again.
+    prefetch::fetch(dir.path(), TIMEOUT, &server.base_url(), 100, FetchArgs { from: day(1), to: Some(day(2)), rate: None, dry_run: false, if_stale: false }).unwrap();
+    assert_eq!(cache::cached_days(dir.path()).unwrap(), vec![day(1), day(2)]);
+}
+
+#[test]
+fn prefetch_walks_backwards_to_an_earlier_day() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path_matches(r"^/2025-01-0[1-3]\.json$");
+        then.status(200).body(puzzle_json("crane"));
+    });
+    let dir = tempfile::tempdir().unwrap();
+    cache::prepare(dir.path()).unwrap();
+
+    prefetch::fetch(dir.path(), TIMEOUT, &server.base_url(), 100, FetchArgs { from: day(3), to: Some(day(1)), rate: None, dry_run: false, if_stale: false }).unwrap();
+    assert_eq!(cache::cached_days(dir.path()).unwrap(), vec![day(1), day(2), day(3)]);
+    mock.assert_calls(3);
+}
+
+#[test]
+fn offline_obscurity_on_an_empty_cache_makes_no_request() {
+    let server = MockServer::start();
+    let any = server.mock(|when, then| {
+        when.any_request();
+        then.status(200).body("crane\nslate\n");
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+    fs::write(path("config.toml"), "").unwrap();
+    fs::write(path("words.txt"), "crane\nslate\n").unwrap();
+    let args = [
+        "wordle-rs", "--config", &path("config.toml"), "--cache-dir", &path("cache"), "--data-dir", &path("data"),
+        "--nyt-url", &server.base_url(), "--dictionary-url", &server.url("/words.txt"), "--answers-url", &server.url("/answers.txt"),
+        "--dictionary", &path("words.txt"), "--offline", "--obscurity", "easy", "--guesses", "crane",
+    ];
+
+    let result = wordle_rs::run(Cli::parse_from(args));
+    assert!(matches!(result, Err(WordleError::NoFrequencies)), "{:?}", result);
+    any.assert_calls(0);
+    assert!(!cache::frequencies_path(&dir.path().join("cache")).exists());
+}